@@ -1,11 +1,11 @@
 use std::cell::RefCell;
 use std::collections::VecDeque;
 use std::rc::Rc;
-use std::time::{Duration, Instant};
 
 use compose_rt::Recomposer;
+use oxui::rendering::RenderBox;
 use oxui::rendering::{Axis, FlexFit, PipelineOwner, Size};
-use oxui::rendering::{PaintContext, RenderBox};
+use oxui::scheduler::{Ticker, TickerCallback};
 use oxui::widgets::{BuildContext, ConstrainedBox, Flex, Widget};
 use skulpin::app::AppDrawArgs;
 use skulpin::app::AppError;
@@ -58,7 +58,8 @@ struct App {
     recomposer: Recomposer,
     pipeline: PipelineOwner,
     previous_clicks: VecDeque<bool>,
-    previous_frame: Instant,
+    // the root changes with every frame, the ticker keeps them coming
+    _ticker: Rc<Ticker>,
 }
 
 impl App {
@@ -66,11 +67,14 @@ impl App {
     where
         W: 'static + Widget,
     {
+        let pipeline = PipelineOwner::new(Size::new(width as f32, height as f32), root);
+        let ticker = Ticker::new(pipeline.scheduler(), TickerCallback::new(|_| {}));
+        ticker.start();
         App {
             recomposer: Recomposer::new(1000),
-            pipeline: PipelineOwner::new(Size::new(width as f32, height as f32), root),
+            pipeline,
             previous_clicks: VecDeque::new(),
-            previous_frame: Instant::now(),
+            _ticker: ticker,
         }
     }
 }
@@ -94,17 +98,13 @@ impl AppHandler for App {
     fn draw(&mut self, draw_args: AppDrawArgs) {
         // click to next frame
         //if let Some(_) = self.previous_clicks.pop_front() {
-        if self.previous_frame.elapsed() > Duration::from_millis(16) {
+        if self.pipeline.scheduler().has_scheduled_frame() {
             let canvas = draw_args.canvas;
             canvas.clear(0);
 
-            let mut context = PaintContext::new(canvas);
-
             self.recomposer.compose(|cx| {
-                self.pipeline.draw_frame(cx, &mut context);
+                self.pipeline.draw_frame(cx, canvas);
             });
-
-            self.previous_frame = draw_args.time_state.current_instant();
         }
     }
 
@@ -4,8 +4,9 @@ use std::rc::Rc;
 use std::time::Instant;
 
 use compose_rt::Recomposer;
+use oxui::gestures::{PointerEvent, PointerEventKind};
+use oxui::rendering::RenderBox;
 use oxui::rendering::{Axis, FlexFit, Offset, PipelineOwner, Size};
-use oxui::rendering::{PaintContext, RenderBox};
 use oxui::widgets::{BuildContext, ConstrainedBox, Flex, Widget};
 use skulpin::app::AppDrawArgs;
 use skulpin::app::AppError;
@@ -83,15 +84,26 @@ impl AppHandler for App {
         // if input_state.is_key_down(VirtualKeyCode::Escape) {
         //     app_control.enqueue_terminate_process();
         // }
+        let now = update_args.time_state.total_time();
         if update_args
             .input_state
             .is_mouse_just_down(MouseButton::Left)
         {
             let p = update_args.input_state.mouse_position();
             let position = Offset::new(p.x as f32, p.y as f32);
-            self.pipeline.handle_event(position);
+            self.pipeline.handle_event(
+                PointerEvent::new(PointerEventKind::Down, 0, position).with_timestamp(now),
+            );
             self.previous_clicks.push_back(position);
         }
+        if update_args.input_state.is_mouse_just_up(MouseButton::Left) {
+            let p = update_args.input_state.mouse_position();
+            let position = Offset::new(p.x as f32, p.y as f32);
+            self.pipeline.handle_event(
+                PointerEvent::new(PointerEventKind::Up, 0, position).with_timestamp(now),
+            );
+        }
+        self.pipeline.handle_deadlines(now);
     }
 
     fn draw(&mut self, draw_args: AppDrawArgs) {
@@ -100,10 +112,8 @@ impl AppHandler for App {
             //if self.previous_frame.elapsed() > Duration::from_millis(100) {
             let canvas = draw_args.canvas;
             canvas.clear(0);
-
-            let mut context = PaintContext::new(canvas);
             self.recomposer.compose(|cx| {
-                self.pipeline.draw_frame(cx, &mut context);
+                self.pipeline.draw_frame(cx, canvas);
             });
             self.previous_frame = draw_args.time_state.current_instant();
         }
@@ -0,0 +1,485 @@
+use std::{
+    cell::{Cell, RefCell},
+    rc::{Rc, Weak},
+    time::Duration,
+};
+
+use crate::{
+    animation::{AnimationStatus, ListenerId, Listeners},
+    physics::{Simulation, SpringDescription, SpringSimulation, Tolerance},
+    scheduler::{SchedulerBinding, Ticker, TickerCallback},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AnimationDirection {
+    Forward,
+    Reverse,
+}
+
+/// Drives a value between its bounds over time, with each frame of a scheduler.
+///
+/// The value moves linearly over the duration of the controller, or as a simulation tells
+/// it, e.g. a spring after a fling. Listeners are notified of each change of the value,
+/// status listeners of each change of the status.
+///
+/// Driving the controller takes an `Rc` of it, the ticker it creates holds it weakly:
+/// ```ignore
+/// let controller = Rc::new(
+///     AnimationController::new(&scheduler).with_duration(Duration::from_millis(200)),
+/// );
+/// controller.forward();
+/// ```
+#[derive(Debug)]
+pub struct AnimationController {
+    scheduler: Rc<SchedulerBinding>,
+    lower_bound: f32,
+    upper_bound: f32,
+    duration: Cell<Option<Duration>>,
+    reverse_duration: Cell<Option<Duration>>,
+    value: Cell<f32>,
+    status: Cell<AnimationStatus>,
+    last_reported_status: Cell<AnimationStatus>,
+    direction: Cell<AnimationDirection>,
+    simulation: RefCell<Option<Box<dyn Simulation>>>,
+    last_elapsed: Cell<Option<Duration>>,
+    ticker: RefCell<Option<Rc<Ticker>>>,
+    listeners: Listeners<()>,
+    status_listeners: Listeners<AnimationStatus>,
+}
+
+impl AnimationController {
+    /// A controller from 0 to 1, dismissed.
+    pub fn new(scheduler: &Rc<SchedulerBinding>) -> Self {
+        AnimationController {
+            scheduler: scheduler.clone(),
+            lower_bound: 0.0,
+            upper_bound: 1.0,
+            duration: Cell::new(None),
+            reverse_duration: Cell::new(None),
+            value: Cell::new(0.0),
+            status: Cell::new(AnimationStatus::Dismissed),
+            last_reported_status: Cell::new(AnimationStatus::Dismissed),
+            direction: Cell::new(AnimationDirection::Forward),
+            simulation: RefCell::new(None),
+            last_elapsed: Cell::new(None),
+            ticker: RefCell::new(None),
+            listeners: Listeners::new(),
+            status_listeners: Listeners::new(),
+        }
+    }
+
+    /// A controller without bounds at 0, e.g. for a physics simulation of a position.
+    pub fn unbounded(scheduler: &Rc<SchedulerBinding>) -> Self {
+        AnimationController::new(scheduler).with_bounds(f32::NEG_INFINITY, f32::INFINITY)
+    }
+
+    /// How long it takes to go from the lower to the upper bound.
+    pub fn with_duration(self, duration: Duration) -> Self {
+        self.duration.set(Some(duration));
+        self
+    }
+
+    /// How long it takes to go from the upper to the lower bound, the duration by default.
+    pub fn with_reverse_duration(self, duration: Duration) -> Self {
+        self.reverse_duration.set(Some(duration));
+        self
+    }
+
+    pub fn with_bounds(mut self, lower_bound: f32, upper_bound: f32) -> Self {
+        assert!(lower_bound <= upper_bound);
+        self.lower_bound = lower_bound;
+        self.upper_bound = upper_bound;
+        let value = self.value.get();
+        self.with_value(value)
+    }
+
+    pub fn with_value(self, value: f32) -> Self {
+        self.value
+            .set(value.clamp(self.lower_bound, self.upper_bound));
+        self.update_status_from_value();
+        self.last_reported_status.set(self.status.get());
+        self
+    }
+
+    pub fn lower_bound(&self) -> f32 {
+        self.lower_bound
+    }
+
+    pub fn upper_bound(&self) -> f32 {
+        self.upper_bound
+    }
+
+    pub fn duration(&self) -> Option<Duration> {
+        self.duration.get()
+    }
+
+    pub fn set_duration(&self, duration: Option<Duration>) {
+        self.duration.set(duration);
+    }
+
+    pub fn set_reverse_duration(&self, duration: Option<Duration>) {
+        self.reverse_duration.set(duration);
+    }
+
+    pub fn value(&self) -> f32 {
+        self.value.get()
+    }
+
+    /// Stops the animation and jumps to `value`, clamped to the bounds.
+    pub fn set_value(&self, value: f32) {
+        self.stop();
+        self.value
+            .set(value.clamp(self.lower_bound, self.upper_bound));
+        self.update_status_from_value();
+        self.listeners.notify(());
+        self.check_status_changed();
+    }
+
+    /// Stops the animation at the lower bound.
+    pub fn reset(&self) {
+        self.set_value(self.lower_bound);
+    }
+
+    /// The rate of change of the value, in units per second, 0 when not animating.
+    pub fn velocity(&self) -> f32 {
+        if !self.is_animating() {
+            return 0.0;
+        }
+        let elapsed = self.last_elapsed.get().unwrap_or_default();
+        match &*self.simulation.borrow() {
+            Some(simulation) => simulation.dx(elapsed.as_secs_f32()),
+            None => 0.0,
+        }
+    }
+
+    pub fn status(&self) -> AnimationStatus {
+        self.status.get()
+    }
+
+    /// Whether the value is changing with the frames.
+    pub fn is_animating(&self) -> bool {
+        self.ticker
+            .borrow()
+            .as_ref()
+            .is_some_and(|ticker| ticker.is_active())
+    }
+
+    /// Mutes the ticker, e.g. while the animation is offscreen, its time keeps running.
+    pub fn set_muted(&self, muted: bool) {
+        if let Some(ticker) = &*self.ticker.borrow() {
+            ticker.set_muted(muted);
+        }
+    }
+
+    /// Animates to the upper bound over the rest of the duration.
+    pub fn forward(self: &Rc<Self>) {
+        self.direction.set(AnimationDirection::Forward);
+        self.animate_to_internal(self.upper_bound, None);
+    }
+
+    /// Animates to the lower bound over the rest of the reverse duration.
+    pub fn reverse(self: &Rc<Self>) {
+        self.direction.set(AnimationDirection::Reverse);
+        self.animate_to_internal(self.lower_bound, None);
+    }
+
+    /// Animates forward to `target` over `duration`, by default the part of the duration
+    /// the distance to `target` is of the bounds.
+    pub fn animate_to(self: &Rc<Self>, target: f32, duration: Option<Duration>) {
+        self.direction.set(AnimationDirection::Forward);
+        self.animate_to_internal(target, duration);
+    }
+
+    /// Animates in reverse to `target`, see [`AnimationController::animate_to`].
+    pub fn animate_back(self: &Rc<Self>, target: f32, duration: Option<Duration>) {
+        self.direction.set(AnimationDirection::Reverse);
+        self.animate_to_internal(target, duration);
+    }
+
+    /// Runs from `min` to `max` over `period` until stopped, then again from `min`, or
+    /// back from `max` when `reverse`. They default to the bounds and the duration.
+    pub fn repeat(
+        self: &Rc<Self>,
+        min: Option<f32>,
+        max: Option<f32>,
+        reverse: bool,
+        period: Option<Duration>,
+    ) {
+        let min = min.unwrap_or(self.lower_bound);
+        let max = max.unwrap_or(self.upper_bound);
+        let period = period
+            .or_else(|| self.duration.get())
+            .expect("AnimationController::repeat called without a period or a duration");
+        assert!(min <= max && min >= self.lower_bound && max <= self.upper_bound);
+        self.stop();
+        let simulation = RepeatingSimulation {
+            initial_value: self.value.get(),
+            min,
+            max,
+            reverse,
+            period: period.as_secs_f32(),
+            controller: Rc::downgrade(self),
+        };
+        self.start_simulation(Box::new(simulation));
+    }
+
+    /// Flings the value with a critically damped spring at `velocity`, in units per second:
+    /// forward to the upper bound, or in reverse to the lower one when negative.
+    pub fn fling(self: &Rc<Self>, velocity: f32) {
+        let tolerance = Tolerance::new(0.01, Tolerance::DEFAULT.time, f32::INFINITY);
+        let (direction, target) = if velocity < 0.0 {
+            (
+                AnimationDirection::Reverse,
+                self.lower_bound - tolerance.distance,
+            )
+        } else {
+            (
+                AnimationDirection::Forward,
+                self.upper_bound + tolerance.distance,
+            )
+        };
+        self.direction.set(direction);
+        self.stop();
+        let spring = SpringDescription::with_damping_ratio(1.0, 500.0, 1.0);
+        let simulation = SpringSimulation::new(spring, self.value.get(), target, velocity)
+            .with_tolerance(tolerance);
+        self.start_simulation(Box::new(simulation));
+    }
+
+    /// Drives the value with `simulation` until it is done, forward.
+    pub fn animate_with<S>(self: &Rc<Self>, simulation: S)
+    where
+        S: 'static + Simulation,
+    {
+        self.direction.set(AnimationDirection::Forward);
+        self.stop();
+        self.start_simulation(Box::new(simulation));
+    }
+
+    /// Stops the animation where it is, the status does not change.
+    pub fn stop(&self) {
+        self.simulation.replace(None);
+        self.last_elapsed.set(None);
+        if let Some(ticker) = &*self.ticker.borrow() {
+            ticker.stop();
+        }
+    }
+
+    /// Calls `listener` each time the value changes.
+    pub fn add_listener<F>(&self, listener: F) -> ListenerId
+    where
+        F: 'static + Fn(),
+    {
+        self.listeners.add(move |()| listener())
+    }
+
+    pub fn remove_listener(&self, id: ListenerId) {
+        self.listeners.remove(id);
+    }
+
+    /// Calls `listener` with the status each time it changes.
+    pub fn add_status_listener<F>(&self, listener: F) -> ListenerId
+    where
+        F: 'static + Fn(AnimationStatus),
+    {
+        self.status_listeners.add(listener)
+    }
+
+    pub fn remove_status_listener(&self, id: ListenerId) {
+        self.status_listeners.remove(id);
+    }
+
+    fn animate_to_internal(self: &Rc<Self>, target: f32, duration: Option<Duration>) {
+        let range = self.upper_bound - self.lower_bound;
+        let duration = duration.unwrap_or_else(|| {
+            let full = match (self.direction.get(), self.reverse_duration.get()) {
+                (AnimationDirection::Reverse, Some(reverse_duration)) => reverse_duration,
+                _ => self
+                    .duration
+                    .get()
+                    .expect("AnimationController animated without a duration"),
+            };
+            let remaining = if range.is_finite() {
+                (target - self.value.get()).abs() / range
+            } else {
+                1.0
+            };
+            full.mul_f32(remaining)
+        });
+        self.stop();
+
+        if duration.is_zero() {
+            if self.value.get() != target {
+                self.value
+                    .set(target.clamp(self.lower_bound, self.upper_bound));
+                self.listeners.notify(());
+            }
+            self.status.set(match self.direction.get() {
+                AnimationDirection::Forward => AnimationStatus::Completed,
+                AnimationDirection::Reverse => AnimationStatus::Dismissed,
+            });
+            self.check_status_changed();
+            return;
+        }
+        let simulation = InterpolationSimulation {
+            begin: self.value.get(),
+            end: target,
+            duration: duration.as_secs_f32(),
+        };
+        self.start_simulation(Box::new(simulation));
+    }
+
+    fn start_simulation(self: &Rc<Self>, simulation: Box<dyn Simulation>) {
+        self.value
+            .set(simulation.x(0.0).clamp(self.lower_bound, self.upper_bound));
+        self.simulation.replace(Some(simulation));
+        self.last_elapsed.set(Some(Duration::ZERO));
+        self.ticker().start();
+        self.status.set(match self.direction.get() {
+            AnimationDirection::Forward => AnimationStatus::Forward,
+            AnimationDirection::Reverse => AnimationStatus::Reverse,
+        });
+        self.check_status_changed();
+    }
+
+    fn ticker(self: &Rc<Self>) -> Rc<Ticker> {
+        self.ticker
+            .borrow_mut()
+            .get_or_insert_with(|| {
+                let this = Rc::downgrade(self);
+                Ticker::new(
+                    &self.scheduler,
+                    TickerCallback::new(move |elapsed| {
+                        if let Some(controller) = this.upgrade() {
+                            controller.tick(elapsed);
+                        }
+                    }),
+                )
+            })
+            .clone()
+    }
+
+    fn tick(&self, elapsed: Duration) {
+        self.last_elapsed.set(Some(elapsed));
+        let seconds = elapsed.as_secs_f32();
+        let (value, done) = match &*self.simulation.borrow() {
+            Some(simulation) => (simulation.x(seconds), simulation.is_done(seconds)),
+            None => return,
+        };
+        self.value
+            .set(value.clamp(self.lower_bound, self.upper_bound));
+        if done {
+            self.status.set(match self.direction.get() {
+                AnimationDirection::Forward => AnimationStatus::Completed,
+                AnimationDirection::Reverse => AnimationStatus::Dismissed,
+            });
+            self.stop();
+        }
+        self.listeners.notify(());
+        self.check_status_changed();
+    }
+
+    fn update_status_from_value(&self) {
+        let value = self.value.get();
+        let status = if value == self.upper_bound {
+            AnimationStatus::Completed
+        } else if value == self.lower_bound {
+            AnimationStatus::Dismissed
+        } else {
+            match self.direction.get() {
+                AnimationDirection::Forward => AnimationStatus::Forward,
+                AnimationDirection::Reverse => AnimationStatus::Reverse,
+            }
+        };
+        self.status.set(status);
+    }
+
+    fn check_status_changed(&self) {
+        let status = self.status.get();
+        if self.last_reported_status.replace(status) != status {
+            self.status_listeners.notify(status);
+        }
+    }
+}
+
+/// Moves linearly from `begin` to `end` over `duration` seconds.
+#[derive(Debug)]
+struct InterpolationSimulation {
+    begin: f32,
+    end: f32,
+    duration: f32,
+}
+
+impl Simulation for InterpolationSimulation {
+    fn x(&self, time: f32) -> f32 {
+        let t = (time / self.duration).clamp(0.0, 1.0);
+        if t == 0.0 {
+            self.begin
+        } else if t == 1.0 {
+            self.end
+        } else {
+            self.begin + (self.end - self.begin) * t
+        }
+    }
+
+    fn dx(&self, time: f32) -> f32 {
+        let epsilon = self.tolerance().time;
+        (self.x(time + epsilon) - self.x(time - epsilon)) / (2.0 * epsilon)
+    }
+
+    fn is_done(&self, time: f32) -> bool {
+        time > self.duration
+    }
+}
+
+/// Runs from `min` to `max` over `period` seconds, and back when `reverse`, starting where
+/// `initial_value` is. It tells the controller the direction it runs in.
+#[derive(Debug)]
+struct RepeatingSimulation {
+    initial_value: f32,
+    min: f32,
+    max: f32,
+    reverse: bool,
+    period: f32,
+    controller: Weak<AnimationController>,
+}
+
+impl Simulation for RepeatingSimulation {
+    fn x(&self, time: f32) -> f32 {
+        if self.max == self.min {
+            return self.min;
+        }
+        let initial_time = (self.initial_value - self.min) / (self.max - self.min) * self.period;
+        let cycles = (time + initial_time) / self.period;
+        let t = cycles.fract();
+        let backwards = self.reverse && cycles as u64 % 2 == 1;
+        if let Some(controller) = self.controller.upgrade() {
+            controller.direction.set(if backwards {
+                AnimationDirection::Reverse
+            } else {
+                AnimationDirection::Forward
+            });
+        }
+        if backwards {
+            self.max + (self.min - self.max) * t
+        } else {
+            self.min + (self.max - self.min) * t
+        }
+    }
+
+    fn dx(&self, time: f32) -> f32 {
+        let speed = (self.max - self.min) / self.period;
+        let initial_time = match self.max == self.min {
+            true => 0.0,
+            false => (self.initial_value - self.min) / (self.max - self.min) * self.period,
+        };
+        match self.reverse && ((time + initial_time) / self.period) as u64 % 2 == 1 {
+            true => -speed,
+            false => speed,
+        }
+    }
+
+    fn is_done(&self, _time: f32) -> bool {
+        false
+    }
+}
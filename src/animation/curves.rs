@@ -0,0 +1,238 @@
+use std::{f32::consts::PI, fmt::Debug};
+
+use crate::physics::{SpringDescription, Tolerance};
+
+/// Maps the progress of an animation, from 0 to 1, to the progress of its value, e.g. to
+/// ease in and out.
+pub trait Curve: Debug {
+    /// The progress at `t`, 0 and 1 at the ends whatever the curve.
+    fn transform(&self, t: f32) -> f32 {
+        assert!((0.0..=1.0).contains(&t), "{} is out of 0..=1", t);
+        if t == 0.0 || t == 1.0 {
+            t
+        } else {
+            self.transform_internal(t)
+        }
+    }
+
+    /// The progress at `t`, strictly between 0 and 1.
+    fn transform_internal(&self, t: f32) -> f32;
+}
+
+/// The identity, the value moves at a constant rate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Linear;
+
+impl Curve for Linear {
+    fn transform_internal(&self, t: f32) -> f32 {
+        t
+    }
+}
+
+/// A cubic bezier from (0, 0) to (1, 1) with the control points (a, b) and (c, d), as the
+/// `cubic-bezier` timing function of CSS.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cubic {
+    pub a: f32,
+    pub b: f32,
+    pub c: f32,
+    pub d: f32,
+}
+
+impl Cubic {
+    pub const fn new(a: f32, b: f32, c: f32, d: f32) -> Self {
+        Cubic { a, b, c, d }
+    }
+
+    fn evaluate(a: f32, b: f32, m: f32) -> f32 {
+        3.0 * a * (1.0 - m) * (1.0 - m) * m + 3.0 * b * (1.0 - m) * m * m + m * m * m
+    }
+}
+
+impl Curve for Cubic {
+    fn transform_internal(&self, t: f32) -> f32 {
+        // bisects the parameter of the curve whose x is t
+        let (mut start, mut end) = (0.0, 1.0);
+        loop {
+            let midpoint = (start + end) / 2.0;
+            let estimate = Cubic::evaluate(self.a, self.c, midpoint);
+            if (t - estimate).abs() < 1e-3 {
+                return Cubic::evaluate(self.b, self.d, midpoint);
+            }
+            if estimate < t {
+                start = midpoint;
+            } else {
+                end = midpoint;
+            }
+        }
+    }
+}
+
+/// Oscillates around the start with a growing amplitude, then snaps to the end.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ElasticInCurve {
+    /// The duration of an oscillation, as a part of the animation.
+    pub period: f32,
+}
+
+impl Curve for ElasticInCurve {
+    fn transform_internal(&self, t: f32) -> f32 {
+        let s = self.period / 4.0;
+        let t = t - 1.0;
+        -(2.0f32.powf(10.0 * t)) * ((t - s) * 2.0 * PI / self.period).sin()
+    }
+}
+
+/// Overshoots the end, then oscillates around it with a shrinking amplitude.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ElasticOutCurve {
+    /// The duration of an oscillation, as a part of the animation.
+    pub period: f32,
+}
+
+impl Curve for ElasticOutCurve {
+    fn transform_internal(&self, t: f32) -> f32 {
+        let s = self.period / 4.0;
+        2.0f32.powf(-10.0 * t) * ((t - s) * 2.0 * PI / self.period).sin() + 1.0
+    }
+}
+
+/// Oscillates around the start with a growing amplitude, then around the end with a
+/// shrinking one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ElasticInOutCurve {
+    /// The duration of an oscillation, as a part of the animation.
+    pub period: f32,
+}
+
+impl Curve for ElasticInOutCurve {
+    fn transform_internal(&self, t: f32) -> f32 {
+        let s = self.period / 4.0;
+        let t = 2.0 * t - 1.0;
+        let oscillation = ((t - s) * 2.0 * PI / self.period).sin();
+        if t < 0.0 {
+            -0.5 * 2.0f32.powf(10.0 * t) * oscillation
+        } else {
+            2.0f32.powf(-10.0 * t) * oscillation * 0.5 + 1.0
+        }
+    }
+}
+
+fn bounce(t: f32) -> f32 {
+    if t < 1.0 / 2.75 {
+        7.5625 * t * t
+    } else if t < 2.0 / 2.75 {
+        let t = t - 1.5 / 2.75;
+        7.5625 * t * t + 0.75
+    } else if t < 2.5 / 2.75 {
+        let t = t - 2.25 / 2.75;
+        7.5625 * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / 2.75;
+        7.5625 * t * t + 0.984375
+    }
+}
+
+/// Bounces off the start with a growing amplitude.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BounceInCurve;
+
+impl Curve for BounceInCurve {
+    fn transform_internal(&self, t: f32) -> f32 {
+        1.0 - bounce(1.0 - t)
+    }
+}
+
+/// Bounces off the end with a shrinking amplitude, as a dropped ball.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BounceOutCurve;
+
+impl Curve for BounceOutCurve {
+    fn transform_internal(&self, t: f32) -> f32 {
+        bounce(t)
+    }
+}
+
+/// Bounces off the start with a growing amplitude, then off the end with a shrinking one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BounceInOutCurve;
+
+impl Curve for BounceInOutCurve {
+    fn transform_internal(&self, t: f32) -> f32 {
+        if t < 0.5 {
+            (1.0 - bounce(1.0 - t * 2.0)) * 0.5
+        } else {
+            bounce(t * 2.0 - 1.0) * 0.5 + 0.5
+        }
+    }
+}
+
+/// A spring released at the start, at rest, pulling to the end. The animation lasts until
+/// the motion of the spring decays to the default tolerance, an underdamped spring
+/// overshoots the end.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpringCurve {
+    pub spring: SpringDescription,
+}
+
+impl SpringCurve {
+    pub const fn new(spring: SpringDescription) -> Self {
+        SpringCurve { spring }
+    }
+}
+
+impl Curve for SpringCurve {
+    fn transform_internal(&self, t: f32) -> f32 {
+        let SpringDescription {
+            mass,
+            stiffness,
+            damping,
+        } = self.spring;
+        let cmk = damping * damping - 4.0 * mass * stiffness;
+        let critical = cmk.abs() <= 16.0 * f32::EPSILON * 4.0 * mass * stiffness;
+        // the slowest decay of the motion, which the duration of the curve is scaled to
+        let decay = if critical || cmk < 0.0 {
+            -damping / (2.0 * mass)
+        } else {
+            (-damping + cmk.sqrt()) / (2.0 * mass)
+        };
+        let time = t * Tolerance::DEFAULT.distance.ln() / decay;
+        if critical {
+            1.0 - (1.0 - decay * time) * (decay * time).exp()
+        } else if cmk < 0.0 {
+            let w = (-cmk).sqrt() / (2.0 * mass);
+            let (sin, cos) = (w * time).sin_cos();
+            1.0 - (decay * time).exp() * (cos - decay / w * sin)
+        } else {
+            let r1 = (-damping - cmk.sqrt()) / (2.0 * mass);
+            // released at rest: c1 + c2 = -1 and c1 r1 + c2 r2 = 0
+            let c2 = r1 / (decay - r1);
+            let c1 = -1.0 - c2;
+            1.0 + c1 * (r1 * time).exp() + c2 * (decay * time).exp()
+        }
+    }
+}
+
+/// The curves commonly animated with.
+pub struct Curves;
+
+impl Curves {
+    pub const LINEAR: Linear = Linear;
+    pub const EASE: Cubic = Cubic::new(0.25, 0.1, 0.25, 1.0);
+    pub const EASE_IN: Cubic = Cubic::new(0.42, 0.0, 1.0, 1.0);
+    pub const EASE_OUT: Cubic = Cubic::new(0.0, 0.0, 0.58, 1.0);
+    pub const EASE_IN_OUT: Cubic = Cubic::new(0.42, 0.0, 0.58, 1.0);
+    pub const FAST_OUT_SLOW_IN: Cubic = Cubic::new(0.4, 0.0, 0.2, 1.0);
+    pub const ELASTIC_IN: ElasticInCurve = ElasticInCurve { period: 0.4 };
+    pub const ELASTIC_OUT: ElasticOutCurve = ElasticOutCurve { period: 0.4 };
+    pub const ELASTIC_IN_OUT: ElasticInOutCurve = ElasticInOutCurve { period: 0.4 };
+    pub const BOUNCE_IN: BounceInCurve = BounceInCurve;
+    pub const BOUNCE_OUT: BounceOutCurve = BounceOutCurve;
+    pub const BOUNCE_IN_OUT: BounceInOutCurve = BounceInOutCurve;
+    /// A spring damped by half of the critical damping, overshooting the end once.
+    pub const SPRING: SpringCurve = SpringCurve::new(SpringDescription {
+        mass: 1.0,
+        stiffness: 100.0,
+        damping: 10.0,
+    });
+}
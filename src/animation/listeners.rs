@@ -0,0 +1,58 @@
+use std::{
+    cell::{Cell, RefCell},
+    fmt,
+    rc::Rc,
+};
+
+/// Identifies a listener added to an animation, to remove it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ListenerId(usize);
+
+type Listener<T> = Rc<dyn Fn(T)>;
+
+/// Listeners called with a value of type `T`, they may add or remove listeners while
+/// notified, the changes apply from the next notification.
+pub(crate) struct Listeners<T> {
+    next_id: Cell<usize>,
+    listeners: RefCell<Vec<(ListenerId, Listener<T>)>>,
+}
+
+impl<T: Copy> Listeners<T> {
+    pub(crate) fn new() -> Self {
+        Listeners {
+            next_id: Cell::new(0),
+            listeners: RefCell::new(Vec::new()),
+        }
+    }
+
+    pub(crate) fn add<F>(&self, listener: F) -> ListenerId
+    where
+        F: 'static + Fn(T),
+    {
+        let id = ListenerId(self.next_id.get());
+        self.next_id.set(id.0 + 1);
+        self.listeners.borrow_mut().push((id, Rc::new(listener)));
+        id
+    }
+
+    pub(crate) fn remove(&self, id: ListenerId) {
+        self.listeners
+            .borrow_mut()
+            .retain(|(listener, _)| *listener != id);
+    }
+
+    pub(crate) fn notify(&self, value: T) {
+        let listeners = self.listeners.borrow().clone();
+        for (_, listener) in listeners {
+            listener(value);
+        }
+    }
+}
+
+impl<T> fmt::Debug for Listeners<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Listeners")
+            .field("len", &self.listeners.borrow().len())
+            .finish()
+    }
+}
@@ -0,0 +1,14 @@
+mod status;
+pub use status::*;
+
+mod listeners;
+pub use listeners::*;
+
+mod animation_controller;
+pub use animation_controller::*;
+
+mod curves;
+pub use curves::*;
+
+mod tween;
+pub use tween::*;
@@ -0,0 +1,20 @@
+/// Where an animation is going.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnimationStatus {
+    /// Stopped at its beginning.
+    Dismissed,
+    /// Running from its beginning to its end.
+    Forward,
+    /// Running from its end to its beginning.
+    Reverse,
+    /// Stopped at its end.
+    Completed,
+}
+
+impl AnimationStatus {
+    /// Whether the animation is running or stopped in the middle, neither at its beginning
+    /// nor at its end.
+    pub fn is_animating(self) -> bool {
+        matches!(self, AnimationStatus::Forward | AnimationStatus::Reverse)
+    }
+}
@@ -0,0 +1,103 @@
+use crate::{
+    animation::{AnimationController, Curve},
+    rendering::{BoxConstraints, Offset, Size},
+    ui::Color,
+};
+
+/// Values interpolated linearly, `t` is 0 at `a` and 1 at `b` and may go past them, e.g.
+/// with an elastic curve.
+pub trait Lerp: Copy {
+    fn lerp(a: Self, b: Self, t: f32) -> Self;
+}
+
+impl Lerp for f32 {
+    fn lerp(a: Self, b: Self, t: f32) -> Self {
+        // equal infinite bounds stay infinite, a finite and an infinite one have nothing in
+        // between so the value jumps halfway
+        if a == b {
+            a
+        } else if a.is_infinite() || b.is_infinite() {
+            if t < 0.5 {
+                a
+            } else {
+                b
+            }
+        } else {
+            a + (b - a) * t
+        }
+    }
+}
+
+impl Lerp for Offset {
+    fn lerp(a: Self, b: Self, t: f32) -> Self {
+        Offset::new(f32::lerp(a.x, b.x, t), f32::lerp(a.y, b.y, t))
+    }
+}
+
+impl Lerp for Size {
+    fn lerp(a: Self, b: Self, t: f32) -> Self {
+        Size::new(
+            f32::lerp(a.width, b.width, t),
+            f32::lerp(a.height, b.height, t),
+        )
+    }
+}
+
+impl Lerp for BoxConstraints {
+    fn lerp(a: Self, b: Self, t: f32) -> Self {
+        BoxConstraints {
+            min_width: f32::lerp(a.min_width, b.min_width, t),
+            max_width: f32::lerp(a.max_width, b.max_width, t),
+            min_height: f32::lerp(a.min_height, b.min_height, t),
+            max_height: f32::lerp(a.max_height, b.max_height, t),
+        }
+    }
+}
+
+impl Lerp for Color {
+    fn lerp(a: Self, b: Self, t: f32) -> Self {
+        let channel =
+            |a: u8, b: u8| f32::lerp(a as f32, b as f32, t).round().clamp(0.0, 255.0) as u8;
+        Color::from_argb(
+            channel(a.a(), b.a()),
+            channel(a.r(), b.r()),
+            channel(a.g(), b.g()),
+            channel(a.b(), b.b()),
+        )
+    }
+}
+
+/// Maps the value of an animation, from 0 to 1, to a value from `begin` to `end`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tween<T> {
+    pub begin: T,
+    pub end: T,
+}
+
+impl<T: Lerp> Tween<T> {
+    pub fn new(begin: T, end: T) -> Self {
+        Tween { begin, end }
+    }
+
+    /// The value at `t`, `begin` at 0 and `end` at 1.
+    pub fn transform(&self, t: f32) -> T {
+        if t == 0.0 {
+            self.begin
+        } else if t == 1.0 {
+            self.end
+        } else {
+            T::lerp(self.begin, self.end, t)
+        }
+    }
+
+    /// The value at the current value of `controller`.
+    pub fn evaluate(&self, controller: &AnimationController) -> T {
+        self.transform(controller.value())
+    }
+
+    /// The value at the current value of `controller` along `curve`, the controller going
+    /// from 0 to 1.
+    pub fn evaluate_curved(&self, controller: &AnimationController, curve: &dyn Curve) -> T {
+        self.transform(curve.transform(controller.value()))
+    }
+}
@@ -0,0 +1,245 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fmt::Debug,
+    rc::{Rc, Weak},
+};
+
+/// A participant of the gesture arena of a pointer.
+pub trait GestureArenaMember: Debug {
+    /// Called when this member wins the arena for `pointer`.
+    fn accept_gesture(&self, pointer: usize);
+
+    /// Called when this member loses the arena for `pointer`.
+    fn reject_gesture(&self, pointer: usize);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GestureDisposition {
+    Accepted,
+    Rejected,
+}
+
+pub(crate) fn is_same_member(
+    a: &Rc<dyn GestureArenaMember>,
+    b: &Rc<dyn GestureArenaMember>,
+) -> bool {
+    Rc::as_ptr(a) as *const () == Rc::as_ptr(b) as *const ()
+}
+
+/// A member's ticket into the arena of a pointer.
+#[derive(Debug, Clone)]
+pub struct GestureArenaEntry {
+    pub(crate) arena: Rc<GestureArenaManager>,
+    pub(crate) pointer: usize,
+    pub(crate) member: Weak<dyn GestureArenaMember>,
+}
+
+impl GestureArenaEntry {
+    /// Declares the member won or lost, has no effect once the arena is resolved.
+    pub fn resolve(&self, disposition: GestureDisposition) {
+        if let Some(member) = self.member.upgrade() {
+            self.arena.resolve(self.pointer, &member, disposition);
+        }
+    }
+}
+
+#[derive(Debug)]
+struct GestureArena {
+    members: Vec<Rc<dyn GestureArenaMember>>,
+    is_open: bool,
+    is_held: bool,
+    has_pending_sweep: bool,
+    eager_winner: Option<Rc<dyn GestureArenaMember>>,
+}
+
+impl GestureArena {
+    fn new() -> Self {
+        GestureArena {
+            members: Vec::new(),
+            is_open: true,
+            is_held: false,
+            has_pending_sweep: false,
+            eager_winner: None,
+        }
+    }
+}
+
+/// Decides which of the recognizers competing for a pointer wins.
+///
+/// Members join while the arena is open, i.e. until the down event has been dispatched.
+/// The arena resolves as soon as one member accepts once the arena is closed, otherwise
+/// the first member wins when the pointer goes up. A single member left in a closed arena
+/// wins by default once the current event is handled, see
+/// [`GestureArenaManager::flush`].
+#[derive(Debug, Default)]
+pub struct GestureArenaManager {
+    arenas: RefCell<HashMap<usize, GestureArena>>,
+    pending_defaults: RefCell<Vec<usize>>,
+}
+
+impl GestureArenaManager {
+    pub fn new() -> Self {
+        GestureArenaManager::default()
+    }
+
+    pub fn add(
+        self: &Rc<Self>,
+        pointer: usize,
+        member: Rc<dyn GestureArenaMember>,
+    ) -> GestureArenaEntry {
+        self.arenas
+            .borrow_mut()
+            .entry(pointer)
+            .or_insert_with(GestureArena::new)
+            .members
+            .push(member.clone());
+        GestureArenaEntry {
+            arena: self.clone(),
+            pointer,
+            member: Rc::downgrade(&member),
+        }
+    }
+
+    /// Prevents new members from entering the arena.
+    pub fn close(&self, pointer: usize) {
+        match self.arenas.borrow_mut().get_mut(&pointer) {
+            Some(arena) => arena.is_open = false,
+            None => return,
+        }
+        self.try_resolve(pointer);
+    }
+
+    /// Forces the arena to resolve in favor of its first member.
+    pub fn sweep(&self, pointer: usize) {
+        let arena = {
+            let mut arenas = self.arenas.borrow_mut();
+            match arenas.get_mut(&pointer) {
+                Some(arena) if arena.is_held => {
+                    arena.has_pending_sweep = true;
+                    return;
+                }
+                Some(_) => arenas.remove(&pointer).unwrap(),
+                None => return,
+            }
+        };
+        let mut members = arena.members.into_iter();
+        if let Some(winner) = members.next() {
+            winner.accept_gesture(pointer);
+            for member in members {
+                member.reject_gesture(pointer);
+            }
+        }
+    }
+
+    /// Defers sweeping the arena until [`GestureArenaManager::release`].
+    pub fn hold(&self, pointer: usize) {
+        if let Some(arena) = self.arenas.borrow_mut().get_mut(&pointer) {
+            arena.is_held = true;
+        }
+    }
+
+    pub fn release(&self, pointer: usize) {
+        let has_pending_sweep = match self.arenas.borrow_mut().get_mut(&pointer) {
+            Some(arena) => {
+                arena.is_held = false;
+                arena.has_pending_sweep
+            }
+            None => return,
+        };
+        if has_pending_sweep {
+            self.sweep(pointer);
+        }
+    }
+
+    pub fn resolve(
+        &self,
+        pointer: usize,
+        member: &Rc<dyn GestureArenaMember>,
+        disposition: GestureDisposition,
+    ) {
+        match disposition {
+            GestureDisposition::Rejected => {
+                let is_open = {
+                    let mut arenas = self.arenas.borrow_mut();
+                    let arena = match arenas.get_mut(&pointer) {
+                        Some(arena) => arena,
+                        None => return,
+                    };
+                    let len = arena.members.len();
+                    arena.members.retain(|m| !is_same_member(m, member));
+                    if arena.members.len() == len {
+                        return;
+                    }
+                    arena.is_open
+                };
+                member.reject_gesture(pointer);
+                if !is_open {
+                    self.try_resolve(pointer);
+                }
+            }
+            GestureDisposition::Accepted => {
+                let is_open = match self.arenas.borrow_mut().get_mut(&pointer) {
+                    Some(arena) => {
+                        if arena.is_open && arena.eager_winner.is_none() {
+                            arena.eager_winner = Some(member.clone());
+                        }
+                        arena.is_open
+                    }
+                    None => return,
+                };
+                if !is_open {
+                    self.resolve_in_favor_of(pointer, member);
+                }
+            }
+        }
+    }
+
+    fn try_resolve(&self, pointer: usize) {
+        let (members, eager_winner) = match self.arenas.borrow().get(&pointer) {
+            Some(arena) => (arena.members.len(), arena.eager_winner.clone()),
+            None => return,
+        };
+        if members == 0 {
+            self.arenas.borrow_mut().remove(&pointer);
+        } else if members == 1 {
+            self.pending_defaults.borrow_mut().push(pointer);
+        } else if let Some(winner) = eager_winner {
+            self.resolve_in_favor_of(pointer, &winner);
+        }
+    }
+
+    /// Resolves the arenas left with a single member in favor of that member.
+    pub fn flush(&self) {
+        loop {
+            let pointers = std::mem::take(&mut *self.pending_defaults.borrow_mut());
+            if pointers.is_empty() {
+                break;
+            }
+            for pointer in pointers {
+                // the arena may have been resolved since
+                let arena = match self.arenas.borrow_mut().remove(&pointer) {
+                    Some(arena) => arena,
+                    None => continue,
+                };
+                debug_assert_eq!(arena.members.len(), 1);
+                for member in &arena.members {
+                    member.accept_gesture(pointer);
+                }
+            }
+        }
+    }
+
+    fn resolve_in_favor_of(&self, pointer: usize, winner: &Rc<dyn GestureArenaMember>) {
+        let arena = match self.arenas.borrow_mut().remove(&pointer) {
+            Some(arena) => arena,
+            None => return,
+        };
+        for member in &arena.members {
+            if !is_same_member(member, winner) {
+                member.reject_gesture(pointer);
+            }
+        }
+        winner.accept_gesture(pointer);
+    }
+}
@@ -0,0 +1,127 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    rc::{Rc, Weak},
+    time::Duration,
+};
+
+use crate::{
+    gestures::{
+        GestureArenaManager, GestureRecognizer, HitTestResult, PointerEvent, PointerEventKind,
+        PointerRouter,
+    },
+    rendering::RenderBox,
+};
+
+/// Routes pointer events to the targets under the pointer.
+///
+/// The hit test path of a down event is kept until the pointer is released, so all later
+/// move, up and cancel events of that pointer reach the same targets, even once the
+/// pointer has left their bounds. Recognizers of the targets hit by a down event compete
+/// in the gesture arena of the pointer, which closes once the down event is dispatched
+/// and is swept when the pointer goes up.
+#[derive(Debug, Default)]
+pub struct GestureBinding {
+    hit_tests: HashMap<usize, HitTestResult>,
+    pub(crate) pointer_router: Rc<PointerRouter>,
+    pub(crate) gesture_arena: Rc<GestureArenaManager>,
+    recognizers: RefCell<Vec<Weak<dyn GestureRecognizer>>>,
+}
+
+impl GestureBinding {
+    pub fn new() -> Self {
+        GestureBinding::default()
+    }
+
+    pub fn handle_event(&mut self, root: &Rc<RefCell<dyn RenderBox>>, event: PointerEvent) {
+        self.handle_deadlines(event.timestamp);
+
+        let result = match event.kind {
+            PointerEventKind::Down | PointerEventKind::Hover | PointerEventKind::Scroll { .. } => {
+                let mut result = HitTestResult::new();
+                root.borrow().hit_test(event.position, &mut result);
+                if event.kind == PointerEventKind::Down {
+                    self.hit_tests.insert(event.pointer, result.clone());
+                }
+                Some(result)
+            }
+            PointerEventKind::Move => self.hit_tests.get(&event.pointer).cloned(),
+            PointerEventKind::Up | PointerEventKind::Cancel => {
+                self.hit_tests.remove(&event.pointer)
+            }
+        };
+
+        if let Some(result) = result {
+            self.dispatch_event(&event, &result);
+        }
+
+        match event.kind {
+            PointerEventKind::Down => {
+                self.pointer_router.route(&event);
+                self.gesture_arena.close(event.pointer);
+            }
+            PointerEventKind::Move | PointerEventKind::Cancel => {
+                self.pointer_router.route(&event);
+            }
+            PointerEventKind::Up => {
+                self.pointer_router.route(&event);
+                self.gesture_arena.sweep(event.pointer);
+            }
+            _ => {}
+        }
+        self.gesture_arena.flush();
+    }
+
+    /// Dispatches `event` to the targets of `result`, adding their recognizers to the
+    /// pointer for down events.
+    pub fn dispatch_event(&self, event: &PointerEvent, result: &HitTestResult) {
+        for entry in &result.path {
+            let event = event.transformed(entry.transform);
+            entry
+                .target
+                .borrow_mut()
+                .handle_event(event.clone(), entry.clone());
+            if event.kind == PointerEventKind::Down {
+                let recognizers = entry.target.borrow().gesture_recognizers();
+                for recognizer in recognizers {
+                    self.add_recognizer(&recognizer);
+                    recognizer.add_pointer(&event, self);
+                }
+            }
+        }
+    }
+
+    /// Lets recognizers waiting for a timeout, e.g. a long press, check it against `now`.
+    ///
+    /// Events call this with their timestamp, the app should call it periodically while
+    /// no events arrive.
+    pub fn handle_deadlines(&self, now: Duration) {
+        let recognizers: Vec<_> = {
+            let mut recognizers = self.recognizers.borrow_mut();
+            recognizers.retain(|r| r.strong_count() > 0);
+            recognizers.iter().filter_map(|r| r.upgrade()).collect()
+        };
+        for recognizer in recognizers {
+            recognizer.handle_deadline(now);
+        }
+        self.gesture_arena.flush();
+    }
+
+    fn add_recognizer(&self, recognizer: &Rc<dyn GestureRecognizer>) {
+        let mut recognizers = self.recognizers.borrow_mut();
+        let ptr = Rc::as_ptr(recognizer) as *const ();
+        if !recognizers.iter().any(|r| r.as_ptr() as *const () == ptr) {
+            recognizers.push(Rc::downgrade(recognizer));
+        }
+    }
+
+    /// Whether the pointer is down, i.e. its events are routed to a stored hit test path.
+    pub fn is_tracking(&self, pointer: usize) -> bool {
+        self.hit_tests.contains_key(&pointer)
+    }
+
+    /// The hit test path events of the pointer are routed to while it is down.
+    pub fn hit_test_result(&self, pointer: usize) -> Option<&HitTestResult> {
+        self.hit_tests.get(&pointer)
+    }
+}
@@ -0,0 +1,33 @@
+use std::time::Duration;
+
+/// Time a pointer has to stay down before a tap down is reported, unless the tap wins
+/// the arena earlier.
+pub const PRESS_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Maximum time between the up of the first tap and the down of the second tap of a
+/// double tap.
+pub const DOUBLE_TAP_TIMEOUT: Duration = Duration::from_millis(300);
+
+/// Time a pointer has to stay down to trigger a long press.
+pub const LONG_PRESS_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Distance a pointer can travel before it is considered a drag.
+pub const TOUCH_SLOP: f32 = 18.0;
+
+/// Maximum distance between the two taps of a double tap.
+pub const DOUBLE_TAP_SLOP: f32 = 100.0;
+
+/// Distance a pointer can travel before it is considered a pan.
+pub const PAN_SLOP: f32 = TOUCH_SLOP * 2.0;
+
+/// Change of the span between pointers before it is considered a scale.
+pub const SCALE_SLOP: f32 = TOUCH_SLOP;
+
+/// Distance a pointer has to travel for a drag to end in a fling.
+pub const MIN_FLING_DISTANCE: f32 = TOUCH_SLOP;
+
+/// Speed, in pixels per second, a pointer has to move at for a drag to end in a fling.
+pub const MIN_FLING_VELOCITY: f32 = 50.0;
+
+/// Speed, in pixels per second, the velocity of a fling is clamped to.
+pub const MAX_FLING_VELOCITY: f32 = 8000.0;
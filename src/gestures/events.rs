@@ -0,0 +1,113 @@
+use std::time::Duration;
+
+use crate::rendering::{Matrix4, Offset};
+
+/// The bit of [`PointerEvent::buttons`] for the primary mouse button, or a touch or pen
+/// contact.
+pub const PRIMARY_BUTTON: u32 = 0x01;
+
+/// The bit of [`PointerEvent::buttons`] for the secondary mouse button, or the pen barrel
+/// button.
+pub const SECONDARY_BUTTON: u32 = 0x02;
+
+/// The bit of [`PointerEvent::buttons`] for the middle mouse button.
+pub const MIDDLE_BUTTON: u32 = 0x04;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointerDeviceKind {
+    Mouse,
+    Touch,
+    Pen,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PointerEventKind {
+    /// The pointer made contact, its hit test path receives the rest of the sequence.
+    Down,
+    /// The pointer moved while in contact.
+    Move,
+    /// The pointer stopped making contact.
+    Up,
+    /// The pointer sequence was aborted by the platform.
+    Cancel,
+    /// The pointer moved without being in contact, e.g. a mouse without buttons pressed.
+    Hover,
+    /// The pointer scrolled, e.g. by a mouse wheel.
+    Scroll { scroll_delta: Offset },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PointerEvent {
+    pub kind: PointerEventKind,
+    /// Identifies the pointer, unique among the pointers currently in contact.
+    pub pointer: usize,
+    pub device_kind: PointerDeviceKind,
+    /// Bit field of the pressed buttons, see [`PRIMARY_BUTTON`].
+    pub buttons: u32,
+    /// Time of the event, relative to an arbitrary origin.
+    pub timestamp: Duration,
+    /// Position in global coordinates.
+    pub position: Offset,
+    /// Position in the coordinates of the target handling the event.
+    pub local_position: Offset,
+    /// Distance moved since the previous event of this pointer.
+    pub delta: Offset,
+    /// Transform from global coordinates to those of the target, `None` for identity.
+    pub transform: Option<Matrix4>,
+}
+
+impl PointerEvent {
+    pub fn new(kind: PointerEventKind, pointer: usize, position: Offset) -> Self {
+        let buttons = match kind {
+            PointerEventKind::Down | PointerEventKind::Move => PRIMARY_BUTTON,
+            _ => 0,
+        };
+        PointerEvent {
+            kind,
+            pointer,
+            device_kind: PointerDeviceKind::Mouse,
+            buttons,
+            timestamp: Duration::ZERO,
+            position,
+            local_position: position,
+            delta: Offset::zero(),
+            transform: None,
+        }
+    }
+
+    pub fn with_device_kind(mut self, device_kind: PointerDeviceKind) -> Self {
+        self.device_kind = device_kind;
+        self
+    }
+
+    pub fn with_buttons(mut self, buttons: u32) -> Self {
+        self.buttons = buttons;
+        self
+    }
+
+    pub fn with_timestamp(mut self, timestamp: Duration) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    pub fn with_delta(mut self, delta: Offset) -> Self {
+        self.delta = delta;
+        self
+    }
+
+    /// This event as seen by a target with the global-to-local `transform`.
+    pub fn transformed(&self, transform: Option<Matrix4>) -> Self {
+        PointerEvent {
+            local_position: PointerEvent::transform_position(transform, self.position),
+            transform,
+            ..self.clone()
+        }
+    }
+
+    pub fn transform_position(transform: Option<Matrix4>, position: Offset) -> Offset {
+        match transform {
+            Some(transform) => transform.transform_point(position),
+            None => position,
+        }
+    }
+}
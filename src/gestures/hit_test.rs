@@ -1,49 +1,140 @@
 use std::{cell::RefCell, fmt::Debug, rc::Rc};
 
-use crate::rendering::Offset;
+use crate::{
+    gestures::{GestureRecognizer, PointerEvent},
+    rendering::{Matrix4, Offset},
+    services::{KeyEvent, TextInputClient},
+};
 
 pub trait HitTestTarget: Debug {
     fn handle_event(&mut self, event: PointerEvent, entry: HitTestEntry) {}
+
+    /// Handles a key event while this target has the keyboard focus, returns whether it
+    /// was handled, which stops it from reaching the targets further out.
+    fn handle_key_event(&mut self, event: &KeyEvent) -> bool {
+        false
+    }
+
+    /// Called when this target gains or loses the keyboard focus.
+    fn handle_focus_change(&mut self, has_focus: bool) {}
+
+    /// This target as the client of input methods, while it has the keyboard focus.
+    fn text_input_client(&mut self) -> Option<&mut dyn TextInputClient> {
+        None
+    }
+
+    /// Recognizers competing for the pointers going down on this target.
+    fn gesture_recognizers(&self) -> Vec<Rc<dyn GestureRecognizer>> {
+        Vec::new()
+    }
 }
 
-#[derive(Debug)]
+/// How a target with recognizers behaves during hit testing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HitTestBehavior {
+    /// Hit only if a child is hit.
+    DeferToChild,
+    /// Hit anywhere within its bounds.
+    Opaque,
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct HitTestResult {
     pub(crate) path: Vec<HitTestEntry>,
+    /// Global-to-local transforms of the pushed transforms, the innermost last.
+    transforms: Vec<Matrix4>,
 }
 
 impl HitTestResult {
     pub fn new() -> HitTestResult {
-        HitTestResult { path: Vec::new() }
+        HitTestResult::default()
     }
 
-    pub fn add(&mut self, entry: HitTestEntry) {
+    /// Adds `entry` to the path, recording the transform currently pushed.
+    pub fn add(&mut self, mut entry: HitTestEntry) {
+        entry.transform = self.transforms.last().copied();
         self.path.push(entry)
     }
+
+    pub fn path(&self) -> &[HitTestEntry] {
+        &self.path
+    }
+
+    /// Pushes a transform from the coordinates of a parent to those of its child, entries
+    /// added until the matching [`HitTestResult::pop_transform`] record it.
+    pub fn push_transform(&mut self, transform: Matrix4) {
+        let global = match self.transforms.last() {
+            Some(current) => transform * *current,
+            None => transform,
+        };
+        self.transforms.push(global);
+    }
+
+    /// Pushes the offset of a child painted at `offset` in its parent.
+    pub fn push_offset(&mut self, offset: Offset) {
+        self.push_transform(Matrix4::translation(-offset.x, -offset.y, 0.0));
+    }
+
+    pub fn pop_transform(&mut self) {
+        debug_assert!(!self.transforms.is_empty());
+        self.transforms.pop();
+    }
+
+    /// Hit tests a child painted at `offset`, `hit_test` receives the position in the
+    /// child's coordinates.
+    pub fn add_with_paint_offset<F>(
+        &mut self,
+        offset: Offset,
+        position: Offset,
+        hit_test: F,
+    ) -> bool
+    where
+        F: FnOnce(&mut HitTestResult, Offset) -> bool,
+    {
+        self.push_offset(offset);
+        let is_hit = hit_test(self, position - offset);
+        self.pop_transform();
+        is_hit
+    }
+
+    /// Hit tests a child painted with `transform`, mapping the child's coordinates to
+    /// those of its parent. A child under a singular transform is never hit.
+    pub fn add_with_paint_transform<F>(
+        &mut self,
+        transform: Matrix4,
+        position: Offset,
+        hit_test: F,
+    ) -> bool
+    where
+        F: FnOnce(&mut HitTestResult, Offset) -> bool,
+    {
+        let inverse = match transform.invert() {
+            Some(inverse) => inverse,
+            None => return false,
+        };
+        self.push_transform(inverse);
+        let is_hit = hit_test(self, inverse.transform_point(position));
+        self.pop_transform();
+        is_hit
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct HitTestEntry {
     pub(crate) target: Rc<RefCell<dyn HitTestTarget>>,
-    //transform: Option<Matrix4>
+    /// Transform from global coordinates to those of the target, `None` for identity.
+    pub(crate) transform: Option<Matrix4>,
 }
 
 impl HitTestEntry {
     pub fn new(target: Rc<RefCell<dyn HitTestTarget>>) -> Self {
-        HitTestEntry { target }
+        HitTestEntry {
+            target,
+            transform: None,
+        }
     }
-}
 
-#[derive(Debug)]
-pub struct PointerEvent {
-    down: bool,
-    position: Offset,
-}
-
-impl PointerEvent {
-    pub fn new(position: Offset) -> Self {
-        PointerEvent {
-            down: false,
-            position,
-        }
+    pub fn transform(&self) -> Option<Matrix4> {
+        self.transform
     }
 }
@@ -0,0 +1,215 @@
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+use crate::{
+    gestures::{
+        invoke_callback, GestureArenaMember, GestureBinding, GestureCallback, GestureDisposition,
+        GestureRecognizer, PointerEvent, PointerEventKind, PointerTracker, LONG_PRESS_TIMEOUT,
+        TOUCH_SLOP,
+    },
+    rendering::Offset,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LongPressStartDetails {
+    pub global_position: Offset,
+    pub local_position: Offset,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LongPressMoveUpdateDetails {
+    pub global_position: Offset,
+    pub local_position: Offset,
+    /// Distance moved since the long press started.
+    pub offset_from_origin: Offset,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LongPressEndDetails {
+    pub global_position: Offset,
+    pub local_position: Offset,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LongPressCallbacks {
+    pub on_long_press_start: Option<GestureCallback<LongPressStartDetails>>,
+    pub on_long_press: Option<GestureCallback<()>>,
+    pub on_long_press_move_update: Option<GestureCallback<LongPressMoveUpdateDetails>>,
+    pub on_long_press_end: Option<GestureCallback<LongPressEndDetails>>,
+}
+
+impl LongPressCallbacks {
+    pub fn with_on_long_press_start(mut self, f: impl Fn(LongPressStartDetails) + 'static) -> Self {
+        self.on_long_press_start = Some(GestureCallback::new(f));
+        self
+    }
+
+    pub fn with_on_long_press(mut self, f: impl Fn(()) + 'static) -> Self {
+        self.on_long_press = Some(GestureCallback::new(f));
+        self
+    }
+
+    pub fn with_on_long_press_move_update(
+        mut self,
+        f: impl Fn(LongPressMoveUpdateDetails) + 'static,
+    ) -> Self {
+        self.on_long_press_move_update = Some(GestureCallback::new(f));
+        self
+    }
+
+    pub fn with_on_long_press_end(mut self, f: impl Fn(LongPressEndDetails) + 'static) -> Self {
+        self.on_long_press_end = Some(GestureCallback::new(f));
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.on_long_press_start.is_none()
+            && self.on_long_press.is_none()
+            && self.on_long_press_move_update.is_none()
+            && self.on_long_press_end.is_none()
+    }
+}
+
+#[derive(Debug, Default)]
+struct LongPressState {
+    primary_pointer: Option<usize>,
+    down: Option<PointerEvent>,
+    deadline: Option<Duration>,
+    accepted: bool,
+}
+
+/// Recognizes a pointer staying down for [`LONG_PRESS_TIMEOUT`] without moving further
+/// than [`TOUCH_SLOP`].
+///
+/// Winning the arena earlier has no effect, the long press claims the arena once the
+/// timeout expired and may then move freely.
+#[derive(Debug, Default)]
+pub struct LongPressGestureRecognizer {
+    callbacks: RefCell<LongPressCallbacks>,
+    tracker: PointerTracker,
+    state: RefCell<LongPressState>,
+}
+
+impl LongPressGestureRecognizer {
+    pub fn new(callbacks: LongPressCallbacks) -> Self {
+        LongPressGestureRecognizer {
+            callbacks: RefCell::new(callbacks),
+            ..Default::default()
+        }
+    }
+
+    pub fn set_callbacks(&self, callbacks: LongPressCallbacks) {
+        *self.callbacks.borrow_mut() = callbacks;
+    }
+
+    fn reset(&self) {
+        *self.state.borrow_mut() = LongPressState::default();
+    }
+
+    fn give_up(&self, pointer: usize) {
+        self.reset();
+        self.tracker.stop_tracking(pointer, self);
+        self.tracker.resolve(GestureDisposition::Rejected);
+    }
+}
+
+impl GestureArenaMember for LongPressGestureRecognizer {
+    fn accept_gesture(&self, pointer: usize) {}
+
+    fn reject_gesture(&self, pointer: usize) {
+        self.tracker.stop_tracking(pointer, self);
+        if self.state.borrow().primary_pointer == Some(pointer) {
+            self.reset();
+        }
+    }
+}
+
+impl GestureRecognizer for LongPressGestureRecognizer {
+    fn add_pointer(self: Rc<Self>, event: &PointerEvent, binding: &GestureBinding) {
+        {
+            let mut state = self.state.borrow_mut();
+            if state.primary_pointer.is_some() {
+                return;
+            }
+            *state = LongPressState {
+                primary_pointer: Some(event.pointer),
+                down: Some(event.clone()),
+                deadline: Some(event.timestamp + LONG_PRESS_TIMEOUT),
+                accepted: false,
+            };
+        }
+        self.tracker.start_tracking(self.clone(), event, binding);
+    }
+
+    fn handle_event(&self, event: &PointerEvent) {
+        if self.state.borrow().primary_pointer != Some(event.pointer) {
+            return;
+        }
+        self.handle_deadline(event.timestamp);
+
+        let (down_position, accepted) = match &*self.state.borrow() {
+            LongPressState {
+                down: Some(down),
+                accepted,
+                ..
+            } => (down.position, *accepted),
+            _ => return,
+        };
+        match event.kind {
+            PointerEventKind::Move if accepted => {
+                let callback = self.callbacks.borrow().on_long_press_move_update.clone();
+                invoke_callback(
+                    callback,
+                    LongPressMoveUpdateDetails {
+                        global_position: event.position,
+                        local_position: event.local_position,
+                        offset_from_origin: event.position - down_position,
+                    },
+                );
+            }
+            PointerEventKind::Move => {
+                if (event.position - down_position).distance() > TOUCH_SLOP {
+                    self.give_up(event.pointer);
+                }
+            }
+            PointerEventKind::Up if accepted => {
+                self.reset();
+                self.tracker.stop_tracking(event.pointer, self);
+                let callback = self.callbacks.borrow().on_long_press_end.clone();
+                invoke_callback(
+                    callback,
+                    LongPressEndDetails {
+                        global_position: event.position,
+                        local_position: event.local_position,
+                    },
+                );
+            }
+            PointerEventKind::Up | PointerEventKind::Cancel => self.give_up(event.pointer),
+            _ => {}
+        }
+    }
+
+    fn handle_deadline(&self, now: Duration) {
+        let down = {
+            let mut state = self.state.borrow_mut();
+            match (state.deadline, &state.down) {
+                (Some(deadline), Some(down)) if now >= deadline => {
+                    let down = down.clone();
+                    state.deadline = None;
+                    state.accepted = true;
+                    down
+                }
+                _ => return,
+            }
+        };
+        self.tracker.resolve(GestureDisposition::Accepted);
+        let callbacks = self.callbacks.borrow().clone();
+        invoke_callback(
+            callbacks.on_long_press_start,
+            LongPressStartDetails {
+                global_position: down.position,
+                local_position: down.local_position,
+            },
+        );
+        invoke_callback(callbacks.on_long_press, ());
+    }
+}
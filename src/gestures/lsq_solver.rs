@@ -0,0 +1,109 @@
+/// Below which a vector is considered zero while solving.
+const PRECISION_ERROR_TOLERANCE: f64 = 1e-10;
+
+/// A polynomial fitted to samples, `coefficients[i]` being the coefficient of `x^i`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PolynomialFit {
+    pub coefficients: Vec<f32>,
+    /// The coefficient of determination of the fit, 1 for samples right on the curve.
+    pub confidence: f32,
+}
+
+/// Fits a polynomial to weighted samples `(x, y)` by least squares.
+#[derive(Debug, Clone, Copy)]
+pub struct LeastSquaresSolver<'a> {
+    pub x: &'a [f32],
+    pub y: &'a [f32],
+    pub w: &'a [f32],
+}
+
+impl<'a> LeastSquaresSolver<'a> {
+    pub fn new(x: &'a [f32], y: &'a [f32], w: &'a [f32]) -> Self {
+        assert!(x.len() == y.len() && y.len() == w.len());
+        LeastSquaresSolver { x, y, w }
+    }
+
+    /// The polynomial of `degree` fitting the samples best, `None` if there are too few
+    /// samples or they do not determine the polynomial, e.g. all at the same `x`.
+    pub fn solve(&self, degree: usize) -> Option<PolynomialFit> {
+        if degree > self.x.len() {
+            return None;
+        }
+        let m = self.x.len();
+        let n = degree + 1;
+        let x: Vec<f64> = self.x.iter().map(|x| *x as f64).collect();
+        let y: Vec<f64> = self.y.iter().map(|y| *y as f64).collect();
+        let w: Vec<f64> = self.w.iter().map(|w| *w as f64).collect();
+        let dot = |a: &[f64], b: &[f64]| a.iter().zip(b).map(|(a, b)| a * b).sum::<f64>();
+
+        // the powers of x as rows, weighted
+        let mut a = vec![vec![0.0; m]; n];
+        for h in 0..m {
+            a[0][h] = w[h];
+            for i in 1..n {
+                a[i][h] = a[i - 1][h] * x[h];
+            }
+        }
+
+        // QR decomposition of a by the Gram-Schmidt process
+        let mut q = vec![vec![0.0; m]; n];
+        let mut r = vec![vec![0.0; n]; n];
+        for j in 0..n {
+            let mut row = a[j].clone();
+            for qi in &q[..j] {
+                let projection = dot(&row, qi);
+                for (value, qi) in row.iter_mut().zip(qi) {
+                    *value -= projection * qi;
+                }
+            }
+            q[j] = row;
+            let norm = dot(&q[j], &q[j]).sqrt();
+            if norm < PRECISION_ERROR_TOLERANCE {
+                // linearly dependent or zero
+                return None;
+            }
+            for value in &mut q[j] {
+                *value /= norm;
+            }
+            for i in j..n {
+                r[j][i] = dot(&q[j], &a[i]);
+            }
+        }
+
+        // solve R b = Qt W y by back substitution
+        let wy: Vec<f64> = y.iter().zip(&w).map(|(y, w)| y * w).collect();
+        let mut coefficients = vec![0.0; n];
+        for i in (0..n).rev() {
+            coefficients[i] = dot(&q[i], &wy);
+            for j in (i + 1..n).rev() {
+                coefficients[i] -= r[i][j] * coefficients[j];
+            }
+            coefficients[i] /= r[i][i];
+        }
+
+        let y_mean = y.iter().sum::<f64>() / m as f64;
+        let mut sum_squared_error = 0.0;
+        let mut sum_squared_total = 0.0;
+        for h in 0..m {
+            let mut term = 1.0;
+            let mut error = y[h] - coefficients[0];
+            for coefficient in &coefficients[1..] {
+                term *= x[h];
+                error -= term * coefficient;
+            }
+            sum_squared_error += w[h] * w[h] * error * error;
+            let deviation = y[h] - y_mean;
+            sum_squared_total += w[h] * w[h] * deviation * deviation;
+        }
+        let confidence = if sum_squared_total <= PRECISION_ERROR_TOLERANCE {
+            1.0
+        } else {
+            1.0 - sum_squared_error / sum_squared_total
+        };
+
+        Some(PolynomialFit {
+            coefficients: coefficients.into_iter().map(|c| c as f32).collect(),
+            confidence: confidence as f32,
+        })
+    }
+}
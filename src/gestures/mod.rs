@@ -1,2 +1,41 @@
+mod events;
+pub use events::*;
+
 mod hit_test;
 pub use hit_test::*;
+
+mod constants;
+pub use constants::*;
+
+mod arena;
+pub use arena::*;
+
+mod lsq_solver;
+pub use lsq_solver::*;
+
+mod velocity_tracker;
+pub use velocity_tracker::*;
+
+mod pointer_router;
+pub use pointer_router::*;
+
+mod recognizer;
+pub use recognizer::*;
+
+mod tap;
+pub use tap::*;
+
+mod multitap;
+pub use multitap::*;
+
+mod long_press;
+pub use long_press::*;
+
+mod monodrag;
+pub use monodrag::*;
+
+mod scale;
+pub use scale::*;
+
+mod binding;
+pub use binding::*;
@@ -0,0 +1,338 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc, time::Duration};
+
+use crate::{
+    gestures::{
+        invoke_callback, GestureArenaMember, GestureBinding, GestureCallback, GestureDisposition,
+        GestureRecognizer, PointerEvent, PointerEventKind, PointerTracker, Velocity,
+        VelocityEstimate, VelocityTracker, MAX_FLING_VELOCITY, MIN_FLING_DISTANCE,
+        MIN_FLING_VELOCITY, PAN_SLOP, TOUCH_SLOP,
+    },
+    rendering::{Axis, Matrix4, Offset},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DragDownDetails {
+    pub global_position: Offset,
+    pub local_position: Offset,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DragStartDetails {
+    pub global_position: Offset,
+    pub local_position: Offset,
+    pub timestamp: Duration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DragUpdateDetails {
+    /// Distance moved since the previous update, zero on the cross axis of the drag.
+    pub delta: Offset,
+    /// Distance moved along the axis of the drag, `None` for pans.
+    pub primary_delta: Option<f32>,
+    pub global_position: Offset,
+    pub local_position: Offset,
+    pub timestamp: Duration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct DragEndDetails {
+    /// Velocity of the pointer when it was lifted, zero on the cross axis of the drag, and
+    /// zero altogether unless the drag ended in a fling.
+    pub velocity: Velocity,
+    /// Velocity along the axis of the drag, `None` for pans.
+    pub primary_velocity: Option<f32>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DragCallbacks {
+    pub on_down: Option<GestureCallback<DragDownDetails>>,
+    pub on_start: Option<GestureCallback<DragStartDetails>>,
+    pub on_update: Option<GestureCallback<DragUpdateDetails>>,
+    pub on_end: Option<GestureCallback<DragEndDetails>>,
+    pub on_cancel: Option<GestureCallback<()>>,
+}
+
+impl DragCallbacks {
+    pub fn with_on_down(mut self, f: impl Fn(DragDownDetails) + 'static) -> Self {
+        self.on_down = Some(GestureCallback::new(f));
+        self
+    }
+
+    pub fn with_on_start(mut self, f: impl Fn(DragStartDetails) + 'static) -> Self {
+        self.on_start = Some(GestureCallback::new(f));
+        self
+    }
+
+    pub fn with_on_update(mut self, f: impl Fn(DragUpdateDetails) + 'static) -> Self {
+        self.on_update = Some(GestureCallback::new(f));
+        self
+    }
+
+    pub fn with_on_end(mut self, f: impl Fn(DragEndDetails) + 'static) -> Self {
+        self.on_end = Some(GestureCallback::new(f));
+        self
+    }
+
+    pub fn with_on_cancel(mut self, f: impl Fn(()) + 'static) -> Self {
+        self.on_cancel = Some(GestureCallback::new(f));
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.on_down.is_none()
+            && self.on_start.is_none()
+            && self.on_update.is_none()
+            && self.on_end.is_none()
+            && self.on_cancel.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DragState {
+    Ready,
+    Possible,
+    Accepted,
+}
+
+impl Default for DragState {
+    fn default() -> Self {
+        DragState::Ready
+    }
+}
+
+#[derive(Debug, Default)]
+struct DragRecognizerState {
+    drag_state: DragState,
+    initial_position: Offset,
+    transform: Option<Matrix4>,
+    pending_delta: Offset,
+    last_timestamp: Duration,
+    positions: HashMap<usize, Offset>,
+    velocity_trackers: HashMap<usize, VelocityTracker>,
+}
+
+/// Recognizes a drag along an axis, or in any direction for pans.
+///
+/// The drag claims the arena once the pointers moved further than [`TOUCH_SLOP`] along its
+/// axis, or [`PAN_SLOP`] for pans.
+#[derive(Debug, Default)]
+pub struct DragGestureRecognizer {
+    axis: Option<Axis>,
+    callbacks: RefCell<DragCallbacks>,
+    tracker: PointerTracker,
+    state: RefCell<DragRecognizerState>,
+}
+
+impl DragGestureRecognizer {
+    pub fn new(axis: impl Into<Option<Axis>>, callbacks: DragCallbacks) -> Self {
+        DragGestureRecognizer {
+            axis: axis.into(),
+            callbacks: RefCell::new(callbacks),
+            ..Default::default()
+        }
+    }
+
+    pub fn horizontal(callbacks: DragCallbacks) -> Self {
+        DragGestureRecognizer::new(Axis::Horizontal, callbacks)
+    }
+
+    pub fn vertical(callbacks: DragCallbacks) -> Self {
+        DragGestureRecognizer::new(Axis::Vertical, callbacks)
+    }
+
+    pub fn pan(callbacks: DragCallbacks) -> Self {
+        DragGestureRecognizer::new(None, callbacks)
+    }
+
+    pub fn set_callbacks(&self, callbacks: DragCallbacks) {
+        *self.callbacks.borrow_mut() = callbacks;
+    }
+
+    fn has_sufficient_pending_drag(&self, pending_delta: Offset) -> bool {
+        match self.axis {
+            Some(Axis::Horizontal) => pending_delta.x.abs() > TOUCH_SLOP,
+            Some(Axis::Vertical) => pending_delta.y.abs() > TOUCH_SLOP,
+            None => pending_delta.distance() > PAN_SLOP,
+        }
+    }
+
+    fn delta_for_details(&self, delta: Offset) -> (Offset, Option<f32>) {
+        match self.axis {
+            Some(Axis::Horizontal) => (Offset::new(delta.x, 0.0), Some(delta.x)),
+            Some(Axis::Vertical) => (Offset::new(0.0, delta.y), Some(delta.y)),
+            None => (delta, None),
+        }
+    }
+
+    /// Whether the pointer moved far and fast enough for the drag to end in a fling.
+    fn is_fling_gesture(&self, estimate: &VelocityEstimate) -> bool {
+        let (velocity, offset) = (estimate.pixels_per_second, estimate.offset);
+        match self.axis {
+            Some(Axis::Horizontal) => {
+                velocity.x.abs() > MIN_FLING_VELOCITY && offset.x.abs() > MIN_FLING_DISTANCE
+            }
+            Some(Axis::Vertical) => {
+                velocity.y.abs() > MIN_FLING_VELOCITY && offset.y.abs() > MIN_FLING_DISTANCE
+            }
+            None => velocity.distance() > MIN_FLING_VELOCITY && offset.distance() > PAN_SLOP,
+        }
+    }
+
+    fn end_details(&self, estimate: Option<VelocityEstimate>) -> DragEndDetails {
+        let velocity = match estimate {
+            Some(estimate) if self.is_fling_gesture(&estimate) => {
+                let (velocity, _) = self.delta_for_details(estimate.pixels_per_second);
+                Velocity::new(velocity).clamp_magnitude(MIN_FLING_VELOCITY, MAX_FLING_VELOCITY)
+            }
+            _ => Velocity::ZERO,
+        };
+        let (_, primary_velocity) = self.delta_for_details(velocity.pixels_per_second);
+        DragEndDetails {
+            velocity,
+            primary_velocity,
+        }
+    }
+
+    /// Stops tracking `pointer`, ending the drag with its velocity if it was the last one.
+    fn stop_tracking(&self, pointer: usize) {
+        let tracker = {
+            let mut state = self.state.borrow_mut();
+            state.positions.remove(&pointer);
+            state.velocity_trackers.remove(&pointer)
+        };
+        if self.tracker.stop_tracking(pointer, self) {
+            let estimate = tracker.and_then(|tracker| tracker.get_velocity_estimate());
+            self.did_stop_tracking_last_pointer(estimate);
+        }
+    }
+
+    fn did_stop_tracking_last_pointer(&self, estimate: Option<VelocityEstimate>) {
+        let drag_state = std::mem::take(&mut self.state.borrow_mut().drag_state);
+        match drag_state {
+            DragState::Possible => {
+                self.tracker.resolve(GestureDisposition::Rejected);
+                let callback = self.callbacks.borrow().on_cancel.clone();
+                invoke_callback(callback, ());
+            }
+            DragState::Accepted => {
+                let callback = self.callbacks.borrow().on_end.clone();
+                invoke_callback(callback, self.end_details(estimate));
+            }
+            DragState::Ready => {}
+        }
+    }
+}
+
+impl GestureArenaMember for DragGestureRecognizer {
+    fn accept_gesture(&self, pointer: usize) {
+        let details = {
+            let mut state = self.state.borrow_mut();
+            if state.drag_state == DragState::Accepted {
+                return;
+            }
+            state.drag_state = DragState::Accepted;
+            // the slop is not reported, the drag starts where it was recognized
+            let delta = std::mem::take(&mut state.pending_delta);
+            state.initial_position += delta;
+            DragStartDetails {
+                global_position: state.initial_position,
+                local_position: PointerEvent::transform_position(
+                    state.transform,
+                    state.initial_position,
+                ),
+                timestamp: state.last_timestamp,
+            }
+        };
+        let callback = self.callbacks.borrow().on_start.clone();
+        invoke_callback(callback, details);
+    }
+
+    fn reject_gesture(&self, pointer: usize) {
+        self.stop_tracking(pointer);
+    }
+}
+
+impl GestureRecognizer for DragGestureRecognizer {
+    fn add_pointer(self: Rc<Self>, event: &PointerEvent, binding: &GestureBinding) {
+        let (is_first, is_accepted) = {
+            let mut state = self.state.borrow_mut();
+            state.positions.insert(event.pointer, event.position);
+            let mut tracker = VelocityTracker::new();
+            tracker.add_position(event.timestamp, event.position);
+            state.velocity_trackers.insert(event.pointer, tracker);
+            match state.drag_state {
+                DragState::Ready => {
+                    state.drag_state = DragState::Possible;
+                    state.initial_position = event.position;
+                    state.transform = event.transform;
+                    state.pending_delta = Offset::zero();
+                    state.last_timestamp = event.timestamp;
+                    (true, false)
+                }
+                DragState::Possible => (false, false),
+                DragState::Accepted => (false, true),
+            }
+        };
+        self.tracker.start_tracking(self.clone(), event, binding);
+        if is_accepted {
+            self.tracker
+                .resolve_pointer(event.pointer, GestureDisposition::Accepted);
+        }
+        if is_first {
+            let callback = self.callbacks.borrow().on_down.clone();
+            invoke_callback(
+                callback,
+                DragDownDetails {
+                    global_position: event.position,
+                    local_position: event.local_position,
+                },
+            );
+        }
+    }
+
+    fn handle_event(&self, event: &PointerEvent) {
+        if !self.tracker.is_tracking(event.pointer) {
+            return;
+        }
+        match event.kind {
+            PointerEventKind::Move => {
+                let (drag_state, delta, pending_delta) = {
+                    let mut state = self.state.borrow_mut();
+                    let last = state
+                        .positions
+                        .insert(event.pointer, event.position)
+                        .unwrap_or(event.position);
+                    let delta = event.position - last;
+                    state.last_timestamp = event.timestamp;
+                    if let Some(tracker) = state.velocity_trackers.get_mut(&event.pointer) {
+                        tracker.add_position(event.timestamp, event.position);
+                    }
+                    if state.drag_state != DragState::Accepted {
+                        state.pending_delta += delta;
+                    }
+                    (state.drag_state, delta, state.pending_delta)
+                };
+                if drag_state == DragState::Accepted {
+                    let (delta, primary_delta) = self.delta_for_details(delta);
+                    let callback = self.callbacks.borrow().on_update.clone();
+                    invoke_callback(
+                        callback,
+                        DragUpdateDetails {
+                            delta,
+                            primary_delta,
+                            global_position: event.position,
+                            local_position: event.local_position,
+                            timestamp: event.timestamp,
+                        },
+                    );
+                } else if self.has_sufficient_pending_drag(pending_delta) {
+                    self.tracker.resolve(GestureDisposition::Accepted);
+                }
+            }
+            PointerEventKind::Up | PointerEventKind::Cancel => {
+                self.stop_tracking(event.pointer);
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+use crate::{
+    gestures::{
+        invoke_callback, GestureArenaEntry, GestureArenaMember, GestureBinding, GestureCallback,
+        GestureDisposition, GestureRecognizer, PointerEvent, PointerEventKind, PointerTracker,
+        TapDownDetails, DOUBLE_TAP_SLOP, DOUBLE_TAP_TIMEOUT, TOUCH_SLOP,
+    },
+    rendering::Offset,
+};
+
+#[derive(Debug, Clone, Default)]
+pub struct DoubleTapCallbacks {
+    pub on_double_tap_down: Option<GestureCallback<TapDownDetails>>,
+    pub on_double_tap: Option<GestureCallback<()>>,
+    pub on_double_tap_cancel: Option<GestureCallback<()>>,
+}
+
+impl DoubleTapCallbacks {
+    pub fn with_on_double_tap_down(mut self, f: impl Fn(TapDownDetails) + 'static) -> Self {
+        self.on_double_tap_down = Some(GestureCallback::new(f));
+        self
+    }
+
+    pub fn with_on_double_tap(mut self, f: impl Fn(()) + 'static) -> Self {
+        self.on_double_tap = Some(GestureCallback::new(f));
+        self
+    }
+
+    pub fn with_on_double_tap_cancel(mut self, f: impl Fn(()) + 'static) -> Self {
+        self.on_double_tap_cancel = Some(GestureCallback::new(f));
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.on_double_tap_down.is_none()
+            && self.on_double_tap.is_none()
+            && self.on_double_tap_cancel.is_none()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct TapRecord {
+    pointer: usize,
+    position: Offset,
+}
+
+#[derive(Debug, Default)]
+struct DoubleTapState {
+    first_tap: Option<(TapRecord, Option<GestureArenaEntry>)>,
+    current: Option<TapRecord>,
+    deadline: Option<Duration>,
+}
+
+/// Recognizes two taps in quick succession.
+///
+/// After the first tap the recognizer holds its arena, so competing taps only win once the
+/// second tap did not come within [`DOUBLE_TAP_TIMEOUT`].
+#[derive(Debug, Default)]
+pub struct DoubleTapGestureRecognizer {
+    callbacks: RefCell<DoubleTapCallbacks>,
+    tracker: PointerTracker,
+    state: RefCell<DoubleTapState>,
+}
+
+impl DoubleTapGestureRecognizer {
+    pub fn new(callbacks: DoubleTapCallbacks) -> Self {
+        DoubleTapGestureRecognizer {
+            callbacks: RefCell::new(callbacks),
+            ..Default::default()
+        }
+    }
+
+    pub fn set_callbacks(&self, callbacks: DoubleTapCallbacks) {
+        *self.callbacks.borrow_mut() = callbacks;
+    }
+
+    fn reset(&self) {
+        let DoubleTapState {
+            first_tap, current, ..
+        } = std::mem::take(&mut *self.state.borrow_mut());
+        if let Some(current) = &current {
+            self.tracker.stop_tracking(current.pointer, self);
+            if first_tap.is_some() {
+                let callback = self.callbacks.borrow().on_double_tap_cancel.clone();
+                invoke_callback(callback, ());
+            }
+        }
+        self.tracker.resolve(GestureDisposition::Rejected);
+        if let Some((_, Some(entry))) = first_tap {
+            entry.arena.release(entry.pointer);
+        }
+    }
+
+    fn register_first_tap(&self, event: &PointerEvent) {
+        self.tracker.stop_tracking(event.pointer, self);
+        let entry = self.tracker.entry(event.pointer);
+        if let Some(entry) = &entry {
+            entry.arena.hold(entry.pointer);
+        }
+        let mut state = self.state.borrow_mut();
+        let current = state.current.take().unwrap();
+        state.first_tap = Some((current, entry));
+        state.deadline = Some(event.timestamp + DOUBLE_TAP_TIMEOUT);
+    }
+
+    fn register_second_tap(&self, event: &PointerEvent) {
+        self.tracker.stop_tracking(event.pointer, self);
+        let first_tap = std::mem::take(&mut *self.state.borrow_mut()).first_tap;
+        self.tracker.resolve(GestureDisposition::Accepted);
+        if let Some((_, Some(entry))) = first_tap {
+            entry.arena.release(entry.pointer);
+        }
+        let callback = self.callbacks.borrow().on_double_tap.clone();
+        invoke_callback(callback, ());
+    }
+}
+
+impl GestureArenaMember for DoubleTapGestureRecognizer {
+    fn accept_gesture(&self, pointer: usize) {}
+
+    fn reject_gesture(&self, pointer: usize) {
+        let is_involved = {
+            let state = self.state.borrow();
+            matches!(&state.first_tap, Some((tap, _)) if tap.pointer == pointer)
+                || matches!(&state.current, Some(tap) if tap.pointer == pointer)
+        };
+        if is_involved {
+            self.reset();
+        }
+    }
+}
+
+impl GestureRecognizer for DoubleTapGestureRecognizer {
+    fn add_pointer(self: Rc<Self>, event: &PointerEvent, binding: &GestureBinding) {
+        self.handle_deadline(event.timestamp);
+        let is_second_tap = {
+            let mut state = self.state.borrow_mut();
+            if state.current.is_some() {
+                return;
+            }
+            if let Some((first_tap, _)) = &state.first_tap {
+                if (event.position - first_tap.position).distance() > DOUBLE_TAP_SLOP {
+                    return;
+                }
+            }
+            state.current = Some(TapRecord {
+                pointer: event.pointer,
+                position: event.position,
+            });
+            state.first_tap.is_some()
+        };
+        self.tracker.start_tracking(self.clone(), event, binding);
+        if is_second_tap {
+            let callback = self.callbacks.borrow().on_double_tap_down.clone();
+            invoke_callback(callback, TapDownDetails::from(event));
+        }
+    }
+
+    fn handle_event(&self, event: &PointerEvent) {
+        let (down_position, is_second_tap) = match &*self.state.borrow() {
+            DoubleTapState {
+                current: Some(tap),
+                first_tap,
+                ..
+            } if tap.pointer == event.pointer => (tap.position, first_tap.is_some()),
+            _ => return,
+        };
+
+        match event.kind {
+            PointerEventKind::Move => {
+                if (event.position - down_position).distance() > TOUCH_SLOP {
+                    self.reset();
+                }
+            }
+            PointerEventKind::Up if is_second_tap => self.register_second_tap(event),
+            PointerEventKind::Up => self.register_first_tap(event),
+            PointerEventKind::Cancel => self.reset(),
+            _ => {}
+        }
+    }
+
+    fn handle_deadline(&self, now: Duration) {
+        let expired = {
+            let state = self.state.borrow();
+            // only waits while the second tap did not start
+            state.current.is_none() && matches!(state.deadline, Some(deadline) if now >= deadline)
+        };
+        if expired {
+            self.reset();
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use crate::{
+    gestures::{GestureRecognizer, PointerEvent},
+    rendering::Matrix4,
+};
+
+/// Forwards the events of a pointer to the recognizers tracking it.
+#[derive(Debug, Default)]
+pub struct PointerRouter {
+    routes: RefCell<HashMap<usize, Vec<(Rc<dyn GestureRecognizer>, Option<Matrix4>)>>>,
+}
+
+fn is_same_route(a: &Rc<dyn GestureRecognizer>, b: &dyn GestureRecognizer) -> bool {
+    Rc::as_ptr(a) as *const () == b as *const dyn GestureRecognizer as *const ()
+}
+
+impl PointerRouter {
+    pub fn new() -> Self {
+        PointerRouter::default()
+    }
+
+    /// Routes the events of `pointer` to `route`, transformed to local coordinates by
+    /// `transform`.
+    pub fn add_route(
+        &self,
+        pointer: usize,
+        route: Rc<dyn GestureRecognizer>,
+        transform: Option<Matrix4>,
+    ) {
+        self.routes
+            .borrow_mut()
+            .entry(pointer)
+            .or_default()
+            .push((route, transform));
+    }
+
+    pub fn remove_route(&self, pointer: usize, route: &dyn GestureRecognizer) {
+        let mut routes = self.routes.borrow_mut();
+        if let Some(pointer_routes) = routes.get_mut(&pointer) {
+            pointer_routes.retain(|(r, _)| !is_same_route(r, route));
+            if pointer_routes.is_empty() {
+                routes.remove(&pointer);
+            }
+        }
+    }
+
+    fn has_route(&self, pointer: usize, route: &dyn GestureRecognizer) -> bool {
+        self.routes.borrow().get(&pointer).map_or(false, |routes| {
+            routes.iter().any(|(r, _)| is_same_route(r, route))
+        })
+    }
+
+    pub fn route(&self, event: &PointerEvent) {
+        let routes = match self.routes.borrow().get(&event.pointer) {
+            Some(routes) => routes.clone(),
+            None => return,
+        };
+        for (route, transform) in routes {
+            // a route removed by an earlier one no longer receives the event
+            if self.has_route(event.pointer, &*route) {
+                route.handle_event(&event.transformed(transform));
+            }
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    fmt::{self, Debug},
+    rc::Rc,
+    time::Duration,
+};
+
+use crate::gestures::{
+    GestureArenaEntry, GestureArenaMember, GestureBinding, GestureDisposition, PointerEvent,
+    PointerRouter,
+};
+
+/// Recognizes a gesture from the events of the pointers added to it.
+pub trait GestureRecognizer: GestureArenaMember {
+    /// Registers a down event that hit the target owning this recognizer, usually by
+    /// tracking the pointer and entering its arena.
+    fn add_pointer(self: Rc<Self>, event: &PointerEvent, binding: &GestureBinding);
+
+    /// Handles an event of a tracked pointer.
+    fn handle_event(&self, event: &PointerEvent);
+
+    /// Called with the current time, for recognizers waiting for a timeout.
+    fn handle_deadline(&self, now: Duration) {}
+}
+
+/// A gesture callback, receiving the details of the gesture.
+pub struct GestureCallback<T>(Rc<dyn Fn(T)>);
+
+impl<T> GestureCallback<T> {
+    pub fn new<F>(f: F) -> Self
+    where
+        F: 'static + Fn(T),
+    {
+        GestureCallback(Rc::new(f))
+    }
+
+    pub fn call(&self, details: T) {
+        (self.0)(details)
+    }
+}
+
+impl<T> Clone for GestureCallback<T> {
+    fn clone(&self) -> Self {
+        GestureCallback(self.0.clone())
+    }
+}
+
+impl<T> Debug for GestureCallback<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("GestureCallback")
+    }
+}
+
+pub(crate) fn invoke_callback<T>(callback: Option<GestureCallback<T>>, details: T) {
+    if let Some(callback) = callback {
+        callback.call(details);
+    }
+}
+
+/// The pointers a recognizer tracks and its entries in their arenas.
+///
+/// Entries outlive the tracking of a pointer, so a recognizer can still resolve the arena
+/// of a pointer that already went up.
+#[derive(Debug, Default)]
+pub(crate) struct PointerTracker {
+    router: RefCell<Option<Rc<PointerRouter>>>,
+    tracked: RefCell<HashSet<usize>>,
+    entries: RefCell<HashMap<usize, GestureArenaEntry>>,
+}
+
+impl PointerTracker {
+    /// Routes the events of the pointer of `event` to `route` and enters its arena.
+    pub(crate) fn start_tracking(
+        &self,
+        route: Rc<dyn GestureRecognizer>,
+        event: &PointerEvent,
+        binding: &GestureBinding,
+    ) {
+        binding
+            .pointer_router
+            .add_route(event.pointer, route.clone(), event.transform);
+        *self.router.borrow_mut() = Some(binding.pointer_router.clone());
+        self.tracked.borrow_mut().insert(event.pointer);
+        let entry = binding.gesture_arena.add(event.pointer, route);
+        self.entries.borrow_mut().insert(event.pointer, entry);
+    }
+
+    /// Stops routing the events of `pointer`, returns whether no pointer is tracked anymore.
+    pub(crate) fn stop_tracking(&self, pointer: usize, route: &dyn GestureRecognizer) -> bool {
+        if self.tracked.borrow_mut().remove(&pointer) {
+            if let Some(router) = &*self.router.borrow() {
+                router.remove_route(pointer, route);
+            }
+        }
+        self.tracked.borrow().is_empty()
+    }
+
+    pub(crate) fn is_tracking(&self, pointer: usize) -> bool {
+        self.tracked.borrow().contains(&pointer)
+    }
+
+    pub(crate) fn entry(&self, pointer: usize) -> Option<GestureArenaEntry> {
+        self.entries.borrow().get(&pointer).cloned()
+    }
+
+    /// Resolves the arenas of all pointers added since the last resolution.
+    pub(crate) fn resolve(&self, disposition: GestureDisposition) {
+        let entries: Vec<_> = self.entries.borrow_mut().drain().map(|(_, e)| e).collect();
+        for entry in entries {
+            entry.resolve(disposition);
+        }
+    }
+
+    pub(crate) fn resolve_pointer(&self, pointer: usize, disposition: GestureDisposition) {
+        let entry = self.entries.borrow_mut().remove(&pointer);
+        if let Some(entry) = entry {
+            entry.resolve(disposition);
+        }
+    }
+}
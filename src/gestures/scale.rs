@@ -0,0 +1,278 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use crate::{
+    gestures::{
+        invoke_callback, GestureArenaMember, GestureBinding, GestureCallback, GestureDisposition,
+        GestureRecognizer, PointerEvent, PointerEventKind, PointerTracker, PAN_SLOP, SCALE_SLOP,
+    },
+    rendering::{Matrix4, Offset},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScaleStartDetails {
+    pub focal_point: Offset,
+    pub local_focal_point: Offset,
+    pub pointer_count: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScaleUpdateDetails {
+    pub focal_point: Offset,
+    pub local_focal_point: Offset,
+    /// Span between the pointers relative to their span at the start of the gesture.
+    pub scale: f32,
+    pub pointer_count: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScaleEndDetails {
+    pub pointer_count: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ScaleCallbacks {
+    pub on_start: Option<GestureCallback<ScaleStartDetails>>,
+    pub on_update: Option<GestureCallback<ScaleUpdateDetails>>,
+    pub on_end: Option<GestureCallback<ScaleEndDetails>>,
+}
+
+impl ScaleCallbacks {
+    pub fn with_on_start(mut self, f: impl Fn(ScaleStartDetails) + 'static) -> Self {
+        self.on_start = Some(GestureCallback::new(f));
+        self
+    }
+
+    pub fn with_on_update(mut self, f: impl Fn(ScaleUpdateDetails) + 'static) -> Self {
+        self.on_update = Some(GestureCallback::new(f));
+        self
+    }
+
+    pub fn with_on_end(mut self, f: impl Fn(ScaleEndDetails) + 'static) -> Self {
+        self.on_end = Some(GestureCallback::new(f));
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.on_start.is_none() && self.on_update.is_none() && self.on_end.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ScaleState {
+    Ready,
+    Possible,
+    Accepted,
+    Started,
+}
+
+impl Default for ScaleState {
+    fn default() -> Self {
+        ScaleState::Ready
+    }
+}
+
+#[derive(Debug, Default)]
+struct ScaleRecognizerState {
+    scale_state: ScaleState,
+    positions: HashMap<usize, Offset>,
+    transform: Option<Matrix4>,
+    initial_focal_point: Offset,
+    initial_span: f32,
+    current_focal_point: Offset,
+    current_span: f32,
+}
+
+impl ScaleRecognizerState {
+    fn update(&mut self) {
+        let count = self.positions.len().max(1) as f32;
+        let sum = self
+            .positions
+            .values()
+            .fold(Offset::zero(), |sum, position| sum + *position);
+        self.current_focal_point = Offset::new(sum.x / count, sum.y / count);
+        self.current_span = self
+            .positions
+            .values()
+            .map(|position| (*position - self.current_focal_point).distance())
+            .sum::<f32>()
+            / count;
+    }
+
+    fn scale(&self) -> f32 {
+        if self.initial_span > 0.0 {
+            self.current_span / self.initial_span
+        } else {
+            1.0
+        }
+    }
+
+    fn start_details(&self) -> ScaleStartDetails {
+        ScaleStartDetails {
+            focal_point: self.current_focal_point,
+            local_focal_point: PointerEvent::transform_position(
+                self.transform,
+                self.current_focal_point,
+            ),
+            pointer_count: self.positions.len(),
+        }
+    }
+}
+
+/// Recognizes the focal point and scale of the pointers in contact.
+///
+/// The gesture claims the arena once the span between the pointers changed by more than
+/// [`SCALE_SLOP`] or the focal point moved further than [`PAN_SLOP`]. Adding or removing a
+/// pointer ends the gesture and starts a new one.
+#[derive(Debug, Default)]
+pub struct ScaleGestureRecognizer {
+    callbacks: RefCell<ScaleCallbacks>,
+    tracker: PointerTracker,
+    state: RefCell<ScaleRecognizerState>,
+}
+
+impl ScaleGestureRecognizer {
+    pub fn new(callbacks: ScaleCallbacks) -> Self {
+        ScaleGestureRecognizer {
+            callbacks: RefCell::new(callbacks),
+            ..Default::default()
+        }
+    }
+
+    pub fn set_callbacks(&self, callbacks: ScaleCallbacks) {
+        *self.callbacks.borrow_mut() = callbacks;
+    }
+
+    /// Restarts the gesture from the current pointers.
+    fn reconfigure(&self) {
+        let ended = {
+            let mut state = self.state.borrow_mut();
+            state.update();
+            state.initial_focal_point = state.current_focal_point;
+            state.initial_span = state.current_span;
+            if state.scale_state == ScaleState::Started {
+                state.scale_state = ScaleState::Accepted;
+                Some(ScaleEndDetails {
+                    pointer_count: state.positions.len(),
+                })
+            } else {
+                None
+            }
+        };
+        if let Some(details) = ended {
+            let callback = self.callbacks.borrow().on_end.clone();
+            invoke_callback(callback, details);
+        }
+    }
+
+    fn advance(&self) {
+        let (scale_state, should_accept) = {
+            let mut state = self.state.borrow_mut();
+            state.update();
+            let should_accept = (state.current_span - state.initial_span).abs() > SCALE_SLOP
+                || (state.current_focal_point - state.initial_focal_point).distance() > PAN_SLOP;
+            (state.scale_state, should_accept)
+        };
+        match scale_state {
+            ScaleState::Possible if should_accept => {
+                self.tracker.resolve(GestureDisposition::Accepted);
+            }
+            ScaleState::Accepted => self.start(),
+            _ => {}
+        }
+
+        let details = {
+            let state = self.state.borrow();
+            if state.scale_state != ScaleState::Started {
+                return;
+            }
+            ScaleUpdateDetails {
+                focal_point: state.current_focal_point,
+                local_focal_point: PointerEvent::transform_position(
+                    state.transform,
+                    state.current_focal_point,
+                ),
+                scale: state.scale(),
+                pointer_count: state.positions.len(),
+            }
+        };
+        let callback = self.callbacks.borrow().on_update.clone();
+        invoke_callback(callback, details);
+    }
+
+    fn start(&self) {
+        let details = {
+            let mut state = self.state.borrow_mut();
+            state.scale_state = ScaleState::Started;
+            state.start_details()
+        };
+        let callback = self.callbacks.borrow().on_start.clone();
+        invoke_callback(callback, details);
+    }
+
+    fn remove_pointer(&self, pointer: usize) {
+        self.state.borrow_mut().positions.remove(&pointer);
+        if self.tracker.stop_tracking(pointer, self) {
+            let scale_state = std::mem::take(&mut *self.state.borrow_mut()).scale_state;
+            match scale_state {
+                ScaleState::Possible => self.tracker.resolve(GestureDisposition::Rejected),
+                ScaleState::Started => {
+                    let callback = self.callbacks.borrow().on_end.clone();
+                    invoke_callback(callback, ScaleEndDetails { pointer_count: 0 });
+                }
+                _ => {}
+            }
+        } else {
+            self.reconfigure();
+        }
+    }
+}
+
+impl GestureArenaMember for ScaleGestureRecognizer {
+    fn accept_gesture(&self, pointer: usize) {
+        if self.state.borrow().scale_state == ScaleState::Possible {
+            self.start();
+        }
+    }
+
+    fn reject_gesture(&self, pointer: usize) {
+        if self.tracker.is_tracking(pointer) {
+            self.remove_pointer(pointer);
+        }
+    }
+}
+
+impl GestureRecognizer for ScaleGestureRecognizer {
+    fn add_pointer(self: Rc<Self>, event: &PointerEvent, binding: &GestureBinding) {
+        {
+            let mut state = self.state.borrow_mut();
+            state.positions.insert(event.pointer, event.position);
+            if state.scale_state == ScaleState::Ready {
+                state.scale_state = ScaleState::Possible;
+                state.transform = event.transform;
+            }
+        }
+        self.tracker.start_tracking(self.clone(), event, binding);
+        self.reconfigure();
+        if self.state.borrow().scale_state != ScaleState::Possible {
+            self.tracker
+                .resolve_pointer(event.pointer, GestureDisposition::Accepted);
+        }
+    }
+
+    fn handle_event(&self, event: &PointerEvent) {
+        if !self.tracker.is_tracking(event.pointer) {
+            return;
+        }
+        match event.kind {
+            PointerEventKind::Move => {
+                self.state
+                    .borrow_mut()
+                    .positions
+                    .insert(event.pointer, event.position);
+                self.advance();
+            }
+            PointerEventKind::Up | PointerEventKind::Cancel => self.remove_pointer(event.pointer),
+            _ => {}
+        }
+    }
+}
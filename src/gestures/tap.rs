@@ -0,0 +1,239 @@
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+use crate::{
+    gestures::{
+        invoke_callback, GestureArenaMember, GestureBinding, GestureCallback, GestureDisposition,
+        GestureRecognizer, PointerDeviceKind, PointerEvent, PointerEventKind, PointerTracker,
+        PRESS_TIMEOUT, TOUCH_SLOP,
+    },
+    rendering::Offset,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TapDownDetails {
+    pub global_position: Offset,
+    pub local_position: Offset,
+    pub kind: PointerDeviceKind,
+}
+
+impl From<&PointerEvent> for TapDownDetails {
+    fn from(event: &PointerEvent) -> Self {
+        TapDownDetails {
+            global_position: event.position,
+            local_position: event.local_position,
+            kind: event.device_kind,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TapUpDetails {
+    pub global_position: Offset,
+    pub local_position: Offset,
+    pub kind: PointerDeviceKind,
+}
+
+impl From<&PointerEvent> for TapUpDetails {
+    fn from(event: &PointerEvent) -> Self {
+        TapUpDetails {
+            global_position: event.position,
+            local_position: event.local_position,
+            kind: event.device_kind,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TapCallbacks {
+    pub on_tap_down: Option<GestureCallback<TapDownDetails>>,
+    pub on_tap_up: Option<GestureCallback<TapUpDetails>>,
+    pub on_tap: Option<GestureCallback<()>>,
+    pub on_tap_cancel: Option<GestureCallback<()>>,
+}
+
+impl TapCallbacks {
+    pub fn with_on_tap_down(mut self, f: impl Fn(TapDownDetails) + 'static) -> Self {
+        self.on_tap_down = Some(GestureCallback::new(f));
+        self
+    }
+
+    pub fn with_on_tap_up(mut self, f: impl Fn(TapUpDetails) + 'static) -> Self {
+        self.on_tap_up = Some(GestureCallback::new(f));
+        self
+    }
+
+    pub fn with_on_tap(mut self, f: impl Fn(()) + 'static) -> Self {
+        self.on_tap = Some(GestureCallback::new(f));
+        self
+    }
+
+    pub fn with_on_tap_cancel(mut self, f: impl Fn(()) + 'static) -> Self {
+        self.on_tap_cancel = Some(GestureCallback::new(f));
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.on_tap_down.is_none()
+            && self.on_tap_up.is_none()
+            && self.on_tap.is_none()
+            && self.on_tap_cancel.is_none()
+    }
+}
+
+#[derive(Debug, Default)]
+struct TapState {
+    primary_pointer: Option<usize>,
+    down: Option<PointerEvent>,
+    up: Option<PointerEvent>,
+    deadline: Option<Duration>,
+    won_arena: bool,
+    sent_tap_down: bool,
+}
+
+/// Recognizes a tap, a pointer going down and up without moving further than
+/// [`TOUCH_SLOP`].
+///
+/// The tap down is reported once the tap wins the arena or after [`PRESS_TIMEOUT`], the tap
+/// itself once it won and the pointer went up.
+#[derive(Debug, Default)]
+pub struct TapGestureRecognizer {
+    callbacks: RefCell<TapCallbacks>,
+    tracker: PointerTracker,
+    state: RefCell<TapState>,
+}
+
+impl TapGestureRecognizer {
+    pub fn new(callbacks: TapCallbacks) -> Self {
+        TapGestureRecognizer {
+            callbacks: RefCell::new(callbacks),
+            ..Default::default()
+        }
+    }
+
+    pub fn set_callbacks(&self, callbacks: TapCallbacks) {
+        *self.callbacks.borrow_mut() = callbacks;
+    }
+
+    fn check_down(&self) {
+        let down = {
+            let mut state = self.state.borrow_mut();
+            if state.sent_tap_down {
+                return;
+            }
+            state.sent_tap_down = true;
+            state.deadline = None;
+            state.down.clone()
+        };
+        if let Some(down) = down {
+            let callback = self.callbacks.borrow().on_tap_down.clone();
+            invoke_callback(callback, TapDownDetails::from(&down));
+        }
+    }
+
+    fn check_up(&self) {
+        let up = {
+            let state = self.state.borrow();
+            match &state.up {
+                Some(up) if state.won_arena => up.clone(),
+                _ => return,
+            }
+        };
+        self.reset();
+        let callbacks = self.callbacks.borrow().clone();
+        invoke_callback(callbacks.on_tap_up, TapUpDetails::from(&up));
+        invoke_callback(callbacks.on_tap, ());
+    }
+
+    fn check_cancel(&self) {
+        if self.state.borrow().sent_tap_down {
+            let callback = self.callbacks.borrow().on_tap_cancel.clone();
+            invoke_callback(callback, ());
+        }
+    }
+
+    fn reset(&self) {
+        *self.state.borrow_mut() = TapState::default();
+    }
+
+    fn give_up(&self, pointer: usize) {
+        if self.state.borrow().won_arena {
+            self.check_cancel();
+            self.reset();
+        }
+        self.tracker.stop_tracking(pointer, self);
+        // loses the arena unless already won, cancelling in `reject_gesture`
+        self.tracker.resolve(GestureDisposition::Rejected);
+    }
+}
+
+impl GestureArenaMember for TapGestureRecognizer {
+    fn accept_gesture(&self, pointer: usize) {
+        if self.state.borrow().primary_pointer != Some(pointer) {
+            return;
+        }
+        self.state.borrow_mut().won_arena = true;
+        self.check_down();
+        self.check_up();
+    }
+
+    fn reject_gesture(&self, pointer: usize) {
+        self.tracker.stop_tracking(pointer, self);
+        if self.state.borrow().primary_pointer == Some(pointer) {
+            self.check_cancel();
+            self.reset();
+        }
+    }
+}
+
+impl GestureRecognizer for TapGestureRecognizer {
+    fn add_pointer(self: Rc<Self>, event: &PointerEvent, binding: &GestureBinding) {
+        {
+            let mut state = self.state.borrow_mut();
+            // a new tap can start while the previous one waits for its arena
+            if state.primary_pointer.is_some() && state.up.is_none() {
+                return;
+            }
+            *state = TapState {
+                primary_pointer: Some(event.pointer),
+                down: Some(event.clone()),
+                deadline: Some(event.timestamp + PRESS_TIMEOUT),
+                ..Default::default()
+            };
+        }
+        self.tracker.start_tracking(self.clone(), event, binding);
+    }
+
+    fn handle_event(&self, event: &PointerEvent) {
+        let down_position = match &*self.state.borrow() {
+            TapState {
+                primary_pointer: Some(pointer),
+                down: Some(down),
+                ..
+            } if *pointer == event.pointer => down.position,
+            _ => return,
+        };
+        self.handle_deadline(event.timestamp);
+
+        match event.kind {
+            PointerEventKind::Move => {
+                if (event.position - down_position).distance() > TOUCH_SLOP {
+                    self.give_up(event.pointer);
+                }
+            }
+            PointerEventKind::Up => {
+                self.state.borrow_mut().up = Some(event.clone());
+                self.tracker.stop_tracking(event.pointer, self);
+                self.check_up();
+            }
+            PointerEventKind::Cancel => self.give_up(event.pointer),
+            _ => {}
+        }
+    }
+
+    fn handle_deadline(&self, now: Duration) {
+        let expired = matches!(self.state.borrow().deadline, Some(deadline) if now >= deadline);
+        if expired {
+            self.check_down();
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use std::time::Duration;
+
+use crate::{gestures::LeastSquaresSolver, rendering::Offset};
+
+/// Samples kept by a tracker.
+const HISTORY_SIZE: usize = 20;
+
+/// Age of the oldest sample an estimate takes into account.
+const HORIZON: Duration = Duration::from_millis(100);
+
+/// A gap between two samples after which the pointer is assumed to have stopped.
+const ASSUME_POINTER_MOVE_STOPPED: Duration = Duration::from_millis(40);
+
+/// Samples needed to fit the motion of a pointer.
+const MIN_SAMPLE_SIZE: usize = 3;
+
+/// The velocity of a pointer.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Velocity {
+    pub pixels_per_second: Offset,
+}
+
+impl Velocity {
+    pub const ZERO: Velocity = Velocity {
+        pixels_per_second: Offset { x: 0.0, y: 0.0 },
+    };
+
+    pub fn new(pixels_per_second: Offset) -> Self {
+        Velocity { pixels_per_second }
+    }
+
+    /// The velocity in the same direction with its speed clamped to `min..=max`.
+    pub fn clamp_magnitude(&self, min: f32, max: f32) -> Self {
+        let speed = self.pixels_per_second.distance();
+        if speed == 0.0 {
+            return *self;
+        }
+        let scale = speed.clamp(min, max) / speed;
+        Velocity::new(Offset::new(
+            self.pixels_per_second.x * scale,
+            self.pixels_per_second.y * scale,
+        ))
+    }
+}
+
+/// A velocity fitted to the recent motion of a pointer, and how well it fits.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VelocityEstimate {
+    pub pixels_per_second: Offset,
+    /// From 0 to 1, how well the motion fits the velocity.
+    pub confidence: f32,
+    /// Time between the oldest and the newest sample the estimate is based on.
+    pub duration: Duration,
+    /// Distance between the oldest and the newest sample the estimate is based on.
+    pub offset: Offset,
+}
+
+/// Estimates the velocity of a pointer from the positions it moved through.
+///
+/// The velocity is the slope at the newest sample of a quadratic fitted by least squares
+/// to the samples of the last 100ms, up to the first pause of the pointer.
+#[derive(Debug, Clone, Default)]
+pub struct VelocityTracker {
+    samples: [Option<(Duration, Offset)>; HISTORY_SIZE],
+    index: usize,
+}
+
+impl VelocityTracker {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn add_position(&mut self, timestamp: Duration, position: Offset) {
+        self.index = (self.index + 1) % HISTORY_SIZE;
+        self.samples[self.index] = Some((timestamp, position));
+    }
+
+    /// The estimated velocity, `None` before any position was added.
+    pub fn get_velocity_estimate(&self) -> Option<VelocityEstimate> {
+        let (newest_time, newest_position) = self.samples[self.index]?;
+        let mut x = Vec::new();
+        let mut y = Vec::new();
+        let mut time = Vec::new();
+        let (mut previous_time, mut oldest) = (newest_time, (newest_time, newest_position));
+
+        let mut index = self.index;
+        while let Some((sample_time, position)) = self.samples[index] {
+            let age = newest_time.saturating_sub(sample_time);
+            let gap = previous_time.abs_diff(sample_time);
+            previous_time = sample_time;
+            if age > HORIZON || gap > ASSUME_POINTER_MOVE_STOPPED {
+                break;
+            }
+            oldest = (sample_time, position);
+            x.push(position.x);
+            y.push(position.y);
+            time.push(-age.as_secs_f32() * 1000.0);
+            index = (index + HISTORY_SIZE - 1) % HISTORY_SIZE;
+            if time.len() == HISTORY_SIZE {
+                break;
+            }
+        }
+
+        let duration = newest_time.saturating_sub(oldest.0);
+        let offset = newest_position - oldest.1;
+        if time.len() >= MIN_SAMPLE_SIZE {
+            let w = vec![1.0; time.len()];
+            let x_fit = LeastSquaresSolver::new(&time, &x, &w).solve(2);
+            let y_fit = LeastSquaresSolver::new(&time, &y, &w).solve(2);
+            if let (Some(x_fit), Some(y_fit)) = (x_fit, y_fit) {
+                return Some(VelocityEstimate {
+                    // the fit is in pixels per millisecond
+                    pixels_per_second: Offset::new(
+                        x_fit.coefficients[1] * 1000.0,
+                        y_fit.coefficients[1] * 1000.0,
+                    ),
+                    confidence: x_fit.confidence * y_fit.confidence,
+                    duration,
+                    offset,
+                });
+            }
+        }
+        // the pointer was seen, but not moving long enough to tell
+        Some(VelocityEstimate {
+            pixels_per_second: Offset::zero(),
+            confidence: 1.0,
+            duration,
+            offset,
+        })
+    }
+
+    /// The estimated velocity, zero if it cannot be estimated.
+    pub fn get_velocity(&self) -> Velocity {
+        self.get_velocity_estimate()
+            .map_or(Velocity::ZERO, |estimate| {
+                Velocity::new(estimate.pixels_per_second)
+            })
+    }
+}
@@ -1,7 +1,13 @@
 #![feature(trait_upcasting)]
 
+pub mod animation;
 pub mod gestures;
+pub mod painting;
+pub mod physics;
 pub mod rendering;
+pub mod scheduler;
+pub mod semantics;
+pub mod services;
 pub mod ui;
 pub mod widgets;
 
@@ -0,0 +1,8 @@
+mod text_style;
+pub use text_style::*;
+
+mod text_span;
+pub use text_span::*;
+
+mod text_painter;
+pub use text_painter::*;
@@ -0,0 +1,317 @@
+use std::{
+    cell::{Cell, RefCell, RefMut},
+    fmt::{self, Debug},
+    ops::Range,
+};
+
+use skia_safe::{
+    textlayout::{
+        self, Affinity, Paragraph, ParagraphBuilder, ParagraphStyle, RectHeightStyle,
+        RectWidthStyle,
+    },
+    Canvas, Rect,
+};
+
+use crate::{
+    painting::{font_collection, TextSpan, TextStyle},
+    rendering::{Offset, TextAlign, TextBaseline, TextDirection},
+};
+
+const ELLIPSIS: &str = "\u{2026}";
+
+impl From<TextAlign> for textlayout::TextAlign {
+    fn from(text_align: TextAlign) -> Self {
+        match text_align {
+            TextAlign::Left => textlayout::TextAlign::Left,
+            TextAlign::Right => textlayout::TextAlign::Right,
+            TextAlign::Center => textlayout::TextAlign::Center,
+            TextAlign::Justify => textlayout::TextAlign::Justify,
+            TextAlign::Start => textlayout::TextAlign::Start,
+            TextAlign::End => textlayout::TextAlign::End,
+        }
+    }
+}
+
+impl From<TextDirection> for textlayout::TextDirection {
+    fn from(text_direction: TextDirection) -> Self {
+        match text_direction {
+            TextDirection::RTL => textlayout::TextDirection::RTL,
+            TextDirection::LTR => textlayout::TextDirection::LTR,
+        }
+    }
+}
+
+/// Lays out and paints a [`TextSpan`], and maps between positions in its text and
+/// offsets in the laid out paragraph.
+///
+/// Changing what is laid out drops the paragraph, it is built again on the next use.
+pub struct TextPainter {
+    pub(crate) text: TextSpan,
+    /// The style the spans inherit from.
+    pub(crate) style: TextStyle,
+    pub(crate) text_align: TextAlign,
+    pub(crate) text_direction: TextDirection,
+    pub(crate) max_lines: Option<usize>,
+    /// Whether the last line ends with an ellipsis when the text overflows.
+    pub(crate) ellipsis: bool,
+    /// The width of the last layout, queries use the paragraph laid out at it.
+    width: Cell<f32>,
+    /// Built on first use, dropped whenever the text or how it is shaped changes.
+    paragraph: RefCell<Option<Paragraph>>,
+    /// The width the paragraph was last laid out at, intrinsics lay it out at others.
+    paragraph_width: Cell<Option<f32>>,
+}
+
+impl Debug for TextPainter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TextPainter")
+            .field("text", &self.text)
+            .field("style", &self.style)
+            .field("text_align", &self.text_align)
+            .field("text_direction", &self.text_direction)
+            .field("max_lines", &self.max_lines)
+            .field("ellipsis", &self.ellipsis)
+            .field("width", &self.width.get())
+            .finish()
+    }
+}
+
+impl TextPainter {
+    pub fn new(text: impl Into<TextSpan>) -> Self {
+        TextPainter {
+            text: text.into(),
+            style: Default::default(),
+            text_align: TextAlign::Start,
+            text_direction: TextDirection::LTR,
+            max_lines: None,
+            ellipsis: false,
+            width: Cell::new(0.0),
+            paragraph: RefCell::new(None),
+            paragraph_width: Cell::new(None),
+        }
+    }
+
+    pub fn set_text(&mut self, text: TextSpan) {
+        self.text = text;
+        self.mark_needs_rebuild();
+    }
+
+    pub fn set_style(&mut self, style: TextStyle) {
+        self.style = style;
+        self.mark_needs_rebuild();
+    }
+
+    pub fn set_text_align(&mut self, text_align: TextAlign) {
+        self.text_align = text_align;
+        self.mark_needs_rebuild();
+    }
+
+    pub fn set_text_direction(&mut self, text_direction: TextDirection) {
+        self.text_direction = text_direction;
+        self.mark_needs_rebuild();
+    }
+
+    pub fn set_max_lines(&mut self, max_lines: Option<usize>) {
+        self.max_lines = max_lines;
+        self.mark_needs_rebuild();
+    }
+
+    pub fn set_ellipsis(&mut self, ellipsis: bool) {
+        self.ellipsis = ellipsis;
+        self.mark_needs_rebuild();
+    }
+
+    /// Lays out the text as wide as its longest line within the given widths.
+    pub fn layout(&self, min_width: f32, max_width: f32) {
+        let width = self.fit(min_width, max_width);
+        self.width.set(width);
+    }
+
+    /// The width of the last layout.
+    pub fn width(&self) -> f32 {
+        self.width.get()
+    }
+
+    pub fn height(&self) -> f32 {
+        self.laid_out().height()
+    }
+
+    /// Width of the longest word, the narrowest the text gets without clipping.
+    pub fn min_intrinsic_width(&self) -> f32 {
+        self.layout_paragraph(f32::INFINITY);
+        self.paragraph().min_intrinsic_width().ceil()
+    }
+
+    /// Width of the text without soft line breaks.
+    pub fn max_intrinsic_width(&self) -> f32 {
+        self.layout_paragraph(f32::INFINITY);
+        self.paragraph().max_intrinsic_width().ceil()
+    }
+
+    /// The height the text would have when laid out within the given widths.
+    pub fn height_for_width(&self, min_width: f32, max_width: f32) -> f32 {
+        self.fit(min_width, max_width);
+        self.paragraph().height()
+    }
+
+    /// Distance from the top of the text to the baseline of its first line.
+    pub fn distance_to_baseline(&self, baseline: TextBaseline) -> f32 {
+        let paragraph = self.laid_out();
+        match baseline {
+            TextBaseline::Alphabetic => paragraph.alphabetic_baseline(),
+            TextBaseline::Ideographic => paragraph.ideographic_baseline(),
+        }
+    }
+
+    /// Number of lines after the last layout, at most `max_lines`.
+    pub fn line_count(&self) -> usize {
+        self.laid_out().line_number()
+    }
+
+    /// Whether the text had more lines than `max_lines` in the last layout.
+    pub fn did_exceed_max_lines(&self) -> bool {
+        self.laid_out().did_exceed_max_lines()
+    }
+
+    /// Height of a line of the base style, e.g. that of the caret in empty text.
+    pub fn preferred_line_height(&self) -> f32 {
+        let mut paragraph_style = ParagraphStyle::new();
+        paragraph_style.set_text_style(&self.style.to_skia());
+        let mut builder = ParagraphBuilder::new(&paragraph_style, font_collection());
+        builder.add_text(" ");
+        let mut paragraph = builder.build();
+        paragraph.layout(f32::INFINITY);
+        paragraph.height()
+    }
+
+    pub fn paint(&self, canvas: &mut Canvas, offset: Offset) {
+        self.laid_out().paint(canvas, (offset.x, offset.y));
+    }
+
+    /// The text position closest to `offset`, i.e. where a caret placed there goes.
+    pub fn get_position_for_offset(&self, offset: Offset) -> usize {
+        let position = self
+            .laid_out()
+            .get_glyph_position_at_coordinate((offset.x, offset.y));
+        position.position.max(0) as usize
+    }
+
+    /// The index of the character painted at `offset`, if any.
+    pub fn get_character_at_offset(&self, offset: Offset) -> Option<usize> {
+        let paragraph = self.laid_out();
+        // the boundary closest to the offset, after the character hit when upstream
+        let position = paragraph.get_glyph_position_at_coordinate((offset.x, offset.y));
+        let boundary = usize::try_from(position.position).ok()?;
+        let index = match position.affinity {
+            Affinity::Downstream => boundary,
+            Affinity::Upstream => boundary.checked_sub(1)?,
+        };
+        // the closest boundary may be far away, e.g. when past the end of a line
+        let boxes = paragraph.get_rects_for_range(
+            index..index + 1,
+            RectHeightStyle::Max,
+            RectWidthStyle::Tight,
+        );
+        boxes
+            .iter()
+            .any(|text_box| {
+                let rect = &text_box.rect;
+                (rect.left..rect.right).contains(&offset.x)
+                    && (rect.top..rect.bottom).contains(&offset.y)
+            })
+            .then_some(index)
+    }
+
+    /// The rects covering the text between the positions of `range`, one per line.
+    pub fn get_boxes_for_range(&self, range: Range<usize>) -> Vec<Rect> {
+        self.laid_out()
+            .get_rects_for_range(range, RectHeightStyle::Max, RectWidthStyle::Tight)
+            .iter()
+            .map(|text_box| text_box.rect)
+            .collect()
+    }
+
+    /// The top left of a caret at `position`, on the line of the character before it.
+    pub fn get_offset_for_caret(&self, position: usize) -> Offset {
+        let text: Vec<u16> = self.text.to_plain_text().encode_utf16().collect();
+        let position = position.min(text.len());
+        let is_line_break = |index: usize| text[index] == '\n' as u16;
+        let char_before = position
+            .checked_sub(1)
+            .map(|start| self.get_boxes_for_range(start..position))
+            .filter(|boxes| !boxes.is_empty());
+        match char_before {
+            Some(boxes) if !is_line_break(position - 1) => {
+                let rect = boxes.last().unwrap();
+                Offset::new(rect.right, rect.top)
+            }
+            // the caret starts the line after a line break
+            Some(boxes) => {
+                let next = (position < text.len() && !is_line_break(position))
+                    .then(|| self.get_boxes_for_range(position..position + 1))
+                    .and_then(|boxes| boxes.first().copied());
+                match next {
+                    Some(rect) => Offset::new(rect.left, rect.top),
+                    None => Offset::new(0.0, boxes.last().unwrap().bottom),
+                }
+            }
+            None => {
+                let boxes = self.get_boxes_for_range(position..position + 1);
+                match boxes.first() {
+                    Some(rect) => Offset::new(rect.left, rect.top),
+                    None => Offset::zero(),
+                }
+            }
+        }
+    }
+
+    fn mark_needs_rebuild(&mut self) {
+        self.paragraph.replace(None);
+        self.paragraph_width.set(None);
+    }
+
+    pub(crate) fn paragraph(&self) -> RefMut<'_, Paragraph> {
+        RefMut::map(self.paragraph.borrow_mut(), |paragraph| {
+            paragraph.get_or_insert_with(|| self.build_paragraph())
+        })
+    }
+
+    /// The paragraph laid out at the width of the last layout.
+    fn laid_out(&self) -> RefMut<'_, Paragraph> {
+        self.layout_paragraph(self.width.get());
+        self.paragraph()
+    }
+
+    fn build_paragraph(&self) -> Paragraph {
+        let text_style = self.style.to_skia();
+        let mut paragraph_style = ParagraphStyle::new();
+        paragraph_style.set_text_style(&text_style);
+        paragraph_style.set_text_align(self.text_align.into());
+        paragraph_style.set_text_direction(self.text_direction.into());
+        paragraph_style.set_max_lines(self.max_lines);
+        if self.ellipsis {
+            paragraph_style.set_ellipsis(ELLIPSIS);
+        }
+        let mut builder = ParagraphBuilder::new(&paragraph_style, font_collection());
+        self.text.build(&mut builder, &self.style);
+        builder.build()
+    }
+
+    fn layout_paragraph(&self, width: f32) {
+        if self.paragraph_width.get() != Some(width) {
+            self.paragraph().layout(width);
+            self.paragraph_width.set(Some(width));
+        }
+    }
+
+    /// Lays out the paragraph as wide as its longest line within the given widths and
+    /// returns that width.
+    fn fit(&self, min_width: f32, max_width: f32) -> f32 {
+        self.layout_paragraph(max_width);
+        let max_intrinsic_width = self.paragraph().max_intrinsic_width().ceil();
+        let width = max_intrinsic_width.clamp(min_width, max_width);
+        // aligning lines needs the final width
+        self.layout_paragraph(width);
+        width
+    }
+}
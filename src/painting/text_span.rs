@@ -0,0 +1,131 @@
+use std::{cell::RefCell, rc::Rc};
+
+use skia_safe::textlayout::ParagraphBuilder;
+
+use crate::{gestures::HitTestTarget, painting::TextStyle};
+
+/// A tree of styled text. Each span inherits the style of its parent, overriding the
+/// properties its own style sets, and is followed by the text of its children.
+///
+/// Positions in the text are in UTF-16 code units, the unit skia measures text in.
+#[derive(Debug, Clone, Default)]
+pub struct TextSpan {
+    pub text: Option<String>,
+    pub style: Option<TextStyle>,
+    pub children: Vec<TextSpan>,
+
+    /// Receives the pointer events hitting the text of this span, e.g. to make it a link.
+    pub target: Option<Rc<RefCell<dyn HitTestTarget>>>,
+}
+
+impl TextSpan {
+    pub fn new(text: impl Into<String>) -> Self {
+        TextSpan {
+            text: Some(text.into()),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_style(mut self, style: TextStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    pub fn with_child(mut self, child: impl Into<TextSpan>) -> Self {
+        self.children.push(child.into());
+        self
+    }
+
+    pub fn with_target(mut self, target: Rc<RefCell<dyn HitTestTarget>>) -> Self {
+        self.target = Some(target);
+        self
+    }
+
+    /// The text of this span and its descendants, without styling.
+    pub fn to_plain_text(&self) -> String {
+        let mut text = String::new();
+        self.visit_texts(&mut |span, span_text| {
+            text.push_str(span_text);
+            true
+        });
+        text
+    }
+
+    /// The span whose own text contains the character at `position`.
+    pub fn get_span_for_position(&self, position: usize) -> Option<&TextSpan> {
+        let mut offset = 0;
+        let mut found = None;
+        self.visit_texts(&mut |span, text| {
+            let end = offset + text.encode_utf16().count();
+            if (offset..end).contains(&position) {
+                found = Some(span);
+                return false;
+            }
+            offset = end;
+            true
+        });
+        found
+    }
+
+    /// Visits the spans with text in order, until `visitor` returns `false`.
+    fn visit_texts<'a>(&'a self, visitor: &mut dyn FnMut(&'a TextSpan, &'a str) -> bool) -> bool {
+        if let Some(text) = &self.text {
+            if !visitor(self, text) {
+                return false;
+            }
+        }
+        self.children.iter().all(|child| child.visit_texts(visitor))
+    }
+
+    /// Whether the spans of both trees have the same targets, for trees that are equal.
+    pub fn has_same_targets(&self, other: &TextSpan) -> bool {
+        let same_target = match (&self.target, &other.target) {
+            (Some(a), Some(b)) => Rc::as_ptr(a) as *const () == Rc::as_ptr(b) as *const (),
+            (None, None) => true,
+            _ => false,
+        };
+        same_target
+            && self.children.len() == other.children.len()
+            && self
+                .children
+                .iter()
+                .zip(&other.children)
+                .all(|(a, b)| a.has_same_targets(b))
+    }
+
+    /// Adds the text of this tree to `builder`, styled on top of `style`.
+    pub(crate) fn build(&self, builder: &mut ParagraphBuilder, style: &TextStyle) {
+        let style = match &self.style {
+            Some(own) => style.merge(own),
+            None => style.clone(),
+        };
+        builder.push_style(&style.to_skia());
+        if let Some(text) = &self.text {
+            builder.add_text(text);
+        }
+        for child in self.children.iter() {
+            child.build(builder, &style);
+        }
+        builder.pop();
+    }
+}
+
+/// Spans are equal when they are laid out the same, their targets are compared with
+/// [`TextSpan::has_same_targets`].
+impl PartialEq for TextSpan {
+    fn eq(&self, other: &Self) -> bool {
+        self.text == other.text && self.style == other.style && self.children == other.children
+    }
+}
+
+impl From<&str> for TextSpan {
+    fn from(text: &str) -> Self {
+        TextSpan::new(text)
+    }
+}
+
+impl From<String> for TextSpan {
+    fn from(text: String) -> Self {
+        TextSpan::new(text)
+    }
+}
@@ -0,0 +1,215 @@
+use std::ops::BitOr;
+
+use skia_safe::{
+    textlayout::{
+        self,
+        font_style::{Slant, Width},
+        FontStyle,
+    },
+    FontMgr,
+};
+
+use crate::ui::Color;
+
+/// The font size used when no style sets one.
+pub const DEFAULT_FONT_SIZE: f32 = 14.0;
+
+thread_local! {
+    static FONT_COLLECTION: textlayout::FontCollection = {
+        let mut font_collection = textlayout::FontCollection::new();
+        font_collection.set_default_font_manager(FontMgr::new(), None);
+        font_collection
+    };
+}
+
+/// The fonts of the platform, shared by all paragraphs.
+pub(crate) fn font_collection() -> textlayout::FontCollection {
+    FONT_COLLECTION.with(|font_collection| font_collection.clone())
+}
+
+/// The thickness of the glyphs, from 100 (thin) to 900 (black).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FontWeight(pub u16);
+
+impl FontWeight {
+    pub const W100: FontWeight = FontWeight(100);
+    pub const W200: FontWeight = FontWeight(200);
+    pub const W300: FontWeight = FontWeight(300);
+    pub const W400: FontWeight = FontWeight(400);
+    pub const W500: FontWeight = FontWeight(500);
+    pub const W600: FontWeight = FontWeight(600);
+    pub const W700: FontWeight = FontWeight(700);
+    pub const W800: FontWeight = FontWeight(800);
+    pub const W900: FontWeight = FontWeight(900);
+    pub const NORMAL: FontWeight = FontWeight::W400;
+    pub const BOLD: FontWeight = FontWeight::W700;
+}
+
+/// Lines drawn along the text, combined with `|`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub struct TextDecoration {
+    pub underline: bool,
+    pub overline: bool,
+    pub line_through: bool,
+}
+
+impl TextDecoration {
+    pub const NONE: TextDecoration = TextDecoration {
+        underline: false,
+        overline: false,
+        line_through: false,
+    };
+    pub const UNDERLINE: TextDecoration = TextDecoration {
+        underline: true,
+        ..TextDecoration::NONE
+    };
+    pub const OVERLINE: TextDecoration = TextDecoration {
+        overline: true,
+        ..TextDecoration::NONE
+    };
+    pub const LINE_THROUGH: TextDecoration = TextDecoration {
+        line_through: true,
+        ..TextDecoration::NONE
+    };
+
+    fn to_skia(self) -> textlayout::TextDecoration {
+        let mut decoration = textlayout::TextDecoration::NO_DECORATION;
+        if self.underline {
+            decoration.insert(textlayout::TextDecoration::UNDERLINE);
+        }
+        if self.overline {
+            decoration.insert(textlayout::TextDecoration::OVERLINE);
+        }
+        if self.line_through {
+            decoration.insert(textlayout::TextDecoration::LINE_THROUGH);
+        }
+        decoration
+    }
+}
+
+impl BitOr for TextDecoration {
+    type Output = TextDecoration;
+
+    fn bitor(self, rhs: TextDecoration) -> TextDecoration {
+        TextDecoration {
+            underline: self.underline || rhs.underline,
+            overline: self.overline || rhs.overline,
+            line_through: self.line_through || rhs.line_through,
+        }
+    }
+}
+
+/// How text is drawn. Properties left as `None` are inherited from the enclosing
+/// style, or use the platform defaults.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TextStyle {
+    pub color: Option<Color>,
+    pub font_size: Option<f32>,
+    pub font_families: Vec<String>,
+    pub font_weight: Option<FontWeight>,
+
+    /// Height of a line as a multiple of the font size.
+    pub height: Option<f32>,
+
+    /// Extra space added after each glyph, may be negative.
+    pub letter_spacing: Option<f32>,
+
+    pub decoration: Option<TextDecoration>,
+
+    /// Color of the decoration, the text color if unset.
+    pub decoration_color: Option<Color>,
+}
+
+impl TextStyle {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_color(mut self, color: impl Into<Color>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    pub fn with_font_size(mut self, font_size: f32) -> Self {
+        self.font_size = Some(font_size);
+        self
+    }
+
+    pub fn with_font_family(mut self, font_family: impl Into<String>) -> Self {
+        self.font_families.push(font_family.into());
+        self
+    }
+
+    pub fn with_font_weight(mut self, font_weight: FontWeight) -> Self {
+        self.font_weight = Some(font_weight);
+        self
+    }
+
+    pub fn with_height(mut self, height: f32) -> Self {
+        self.height = Some(height);
+        self
+    }
+
+    pub fn with_letter_spacing(mut self, letter_spacing: f32) -> Self {
+        self.letter_spacing = Some(letter_spacing);
+        self
+    }
+
+    pub fn with_decoration(mut self, decoration: TextDecoration) -> Self {
+        self.decoration = Some(decoration);
+        self
+    }
+
+    pub fn with_decoration_color(mut self, decoration_color: impl Into<Color>) -> Self {
+        self.decoration_color = Some(decoration_color.into());
+        self
+    }
+
+    /// This style with the properties set in `other` replacing its own.
+    pub fn merge(&self, other: &TextStyle) -> TextStyle {
+        TextStyle {
+            color: other.color.or(self.color),
+            font_size: other.font_size.or(self.font_size),
+            font_families: if other.font_families.is_empty() {
+                self.font_families.clone()
+            } else {
+                other.font_families.clone()
+            },
+            font_weight: other.font_weight.or(self.font_weight),
+            height: other.height.or(self.height),
+            letter_spacing: other.letter_spacing.or(self.letter_spacing),
+            decoration: other.decoration.or(self.decoration),
+            decoration_color: other.decoration_color.or(self.decoration_color),
+        }
+    }
+
+    pub(crate) fn to_skia(&self) -> textlayout::TextStyle {
+        let mut style = textlayout::TextStyle::new();
+        style.set_color(self.color.unwrap_or(Color::BLACK));
+        style.set_font_size(self.font_size.unwrap_or(DEFAULT_FONT_SIZE));
+        if !self.font_families.is_empty() {
+            style.set_font_families(&self.font_families);
+        }
+        if let Some(FontWeight(weight)) = self.font_weight {
+            style.set_font_style(FontStyle::new(
+                (weight as i32).into(),
+                Width::NORMAL,
+                Slant::Upright,
+            ));
+        }
+        if let Some(height) = self.height {
+            style.set_height(height);
+            style.set_height_override(true);
+        }
+        if let Some(letter_spacing) = self.letter_spacing {
+            style.set_letter_spacing(letter_spacing);
+        }
+        if let Some(decoration) = self.decoration {
+            style.set_decoration_type(decoration.to_skia());
+            if let Some(decoration_color) = self.decoration_color.or(self.color) {
+                style.set_decoration_color(decoration_color);
+            }
+        }
+        style
+    }
+}
@@ -0,0 +1,116 @@
+use crate::physics::{Simulation, Tolerance};
+
+/// A body sliding from `position` at `velocity` and slowed down by friction, its velocity
+/// multiplied by `drag` each second.
+#[derive(Debug, Clone)]
+pub struct FrictionSimulation {
+    drag: f32,
+    drag_log: f32,
+    position: f32,
+    velocity: f32,
+    tolerance: Tolerance,
+}
+
+impl FrictionSimulation {
+    pub fn new(drag: f32, position: f32, velocity: f32) -> Self {
+        assert!(drag > 0.0 && drag < 1.0);
+        FrictionSimulation {
+            drag,
+            drag_log: drag.ln(),
+            position,
+            velocity,
+            tolerance: Tolerance::DEFAULT,
+        }
+    }
+
+    pub fn with_tolerance(mut self, tolerance: Tolerance) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Where the body comes to rest.
+    pub fn final_x(&self) -> f32 {
+        self.position - self.velocity / self.drag_log
+    }
+
+    /// When the body passes `x`, infinite if it never does.
+    pub fn time_at_x(&self, x: f32) -> f32 {
+        if x == self.position {
+            return 0.0;
+        }
+        let final_x = self.final_x();
+        let passes = if self.velocity > 0.0 {
+            self.position < x && x < final_x
+        } else {
+            final_x < x && x < self.position
+        };
+        if self.velocity == 0.0 || !passes {
+            return f32::INFINITY;
+        }
+        (self.drag_log * (x - self.position) / self.velocity + 1.0).ln() / self.drag_log
+    }
+}
+
+impl Simulation for FrictionSimulation {
+    fn x(&self, time: f32) -> f32 {
+        self.position + self.velocity * (self.drag.powf(time) - 1.0) / self.drag_log
+    }
+
+    fn dx(&self, time: f32) -> f32 {
+        self.velocity * self.drag.powf(time)
+    }
+
+    fn is_done(&self, time: f32) -> bool {
+        self.dx(time).abs() < self.tolerance.velocity
+    }
+
+    fn tolerance(&self) -> Tolerance {
+        self.tolerance
+    }
+}
+
+/// A [`FrictionSimulation`] stopping at `min_x` and `max_x`.
+#[derive(Debug, Clone)]
+pub struct BoundedFrictionSimulation {
+    friction: FrictionSimulation,
+    min_x: f32,
+    max_x: f32,
+}
+
+impl BoundedFrictionSimulation {
+    pub fn new(drag: f32, position: f32, velocity: f32, min_x: f32, max_x: f32) -> Self {
+        assert!(min_x <= position && position <= max_x);
+        BoundedFrictionSimulation {
+            friction: FrictionSimulation::new(drag, position, velocity),
+            min_x,
+            max_x,
+        }
+    }
+
+    pub fn with_tolerance(mut self, tolerance: Tolerance) -> Self {
+        self.friction = self.friction.with_tolerance(tolerance);
+        self
+    }
+}
+
+impl Simulation for BoundedFrictionSimulation {
+    fn x(&self, time: f32) -> f32 {
+        self.friction.x(time).clamp(self.min_x, self.max_x)
+    }
+
+    fn dx(&self, time: f32) -> f32 {
+        self.friction.dx(time)
+    }
+
+    fn is_done(&self, time: f32) -> bool {
+        let x = self.x(time);
+        let distance = self.friction.tolerance.distance;
+        self.friction.is_done(time)
+            || (x - self.min_x).abs() < distance
+            || (x - self.max_x).abs() < distance
+    }
+
+    fn tolerance(&self) -> Tolerance {
+        self.friction.tolerance
+    }
+}
@@ -0,0 +1,14 @@
+mod utils;
+pub use utils::*;
+
+mod tolerance;
+pub use tolerance::*;
+
+mod simulation;
+pub use simulation::*;
+
+mod spring_simulation;
+pub use spring_simulation::*;
+
+mod friction_simulation;
+pub use friction_simulation::*;
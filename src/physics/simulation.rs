@@ -0,0 +1,20 @@
+use std::fmt::Debug;
+
+use crate::physics::Tolerance;
+
+/// A position over time, e.g. of an animation driven by a spring. The time is in seconds
+/// from the start of the simulation.
+pub trait Simulation: Debug {
+    /// The position at `time`.
+    fn x(&self, time: f32) -> f32;
+
+    /// The velocity at `time`.
+    fn dx(&self, time: f32) -> f32;
+
+    /// Whether the simulation has come to rest at `time`, within its tolerance.
+    fn is_done(&self, time: f32) -> bool;
+
+    fn tolerance(&self) -> Tolerance {
+        Tolerance::DEFAULT
+    }
+}
@@ -0,0 +1,187 @@
+use crate::physics::{near_zero, Simulation, Tolerance};
+
+/// The mass, stiffness and damping of a spring.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpringDescription {
+    pub mass: f32,
+    pub stiffness: f32,
+    pub damping: f32,
+}
+
+impl SpringDescription {
+    pub fn new(mass: f32, stiffness: f32, damping: f32) -> Self {
+        SpringDescription {
+            mass,
+            stiffness,
+            damping,
+        }
+    }
+
+    /// A spring damped by `ratio` of the damping making it critically damped: it
+    /// oscillates below 1 and comes to rest slower above.
+    pub fn with_damping_ratio(mass: f32, stiffness: f32, ratio: f32) -> Self {
+        SpringDescription {
+            mass,
+            stiffness,
+            damping: ratio * 2.0 * (mass * stiffness).sqrt(),
+        }
+    }
+}
+
+/// How a spring released away from its rest moves, with the rest at zero.
+#[derive(Debug, Clone, Copy)]
+enum SpringSolution {
+    /// `(c1 + c2 t) e^(r t)`
+    Critical { r: f32, c1: f32, c2: f32 },
+    /// `c1 e^(r1 t) + c2 e^(r2 t)`
+    Overdamped { r1: f32, r2: f32, c1: f32, c2: f32 },
+    /// `e^(r t) (c1 cos(w t) + c2 sin(w t))`
+    Underdamped { w: f32, r: f32, c1: f32, c2: f32 },
+}
+
+impl SpringSolution {
+    fn new(spring: &SpringDescription, distance: f32, velocity: f32) -> Self {
+        let SpringDescription {
+            mass,
+            stiffness,
+            damping,
+        } = *spring;
+        let cmk = damping * damping - 4.0 * mass * stiffness;
+        // a spring with a damping ratio of 1 is critically damped, but for rounding
+        if cmk.abs() <= 16.0 * f32::EPSILON * 4.0 * mass * stiffness {
+            let r = -damping / (2.0 * mass);
+            SpringSolution::Critical {
+                r,
+                c1: distance,
+                c2: velocity - r * distance,
+            }
+        } else if cmk > 0.0 {
+            let r1 = (-damping - cmk.sqrt()) / (2.0 * mass);
+            let r2 = (-damping + cmk.sqrt()) / (2.0 * mass);
+            let c2 = (velocity - r1 * distance) / (r2 - r1);
+            SpringSolution::Overdamped {
+                r1,
+                r2,
+                c1: distance - c2,
+                c2,
+            }
+        } else {
+            let w = (4.0 * mass * stiffness - damping * damping).sqrt() / (2.0 * mass);
+            let r = -damping / (2.0 * mass);
+            SpringSolution::Underdamped {
+                w,
+                r,
+                c1: distance,
+                c2: (velocity - r * distance) / w,
+            }
+        }
+    }
+
+    fn x(&self, t: f32) -> f32 {
+        match *self {
+            SpringSolution::Critical { r, c1, c2 } => (c1 + c2 * t) * (r * t).exp(),
+            SpringSolution::Overdamped { r1, r2, c1, c2 } => {
+                c1 * (r1 * t).exp() + c2 * (r2 * t).exp()
+            }
+            SpringSolution::Underdamped { w, r, c1, c2 } => {
+                (r * t).exp() * (c1 * (w * t).cos() + c2 * (w * t).sin())
+            }
+        }
+    }
+
+    fn dx(&self, t: f32) -> f32 {
+        match *self {
+            SpringSolution::Critical { r, c1, c2 } => {
+                let power = (r * t).exp();
+                r * (c1 + c2 * t) * power + c2 * power
+            }
+            SpringSolution::Overdamped { r1, r2, c1, c2 } => {
+                c1 * r1 * (r1 * t).exp() + c2 * r2 * (r2 * t).exp()
+            }
+            SpringSolution::Underdamped { w, r, c1, c2 } => {
+                let power = (r * t).exp();
+                let (sin, cos) = (w * t).sin_cos();
+                power * (c2 * w * cos - c1 * w * sin) + r * power * (c2 * sin + c1 * cos)
+            }
+        }
+    }
+}
+
+/// A spring pulling a mass from `start`, at `velocity`, to rest at `end`.
+#[derive(Debug, Clone)]
+pub struct SpringSimulation {
+    end: f32,
+    solution: SpringSolution,
+    tolerance: Tolerance,
+}
+
+impl SpringSimulation {
+    pub fn new(spring: SpringDescription, start: f32, end: f32, velocity: f32) -> Self {
+        SpringSimulation {
+            end,
+            solution: SpringSolution::new(&spring, start - end, velocity),
+            tolerance: Tolerance::DEFAULT,
+        }
+    }
+
+    pub fn with_tolerance(mut self, tolerance: Tolerance) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+}
+
+impl Simulation for SpringSimulation {
+    fn x(&self, time: f32) -> f32 {
+        self.end + self.solution.x(time)
+    }
+
+    fn dx(&self, time: f32) -> f32 {
+        self.solution.dx(time)
+    }
+
+    fn is_done(&self, time: f32) -> bool {
+        near_zero(self.solution.x(time), self.tolerance.distance)
+            && near_zero(self.solution.dx(time), self.tolerance.velocity)
+    }
+
+    fn tolerance(&self) -> Tolerance {
+        self.tolerance
+    }
+}
+
+/// A spring simulation snapping to its end once done, e.g. to settle a scroll offset
+/// exactly on a page.
+#[derive(Debug, Clone)]
+pub struct ScrollSpringSimulation(SpringSimulation);
+
+impl ScrollSpringSimulation {
+    pub fn new(spring: SpringDescription, start: f32, end: f32, velocity: f32) -> Self {
+        ScrollSpringSimulation(SpringSimulation::new(spring, start, end, velocity))
+    }
+
+    pub fn with_tolerance(self, tolerance: Tolerance) -> Self {
+        ScrollSpringSimulation(self.0.with_tolerance(tolerance))
+    }
+}
+
+impl Simulation for ScrollSpringSimulation {
+    fn x(&self, time: f32) -> f32 {
+        if self.0.is_done(time) {
+            self.0.end
+        } else {
+            self.0.x(time)
+        }
+    }
+
+    fn dx(&self, time: f32) -> f32 {
+        self.0.dx(time)
+    }
+
+    fn is_done(&self, time: f32) -> bool {
+        self.0.is_done(time)
+    }
+
+    fn tolerance(&self) -> Tolerance {
+        self.0.tolerance()
+    }
+}
@@ -0,0 +1,32 @@
+/// How close a simulation has to get to be done.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tolerance {
+    /// Of the position, in logical pixels or the unit of the simulation.
+    pub distance: f32,
+    /// Of the time, in seconds.
+    pub time: f32,
+    /// Of the velocity, in units per second.
+    pub velocity: f32,
+}
+
+impl Tolerance {
+    pub const DEFAULT: Tolerance = Tolerance {
+        distance: 1e-3,
+        time: 1e-3,
+        velocity: 1e-3,
+    };
+
+    pub fn new(distance: f32, time: f32, velocity: f32) -> Self {
+        Tolerance {
+            distance,
+            time,
+            velocity,
+        }
+    }
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Tolerance::DEFAULT
+    }
+}
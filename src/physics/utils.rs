@@ -0,0 +1,9 @@
+/// Whether `a` and `b` differ by at most `epsilon`.
+pub fn near_equal(a: f32, b: f32, epsilon: f32) -> bool {
+    a == b || (a - b).abs() <= epsilon
+}
+
+/// Whether `a` is within `epsilon` of zero.
+pub fn near_zero(a: f32, epsilon: f32) -> bool {
+    near_equal(a, 0.0, epsilon)
+}
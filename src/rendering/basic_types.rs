@@ -48,7 +48,7 @@ impl From<(f32, f32)> for Size {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub struct Offset {
     pub x: f32,
     pub y: f32,
@@ -62,6 +62,10 @@ impl Offset {
     pub fn zero() -> Self {
         Self { x: 0.0, y: 0.0 }
     }
+
+    pub fn distance(&self) -> f32 {
+        (self.x * self.x + self.y * self.y).sqrt()
+    }
 }
 
 impl std::ops::Add<Offset> for Offset {
@@ -91,7 +95,7 @@ impl std::ops::AddAssign<Offset> for Offset {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Axis {
     Horizontal,
     Vertical,
@@ -106,7 +110,7 @@ impl Axis {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum VerticalDirection {
     Down,
     Up,
@@ -120,6 +124,13 @@ pub enum AxisDirection {
     Left,
 }
 
+impl AxisDirection {
+    /// Whether the direction points towards the origin, i.e. up or left.
+    pub fn is_reversed(&self) -> bool {
+        matches!(self, AxisDirection::Up | AxisDirection::Left)
+    }
+}
+
 impl From<AxisDirection> for Axis {
     fn from(dir: AxisDirection) -> Self {
         match dir {
@@ -131,18 +142,27 @@ impl From<AxisDirection> for Axis {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ScrollDirection {
+    /// Not scrolling.
     Idle,
+    /// Scrolling towards the start of the content, the scroll offset decreases.
     Forward,
+    /// Scrolling towards the end of the content, the scroll offset increases.
     Reverse,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+impl Default for ScrollDirection {
+    fn default() -> Self {
+        ScrollDirection::Idle
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum TextBaseline {
     Alphabetic,
     Ideographic,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum TextDirection {
     /// The text flows from right to left (e.g. Arabic, Hebrew).
     RTL,
@@ -151,7 +171,24 @@ pub enum TextDirection {
     LTR,
 }
 
+/// How lines of text are aligned horizontally within the paragraph.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextAlign {
+    Left,
+    Right,
+    Center,
+
+    /// Stretch lines that end with a soft line break to fill the width.
+    Justify,
+
+    /// The leading edge, left for `TextDirection::LTR` and right for `TextDirection::RTL`.
+    Start,
+
+    /// The trailing edge, right for `TextDirection::LTR` and left for `TextDirection::RTL`.
+    End,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Clip {
     None,
     HardEdge,
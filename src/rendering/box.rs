@@ -1,4 +1,10 @@
-use crate::rendering::{Offset, RenderObject, Size};
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    rendering::{Offset, RenderObject, RenderSliver, RenderSliverToBoxAdapter, Size, TextBaseline},
+    semantics::SemanticsConfiguration,
+    services::FocusNode,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct BoxConstraints {
@@ -64,6 +70,13 @@ impl BoxConstraints {
             max_height,
         }
     }
+    pub fn tight_for_finite(width: f32, height: f32) -> Self {
+        BoxConstraints::tight_for(
+            Some(width).filter(|w| w.is_finite()),
+            Some(height).filter(|h| h.is_finite()),
+        )
+    }
+
     pub fn expand() -> Self {
         BoxConstraints::expand_by(None, None)
     }
@@ -103,6 +116,22 @@ impl BoxConstraints {
         self.min_height >= self.max_height
     }
 
+    pub fn has_bounded_width(&self) -> bool {
+        self.max_width < f32::INFINITY
+    }
+
+    pub fn has_bounded_height(&self) -> bool {
+        self.max_height < f32::INFINITY
+    }
+
+    pub fn has_infinite_width(&self) -> bool {
+        self.min_width >= f32::INFINITY
+    }
+
+    pub fn has_infinite_height(&self) -> bool {
+        self.min_height >= f32::INFINITY
+    }
+
     pub fn is_tight(&self) -> bool {
         self.has_tight_width() && self.has_tight_height()
     }
@@ -174,14 +203,136 @@ impl BoxConstraints {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IntrinsicDimension {
+    MinWidth,
+    MaxWidth,
+    MinHeight,
+    MaxHeight,
+}
+
 pub trait RenderBox: RenderObject {
+    /// Lay out this box, skipped when it is clean and `constraints` are unchanged.
+    ///
+    /// The box becomes a relayout boundary when its parent does not use its size or
+    /// the constraints are tight, so later dirty marks stop here.
     fn layout(&mut self, constraints: &BoxConstraints, parent_use_size: bool) {
-        self.perform_layout(constraints)
+        let state = self.state().clone();
+        state.set_relayout_boundary(!parent_use_size || constraints.is_tight());
+        if !state.needs_layout() && state.constraints() == Some(*constraints) {
+            self.flush_layout();
+            return;
+        }
+        state.set_constraints(*constraints);
+        state.clear_baselines();
+        self.perform_layout(constraints);
+        state.clear_needs_layout();
+        state.mark_needs_paint();
+    }
+
+    /// Re-lay out the dirty parts of this subtree with their cached constraints.
+    fn flush_layout(&mut self) {
+        let state = self.state().clone();
+        if state.needs_layout() {
+            if let Some(constraints) = state.constraints() {
+                state.clear_baselines();
+                self.perform_layout(&constraints);
+                state.clear_needs_layout();
+                state.mark_needs_paint();
+            }
+        } else if state.take_child_needs_layout() {
+            self.visit_children(&mut |child| child.borrow_mut().flush_layout());
+        }
     }
 
     fn perform_layout(&mut self, constraints: &BoxConstraints);
 
+    /// The smallest width this box can have without clipping its content when given
+    /// `height`. Cached until the box is marked as needing layout.
+    fn get_min_intrinsic_width(&self, height: f32) -> f32 {
+        self.state()
+            .intrinsic_dimension(IntrinsicDimension::MinWidth, height, || {
+                self.compute_min_intrinsic_width(height)
+            })
+    }
+
+    /// The width beyond which increasing the width never decreases the preferred height.
+    fn get_max_intrinsic_width(&self, height: f32) -> f32 {
+        self.state()
+            .intrinsic_dimension(IntrinsicDimension::MaxWidth, height, || {
+                self.compute_max_intrinsic_width(height)
+            })
+    }
+
+    fn get_min_intrinsic_height(&self, width: f32) -> f32 {
+        self.state()
+            .intrinsic_dimension(IntrinsicDimension::MinHeight, width, || {
+                self.compute_min_intrinsic_height(width)
+            })
+    }
+
+    fn get_max_intrinsic_height(&self, width: f32) -> f32 {
+        self.state()
+            .intrinsic_dimension(IntrinsicDimension::MaxHeight, width, || {
+                self.compute_max_intrinsic_height(width)
+            })
+    }
+
+    /// Distance from the top of this box to `baseline`, falling back to the bottom
+    /// edge for boxes without one. Only valid after layout.
+    fn get_distance_to_baseline(&self, baseline: TextBaseline) -> f32 {
+        self.get_distance_to_actual_baseline(baseline)
+            .unwrap_or_else(|| self.size().height)
+    }
+
+    /// Distance from the top of this box to `baseline`, if its content has one.
+    fn get_distance_to_actual_baseline(&self, baseline: TextBaseline) -> Option<f32> {
+        self.state().distance_to_baseline(baseline, || {
+            self.compute_distance_to_actual_baseline(baseline)
+        })
+    }
+
+    fn compute_distance_to_actual_baseline(&self, baseline: TextBaseline) -> Option<f32> {
+        None
+    }
+
+    fn compute_min_intrinsic_width(&self, height: f32) -> f32 {
+        0.0
+    }
+
+    fn compute_max_intrinsic_width(&self, height: f32) -> f32 {
+        0.0
+    }
+
+    fn compute_min_intrinsic_height(&self, width: f32) -> f32 {
+        0.0
+    }
+
+    fn compute_max_intrinsic_height(&self, width: f32) -> f32 {
+        0.0
+    }
+
     fn perform_resize(&mut self, constraints: &BoxConstraints);
 
     fn size(&self) -> Size;
+
+    /// The node this box takes the keyboard focus with, it joins the focus tree at the
+    /// node of the nearest ancestor that has one.
+    fn focus_node(&self) -> Option<Rc<FocusNode>> {
+        None
+    }
+
+    /// Describes what this box is to assistive technologies, e.g. its label and the
+    /// actions it can carry out. Boxes with a focus node are focusable without saying so.
+    fn describe_semantics_configuration(&self, config: &mut SemanticsConfiguration) {}
+
+    /// Wraps this box into a sliver, e.g. to place it in a viewport.
+    fn into_sliver(self) -> Rc<RefCell<dyn RenderSliver>>
+    where
+        Self: Sized + 'static,
+    {
+        Rc::new(RefCell::new(RenderSliverToBoxAdapter::new(Rc::new(
+            RefCell::new(self),
+        ))))
+    }
 }
@@ -0,0 +1,588 @@
+use std::{
+    any::{type_name, TypeId},
+    rc::Rc,
+};
+
+use skia_safe::{Color4f, Paint, Rect};
+
+use crate::{
+    gestures::{HitTestEntry, HitTestResult, HitTestTarget, PointerEvent, PointerEventKind},
+    painting::{TextDecoration, TextPainter, TextSpan, TextStyle},
+    rendering::{
+        BoxConstraints, Clip, Offset, PaintContext, RenderBox, RenderObject, RenderState, Size,
+        TextAlign, TextBaseline,
+    },
+    semantics::{SemanticsAction, SemanticsActionHandler, SemanticsConfiguration, SemanticsRole},
+    services::{
+        FocusNode, KeyEvent, LogicalKey, TextEditingController, TextEditingValue, TextInputClient,
+        TextSelection, UndoHistory,
+    },
+    ui::Color,
+};
+
+/// Text edited with the keyboard while this box has the focus, with a caret and a
+/// selection placed by pointers and moved by the arrow keys. A pointer going down on it
+/// requests the focus.
+///
+/// A single line of text is never broken, it scrolls to keep the caret visible. Text an
+/// input method is composing is underlined until committed.
+#[derive(Debug)]
+pub struct RenderEditable {
+    // RenderObject
+    pub(crate) state: Rc<RenderState>,
+
+    // RenderBox
+    pub(crate) size: Size,
+
+    // RenderEditable
+    pub(crate) controller: Rc<TextEditingController>,
+    pub(crate) focus_node: Rc<FocusNode>,
+    pub(crate) painter: TextPainter,
+    /// Number of lines the box is high, it grows with the text when `None`.
+    pub(crate) max_lines: Option<usize>,
+    /// Number of chars the text is limited to, longer input is truncated.
+    pub(crate) max_length: Option<usize>,
+    pub(crate) has_focus: bool,
+    pub(crate) cursor_color: Color,
+    pub(crate) cursor_width: f32,
+    pub(crate) selection_color: Color,
+    pub(crate) history: UndoHistory,
+    /// How far a single line is scrolled left to keep the caret visible.
+    pub(crate) scroll_offset: f32,
+    pub(crate) needs_clipping: bool,
+}
+
+impl RenderEditable {
+    pub fn new(controller: Rc<TextEditingController>) -> Self {
+        let state = Rc::new(RenderState::default());
+        controller.attach(&state);
+        RenderEditable {
+            state,
+            size: Size::zero(),
+            painter: TextPainter::new(controller.text()),
+            controller,
+            focus_node: Rc::new(FocusNode::new()),
+            max_lines: Some(1),
+            max_length: None,
+            has_focus: false,
+            cursor_color: Color::BLACK,
+            cursor_width: 2.0,
+            selection_color: Color::from_argb(0x66, 0x33, 0x99, 0xff),
+            history: UndoHistory::default(),
+            scroll_offset: 0.0,
+            needs_clipping: false,
+        }
+    }
+
+    pub fn with_focus_node(mut self, focus_node: Rc<FocusNode>) -> Self {
+        self.focus_node = focus_node;
+        self
+    }
+
+    pub fn with_style(mut self, style: TextStyle) -> Self {
+        self.painter.style = style;
+        self
+    }
+
+    pub fn with_text_align(mut self, text_align: TextAlign) -> Self {
+        self.painter.text_align = text_align;
+        self
+    }
+
+    pub fn with_max_lines(mut self, max_lines: impl Into<Option<usize>>) -> Self {
+        self.max_lines = max_lines.into();
+        self
+    }
+
+    pub fn with_max_length(mut self, max_length: impl Into<Option<usize>>) -> Self {
+        self.max_length = max_length.into();
+        self
+    }
+
+    pub fn with_cursor_color(mut self, cursor_color: impl Into<Color>) -> Self {
+        self.cursor_color = cursor_color.into();
+        self
+    }
+
+    pub fn with_selection_color(mut self, selection_color: impl Into<Color>) -> Self {
+        self.selection_color = selection_color.into();
+        self
+    }
+
+    pub fn set_controller(&mut self, controller: Rc<TextEditingController>) {
+        if !Rc::ptr_eq(&self.controller, &controller) {
+            self.controller.detach(&self.state);
+            controller.attach(&self.state);
+            self.controller = controller;
+            self.history = UndoHistory::default();
+            self.mark_needs_layout();
+        }
+    }
+
+    /// Replaces the node the box takes the focus with, the focus moves with it on the
+    /// next update of the focus tree.
+    pub fn set_focus_node(&mut self, focus_node: Rc<FocusNode>) {
+        if !Rc::ptr_eq(&self.focus_node, &focus_node) {
+            if self.focus_node.has_primary_focus() {
+                focus_node.request_focus();
+            }
+            self.focus_node = focus_node;
+        }
+    }
+
+    pub fn set_style(&mut self, style: TextStyle) {
+        if self.painter.style != style {
+            self.painter.set_style(style);
+            self.mark_needs_layout();
+        }
+    }
+
+    pub fn set_text_align(&mut self, text_align: TextAlign) {
+        if self.painter.text_align != text_align {
+            self.painter.set_text_align(text_align);
+            self.mark_needs_layout();
+        }
+    }
+
+    pub fn set_max_lines(&mut self, max_lines: impl Into<Option<usize>>) {
+        let max_lines = max_lines.into();
+        if self.max_lines != max_lines {
+            self.max_lines = max_lines;
+            self.mark_needs_layout();
+        }
+    }
+
+    /// Limits the text to `max_length` chars, text already longer is kept.
+    pub fn set_max_length(&mut self, max_length: impl Into<Option<usize>>) {
+        self.max_length = max_length.into();
+    }
+
+    pub fn set_cursor_color(&mut self, cursor_color: impl Into<Color>) {
+        let cursor_color = cursor_color.into();
+        if self.cursor_color != cursor_color {
+            self.cursor_color = cursor_color;
+            self.mark_needs_paint();
+        }
+    }
+
+    pub fn set_selection_color(&mut self, selection_color: impl Into<Color>) {
+        let selection_color = selection_color.into();
+        if self.selection_color != selection_color {
+            self.selection_color = selection_color;
+            self.mark_needs_paint();
+        }
+    }
+
+    pub fn is_multiline(&self) -> bool {
+        self.max_lines != Some(1)
+    }
+
+    /// The caret of the current selection in the coordinates of this box.
+    pub fn caret_rect(&self) -> Rect {
+        let extent = self.controller.selection().extent;
+        let caret = self.painter.get_offset_for_caret(extent);
+        Rect::from_xywh(
+            caret.x - self.scroll_offset,
+            caret.y,
+            self.cursor_width,
+            self.painter.preferred_line_height(),
+        )
+    }
+
+    /// The rects of the selected text in the coordinates of this box.
+    pub fn selection_rects(&self) -> Vec<Rect> {
+        let selection = self.controller.selection();
+        self.painter
+            .get_boxes_for_range(selection.start()..selection.end())
+            .into_iter()
+            .map(|rect| rect.with_offset((-self.scroll_offset, 0.0)))
+            .collect()
+    }
+
+    /// The text position closest to `offset` in the coordinates of this box.
+    pub fn get_position_for_offset(&self, offset: Offset) -> usize {
+        let offset = Offset::new(offset.x + self.scroll_offset, offset.y);
+        self.painter.get_position_for_offset(offset)
+    }
+
+    /// Replaces the value, recording the replaced one in the undo history when the text
+    /// changes.
+    fn edit(&mut self, value: TextEditingValue) {
+        let previous = self.controller.value();
+        if previous.text != value.text {
+            self.history.record(previous);
+        }
+        self.controller.set_value(value);
+    }
+
+    /// Replaces the selection with `text`, cut to fit `max_length` and onto one line
+    /// unless multiline.
+    fn insert(&mut self, text: &str) {
+        let value = self.controller.value();
+        let mut text: String = if self.is_multiline() {
+            text.to_string()
+        } else {
+            text.chars().filter(|c| *c != '\n').collect()
+        };
+        if let Some(max_length) = self.max_length {
+            let kept = value.text.chars().count() - value.selected_text().chars().count();
+            let available = max_length.saturating_sub(kept);
+            text = text.chars().take(available).collect();
+        }
+        if text.is_empty() && value.selection.is_collapsed() {
+            return;
+        }
+        self.edit(value.replace_selection(&text));
+    }
+
+    /// Moves the caret to `position`, keeping the base of the selection when extending it.
+    fn move_caret(&self, position: usize, extend: bool) {
+        let selection = self.controller.selection();
+        let selection = if extend {
+            selection.extend_to(position)
+        } else {
+            TextSelection::collapsed(position)
+        };
+        self.controller.set_selection(selection);
+    }
+
+    /// The position a line above or below the caret, `lines` is negative going up.
+    fn position_across_lines(&self, position: usize, lines: f32) -> usize {
+        let caret = self.painter.get_offset_for_caret(position);
+        let line_height = self.painter.preferred_line_height();
+        let y = caret.y + line_height * (lines + 0.5);
+        if y < 0.0 {
+            0
+        } else if y >= self.painter.height() {
+            self.controller.value().len()
+        } else {
+            self.painter
+                .get_position_for_offset(Offset::new(caret.x, y))
+        }
+    }
+
+    /// The start or end of the line the caret is on.
+    fn position_on_line(&self, position: usize, end: bool) -> usize {
+        let caret = self.painter.get_offset_for_caret(position);
+        let x = if end { f32::MAX } else { 0.0 };
+        let y = caret.y + self.painter.preferred_line_height() / 2.0;
+        self.painter.get_position_for_offset(Offset::new(x, y))
+    }
+
+    /// Keeps the text painted in sync with the controller, the app may change it.
+    fn sync_text(&mut self) {
+        let text = self.build_text_span();
+        if self.painter.text != text {
+            self.painter.set_text(text);
+        }
+    }
+
+    /// The text of the controller, with the text being composed underlined.
+    fn build_text_span(&self) -> TextSpan {
+        let value = self.controller.value();
+        match &value.composing {
+            Some(composing) => {
+                let underline = TextStyle::new().with_decoration(TextDecoration::UNDERLINE);
+                TextSpan::default()
+                    .with_child(value.slice(0, composing.start))
+                    .with_child(
+                        TextSpan::new(value.slice(composing.start, composing.end))
+                            .with_style(underline),
+                    )
+                    .with_child(value.slice(composing.end, value.len()))
+            }
+            None => TextSpan::new(value.text),
+        }
+    }
+}
+
+impl TextInputClient for RenderEditable {
+    fn update_composing(&mut self, text: &str, selection: Option<(usize, usize)>) {
+        let value = self.controller.value();
+        if value.composing.is_none() && text.is_empty() {
+            return;
+        }
+        self.controller.set_value(value.compose(text, selection));
+        // the platform asks for the caret before the next layout
+        self.sync_text();
+    }
+
+    fn commit(&mut self, text: &str) {
+        // the text being composed was never part of the edits to undo
+        let value = self.controller.value().without_composing();
+        self.controller.set_value(value);
+        self.insert(text);
+        self.sync_text();
+    }
+
+    fn caret_rect(&self) -> Rect {
+        RenderEditable::caret_rect(self)
+    }
+}
+
+impl HitTestTarget for RenderEditable {
+    fn handle_event(&mut self, event: PointerEvent, entry: HitTestEntry) {
+        if event.kind == PointerEventKind::Down {
+            self.focus_node.request_focus();
+            self.sync_text();
+            let position = self.get_position_for_offset(event.local_position);
+            self.move_caret(position, false);
+        }
+    }
+
+    fn handle_key_event(&mut self, event: &KeyEvent) -> bool {
+        if !event.is_down() {
+            return false;
+        }
+        // keys may arrive before the last edit is laid out
+        self.sync_text();
+        let value = self.controller.value();
+        let selection = value.selection;
+        let modifiers = event.modifiers;
+        let (by_word, extend) = (modifiers.control, modifiers.shift);
+        match event.logical_key {
+            LogicalKey::ArrowLeft | LogicalKey::ArrowRight => {
+                let forward = event.logical_key == LogicalKey::ArrowRight;
+                let position = match (forward, by_word) {
+                    // collapses the selection to the side it is moved to
+                    _ if !extend && !selection.is_collapsed() => {
+                        if forward {
+                            selection.end()
+                        } else {
+                            selection.start()
+                        }
+                    }
+                    (false, false) => value.previous_char_boundary(selection.extent),
+                    (false, true) => value.previous_word_boundary(selection.extent),
+                    (true, false) => value.next_char_boundary(selection.extent),
+                    (true, true) => value.next_word_boundary(selection.extent),
+                };
+                self.move_caret(position, extend);
+            }
+            LogicalKey::ArrowUp => {
+                let position = self.position_across_lines(selection.extent, -1.0);
+                self.move_caret(position, extend);
+            }
+            LogicalKey::ArrowDown => {
+                let position = self.position_across_lines(selection.extent, 1.0);
+                self.move_caret(position, extend);
+            }
+            LogicalKey::Home => {
+                let position = self.position_on_line(selection.extent, false);
+                self.move_caret(position, extend);
+            }
+            LogicalKey::End => {
+                let position = self.position_on_line(selection.extent, true);
+                self.move_caret(position, extend);
+            }
+            LogicalKey::Backspace => self.edit(value.delete_backward(by_word)),
+            LogicalKey::Delete => self.edit(value.delete_forward(by_word)),
+            LogicalKey::Enter if self.is_multiline() => self.insert("\n"),
+            LogicalKey::Character('a') if modifiers.control => {
+                self.controller
+                    .set_selection(TextSelection::new(0, value.len()));
+            }
+            LogicalKey::Character('z') if modifiers.control && modifiers.shift => {
+                if let Some(next) = self.history.redo(value) {
+                    self.controller.set_value(next);
+                }
+            }
+            LogicalKey::Character('z') if modifiers.control => {
+                if let Some(previous) = self.history.undo(value) {
+                    self.controller.set_value(previous);
+                }
+            }
+            LogicalKey::Character('y') if modifiers.control => {
+                if let Some(next) = self.history.redo(value) {
+                    self.controller.set_value(next);
+                }
+            }
+            _ => match &event.character {
+                Some(text) if !modifiers.control && !modifiers.meta => {
+                    let text: String = text.chars().filter(|c| !c.is_control()).collect();
+                    if text.is_empty() {
+                        return false;
+                    }
+                    self.insert(&text);
+                }
+                _ => return false,
+            },
+        }
+        true
+    }
+
+    fn handle_focus_change(&mut self, has_focus: bool) {
+        if self.has_focus != has_focus {
+            self.has_focus = has_focus;
+            self.mark_needs_paint();
+        }
+        // keeps the text composed so far
+        let value = self.controller.value();
+        if !has_focus && value.composing.is_some() {
+            self.controller.set_value(TextEditingValue {
+                composing: None,
+                ..value
+            });
+        }
+    }
+
+    fn text_input_client(&mut self) -> Option<&mut dyn TextInputClient> {
+        Some(self)
+    }
+}
+
+impl RenderObject for RenderEditable {
+    fn ty_id(&self) -> TypeId {
+        TypeId::of::<Self>()
+    }
+
+    fn ty_name(&self) -> &'static str {
+        type_name::<Self>()
+    }
+
+    fn state(&self) -> &Rc<RenderState> {
+        &self.state
+    }
+
+    fn paint(&self, context: &mut PaintContext, offset: Offset) {
+        let paint_text = |context: &mut PaintContext| {
+            let selection = self.controller.selection();
+            if self.has_focus && !selection.is_collapsed() {
+                let paint = Paint::new(Color4f::from(self.selection_color), None);
+                for rect in self.selection_rects() {
+                    context
+                        .canvas()
+                        .draw_rect(rect.with_offset((offset.x, offset.y)), &paint);
+                }
+            }
+            let origin = Offset::new(offset.x - self.scroll_offset, offset.y);
+            self.painter.paint(context.canvas(), origin);
+            if self.has_focus && selection.is_collapsed() {
+                let paint = Paint::new(Color4f::from(self.cursor_color), None);
+                let caret = self.caret_rect().with_offset((offset.x, offset.y));
+                context.canvas().draw_rect(caret, &paint);
+            }
+        };
+        if self.needs_clipping {
+            context.push_clip_rect(offset, self.size, Clip::HardEdge, paint_text);
+        } else {
+            paint_text(context);
+        }
+    }
+
+    fn hit_test(&self, position: Offset, result: &mut HitTestResult) -> bool {
+        self.size.contains(position)
+    }
+}
+
+impl RenderBox for RenderEditable {
+    fn perform_layout(&mut self, constraints: &BoxConstraints) {
+        self.sync_text();
+        if self.is_multiline() {
+            // lines are aligned within the width of the box
+            let wrap_width = (constraints.max_width - self.cursor_width).max(0.0);
+            let min_width = if wrap_width.is_finite() {
+                wrap_width
+            } else {
+                0.0
+            };
+            self.painter.layout(min_width, wrap_width);
+        } else {
+            self.painter.layout(0.0, f32::INFINITY);
+        }
+
+        let line_height = self.painter.preferred_line_height();
+        let text_height = self.painter.height().max(line_height);
+        let height = match self.max_lines {
+            Some(max_lines) => line_height * max_lines as f32,
+            None => text_height,
+        };
+        let text_width = self.painter.width() + self.cursor_width;
+        // fields take all the width they may, and fit their text when unbounded
+        let width = if constraints.max_width.is_finite() {
+            constraints.max_width
+        } else {
+            text_width
+        };
+        self.size = constraints.constrain(Size::new(width, height));
+
+        // scroll as little as needed to show the caret
+        let max_scroll = (text_width - self.size.width).max(0.0);
+        let caret = self
+            .painter
+            .get_offset_for_caret(self.controller.selection().extent);
+        self.scroll_offset = self
+            .scroll_offset
+            .max(caret.x + self.cursor_width - self.size.width)
+            .min(caret.x)
+            .clamp(0.0, max_scroll);
+
+        self.needs_clipping = text_width > self.size.width || text_height > self.size.height;
+    }
+
+    fn compute_distance_to_actual_baseline(&self, baseline: TextBaseline) -> Option<f32> {
+        Some(self.painter.distance_to_baseline(baseline))
+    }
+
+    fn compute_min_intrinsic_width(&self, height: f32) -> f32 {
+        self.painter.min_intrinsic_width() + self.cursor_width
+    }
+
+    fn compute_max_intrinsic_width(&self, height: f32) -> f32 {
+        self.painter.max_intrinsic_width() + self.cursor_width
+    }
+
+    fn compute_min_intrinsic_height(&self, width: f32) -> f32 {
+        let line_height = self.painter.preferred_line_height();
+        match self.max_lines {
+            Some(max_lines) => line_height * max_lines as f32,
+            None => self
+                .painter
+                .height_for_width(0.0, (width - self.cursor_width).max(0.0))
+                .max(line_height),
+        }
+    }
+
+    fn compute_max_intrinsic_height(&self, width: f32) -> f32 {
+        self.compute_min_intrinsic_height(width)
+    }
+
+    fn perform_resize(&mut self, constraints: &BoxConstraints) {
+        self.size = constraints.smallest();
+    }
+
+    fn size(&self) -> Size {
+        self.size
+    }
+
+    fn focus_node(&self) -> Option<Rc<FocusNode>> {
+        Some(self.focus_node.clone())
+    }
+
+    fn describe_semantics_configuration(&self, config: &mut SemanticsConfiguration) {
+        config.is_semantic_boundary = true;
+        config.role = SemanticsRole::TextField;
+        config.value = Some(self.controller.text());
+        let focus_node = self.focus_node.clone();
+        config.add_action(
+            SemanticsAction::Tap,
+            SemanticsActionHandler::new(move |_| focus_node.request_focus()),
+        );
+        // the text set is cut like typed text
+        let controller = self.controller.clone();
+        let multiline = self.is_multiline();
+        let max_length = self.max_length.unwrap_or(usize::MAX);
+        config.add_action(
+            SemanticsAction::SetText,
+            SemanticsActionHandler::new(move |text| {
+                let text: String = text
+                    .unwrap_or_default()
+                    .chars()
+                    .filter(|c| multiline || *c != '\n')
+                    .take(max_length)
+                    .collect();
+                controller.set_text(text);
+            }),
+        );
+    }
+}
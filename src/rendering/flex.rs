@@ -8,18 +8,18 @@ use std::{
 use crate::{
     gestures::{HitTestEntry, HitTestResult, HitTestTarget},
     rendering::{
-        Axis, BoxConstraints, Clip, Offset, PaintContext, RenderBox, RenderObject, Size,
-        TextBaseline, TextDirection, VerticalDirection,
+        Axis, BoxConstraints, Clip, Offset, PaintContext, RenderBox, RenderObject, RenderState,
+        Size, TextBaseline, TextDirection, VerticalDirection,
     },
 };
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum FlexFit {
     Tight,
     Loose,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum CrossAxisAlignment {
     Start,
     End,
@@ -28,7 +28,7 @@ pub enum CrossAxisAlignment {
     Baseline,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum MainAxisAlignment {
     Start,
     End,
@@ -38,7 +38,7 @@ pub enum MainAxisAlignment {
     SpaceEvenly,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum MainAxisSize {
     Min,
     Max,
@@ -46,6 +46,9 @@ pub enum MainAxisSize {
 
 #[derive(Debug)]
 pub struct RenderFlex {
+    // RenderObject
+    pub(crate) state: Rc<RenderState>,
+
     // RenderBox
     pub(crate) size: Size,
 
@@ -68,6 +71,16 @@ impl RenderFlex {
         self
     }
 
+    pub fn with_main_axis_size(mut self, main_axis_size: MainAxisSize) -> Self {
+        self.main_axis_size = main_axis_size;
+        self
+    }
+
+    pub fn with_main_axis_alignment(mut self, main_axis_alignment: MainAxisAlignment) -> Self {
+        self.main_axis_alignment = main_axis_alignment;
+        self
+    }
+
     pub fn with_text_direction(mut self, text_direction: impl Into<Option<TextDirection>>) -> Self {
         self.text_direction = text_direction.into();
         self
@@ -78,15 +91,102 @@ impl RenderFlex {
         self
     }
 
+    pub fn with_cross_axis_alignment(mut self, cross_axis_alignment: CrossAxisAlignment) -> Self {
+        self.cross_axis_alignment = cross_axis_alignment;
+        self
+    }
+
+    pub fn with_text_baseline(mut self, text_baseline: impl Into<Option<TextBaseline>>) -> Self {
+        self.text_baseline = text_baseline.into();
+        self
+    }
+
+    pub fn with_clip_behavior(mut self, clip_behavior: Clip) -> Self {
+        self.clip_behavior = clip_behavior;
+        self
+    }
+
+    pub fn set_direction(&mut self, direction: Axis) {
+        if self.direction != direction {
+            self.direction = direction;
+            self.mark_needs_layout();
+        }
+    }
+
+    pub fn set_main_axis_size(&mut self, main_axis_size: MainAxisSize) {
+        if self.main_axis_size != main_axis_size {
+            self.main_axis_size = main_axis_size;
+            self.mark_needs_layout();
+        }
+    }
+
+    pub fn set_main_axis_alignment(&mut self, main_axis_alignment: MainAxisAlignment) {
+        if self.main_axis_alignment != main_axis_alignment {
+            self.main_axis_alignment = main_axis_alignment;
+            self.mark_needs_layout();
+        }
+    }
+
+    pub fn set_cross_axis_alignment(&mut self, cross_axis_alignment: CrossAxisAlignment) {
+        if self.cross_axis_alignment != cross_axis_alignment {
+            self.cross_axis_alignment = cross_axis_alignment;
+            self.mark_needs_layout();
+        }
+    }
+
+    pub fn set_vertical_direction(&mut self, vertical_direction: VerticalDirection) {
+        if self.vertical_direction != vertical_direction {
+            self.vertical_direction = vertical_direction;
+            self.mark_needs_layout();
+        }
+    }
+
+    pub fn set_text_direction(&mut self, text_direction: Option<TextDirection>) {
+        if self.text_direction != text_direction {
+            self.text_direction = text_direction;
+            self.mark_needs_layout();
+        }
+    }
+
+    pub fn set_text_baseline(&mut self, text_baseline: Option<TextBaseline>) {
+        if self.text_baseline != text_baseline {
+            self.text_baseline = text_baseline;
+            self.mark_needs_layout();
+        }
+    }
+
+    /// Clipping only applies to overflowing children, so it only needs a repaint.
+    pub fn set_clip_behavior(&mut self, clip_behavior: Clip) {
+        if self.clip_behavior != clip_behavior {
+            self.clip_behavior = clip_behavior;
+            self.mark_needs_paint();
+        }
+    }
+
     pub fn with_child(mut self, child: impl Into<RenderFlexible>) -> Self {
-        self.children.push(child.into());
+        let child = child.into();
+        self.state.adopt(child.inner.borrow().state());
+        self.children.push(child);
         self
     }
+
+    /// Replace the children, marking this flex dirty only if they changed.
+    pub fn set_children(&mut self, children: Vec<RenderFlexible>) {
+        if self.children == children {
+            return;
+        }
+        for child in children.iter() {
+            self.state.adopt(child.inner.borrow().state());
+        }
+        self.children = children;
+        self.mark_needs_layout();
+    }
 }
 
 impl Default for RenderFlex {
     fn default() -> Self {
         Self {
+            state: Default::default(),
             size: Size::zero(),
             children: Vec::new(),
             direction: Axis::Horizontal,
@@ -124,6 +224,21 @@ impl RenderFlexible {
     }
 }
 
+impl<T> From<T> for RenderFlexible
+where
+    T: 'static + RenderBox,
+{
+    fn from(child: T) -> Self {
+        RenderFlexible::new(child, 0, FlexFit::Tight)
+    }
+}
+
+impl PartialEq for RenderFlexible {
+    fn eq(&self, other: &Self) -> bool {
+        self.flex == other.flex && self.fit == other.fit && Rc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
 struct LayoutSizes {
     main_size: f32,
     cross_size: f32,
@@ -151,8 +266,8 @@ impl RenderFlex {
             } else {
                 let inner_constraints = match self.cross_axis_alignment {
                     CrossAxisAlignment::Stretch => match self.direction {
-                        Axis::Horizontal => BoxConstraints::tight_for(constraints.max_height, None),
-                        Axis::Vertical => BoxConstraints::tight_for(None, constraints.max_height),
+                        Axis::Horizontal => BoxConstraints::tight_for(None, constraints.max_height),
+                        Axis::Vertical => BoxConstraints::tight_for(constraints.max_width, None),
                     },
                     _ => match self.direction {
                         Axis::Horizontal => {
@@ -251,6 +366,57 @@ impl RenderFlex {
     }
 }
 
+impl RenderFlex {
+    fn get_intrinsic_size(
+        &self,
+        sizing_direction: Axis,
+        extent: f32,
+        child_size: impl Fn(&dyn RenderBox, f32) -> f32,
+    ) -> f32 {
+        if self.direction == sizing_direction {
+            // Main axis: flexible children share the space in proportion to their flex.
+            let mut total_flex = 0.0f32;
+            let mut inflexible_space = 0.0f32;
+            let mut max_flex_fraction = 0.0f32;
+            for child in self.children.iter() {
+                let size = child_size(&*child.inner.borrow(), extent);
+                if child.flex > 0 {
+                    total_flex += child.flex as f32;
+                    max_flex_fraction = max_flex_fraction.max(size / child.flex as f32);
+                } else {
+                    inflexible_space += size;
+                }
+            }
+            max_flex_fraction * total_flex + inflexible_space
+        } else {
+            // Cross axis: size inflexible children first, then give the flexible ones
+            // their share of the remaining space.
+            let mut total_flex = 0.0f32;
+            let mut inflexible_space = 0.0f32;
+            let mut max_cross_size = 0.0f32;
+            for child in self.children.iter() {
+                if child.flex > 0 {
+                    total_flex += child.flex as f32;
+                } else {
+                    let inner = child.inner.borrow();
+                    let main_size = match self.direction {
+                        Axis::Horizontal => inner.get_max_intrinsic_width(f32::INFINITY),
+                        Axis::Vertical => inner.get_max_intrinsic_height(f32::INFINITY),
+                    };
+                    inflexible_space += main_size;
+                    max_cross_size = max_cross_size.max(child_size(&*inner, main_size));
+                }
+            }
+            let space_per_flex = ((extent - inflexible_space) / total_flex).max(0.0);
+            for child in self.children.iter().filter(|c| c.flex > 0) {
+                let size = child_size(&*child.inner.borrow(), space_per_flex * child.flex as f32);
+                max_cross_size = max_cross_size.max(size);
+            }
+            max_cross_size
+        }
+    }
+}
+
 impl HitTestTarget for RenderFlex {}
 
 impl RenderObject for RenderFlex {
@@ -262,28 +428,44 @@ impl RenderObject for RenderFlex {
         type_name::<Self>()
     }
 
+    fn state(&self) -> &Rc<RenderState> {
+        &self.state
+    }
+
     fn paint(&self, context: &mut PaintContext, offset: Offset) {
         context.draw_rect(offset, self.size);
-        for child in &self.children {
-            child
-                .inner
-                .borrow_mut()
-                .paint(context, child.offset + offset);
-        }
+        let clip_behavior = if self._overflow > 0.0 {
+            self.clip_behavior
+        } else {
+            Clip::None
+        };
+        context.push_clip_rect(offset, self.size, clip_behavior, |context| {
+            for child in &self.children {
+                context.paint_child(&*child.inner.borrow(), child.offset + offset);
+            }
+        });
     }
 
     fn hit_test(&self, position: Offset, result: &mut HitTestResult) -> bool {
         let mut is_hit = false;
         for child in &self.children {
-            let transformed = position - child.offset;
-            if child.inner.borrow().hit_test(transformed, result) {
-                is_hit = true;
-                let entry = HitTestEntry::new(child.inner.clone());
-                result.add(entry);
-            }
+            is_hit |=
+                result.add_with_paint_offset(child.offset, position, |result, transformed| {
+                    let is_child_hit = child.inner.borrow().hit_test(transformed, result);
+                    if is_child_hit {
+                        result.add(HitTestEntry::new(child.inner.clone()));
+                    }
+                    is_child_hit
+                });
         }
         is_hit
     }
+
+    fn visit_children(&self, visitor: &mut dyn FnMut(&Rc<RefCell<dyn RenderBox>>)) {
+        for child in &self.children {
+            visitor(&child.inner);
+        }
+    }
 }
 
 impl RenderBox for RenderFlex {
@@ -297,18 +479,26 @@ impl RenderBox for RenderFlex {
         let mut actual_size = sizes.main_size;
         let mut cross_size = sizes.cross_size;
 
-        let max_baseline_distance = 0.0f32;
-        // TODO: handle baseline alignment
-        // if self.cross_axis_alignment == CrossAxisAlignment::Baseline {
-        //     let max_size_above_baseline = 0.0f32;
-        //     let max_size_below_baseline = 0.0f32;
-        //     for child in self.children.iter_mut() {
-        //         debug_assert!(
-        //             self.text_baseline.is_none(),
-        //             "you must also specify which baseline to use"
-        //         )
-        //     }
-        // }
+        let mut max_baseline_distance = 0.0f32;
+        if self.cross_axis_alignment == CrossAxisAlignment::Baseline
+            && self.direction == Axis::Horizontal
+        {
+            let text_baseline = self
+                .text_baseline
+                .expect("you must also specify which baseline to use");
+            let mut max_size_above_baseline = 0.0f32;
+            let mut max_size_below_baseline = 0.0f32;
+            for child in self.children.iter() {
+                let inner = child.inner.borrow();
+                if let Some(distance) = inner.get_distance_to_actual_baseline(text_baseline) {
+                    max_baseline_distance = max_baseline_distance.max(distance);
+                    max_size_above_baseline = max_size_above_baseline.max(distance);
+                    max_size_below_baseline =
+                        max_size_below_baseline.max(inner.size().height - distance);
+                    cross_size = cross_size.max(max_size_above_baseline + max_size_below_baseline);
+                }
+            }
+        }
 
         // Align items along the main axis.
         match self.direction {
@@ -327,7 +517,7 @@ impl RenderBox for RenderFlex {
         }
         let actual_size_delta = actual_size - allocated_size;
         self._overflow = (-actual_size_delta).max(0.0);
-        let remaining_space = actual_size.max(0.0);
+        let remaining_space = actual_size_delta.max(0.0);
 
         let children_count = self.children.len();
         let (leading_space, between_space) = match self.main_axis_alignment {
@@ -337,13 +527,13 @@ impl RenderBox for RenderFlex {
             MainAxisAlignment::SpaceBetween => (
                 0.0,
                 if children_count > 1 {
-                    (children_count - 1) as f32
+                    remaining_space / (children_count - 1) as f32
                 } else {
                     0.0
                 },
             ),
             MainAxisAlignment::SpaceAround => {
-                let between_space = if children_count > 1 {
+                let between_space = if children_count > 0 {
                     remaining_space / children_count as f32
                 } else {
                     0.0
@@ -352,7 +542,7 @@ impl RenderBox for RenderFlex {
                 (leading_space, between_space)
             }
             MainAxisAlignment::SpaceEvenly => {
-                let between_space = if children_count > 1 {
+                let between_space = if children_count > 0 {
                     remaining_space / (children_count + 1) as f32
                 } else {
                     0.0
@@ -388,25 +578,27 @@ impl RenderBox for RenderFlex {
                         cross_size - child_size.cross_size(self.direction)
                     }
                 }
-                CrossAxisAlignment::Center => cross_size - child_size.cross_size(self.direction),
+                CrossAxisAlignment::Center => {
+                    (cross_size - child_size.cross_size(self.direction)) / 2.0
+                }
                 CrossAxisAlignment::Stretch => 0.0,
-                CrossAxisAlignment::Baseline => {
-                    match self.direction {
-                        Axis::Horizontal => {
-                            // TODO: child.getDistanceToBaseline
-                            let distance: Option<f32> = None;
-                            match distance {
-                                Some(d) => max_baseline_distance - d,
-                                None => 0.0,
-                            }
+                CrossAxisAlignment::Baseline => match (self.direction, self.text_baseline) {
+                    (Axis::Horizontal, Some(text_baseline)) => {
+                        let distance = child
+                            .inner
+                            .borrow()
+                            .get_distance_to_actual_baseline(text_baseline);
+                        match distance {
+                            Some(d) => max_baseline_distance - d,
+                            None => 0.0,
                         }
-                        Axis::Vertical => 0.0,
                     }
-                }
+                    _ => 0.0,
+                },
             };
 
             if flip_main_axis {
-                child_main_position = child_size.main_size(self.direction);
+                child_main_position -= child_size.main_size(self.direction);
             }
 
             child.offset = match self.direction {
@@ -424,6 +616,46 @@ impl RenderBox for RenderFlex {
     fn perform_resize(&mut self, constraints: &BoxConstraints) {
         todo!()
     }
+
+    fn compute_distance_to_actual_baseline(&self, baseline: TextBaseline) -> Option<f32> {
+        let mut distances = self.children.iter().filter_map(|child| {
+            child
+                .inner
+                .borrow()
+                .get_distance_to_actual_baseline(baseline)
+                .map(|distance| distance + child.offset.y)
+        });
+        match self.direction {
+            // the highest baseline among the children of a row
+            Axis::Horizontal => distances.reduce(f32::min),
+            // the baseline of the first child in a column that has one
+            Axis::Vertical => distances.next(),
+        }
+    }
+
+    fn compute_min_intrinsic_width(&self, height: f32) -> f32 {
+        self.get_intrinsic_size(Axis::Horizontal, height, |child, extent| {
+            child.get_min_intrinsic_width(extent)
+        })
+    }
+
+    fn compute_max_intrinsic_width(&self, height: f32) -> f32 {
+        self.get_intrinsic_size(Axis::Horizontal, height, |child, extent| {
+            child.get_max_intrinsic_width(extent)
+        })
+    }
+
+    fn compute_min_intrinsic_height(&self, width: f32) -> f32 {
+        self.get_intrinsic_size(Axis::Vertical, width, |child, extent| {
+            child.get_min_intrinsic_height(extent)
+        })
+    }
+
+    fn compute_max_intrinsic_height(&self, width: f32) -> f32 {
+        self.get_intrinsic_size(Axis::Vertical, width, |child, extent| {
+            child.get_max_intrinsic_height(extent)
+        })
+    }
 }
 
 fn start_is_top_left(
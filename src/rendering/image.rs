@@ -1,12 +1,19 @@
 use crate::gestures::HitTestTarget;
-use crate::rendering::{BoxConstraints, Offset, PaintContext, RenderBox, RenderObject, Size};
+use crate::rendering::{
+    BoxConstraints, Offset, PaintContext, RenderBox, RenderObject, RenderState, Size,
+};
+use crate::semantics::{SemanticsConfiguration, SemanticsRole};
 use crate::ui::Image;
 use std::any::{type_name, TypeId};
 use std::borrow::Borrow;
+use std::rc::Rc;
 
 #[derive(Debug)]
 pub struct RenderImage {
     // RenderObject
+    pub(crate) state: Rc<RenderState>,
+
+    // RenderBox
     pub(crate) size: Size,
 
     // RenderImage
@@ -18,6 +25,7 @@ pub struct RenderImage {
 impl Default for RenderImage {
     fn default() -> Self {
         RenderImage {
+            state: Default::default(),
             image: None,
             width: None,
             height: None,
@@ -29,6 +37,7 @@ impl Default for RenderImage {
 impl RenderImage {
     pub fn new(width: impl Into<Option<f32>>, heigh: impl Into<Option<f32>>) -> Self {
         RenderImage {
+            state: Default::default(),
             image: None,
             width: width.into(),
             height: heigh.into(),
@@ -37,10 +46,23 @@ impl RenderImage {
     }
 }
 
+impl RenderImage {
+    fn size_for_constraints(&self, constraints: &BoxConstraints) -> Size {
+        let constraints = BoxConstraints::tight_for(self.width, self.height).enforce(constraints);
+        match &self.image {
+            Some(img) => constraints
+                .borrow()
+                .constrain_with_aspect_ratio((img.width() as f32, img.height() as f32)),
+            None => constraints.borrow().smallest(),
+        }
+    }
+}
+
 impl From<Image> for RenderImage {
     fn from(img: Image) -> Self {
         let size = Size::new(img.width() as f32, img.height() as f32);
         RenderImage {
+            state: Default::default(),
             image: img.into(),
             width: None,
             height: None,
@@ -60,6 +82,10 @@ impl RenderObject for RenderImage {
         type_name::<Self>()
     }
 
+    fn state(&self) -> &Rc<RenderState> {
+        &self.state
+    }
+
     fn paint(&self, context: &mut PaintContext, offset: Offset) {
         context.draw_rect(offset, self.size);
     }
@@ -67,13 +93,31 @@ impl RenderObject for RenderImage {
 
 impl RenderBox for RenderImage {
     fn perform_layout(&mut self, constraints: &BoxConstraints) {
-        let constraints = BoxConstraints::tight_for(self.width, self.height).enforce(constraints);
-        self.size = match &self.image {
-            Some(img) => constraints
-                .borrow()
-                .constrain_with_aspect_ratio((img.width() as f32, img.height() as f32)),
-            None => constraints.borrow().smallest(),
-        };
+        self.size = self.size_for_constraints(constraints);
+    }
+
+    fn compute_min_intrinsic_width(&self, height: f32) -> f32 {
+        if self.width.is_none() && self.height.is_none() {
+            return 0.0;
+        }
+        self.compute_max_intrinsic_width(height)
+    }
+
+    fn compute_max_intrinsic_width(&self, height: f32) -> f32 {
+        self.size_for_constraints(&BoxConstraints::tight_for_finite(f32::INFINITY, height))
+            .width
+    }
+
+    fn compute_min_intrinsic_height(&self, width: f32) -> f32 {
+        if self.width.is_none() && self.height.is_none() {
+            return 0.0;
+        }
+        self.compute_max_intrinsic_height(width)
+    }
+
+    fn compute_max_intrinsic_height(&self, width: f32) -> f32 {
+        self.size_for_constraints(&BoxConstraints::tight_for_finite(width, f32::INFINITY))
+            .height
     }
 
     fn perform_resize(&mut self, constraints: &BoxConstraints) {
@@ -83,4 +127,8 @@ impl RenderBox for RenderImage {
     fn size(&self) -> Size {
         self.size
     }
+
+    fn describe_semantics_configuration(&self, config: &mut SemanticsConfiguration) {
+        config.role = SemanticsRole::Image;
+    }
 }
@@ -0,0 +1,156 @@
+use std::{
+    cell::{Cell, RefCell},
+    fmt::Debug,
+    rc::Rc,
+};
+
+use skia_safe::{Canvas, ClipOp, Picture, Rect};
+
+use crate::rendering::{Clip, Matrix4, Offset};
+
+/// A node of the retained tree that is composited onto the screen each frame.
+pub trait Layer: Debug {
+    fn composite(&self, canvas: &mut Canvas);
+}
+
+fn composite_children(children: &[Rc<dyn Layer>], canvas: &mut Canvas) {
+    for child in children {
+        child.composite(canvas);
+    }
+}
+
+#[derive(Debug)]
+pub struct PictureLayer {
+    pub(crate) picture: Picture,
+}
+
+impl PictureLayer {
+    pub fn new(picture: Picture) -> Self {
+        PictureLayer { picture }
+    }
+}
+
+impl Layer for PictureLayer {
+    fn composite(&self, canvas: &mut Canvas) {
+        canvas.draw_picture(&self.picture, None, None);
+    }
+}
+
+/// The layer owned by a repaint boundary.
+///
+/// Ancestors keep a reference to it, so repainting the boundary replaces its children in
+/// place without touching the layers above it.
+#[derive(Debug, Default)]
+pub struct OffsetLayer {
+    pub(crate) offset: Cell<Offset>,
+    pub(crate) children: RefCell<Vec<Rc<dyn Layer>>>,
+}
+
+impl OffsetLayer {
+    pub fn new(offset: Offset) -> Self {
+        OffsetLayer {
+            offset: Cell::new(offset),
+            children: RefCell::new(Vec::new()),
+        }
+    }
+
+    pub fn offset(&self) -> Offset {
+        self.offset.get()
+    }
+
+    pub fn set_offset(&self, offset: Offset) {
+        self.offset.set(offset);
+    }
+
+    pub fn set_children(&self, children: Vec<Rc<dyn Layer>>) {
+        *self.children.borrow_mut() = children;
+    }
+}
+
+impl Layer for OffsetLayer {
+    fn composite(&self, canvas: &mut Canvas) {
+        let offset = self.offset.get();
+        let count = canvas.save();
+        canvas.translate((offset.x, offset.y));
+        composite_children(&self.children.borrow(), canvas);
+        canvas.restore_to_count(count);
+    }
+}
+
+#[derive(Debug)]
+pub struct ClipRectLayer {
+    pub(crate) clip_rect: Rect,
+    pub(crate) clip_behavior: Clip,
+    pub(crate) children: Vec<Rc<dyn Layer>>,
+}
+
+impl ClipRectLayer {
+    pub fn new(clip_rect: Rect, clip_behavior: Clip, children: Vec<Rc<dyn Layer>>) -> Self {
+        debug_assert!(clip_behavior != Clip::None);
+        ClipRectLayer {
+            clip_rect,
+            clip_behavior,
+            children,
+        }
+    }
+}
+
+impl Layer for ClipRectLayer {
+    fn composite(&self, canvas: &mut Canvas) {
+        let count = canvas.save();
+        canvas.clip_rect(
+            self.clip_rect,
+            ClipOp::Intersect,
+            self.clip_behavior != Clip::HardEdge,
+        );
+        if self.clip_behavior == Clip::AntiAliasWithSaveLayer {
+            canvas.save_layer_alpha(self.clip_rect, 255);
+        }
+        composite_children(&self.children, canvas);
+        canvas.restore_to_count(count);
+    }
+}
+
+#[derive(Debug)]
+pub struct OpacityLayer {
+    pub(crate) alpha: u8,
+    pub(crate) children: Vec<Rc<dyn Layer>>,
+}
+
+impl OpacityLayer {
+    pub fn new(alpha: u8, children: Vec<Rc<dyn Layer>>) -> Self {
+        OpacityLayer { alpha, children }
+    }
+}
+
+impl Layer for OpacityLayer {
+    fn composite(&self, canvas: &mut Canvas) {
+        let count = canvas.save_layer_alpha(None, u32::from(self.alpha));
+        composite_children(&self.children, canvas);
+        canvas.restore_to_count(count);
+    }
+}
+
+#[derive(Debug)]
+pub struct TransformLayer {
+    pub(crate) transform: Matrix4,
+    pub(crate) children: Vec<Rc<dyn Layer>>,
+}
+
+impl TransformLayer {
+    pub fn new(transform: Matrix4, children: Vec<Rc<dyn Layer>>) -> Self {
+        TransformLayer {
+            transform,
+            children,
+        }
+    }
+}
+
+impl Layer for TransformLayer {
+    fn composite(&self, canvas: &mut Canvas) {
+        let count = canvas.save();
+        canvas.concat_44(&self.transform.to_m44());
+        composite_children(&self.children, canvas);
+        canvas.restore_to_count(count);
+    }
+}
@@ -0,0 +1,173 @@
+use std::ops::Mul;
+
+use skia_safe::M44;
+
+use crate::rendering::Offset;
+
+/// A 4x4 transformation matrix, stored in column-major order.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Matrix4 {
+    pub(crate) storage: [f32; 16],
+}
+
+impl Default for Matrix4 {
+    fn default() -> Self {
+        Matrix4::identity()
+    }
+}
+
+impl Matrix4 {
+    /// Creates a matrix from its values in column-major order.
+    pub fn new(storage: [f32; 16]) -> Self {
+        Matrix4 { storage }
+    }
+
+    pub fn identity() -> Self {
+        Matrix4::diagonal3(1.0, 1.0, 1.0)
+    }
+
+    pub fn translation(x: f32, y: f32, z: f32) -> Self {
+        let mut m = Matrix4::identity();
+        m.storage[12] = x;
+        m.storage[13] = y;
+        m.storage[14] = z;
+        m
+    }
+
+    pub fn diagonal3(x: f32, y: f32, z: f32) -> Self {
+        let mut storage = [0.0; 16];
+        storage[0] = x;
+        storage[5] = y;
+        storage[10] = z;
+        storage[15] = 1.0;
+        Matrix4 { storage }
+    }
+
+    /// Rotation of `radians` around the z axis, clockwise in screen coordinates.
+    pub fn rotation_z(radians: f32) -> Self {
+        let (sin, cos) = radians.sin_cos();
+        let mut m = Matrix4::identity();
+        m.storage[0] = cos;
+        m.storage[1] = sin;
+        m.storage[4] = -sin;
+        m.storage[5] = cos;
+        m
+    }
+
+    pub fn entry(&self, row: usize, column: usize) -> f32 {
+        self.storage[column * 4 + row]
+    }
+
+    pub fn storage(&self) -> &[f32; 16] {
+        &self.storage
+    }
+
+    pub fn is_identity(&self) -> bool {
+        *self == Matrix4::identity()
+    }
+
+    /// Post-multiplies a translation, i.e. translates in the coordinates of this matrix.
+    pub fn translate(&mut self, x: f32, y: f32, z: f32) {
+        *self = *self * Matrix4::translation(x, y, z);
+    }
+
+    /// Post-multiplies a scale.
+    pub fn scale(&mut self, x: f32, y: f32, z: f32) {
+        *self = *self * Matrix4::diagonal3(x, y, z);
+    }
+
+    /// Post-multiplies a rotation around the z axis.
+    pub fn rotate_z(&mut self, radians: f32) {
+        *self = *self * Matrix4::rotation_z(radians);
+    }
+
+    pub fn determinant(&self) -> f32 {
+        let m = &self.storage;
+        let b00 = m[0] * m[5] - m[1] * m[4];
+        let b01 = m[0] * m[6] - m[2] * m[4];
+        let b02 = m[0] * m[7] - m[3] * m[4];
+        let b03 = m[1] * m[6] - m[2] * m[5];
+        let b04 = m[1] * m[7] - m[3] * m[5];
+        let b05 = m[2] * m[7] - m[3] * m[6];
+        let b06 = m[8] * m[13] - m[9] * m[12];
+        let b07 = m[8] * m[14] - m[10] * m[12];
+        let b08 = m[8] * m[15] - m[11] * m[12];
+        let b09 = m[9] * m[14] - m[10] * m[13];
+        let b10 = m[9] * m[15] - m[11] * m[13];
+        let b11 = m[10] * m[15] - m[11] * m[14];
+        b00 * b11 - b01 * b10 + b02 * b09 + b03 * b08 - b04 * b07 + b05 * b06
+    }
+
+    /// The inverse of this matrix, `None` if it is singular.
+    pub fn invert(&self) -> Option<Matrix4> {
+        let m = &self.storage;
+        let b00 = m[0] * m[5] - m[1] * m[4];
+        let b01 = m[0] * m[6] - m[2] * m[4];
+        let b02 = m[0] * m[7] - m[3] * m[4];
+        let b03 = m[1] * m[6] - m[2] * m[5];
+        let b04 = m[1] * m[7] - m[3] * m[5];
+        let b05 = m[2] * m[7] - m[3] * m[6];
+        let b06 = m[8] * m[13] - m[9] * m[12];
+        let b07 = m[8] * m[14] - m[10] * m[12];
+        let b08 = m[8] * m[15] - m[11] * m[12];
+        let b09 = m[9] * m[14] - m[10] * m[13];
+        let b10 = m[9] * m[15] - m[11] * m[13];
+        let b11 = m[10] * m[15] - m[11] * m[14];
+        let det = b00 * b11 - b01 * b10 + b02 * b09 + b03 * b08 - b04 * b07 + b05 * b06;
+        if det == 0.0 {
+            return None;
+        }
+        let inv = 1.0 / det;
+        Some(Matrix4::new([
+            (m[5] * b11 - m[6] * b10 + m[7] * b09) * inv,
+            (-m[1] * b11 + m[2] * b10 - m[3] * b09) * inv,
+            (m[13] * b05 - m[14] * b04 + m[15] * b03) * inv,
+            (-m[9] * b05 + m[10] * b04 - m[11] * b03) * inv,
+            (-m[4] * b11 + m[6] * b08 - m[7] * b07) * inv,
+            (m[0] * b11 - m[2] * b08 + m[3] * b07) * inv,
+            (-m[12] * b05 + m[14] * b02 - m[15] * b01) * inv,
+            (m[8] * b05 - m[10] * b02 + m[11] * b01) * inv,
+            (m[4] * b10 - m[5] * b08 + m[7] * b06) * inv,
+            (-m[0] * b10 + m[1] * b08 - m[3] * b06) * inv,
+            (m[12] * b04 - m[13] * b02 + m[15] * b00) * inv,
+            (-m[8] * b04 + m[9] * b02 - m[11] * b00) * inv,
+            (-m[4] * b09 + m[5] * b07 - m[6] * b06) * inv,
+            (m[0] * b09 - m[1] * b07 + m[2] * b06) * inv,
+            (-m[12] * b03 + m[13] * b01 - m[14] * b00) * inv,
+            (m[8] * b03 - m[9] * b01 + m[10] * b00) * inv,
+        ]))
+    }
+
+    /// Transforms a point on the z = 0 plane, dividing by the resulting w.
+    pub fn transform_point(&self, point: Offset) -> Offset {
+        let m = &self.storage;
+        let x = m[0] * point.x + m[4] * point.y + m[12];
+        let y = m[1] * point.x + m[5] * point.y + m[13];
+        let w = m[3] * point.x + m[7] * point.y + m[15];
+        if w == 1.0 {
+            Offset::new(x, y)
+        } else {
+            Offset::new(x / w, y / w)
+        }
+    }
+
+    pub fn to_m44(&self) -> M44 {
+        M44::col_major(&self.storage)
+    }
+}
+
+impl Mul for Matrix4 {
+    type Output = Matrix4;
+
+    fn mul(self, rhs: Matrix4) -> Self::Output {
+        let mut storage = [0.0; 16];
+        for column in 0..4 {
+            for row in 0..4 {
+                storage[column * 4 + row] = (0..4)
+                    .map(|k| self.entry(row, k) * rhs.entry(k, column))
+                    .sum();
+            }
+        }
+        Matrix4 { storage }
+    }
+}
@@ -1,9 +1,15 @@
 mod basic_types;
 pub use basic_types::*;
 
+mod matrix;
+pub use matrix::*;
+
 mod object;
 pub use object::*;
 
+mod layer;
+pub use layer::*;
+
 mod r#box;
 pub use r#box::*;
 
@@ -16,6 +22,12 @@ pub use proxy_box::*;
 mod image;
 pub use image::*;
 
+mod paragraph;
+pub use paragraph::*;
+
+mod editable;
+pub use editable::*;
+
 mod flex;
 pub use flex::*;
 
@@ -24,3 +36,15 @@ pub use sliver::*;
 
 mod sliver_list;
 pub use sliver_list::*;
+
+mod scroll_simulation;
+pub use scroll_simulation::*;
+
+mod scroll_physics;
+pub use scroll_physics::*;
+
+mod viewport_offset;
+pub use viewport_offset::*;
+
+mod viewport;
+pub use viewport::*;
@@ -1,21 +1,60 @@
-use std::{any::TypeId, cell::RefCell, rc::Rc};
+use std::{
+    any::TypeId,
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    rc::{Rc, Weak},
+    time::Duration,
+};
 
 use compose_rt::Composer;
+use skia_safe::{Canvas, PictureRecorder, Rect};
+use winit::event::WindowEvent;
 
 use crate::{
-    gestures::{HitTestResult, HitTestTarget, PointerEvent},
-    rendering::{BoxConstraints, Offset, RenderBox, Size},
-    widgets::{BuildContext, View, Widget},
+    gestures::{GestureBinding, HitTestResult, HitTestTarget, PointerEvent},
+    rendering::{
+        BoxConstraints, Clip, ClipRectLayer, IntrinsicDimension, Layer, Matrix4, Offset,
+        OffsetLayer, OpacityLayer, PictureLayer, RenderBox, RenderSliver, Size, TextBaseline,
+        TransformLayer,
+    },
+    scheduler::SchedulerBinding,
+    semantics::{SemanticsAction, SemanticsNode, SemanticsNodeId, SemanticsOwner},
+    services::{FocusManager, ImeEvent, KeyEvent, KeyboardInput, RawKeyboard},
+    widgets::{BuildContext, Key, View, Widget},
 };
 use std::fmt::Debug;
 
-pub struct PaintContext<'a> {
-    pub canvas: &'a mut skia_safe::Canvas,
+/// Records painting into pictures and collects them, together with the layers of
+/// repaint boundaries, into a list of layers.
+pub struct PaintContext {
+    estimated_bounds: Size,
+    layers: Vec<Rc<dyn Layer>>,
+    recorder: Option<PictureRecorder>,
 }
 
-impl<'a> PaintContext<'a> {
-    pub fn new(canvas: &'a mut skia_safe::Canvas) -> Self {
-        PaintContext { canvas }
+impl PaintContext {
+    pub fn new(estimated_bounds: Size) -> Self {
+        PaintContext {
+            estimated_bounds,
+            layers: Vec::new(),
+            recorder: None,
+        }
+    }
+
+    /// The canvas of the current picture, recording starts on first use.
+    pub fn canvas(&mut self) -> &mut Canvas {
+        if self.recorder.is_none() {
+            let mut recorder = PictureRecorder::new();
+            recorder.begin_recording(
+                Rect::from_wh(self.estimated_bounds.width, self.estimated_bounds.height),
+                None,
+            );
+            self.recorder = Some(recorder);
+        }
+        self.recorder
+            .as_mut()
+            .and_then(|r| r.recording_canvas())
+            .expect("recording canvas")
     }
 
     pub fn draw_rect(&mut self, offset: Offset, size: Size) {
@@ -24,7 +63,7 @@ impl<'a> PaintContext<'a> {
         paint.set_style(skia_safe::paint::Style::Stroke);
         paint.set_stroke_width(2.0);
 
-        self.canvas.draw_rect(
+        self.canvas().draw_rect(
             skia_safe::Rect {
                 left: offset.x,
                 top: offset.y,
@@ -34,6 +73,351 @@ impl<'a> PaintContext<'a> {
             &paint,
         );
     }
+
+    fn stop_recording(&mut self) {
+        if let Some(mut recorder) = self.recorder.take() {
+            if let Some(picture) = recorder.finish_recording_as_picture(None) {
+                self.layers.push(Rc::new(PictureLayer::new(picture)));
+            }
+        }
+    }
+
+    pub fn add_layer(&mut self, layer: Rc<dyn Layer>) {
+        self.stop_recording();
+        self.layers.push(layer);
+    }
+
+    pub fn finish(mut self) -> Vec<Rc<dyn Layer>> {
+        self.stop_recording();
+        self.layers
+    }
+
+    /// Paint `child`, reusing its layer if it is a clean repaint boundary.
+    pub fn paint_child(&mut self, child: &dyn RenderObject, offset: Offset) {
+        let state = child.state();
+        state.paint_offset.set(offset);
+        if state.is_repaint_boundary() {
+            PaintContext::flush_paint(child, self.estimated_bounds);
+            let layer = state.layer().expect("layer of repaint boundary");
+            layer.set_offset(offset);
+            self.add_layer(layer);
+        } else {
+            state.clear_needs_paint();
+            child.paint(self, offset);
+        }
+    }
+
+    /// Repaint the dirty repaint boundaries of the subtree at `node`.
+    pub fn flush_paint(node: &dyn RenderObject, estimated_bounds: Size) {
+        let state = node.state();
+        if state.needs_paint() && state.is_repaint_boundary() {
+            PaintContext::repaint_composited_child(node, estimated_bounds);
+        } else if state.take_child_needs_paint() {
+            node.visit_children(&mut |child| {
+                PaintContext::flush_paint(&*child.borrow(), estimated_bounds)
+            });
+            node.visit_slivers(&mut |child| {
+                PaintContext::flush_paint(&*child.borrow(), estimated_bounds)
+            });
+        }
+    }
+
+    fn repaint_composited_child(child: &dyn RenderObject, estimated_bounds: Size) {
+        let state = child.state();
+        let layer = state.layer().unwrap_or_else(|| {
+            let layer = Rc::new(OffsetLayer::default());
+            *state.layer.borrow_mut() = Some(layer.clone());
+            layer
+        });
+        state.clear_needs_paint();
+
+        let mut context = PaintContext::new(estimated_bounds);
+        child.paint(&mut context, Offset::zero());
+        layer.set_children(context.finish());
+    }
+
+    pub fn push_clip_rect(
+        &mut self,
+        offset: Offset,
+        size: Size,
+        clip_behavior: Clip,
+        painter: impl FnOnce(&mut PaintContext),
+    ) {
+        if clip_behavior == Clip::None {
+            painter(self);
+            return;
+        }
+        let mut context = PaintContext::new(self.estimated_bounds);
+        painter(&mut context);
+        let clip_rect = Rect::from_xywh(offset.x, offset.y, size.width, size.height);
+        let layer = ClipRectLayer::new(clip_rect, clip_behavior, context.finish());
+        self.add_layer(Rc::new(layer));
+    }
+
+    pub fn push_opacity(&mut self, alpha: u8, painter: impl FnOnce(&mut PaintContext)) {
+        let mut context = PaintContext::new(self.estimated_bounds);
+        painter(&mut context);
+        let layer = OpacityLayer::new(alpha, context.finish());
+        self.add_layer(Rc::new(layer));
+    }
+
+    /// Paint with `transform` applied around `offset`.
+    pub fn push_transform(
+        &mut self,
+        offset: Offset,
+        transform: Matrix4,
+        painter: impl FnOnce(&mut PaintContext),
+    ) {
+        let mut effective_transform = Matrix4::translation(offset.x, offset.y, 0.0);
+        effective_transform = effective_transform * transform;
+        effective_transform.translate(-offset.x, -offset.y, 0.0);
+
+        let mut context = PaintContext::new(self.estimated_bounds);
+        painter(&mut context);
+        let layer = TransformLayer::new(effective_transform, context.finish());
+        self.add_layer(Rc::new(layer));
+    }
+}
+
+/// Dirty flags, cached layout inputs and the retained layer of a render object.
+///
+/// Lives behind an `Rc` of `Cell`s so that a child can mark its ancestors dirty while
+/// they are borrowed, e.g. from a widget update closure.
+#[derive(Debug)]
+pub struct RenderState {
+    needs_layout: Cell<bool>,
+    child_needs_layout: Cell<bool>,
+    relayout_boundary: Cell<bool>,
+    constraints: Cell<Option<BoxConstraints>>,
+    intrinsic_dimensions: RefCell<HashMap<(IntrinsicDimension, u32), f32>>,
+    baselines: RefCell<HashMap<TextBaseline, Option<f32>>>,
+    needs_paint: Cell<bool>,
+    child_needs_paint: Cell<bool>,
+    repaint_boundary: Cell<bool>,
+    layer: RefCell<Option<Rc<OffsetLayer>>>,
+    parent: RefCell<Weak<RenderState>>,
+    paint_offset: Cell<Offset>,
+    key: RefCell<Option<Key>>,
+}
+
+impl Default for RenderState {
+    fn default() -> Self {
+        RenderState {
+            needs_layout: Cell::new(true),
+            child_needs_layout: Cell::new(false),
+            relayout_boundary: Cell::new(false),
+            constraints: Cell::new(None),
+            intrinsic_dimensions: RefCell::new(HashMap::new()),
+            baselines: RefCell::new(HashMap::new()),
+            needs_paint: Cell::new(true),
+            child_needs_paint: Cell::new(false),
+            repaint_boundary: Cell::new(false),
+            layer: RefCell::new(None),
+            parent: RefCell::new(Weak::new()),
+            paint_offset: Cell::new(Offset::zero()),
+            key: RefCell::new(None),
+        }
+    }
+}
+
+impl RenderState {
+    /// State of a render object that paints into its own layer.
+    pub fn repaint_boundary() -> Self {
+        let state = RenderState::default();
+        state.repaint_boundary.set(true);
+        state
+    }
+
+    pub fn needs_layout(&self) -> bool {
+        self.needs_layout.get()
+    }
+
+    /// Whether a relayout boundary somewhere below this node needs layout.
+    pub fn child_needs_layout(&self) -> bool {
+        self.child_needs_layout.get()
+    }
+
+    pub fn is_relayout_boundary(&self) -> bool {
+        self.relayout_boundary.get()
+    }
+
+    /// The constraints of the most recent layout.
+    pub fn constraints(&self) -> Option<BoxConstraints> {
+        self.constraints.get()
+    }
+
+    pub fn needs_paint(&self) -> bool {
+        self.needs_paint.get()
+    }
+
+    /// Whether a repaint boundary somewhere below this node needs paint.
+    pub fn child_needs_paint(&self) -> bool {
+        self.child_needs_paint.get()
+    }
+
+    pub fn is_repaint_boundary(&self) -> bool {
+        self.repaint_boundary.get()
+    }
+
+    /// The layer recorded by the last paint of a repaint boundary.
+    pub fn layer(&self) -> Option<Rc<OffsetLayer>> {
+        self.layer.borrow().clone()
+    }
+
+    /// Where the last paint placed this node, in the layer of its nearest repaint boundary
+    /// ancestor.
+    pub fn paint_offset(&self) -> Offset {
+        self.paint_offset.get()
+    }
+
+    /// Where the last paint placed this node on screen, transforms painted by its
+    /// ancestors are not applied.
+    pub fn global_paint_offset(&self) -> Offset {
+        let mut offset = self.paint_offset.get();
+        let mut parent = self.parent.borrow().upgrade();
+        while let Some(state) = parent {
+            if state.is_repaint_boundary() {
+                offset += state.paint_offset.get();
+            }
+            parent = state.parent.borrow().upgrade();
+        }
+        offset
+    }
+
+    /// The key of the widget this node renders, see [`KeyedSubtree`](crate::widgets::KeyedSubtree).
+    pub fn key(&self) -> Option<Key> {
+        self.key.borrow().clone()
+    }
+
+    pub fn set_key(&self, key: Option<Key>) {
+        *self.key.borrow_mut() = key;
+    }
+
+    /// Link `child` to this node so its dirty marks propagate here.
+    pub fn adopt(self: &Rc<Self>, child: &RenderState) {
+        *child.parent.borrow_mut() = Rc::downgrade(self);
+        self.mark_needs_layout();
+    }
+
+    /// The cached intrinsic `dimension` for `argument`, computed on first use.
+    pub fn intrinsic_dimension(
+        &self,
+        dimension: IntrinsicDimension,
+        argument: f32,
+        compute: impl FnOnce() -> f32,
+    ) -> f32 {
+        let key = (dimension, argument.to_bits());
+        if let Some(value) = self.intrinsic_dimensions.borrow().get(&key) {
+            return *value;
+        }
+        let value = compute();
+        self.intrinsic_dimensions.borrow_mut().insert(key, value);
+        value
+    }
+
+    /// The cached distance to `baseline`, computed on first use after each layout.
+    pub fn distance_to_baseline(
+        &self,
+        baseline: TextBaseline,
+        compute: impl FnOnce() -> Option<f32>,
+    ) -> Option<f32> {
+        if let Some(distance) = self.baselines.borrow().get(&baseline) {
+            return *distance;
+        }
+        let distance = compute();
+        self.baselines.borrow_mut().insert(baseline, distance);
+        distance
+    }
+
+    /// Mark this node dirty. Dirtiness propagates to the parent until a relayout
+    /// boundary is reached, the ancestors above it only record that a descendant
+    /// needs layout.
+    ///
+    /// A parent may have laid out using the cached intrinsic dimensions, so it is
+    /// marked dirty as well whenever those are dropped.
+    pub fn mark_needs_layout(&self) {
+        let had_intrinsics = {
+            let mut intrinsics = self.intrinsic_dimensions.borrow_mut();
+            let mut baselines = self.baselines.borrow_mut();
+            let had_intrinsics = !intrinsics.is_empty() || !baselines.is_empty();
+            intrinsics.clear();
+            baselines.clear();
+            had_intrinsics
+        };
+        if self.needs_layout.replace(true) && !had_intrinsics {
+            return;
+        }
+        if let Some(parent) = self.parent.borrow().upgrade() {
+            if self.relayout_boundary.get() && !had_intrinsics {
+                parent.mark_child_needs_layout();
+            } else {
+                parent.mark_needs_layout();
+            }
+        }
+    }
+
+    /// Mark this node for repaint, up to the nearest repaint boundary whose layer is
+    /// then re-recorded without repainting its ancestors.
+    pub fn mark_needs_paint(&self) {
+        if self.needs_paint.replace(true) {
+            return;
+        }
+        if let Some(parent) = self.parent.borrow().upgrade() {
+            if self.repaint_boundary.get() {
+                parent.mark_child_needs_paint();
+            } else {
+                parent.mark_needs_paint();
+            }
+        }
+    }
+
+    fn mark_child_needs_paint(&self) {
+        if self.child_needs_paint.replace(true) || self.needs_paint.get() {
+            return;
+        }
+        if let Some(parent) = self.parent.borrow().upgrade() {
+            parent.mark_child_needs_paint();
+        }
+    }
+
+    fn mark_child_needs_layout(&self) {
+        if self.child_needs_layout.replace(true) || self.needs_layout.get() {
+            return;
+        }
+        if let Some(parent) = self.parent.borrow().upgrade() {
+            parent.mark_child_needs_layout();
+        }
+    }
+
+    pub(crate) fn set_relayout_boundary(&self, relayout_boundary: bool) {
+        self.relayout_boundary.set(relayout_boundary);
+    }
+
+    pub(crate) fn set_constraints(&self, constraints: BoxConstraints) {
+        self.constraints.set(Some(constraints));
+    }
+
+    pub(crate) fn clear_baselines(&self) {
+        self.baselines.borrow_mut().clear();
+    }
+
+    pub(crate) fn take_child_needs_layout(&self) -> bool {
+        self.child_needs_layout.replace(false)
+    }
+
+    pub(crate) fn clear_needs_layout(&self) {
+        self.needs_layout.set(false);
+        self.child_needs_layout.set(false);
+    }
+
+    pub(crate) fn take_child_needs_paint(&self) -> bool {
+        self.child_needs_paint.replace(false)
+    }
+
+    pub(crate) fn clear_needs_paint(&self) {
+        self.needs_paint.set(false);
+        self.child_needs_paint.set(false);
+    }
 }
 
 pub trait RenderObject: Debug + HitTestTarget {
@@ -41,17 +425,43 @@ pub trait RenderObject: Debug + HitTestTarget {
 
     fn ty_name(&self) -> &'static str;
 
+    fn state(&self) -> &Rc<RenderState>;
+
     fn paint(&self, context: &mut PaintContext, offset: Offset);
 
     fn hit_test(&self, position: Offset, result: &mut HitTestResult) -> bool {
         false
     }
+
+    fn visit_children(&self, visitor: &mut dyn FnMut(&Rc<RefCell<dyn RenderBox>>)) {}
+
+    /// Visits the sliver children, e.g. of a viewport.
+    fn visit_slivers(&self, visitor: &mut dyn FnMut(&Rc<RefCell<dyn RenderSliver>>)) {}
+
+    fn mark_needs_layout(&self) {
+        self.state().mark_needs_layout()
+    }
+
+    fn mark_needs_paint(&self) {
+        self.state().mark_needs_paint()
+    }
 }
 
+/// Composes the render tree of a frame.
+type RootFn = Box<dyn Fn(&mut Composer) -> Rc<RefCell<dyn RenderBox>>>;
+
 pub struct PipelineOwner {
     size: Size,
-    root_fn: Box<dyn Fn(&mut Composer) -> Rc<RefCell<dyn RenderBox>>>,
+    root_fn: RootFn,
     render_view: Option<Rc<RefCell<dyn RenderBox>>>,
+    gestures: GestureBinding,
+    focus: Rc<FocusManager>,
+    raw_keyboard: RawKeyboard,
+    semantics: SemanticsOwner,
+    semantics_enabled: bool,
+    #[cfg(feature = "accesskit")]
+    accesskit: crate::semantics::AccessKitAdapter,
+    scheduler: Rc<SchedulerBinding>,
 }
 
 impl PipelineOwner {
@@ -59,43 +469,159 @@ impl PipelineOwner {
     where
         T: 'static + Widget,
     {
-        let view = View::new(root);
-        let root_fn = Box::new(move |cx: BuildContext| view.create(cx));
         PipelineOwner {
             size,
-            root_fn,
+            root_fn: PipelineOwner::root_fn(root),
             render_view: None,
+            gestures: GestureBinding::new(),
+            focus: FocusManager::new(),
+            raw_keyboard: RawKeyboard::new(),
+            semantics: SemanticsOwner::new(),
+            semantics_enabled: false,
+            #[cfg(feature = "accesskit")]
+            accesskit: Default::default(),
+            scheduler: SchedulerBinding::system(),
         }
     }
 
-    pub fn handle_event(&mut self, position: Offset) {
-        println!("handle_event {:?}", position);
+    /// Begins the frames at the time of `scheduler`, e.g. of a fake clock in tests.
+    pub fn with_scheduler(mut self, scheduler: Rc<SchedulerBinding>) -> Self {
+        self.scheduler = scheduler;
+        self
+    }
 
-        if let Some(view) = &mut self.render_view {
-            let mut result = HitTestResult::new();
-            view.borrow().hit_test(position, &mut result);
+    /// Replaces the root widget, the next frame recomposes the tree from it.
+    pub fn set_root<T>(&mut self, root: T)
+    where
+        T: 'static + Widget,
+    {
+        // the same call site composes the new root, its render objects are kept
+        self.root_fn = PipelineOwner::root_fn(root);
+    }
+
+    fn root_fn<T>(root: T) -> RootFn
+    where
+        T: 'static + Widget,
+    {
+        let view = View::new(root);
+        Box::new(move |cx: BuildContext| view.create(cx))
+    }
+
+    /// The render tree of the last frame.
+    pub fn render_view(&self) -> Option<&Rc<RefCell<dyn RenderBox>>> {
+        self.render_view.as_ref()
+    }
 
-            println!("hit result len {:?}", result.path.len());
+    /// The scheduler of the frames, the platform draws one when it has one scheduled.
+    pub fn scheduler(&self) -> &Rc<SchedulerBinding> {
+        &self.scheduler
+    }
 
-            for entry in result.path {
-                // TODO: PointerEvent
-                entry
-                    .target
-                    .borrow_mut()
-                    .handle_event(PointerEvent::new(position), entry.clone());
-            }
+    pub fn focus_manager(&self) -> &Rc<FocusManager> {
+        &self.focus
+    }
+
+    /// Builds the semantics tree with each frame, while an assistive technology uses it.
+    pub fn set_semantics_enabled(&mut self, enabled: bool) {
+        self.semantics_enabled = enabled;
+        #[cfg(feature = "accesskit")]
+        self.accesskit.reset();
+    }
+
+    /// The semantics tree of the last frame, if enabled.
+    pub fn semantics_tree(&self) -> Option<&SemanticsNode> {
+        self.semantics.root_node()
+    }
+
+    /// Asks the semantics node `id` to carry out `action`, then applies the focus changes
+    /// it requested. Returns whether the node has the action.
+    pub fn perform_semantics_action(
+        &mut self,
+        id: SemanticsNodeId,
+        action: SemanticsAction,
+        argument: Option<&str>,
+    ) -> bool {
+        let performed = self.semantics.perform_action(id, action, argument);
+        self.focus.apply_focus_changes();
+        performed
+    }
+
+    /// The AccessKit update bringing the platform adapter to the semantics tree of the
+    /// last frame, `None` while semantics are disabled.
+    #[cfg(feature = "accesskit")]
+    pub fn accesskit_tree_update(&mut self) -> Option<accesskit::TreeUpdate> {
+        let root = self.semantics.root_node()?;
+        Some(self.accesskit.update(root))
+    }
+
+    /// Carries out an action requested through AccessKit, then applies the focus changes
+    /// it requested. Returns whether the node has the action.
+    #[cfg(feature = "accesskit")]
+    pub fn handle_accesskit_action(&mut self, request: &accesskit::ActionRequest) -> bool {
+        let performed = self.semantics.perform_action_request(request);
+        self.focus.apply_focus_changes();
+        performed
+    }
+
+    /// Dispatches a pointer event, then applies the focus changes its handlers requested,
+    /// e.g. a text field tapped.
+    pub fn handle_event(&mut self, event: PointerEvent) {
+        if let Some(view) = &self.render_view {
+            self.gestures.handle_event(view, event);
+            self.focus.apply_focus_changes();
         }
     }
 
-    pub fn draw_frame(&mut self, cx: &mut Composer, context: &mut PaintContext) {
+    /// Dispatches a key event to the focused node and its ancestors, returns whether one
+    /// handled it.
+    pub fn handle_key_event(&mut self, event: KeyEvent) -> bool {
+        self.focus.handle_key_event(&event)
+    }
+
+    /// Dispatches the keyboard input of a window event, returns whether it was handled.
+    pub fn handle_window_event(&mut self, event: &WindowEvent) -> bool {
+        match self.raw_keyboard.handle_window_event(event) {
+            Some(KeyboardInput::Key(event)) => self.handle_key_event(event),
+            Some(KeyboardInput::Ime(event)) => self.handle_ime_event(event),
+            None => false,
+        }
+    }
+
+    /// Dispatches an input method event to the focused text input client, returns whether
+    /// there is one.
+    pub fn handle_ime_event(&mut self, event: ImeEvent) -> bool {
+        self.focus.handle_ime_event(&event)
+    }
+
+    /// Where the platform places the candidate window of its input method, the caret of
+    /// the focused text input client.
+    pub fn ime_caret_rect(&self) -> Option<Rect> {
+        self.focus.ime_caret_rect()
+    }
+
+    pub fn handle_deadlines(&self, now: Duration) {
+        self.gestures.handle_deadlines(now);
+    }
+
+    /// Ticks the animations, then builds, lays out, paints and composites a frame, and
+    /// runs the callbacks of the frame drawn.
+    pub fn draw_frame(&mut self, cx: &mut Composer, canvas: &mut Canvas) {
+        self.scheduler.handle_begin_frame();
+
         // re-build render tree;
-        self.render_view = Some((self.root_fn)(cx));
+        let root_fn = &self.root_fn;
+        self.render_view = Some(self.scheduler.scope(|| root_fn(cx)));
 
         //println!("{:#?}", self.render_view);
         //println!("{:#?}", self.context);
 
         self.flush_layout();
-        self.flush_paint(context);
+        self.flush_paint();
+        self.flush_focus();
+        self.flush_semantics();
+        self.composite_frame(canvas);
+
+        self.scheduler.handle_draw_frame();
     }
 
     pub fn flush_layout(&mut self) {
@@ -105,9 +631,33 @@ impl PipelineOwner {
         }
     }
 
-    pub fn flush_paint(&mut self, context: &mut PaintContext) {
-        if let Some(view) = &mut self.render_view {
-            view.borrow_mut().paint(context, Offset::zero());
+    pub fn flush_paint(&mut self) {
+        if let Some(view) = &self.render_view {
+            PaintContext::flush_paint(&*view.borrow(), self.size);
+        }
+    }
+
+    /// Updates the focus tree to the nodes and positions of the frame painted.
+    pub fn flush_focus(&mut self) {
+        if let Some(view) = &self.render_view {
+            self.focus.update_tree(view);
+        }
+    }
+
+    /// Rebuilds the semantics tree from the frame painted and its focus, if enabled.
+    pub fn flush_semantics(&mut self) {
+        if let (true, Some(view)) = (self.semantics_enabled, &self.render_view) {
+            self.semantics.update(view);
+        }
+    }
+
+    pub fn composite_frame(&self, canvas: &mut Canvas) {
+        if let Some(layer) = self
+            .render_view
+            .as_ref()
+            .and_then(|view| view.borrow().state().layer())
+        {
+            layer.composite(canvas);
         }
     }
 }
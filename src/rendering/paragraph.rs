@@ -0,0 +1,276 @@
+use std::{
+    any::{type_name, TypeId},
+    fmt::{self, Debug},
+    rc::Rc,
+};
+
+use crate::{
+    gestures::{HitTestEntry, HitTestResult, HitTestTarget},
+    painting::{TextPainter, TextSpan, TextStyle},
+    rendering::{
+        BoxConstraints, Clip, Offset, PaintContext, RenderBox, RenderObject, RenderState, Size,
+        TextAlign, TextBaseline, TextDirection,
+    },
+    semantics::SemanticsConfiguration,
+};
+
+/// How text that does not fit into its box is shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextOverflow {
+    /// Clip the text to the box.
+    Clip,
+
+    /// End the last line with an ellipsis.
+    Ellipsis,
+
+    /// Paint the text outside of the box.
+    Visible,
+}
+
+/// A paragraph of styled text, broken into lines to fit the max width of its constraints.
+///
+/// Pointers hitting the text of a span with a target are dispatched to that target too.
+pub struct RenderParagraph {
+    // RenderObject
+    pub(crate) state: Rc<RenderState>,
+
+    // RenderBox
+    pub(crate) size: Size,
+
+    // RenderParagraph
+    pub(crate) painter: TextPainter,
+    pub(crate) soft_wrap: bool,
+    pub(crate) overflow: TextOverflow,
+    pub(crate) text_width: f32,
+    pub(crate) needs_clipping: bool,
+}
+
+impl Debug for RenderParagraph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RenderParagraph")
+            .field("size", &self.size)
+            .field("painter", &self.painter)
+            .field("soft_wrap", &self.soft_wrap)
+            .field("overflow", &self.overflow)
+            .finish()
+    }
+}
+
+impl RenderParagraph {
+    pub fn new(text: impl Into<TextSpan>) -> Self {
+        RenderParagraph {
+            state: Default::default(),
+            size: Size::zero(),
+            painter: TextPainter::new(text),
+            soft_wrap: true,
+            overflow: TextOverflow::Clip,
+            text_width: 0.0,
+            needs_clipping: false,
+        }
+    }
+
+    pub fn with_style(mut self, style: TextStyle) -> Self {
+        self.painter.style = style;
+        self
+    }
+
+    pub fn with_text_align(mut self, text_align: TextAlign) -> Self {
+        self.painter.text_align = text_align;
+        self
+    }
+
+    pub fn with_text_direction(mut self, text_direction: TextDirection) -> Self {
+        self.painter.text_direction = text_direction;
+        self
+    }
+
+    pub fn with_soft_wrap(mut self, soft_wrap: bool) -> Self {
+        self.soft_wrap = soft_wrap;
+        self
+    }
+
+    pub fn with_overflow(mut self, overflow: TextOverflow) -> Self {
+        self.overflow = overflow;
+        self.painter.ellipsis = overflow == TextOverflow::Ellipsis;
+        self
+    }
+
+    pub fn with_max_lines(mut self, max_lines: impl Into<Option<usize>>) -> Self {
+        self.painter.max_lines = max_lines.into();
+        self
+    }
+
+    pub fn set_text(&mut self, text: impl Into<TextSpan>) {
+        let text = text.into();
+        if self.painter.text != text {
+            self.painter.set_text(text);
+            self.mark_needs_layout();
+        } else {
+            // only the span targets may differ, they are not part of the layout
+            self.painter.text = text;
+        }
+    }
+
+    pub fn set_style(&mut self, style: TextStyle) {
+        if self.painter.style != style {
+            self.painter.set_style(style);
+            self.mark_needs_layout();
+        }
+    }
+
+    pub fn set_text_align(&mut self, text_align: TextAlign) {
+        if self.painter.text_align != text_align {
+            self.painter.set_text_align(text_align);
+            self.mark_needs_layout();
+        }
+    }
+
+    pub fn set_text_direction(&mut self, text_direction: TextDirection) {
+        if self.painter.text_direction != text_direction {
+            self.painter.set_text_direction(text_direction);
+            self.mark_needs_layout();
+        }
+    }
+
+    pub fn set_soft_wrap(&mut self, soft_wrap: bool) {
+        if self.soft_wrap != soft_wrap {
+            self.soft_wrap = soft_wrap;
+            self.mark_needs_layout();
+        }
+    }
+
+    pub fn set_overflow(&mut self, overflow: TextOverflow) {
+        if self.overflow != overflow {
+            self.overflow = overflow;
+            self.painter
+                .set_ellipsis(overflow == TextOverflow::Ellipsis);
+            self.mark_needs_layout();
+        }
+    }
+
+    pub fn set_max_lines(&mut self, max_lines: impl Into<Option<usize>>) {
+        let max_lines = max_lines.into();
+        if self.painter.max_lines != max_lines {
+            self.painter.set_max_lines(max_lines);
+            self.mark_needs_layout();
+        }
+    }
+
+    /// Number of lines after the last layout, at most `max_lines`.
+    pub fn line_count(&self) -> usize {
+        self.painter.line_count()
+    }
+
+    /// Whether the text had more lines than `max_lines` in the last layout.
+    pub fn did_exceed_max_lines(&self) -> bool {
+        self.painter.did_exceed_max_lines()
+    }
+
+    /// The index of the character painted at `offset`, if any.
+    pub fn get_character_at_offset(&self, offset: Offset) -> Option<usize> {
+        self.painter.get_character_at_offset(offset)
+    }
+
+    /// The span painted at `offset` and the index of the character hit.
+    pub fn get_span_for_offset(&self, offset: Offset) -> Option<(&TextSpan, usize)> {
+        let index = self.get_character_at_offset(offset)?;
+        let span = self.painter.text.get_span_for_position(index)?;
+        Some((span, index))
+    }
+
+    /// The max width lines are broken at, unbounded when the text is kept on a line.
+    fn wrap_width(&self, max_width: f32) -> f32 {
+        if self.soft_wrap || self.overflow == TextOverflow::Ellipsis {
+            max_width
+        } else {
+            f32::INFINITY
+        }
+    }
+}
+
+impl HitTestTarget for RenderParagraph {}
+
+impl RenderObject for RenderParagraph {
+    fn ty_id(&self) -> TypeId {
+        TypeId::of::<Self>()
+    }
+
+    fn ty_name(&self) -> &'static str {
+        type_name::<Self>()
+    }
+
+    fn state(&self) -> &Rc<RenderState> {
+        &self.state
+    }
+
+    fn paint(&self, context: &mut PaintContext, offset: Offset) {
+        let paint_text = |context: &mut PaintContext| {
+            self.painter.paint(context.canvas(), offset);
+        };
+        if self.needs_clipping {
+            context.push_clip_rect(offset, self.size, Clip::HardEdge, paint_text);
+        } else {
+            paint_text(context);
+        }
+    }
+
+    fn hit_test(&self, position: Offset, result: &mut HitTestResult) -> bool {
+        if !self.size.contains(position) {
+            return false;
+        }
+        if let Some((span, _)) = self.get_span_for_offset(position) {
+            if let Some(target) = &span.target {
+                result.add(HitTestEntry::new(target.clone()));
+            }
+        }
+        true
+    }
+}
+
+impl RenderBox for RenderParagraph {
+    fn perform_layout(&mut self, constraints: &BoxConstraints) {
+        let max_width = self.wrap_width(constraints.max_width);
+        self.painter.layout(constraints.min_width, max_width);
+        self.text_width = self.painter.width();
+        let did_exceed_max_lines = self.painter.did_exceed_max_lines();
+        let text_size = Size::new(self.text_width, self.painter.height());
+        self.size = constraints.constrain(text_size);
+
+        let did_overflow = self.size.width < text_size.width
+            || self.size.height < text_size.height
+            || did_exceed_max_lines;
+        self.needs_clipping = did_overflow && self.overflow == TextOverflow::Clip;
+    }
+
+    fn compute_distance_to_actual_baseline(&self, baseline: TextBaseline) -> Option<f32> {
+        Some(self.painter.distance_to_baseline(baseline))
+    }
+
+    fn compute_min_intrinsic_width(&self, height: f32) -> f32 {
+        self.painter.min_intrinsic_width()
+    }
+
+    fn compute_max_intrinsic_width(&self, height: f32) -> f32 {
+        self.painter.max_intrinsic_width()
+    }
+
+    fn compute_min_intrinsic_height(&self, width: f32) -> f32 {
+        self.painter.height_for_width(width, self.wrap_width(width))
+    }
+
+    fn compute_max_intrinsic_height(&self, width: f32) -> f32 {
+        self.compute_min_intrinsic_height(width)
+    }
+
+    fn perform_resize(&mut self, constraints: &BoxConstraints) {
+        self.size = constraints.smallest();
+    }
+
+    fn size(&self) -> Size {
+        self.size
+    }
+
+    fn describe_semantics_configuration(&self, config: &mut SemanticsConfiguration) {
+        config.label = self.painter.text.to_plain_text();
+    }
+}
@@ -5,13 +5,23 @@ use std::{
 };
 
 use crate::{
-    gestures::HitTestTarget,
-    rendering::{BoxConstraints, Offset, PaintContext, RenderBox, RenderObject, Size},
+    gestures::{GestureCallback, GestureRecognizer, HitTestBehavior, HitTestEntry, HitTestTarget},
+    rendering::{
+        BoxConstraints, Offset, PaintContext, RenderBox, RenderObject, RenderState, Size,
+        TextBaseline,
+    },
+    semantics::{
+        SemanticsAction, SemanticsActionHandler, SemanticsConfiguration, SemanticsProperties,
+    },
+    services::FocusNode,
 };
 
 #[derive(Debug)]
 pub struct RenderConstrainedBox {
     // RenderObject
+    pub(crate) state: Rc<RenderState>,
+
+    // RenderBox
     pub(crate) size: Size,
 
     // RenderConstrainedBox
@@ -22,11 +32,27 @@ pub struct RenderConstrainedBox {
 impl RenderConstrainedBox {
     pub fn new(constraints: BoxConstraints) -> Self {
         RenderConstrainedBox {
+            state: Default::default(),
             size: Size::zero(),
             additional_constraints: constraints,
             child: None,
         }
     }
+
+    pub fn set_additional_constraints(&mut self, constraints: BoxConstraints) {
+        if self.additional_constraints != constraints {
+            self.additional_constraints = constraints;
+            self.mark_needs_layout();
+        }
+    }
+
+    pub fn set_child(&mut self, child: Rc<RefCell<dyn RenderBox>>) {
+        if matches!(&self.child, Some(c) if Rc::ptr_eq(c, &child)) {
+            return;
+        }
+        self.state.adopt(child.borrow().state());
+        self.child = Some(child);
+    }
 }
 
 impl HitTestTarget for RenderConstrainedBox {}
@@ -40,13 +66,26 @@ impl RenderObject for RenderConstrainedBox {
         type_name::<Self>()
     }
 
+    fn state(&self) -> &Rc<RenderState> {
+        &self.state
+    }
+
     fn paint(&self, context: &mut PaintContext, offset: Offset) {
         context.draw_rect(offset, self.size);
+        if let Some(child) = &self.child {
+            context.paint_child(&*child.borrow(), offset);
+        }
     }
 
     fn hit_test(&self, position: Offset, result: &mut crate::gestures::HitTestResult) -> bool {
         self.size().contains(position)
     }
+
+    fn visit_children(&self, visitor: &mut dyn FnMut(&Rc<RefCell<dyn RenderBox>>)) {
+        if let Some(child) = &self.child {
+            visitor(child);
+        }
+    }
 }
 
 impl RenderBox for RenderConstrainedBox {
@@ -63,6 +102,80 @@ impl RenderBox for RenderConstrainedBox {
         };
     }
 
+    fn compute_distance_to_actual_baseline(&self, baseline: TextBaseline) -> Option<f32> {
+        self.child
+            .as_ref()
+            .and_then(|child| child.borrow().get_distance_to_actual_baseline(baseline))
+    }
+
+    fn compute_min_intrinsic_width(&self, height: f32) -> f32 {
+        let constraints = &self.additional_constraints;
+        if constraints.has_bounded_width() && constraints.has_tight_width() {
+            return constraints.min_width;
+        }
+        let width = self
+            .child
+            .as_ref()
+            .map(|child| child.borrow().get_min_intrinsic_width(height))
+            .unwrap_or(0.0);
+        if constraints.has_infinite_width() {
+            width
+        } else {
+            constraints.constrain_width(width)
+        }
+    }
+
+    fn compute_max_intrinsic_width(&self, height: f32) -> f32 {
+        let constraints = &self.additional_constraints;
+        if constraints.has_bounded_width() && constraints.has_tight_width() {
+            return constraints.min_width;
+        }
+        let width = self
+            .child
+            .as_ref()
+            .map(|child| child.borrow().get_max_intrinsic_width(height))
+            .unwrap_or(0.0);
+        if constraints.has_infinite_width() {
+            width
+        } else {
+            constraints.constrain_width(width)
+        }
+    }
+
+    fn compute_min_intrinsic_height(&self, width: f32) -> f32 {
+        let constraints = &self.additional_constraints;
+        if constraints.has_bounded_height() && constraints.has_tight_height() {
+            return constraints.min_height;
+        }
+        let height = self
+            .child
+            .as_ref()
+            .map(|child| child.borrow().get_min_intrinsic_height(width))
+            .unwrap_or(0.0);
+        if constraints.has_infinite_height() {
+            height
+        } else {
+            constraints.constrain_height(height)
+        }
+    }
+
+    fn compute_max_intrinsic_height(&self, width: f32) -> f32 {
+        let constraints = &self.additional_constraints;
+        if constraints.has_bounded_height() && constraints.has_tight_height() {
+            return constraints.min_height;
+        }
+        let height = self
+            .child
+            .as_ref()
+            .map(|child| child.borrow().get_max_intrinsic_height(width))
+            .unwrap_or(0.0);
+        if constraints.has_infinite_height() {
+            height
+        } else {
+            constraints.constrain_height(height)
+        }
+    }
+
     fn perform_resize(&mut self, constraints: &BoxConstraints) {
         todo!()
     }
@@ -71,3 +184,859 @@ impl RenderBox for RenderConstrainedBox {
         self.size
     }
 }
+
+/// Paints its child into a separate layer, which is reused until the subtree is
+/// marked as needing paint.
+#[derive(Debug)]
+pub struct RenderRepaintBoundary {
+    // RenderObject
+    pub(crate) state: Rc<RenderState>,
+
+    // RenderBox
+    pub(crate) size: Size,
+
+    // RenderRepaintBoundary
+    pub(crate) child: Option<Rc<RefCell<dyn RenderBox>>>,
+}
+
+impl RenderRepaintBoundary {
+    pub fn new() -> Self {
+        RenderRepaintBoundary {
+            state: Rc::new(RenderState::repaint_boundary()),
+            size: Size::zero(),
+            child: None,
+        }
+    }
+
+    pub fn with_child(mut self, child: impl RenderBox + 'static) -> Self {
+        self.set_child(Rc::new(RefCell::new(child)));
+        self
+    }
+
+    pub fn set_child(&mut self, child: Rc<RefCell<dyn RenderBox>>) {
+        if matches!(&self.child, Some(c) if Rc::ptr_eq(c, &child)) {
+            return;
+        }
+        self.state.adopt(child.borrow().state());
+        self.child = Some(child);
+    }
+}
+
+impl HitTestTarget for RenderRepaintBoundary {}
+
+impl RenderObject for RenderRepaintBoundary {
+    fn ty_id(&self) -> TypeId {
+        TypeId::of::<Self>()
+    }
+
+    fn ty_name(&self) -> &'static str {
+        type_name::<Self>()
+    }
+
+    fn state(&self) -> &Rc<RenderState> {
+        &self.state
+    }
+
+    fn paint(&self, context: &mut PaintContext, offset: Offset) {
+        if let Some(child) = &self.child {
+            context.paint_child(&*child.borrow(), offset);
+        }
+    }
+
+    fn hit_test(&self, position: Offset, result: &mut crate::gestures::HitTestResult) -> bool {
+        match &self.child {
+            Some(child) => {
+                let is_hit = child.borrow().hit_test(position, result);
+                if is_hit {
+                    result.add(HitTestEntry::new(child.clone()));
+                }
+                is_hit
+            }
+            None => false,
+        }
+    }
+
+    fn visit_children(&self, visitor: &mut dyn FnMut(&Rc<RefCell<dyn RenderBox>>)) {
+        if let Some(child) = &self.child {
+            visitor(child);
+        }
+    }
+}
+
+impl RenderBox for RenderRepaintBoundary {
+    fn perform_layout(&mut self, constraints: &BoxConstraints) {
+        self.size = match &self.child {
+            Some(child) => {
+                child.borrow_mut().layout(constraints, true);
+                child.borrow().size()
+            }
+            None => constraints.smallest(),
+        };
+    }
+
+    fn compute_distance_to_actual_baseline(&self, baseline: TextBaseline) -> Option<f32> {
+        self.child
+            .as_ref()
+            .and_then(|child| child.borrow().get_distance_to_actual_baseline(baseline))
+    }
+
+    fn compute_min_intrinsic_width(&self, height: f32) -> f32 {
+        self.child
+            .as_ref()
+            .map(|child| child.borrow().get_min_intrinsic_width(height))
+            .unwrap_or(0.0)
+    }
+
+    fn compute_max_intrinsic_width(&self, height: f32) -> f32 {
+        self.child
+            .as_ref()
+            .map(|child| child.borrow().get_max_intrinsic_width(height))
+            .unwrap_or(0.0)
+    }
+
+    fn compute_min_intrinsic_height(&self, width: f32) -> f32 {
+        self.child
+            .as_ref()
+            .map(|child| child.borrow().get_min_intrinsic_height(width))
+            .unwrap_or(0.0)
+    }
+
+    fn compute_max_intrinsic_height(&self, width: f32) -> f32 {
+        self.child
+            .as_ref()
+            .map(|child| child.borrow().get_max_intrinsic_height(width))
+            .unwrap_or(0.0)
+    }
+
+    fn perform_resize(&mut self, constraints: &BoxConstraints) {
+        self.size = match &self.child {
+            Some(child) => {
+                child.borrow_mut().perform_resize(constraints);
+                child.borrow().size()
+            }
+            None => constraints.smallest(),
+        };
+    }
+
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+
+/// Paints its child partially transparent, into an opacity layer unless fully opaque. A
+/// fully transparent child is neither painted nor in the semantics tree, it is still hit.
+#[derive(Debug)]
+pub struct RenderOpacity {
+    // RenderObject
+    pub(crate) state: Rc<RenderState>,
+
+    // RenderBox
+    pub(crate) size: Size,
+
+    // RenderOpacity
+    pub(crate) opacity: f32,
+    pub(crate) child: Option<Rc<RefCell<dyn RenderBox>>>,
+}
+
+impl RenderOpacity {
+    pub fn new(opacity: f32) -> Self {
+        RenderOpacity {
+            state: Default::default(),
+            size: Size::zero(),
+            opacity: opacity.clamp(0.0, 1.0),
+            child: None,
+        }
+    }
+
+    pub fn with_child(mut self, child: impl RenderBox + 'static) -> Self {
+        self.set_child(Rc::new(RefCell::new(child)));
+        self
+    }
+
+    pub fn set_child(&mut self, child: Rc<RefCell<dyn RenderBox>>) {
+        if matches!(&self.child, Some(c) if Rc::ptr_eq(c, &child)) {
+            return;
+        }
+        self.state.adopt(child.borrow().state());
+        self.child = Some(child);
+    }
+
+    pub fn opacity(&self) -> f32 {
+        self.opacity
+    }
+
+    pub fn set_opacity(&mut self, opacity: f32) {
+        let opacity = opacity.clamp(0.0, 1.0);
+        if self.opacity != opacity {
+            self.opacity = opacity;
+            self.mark_needs_paint();
+        }
+    }
+
+    /// The alpha of the opacity layer.
+    pub fn alpha(&self) -> u8 {
+        (self.opacity * 255.0).round() as u8
+    }
+}
+
+impl HitTestTarget for RenderOpacity {}
+
+impl RenderObject for RenderOpacity {
+    fn ty_id(&self) -> TypeId {
+        TypeId::of::<Self>()
+    }
+
+    fn ty_name(&self) -> &'static str {
+        type_name::<Self>()
+    }
+
+    fn state(&self) -> &Rc<RenderState> {
+        &self.state
+    }
+
+    fn paint(&self, context: &mut PaintContext, offset: Offset) {
+        let child = match &self.child {
+            Some(child) => child,
+            None => return,
+        };
+        match self.alpha() {
+            0 => {}
+            255 => context.paint_child(&*child.borrow(), offset),
+            alpha => context.push_opacity(alpha, |context| {
+                context.paint_child(&*child.borrow(), offset)
+            }),
+        }
+    }
+
+    fn hit_test(&self, position: Offset, result: &mut crate::gestures::HitTestResult) -> bool {
+        match &self.child {
+            Some(child) => {
+                let is_hit = child.borrow().hit_test(position, result);
+                if is_hit {
+                    result.add(HitTestEntry::new(child.clone()));
+                }
+                is_hit
+            }
+            None => false,
+        }
+    }
+
+    fn visit_children(&self, visitor: &mut dyn FnMut(&Rc<RefCell<dyn RenderBox>>)) {
+        if let Some(child) = &self.child {
+            visitor(child);
+        }
+    }
+}
+
+impl RenderBox for RenderOpacity {
+    fn perform_layout(&mut self, constraints: &BoxConstraints) {
+        self.size = match &self.child {
+            Some(child) => {
+                child.borrow_mut().layout(constraints, true);
+                child.borrow().size()
+            }
+            None => constraints.smallest(),
+        };
+    }
+
+    fn compute_distance_to_actual_baseline(&self, baseline: TextBaseline) -> Option<f32> {
+        self.child
+            .as_ref()
+            .and_then(|child| child.borrow().get_distance_to_actual_baseline(baseline))
+    }
+
+    fn compute_min_intrinsic_width(&self, height: f32) -> f32 {
+        self.child
+            .as_ref()
+            .map(|child| child.borrow().get_min_intrinsic_width(height))
+            .unwrap_or(0.0)
+    }
+
+    fn compute_max_intrinsic_width(&self, height: f32) -> f32 {
+        self.child
+            .as_ref()
+            .map(|child| child.borrow().get_max_intrinsic_width(height))
+            .unwrap_or(0.0)
+    }
+
+    fn compute_min_intrinsic_height(&self, width: f32) -> f32 {
+        self.child
+            .as_ref()
+            .map(|child| child.borrow().get_min_intrinsic_height(width))
+            .unwrap_or(0.0)
+    }
+
+    fn compute_max_intrinsic_height(&self, width: f32) -> f32 {
+        self.child
+            .as_ref()
+            .map(|child| child.borrow().get_max_intrinsic_height(width))
+            .unwrap_or(0.0)
+    }
+
+    fn perform_resize(&mut self, constraints: &BoxConstraints) {
+        self.size = match &self.child {
+            Some(child) => {
+                child.borrow_mut().perform_resize(constraints);
+                child.borrow().size()
+            }
+            None => constraints.smallest(),
+        };
+    }
+
+    fn size(&self) -> Size {
+        self.size
+    }
+
+    fn describe_semantics_configuration(&self, config: &mut SemanticsConfiguration) {
+        config.excludes_descendants = self.alpha() == 0;
+    }
+}
+
+#[derive(Debug)]
+pub struct RenderGestureDetector {
+    // RenderObject
+    pub(crate) state: Rc<RenderState>,
+
+    // RenderBox
+    pub(crate) size: Size,
+
+    // RenderGestureDetector
+    pub(crate) child: Option<Rc<RefCell<dyn RenderBox>>>,
+    pub(crate) behavior: HitTestBehavior,
+    pub(crate) recognizers: Vec<Rc<dyn GestureRecognizer>>,
+    /// Called when assistive technologies tap the child.
+    pub(crate) on_semantics_tap: Option<GestureCallback<()>>,
+    /// Called when assistive technologies long press the child.
+    pub(crate) on_semantics_long_press: Option<GestureCallback<()>>,
+}
+
+impl RenderGestureDetector {
+    pub fn new(behavior: HitTestBehavior) -> Self {
+        RenderGestureDetector {
+            state: Default::default(),
+            size: Size::zero(),
+            child: None,
+            behavior,
+            recognizers: Vec::new(),
+            on_semantics_tap: None,
+            on_semantics_long_press: None,
+        }
+    }
+
+    pub fn with_child(mut self, child: impl RenderBox + 'static) -> Self {
+        self.set_child(Rc::new(RefCell::new(child)));
+        self
+    }
+
+    pub fn with_recognizer(mut self, recognizer: impl GestureRecognizer + 'static) -> Self {
+        self.recognizers.push(Rc::new(recognizer));
+        self
+    }
+
+    pub fn with_on_semantics_tap(mut self, f: impl Fn(()) + 'static) -> Self {
+        self.on_semantics_tap = Some(GestureCallback::new(f));
+        self
+    }
+
+    pub fn set_child(&mut self, child: Rc<RefCell<dyn RenderBox>>) {
+        if matches!(&self.child, Some(c) if Rc::ptr_eq(c, &child)) {
+            return;
+        }
+        self.state.adopt(child.borrow().state());
+        self.child = Some(child);
+    }
+
+    pub fn set_behavior(&mut self, behavior: HitTestBehavior) {
+        self.behavior = behavior;
+    }
+
+    /// Replaces the recognizers, gestures in progress complete on the previous ones.
+    pub fn set_recognizers(&mut self, recognizers: Vec<Rc<dyn GestureRecognizer>>) {
+        self.recognizers = recognizers;
+    }
+
+    pub fn set_on_semantics_tap(&mut self, on_tap: Option<GestureCallback<()>>) {
+        self.on_semantics_tap = on_tap;
+    }
+
+    pub fn set_on_semantics_long_press(&mut self, on_long_press: Option<GestureCallback<()>>) {
+        self.on_semantics_long_press = on_long_press;
+    }
+}
+
+impl HitTestTarget for RenderGestureDetector {
+    fn gesture_recognizers(&self) -> Vec<Rc<dyn GestureRecognizer>> {
+        self.recognizers.clone()
+    }
+}
+
+impl RenderObject for RenderGestureDetector {
+    fn ty_id(&self) -> TypeId {
+        TypeId::of::<Self>()
+    }
+
+    fn ty_name(&self) -> &'static str {
+        type_name::<Self>()
+    }
+
+    fn state(&self) -> &Rc<RenderState> {
+        &self.state
+    }
+
+    fn paint(&self, context: &mut PaintContext, offset: Offset) {
+        if let Some(child) = &self.child {
+            context.paint_child(&*child.borrow(), offset);
+        }
+    }
+
+    fn hit_test(&self, position: Offset, result: &mut crate::gestures::HitTestResult) -> bool {
+        if !self.size.contains(position) {
+            return false;
+        }
+        let is_child_hit = match &self.child {
+            Some(child) => {
+                let is_hit = child.borrow().hit_test(position, result);
+                if is_hit {
+                    result.add(HitTestEntry::new(child.clone()));
+                }
+                is_hit
+            }
+            None => false,
+        };
+        is_child_hit || self.behavior == HitTestBehavior::Opaque
+    }
+
+    fn visit_children(&self, visitor: &mut dyn FnMut(&Rc<RefCell<dyn RenderBox>>)) {
+        if let Some(child) = &self.child {
+            visitor(child);
+        }
+    }
+}
+
+impl RenderBox for RenderGestureDetector {
+    fn perform_layout(&mut self, constraints: &BoxConstraints) {
+        self.size = match &self.child {
+            Some(child) => {
+                child.borrow_mut().layout(constraints, true);
+                child.borrow().size()
+            }
+            None => constraints.smallest(),
+        };
+    }
+
+    fn compute_distance_to_actual_baseline(&self, baseline: TextBaseline) -> Option<f32> {
+        self.child
+            .as_ref()
+            .and_then(|child| child.borrow().get_distance_to_actual_baseline(baseline))
+    }
+
+    fn compute_min_intrinsic_width(&self, height: f32) -> f32 {
+        self.child
+            .as_ref()
+            .map(|child| child.borrow().get_min_intrinsic_width(height))
+            .unwrap_or(0.0)
+    }
+
+    fn compute_max_intrinsic_width(&self, height: f32) -> f32 {
+        self.child
+            .as_ref()
+            .map(|child| child.borrow().get_max_intrinsic_width(height))
+            .unwrap_or(0.0)
+    }
+
+    fn compute_min_intrinsic_height(&self, width: f32) -> f32 {
+        self.child
+            .as_ref()
+            .map(|child| child.borrow().get_min_intrinsic_height(width))
+            .unwrap_or(0.0)
+    }
+
+    fn compute_max_intrinsic_height(&self, width: f32) -> f32 {
+        self.child
+            .as_ref()
+            .map(|child| child.borrow().get_max_intrinsic_height(width))
+            .unwrap_or(0.0)
+    }
+
+    fn perform_resize(&mut self, constraints: &BoxConstraints) {
+        self.size = match &self.child {
+            Some(child) => {
+                child.borrow_mut().perform_resize(constraints);
+                child.borrow().size()
+            }
+            None => constraints.smallest(),
+        };
+    }
+
+    fn size(&self) -> Size {
+        self.size
+    }
+
+    fn describe_semantics_configuration(&self, config: &mut SemanticsConfiguration) {
+        let callbacks = [
+            (SemanticsAction::Tap, &self.on_semantics_tap),
+            (SemanticsAction::LongPress, &self.on_semantics_long_press),
+        ];
+        for (action, callback) in callbacks {
+            if let Some(callback) = callback.clone() {
+                let handler = SemanticsActionHandler::new(move |_| callback.call(()));
+                config.add_action(action, handler);
+            }
+        }
+    }
+}
+
+/// Places its child in the focus tree with a node, the child keeps its layout and paint.
+///
+/// Key events reaching the node go to its key callback, the child gets them through its
+/// own node if it has one.
+#[derive(Debug)]
+pub struct RenderFocus {
+    // RenderObject
+    pub(crate) state: Rc<RenderState>,
+
+    // RenderBox
+    pub(crate) size: Size,
+
+    // RenderFocus
+    pub(crate) child: Option<Rc<RefCell<dyn RenderBox>>>,
+    pub(crate) focus_node: Rc<FocusNode>,
+}
+
+impl RenderFocus {
+    pub fn new(focus_node: Rc<FocusNode>) -> Self {
+        RenderFocus {
+            state: Default::default(),
+            size: Size::zero(),
+            child: None,
+            focus_node,
+        }
+    }
+
+    pub fn with_child(mut self, child: impl RenderBox + 'static) -> Self {
+        self.set_child(Rc::new(RefCell::new(child)));
+        self
+    }
+
+    pub fn set_child(&mut self, child: Rc<RefCell<dyn RenderBox>>) {
+        if matches!(&self.child, Some(c) if Rc::ptr_eq(c, &child)) {
+            return;
+        }
+        self.state.adopt(child.borrow().state());
+        self.child = Some(child);
+    }
+
+    /// Replaces the node, the focus moves with it on the next update of the focus tree.
+    pub fn set_focus_node(&mut self, focus_node: Rc<FocusNode>) {
+        if !Rc::ptr_eq(&self.focus_node, &focus_node) {
+            if self.focus_node.has_primary_focus() {
+                focus_node.request_focus();
+            }
+            self.focus_node = focus_node;
+        }
+    }
+}
+
+impl HitTestTarget for RenderFocus {}
+
+impl RenderObject for RenderFocus {
+    fn ty_id(&self) -> TypeId {
+        TypeId::of::<Self>()
+    }
+
+    fn ty_name(&self) -> &'static str {
+        type_name::<Self>()
+    }
+
+    fn state(&self) -> &Rc<RenderState> {
+        &self.state
+    }
+
+    fn paint(&self, context: &mut PaintContext, offset: Offset) {
+        if let Some(child) = &self.child {
+            context.paint_child(&*child.borrow(), offset);
+        }
+    }
+
+    fn hit_test(&self, position: Offset, result: &mut crate::gestures::HitTestResult) -> bool {
+        match &self.child {
+            Some(child) => {
+                let is_hit = child.borrow().hit_test(position, result);
+                if is_hit {
+                    result.add(HitTestEntry::new(child.clone()));
+                }
+                is_hit
+            }
+            None => false,
+        }
+    }
+
+    fn visit_children(&self, visitor: &mut dyn FnMut(&Rc<RefCell<dyn RenderBox>>)) {
+        if let Some(child) = &self.child {
+            visitor(child);
+        }
+    }
+}
+
+impl RenderBox for RenderFocus {
+    fn perform_layout(&mut self, constraints: &BoxConstraints) {
+        self.size = match &self.child {
+            Some(child) => {
+                child.borrow_mut().layout(constraints, true);
+                child.borrow().size()
+            }
+            None => constraints.smallest(),
+        };
+    }
+
+    fn compute_distance_to_actual_baseline(&self, baseline: TextBaseline) -> Option<f32> {
+        self.child
+            .as_ref()
+            .and_then(|child| child.borrow().get_distance_to_actual_baseline(baseline))
+    }
+
+    fn compute_min_intrinsic_width(&self, height: f32) -> f32 {
+        self.child
+            .as_ref()
+            .map(|child| child.borrow().get_min_intrinsic_width(height))
+            .unwrap_or(0.0)
+    }
+
+    fn compute_max_intrinsic_width(&self, height: f32) -> f32 {
+        self.child
+            .as_ref()
+            .map(|child| child.borrow().get_max_intrinsic_width(height))
+            .unwrap_or(0.0)
+    }
+
+    fn compute_min_intrinsic_height(&self, width: f32) -> f32 {
+        self.child
+            .as_ref()
+            .map(|child| child.borrow().get_min_intrinsic_height(width))
+            .unwrap_or(0.0)
+    }
+
+    fn compute_max_intrinsic_height(&self, width: f32) -> f32 {
+        self.child
+            .as_ref()
+            .map(|child| child.borrow().get_max_intrinsic_height(width))
+            .unwrap_or(0.0)
+    }
+
+    fn perform_resize(&mut self, constraints: &BoxConstraints) {
+        self.size = match &self.child {
+            Some(child) => {
+                child.borrow_mut().perform_resize(constraints);
+                child.borrow().size()
+            }
+            None => constraints.smallest(),
+        };
+    }
+
+    fn size(&self) -> Size {
+        self.size
+    }
+
+    fn focus_node(&self) -> Option<Rc<FocusNode>> {
+        Some(self.focus_node.clone())
+    }
+}
+
+/// Annotates the semantics of its child, e.g. to label an icon button, or groups the
+/// semantics of its descendants into a node.
+#[derive(Debug)]
+pub struct RenderSemanticsAnnotations {
+    // RenderObject
+    pub(crate) state: Rc<RenderState>,
+
+    // RenderBox
+    pub(crate) size: Size,
+
+    // RenderSemanticsAnnotations
+    pub(crate) child: Option<Rc<RefCell<dyn RenderBox>>>,
+    pub(crate) properties: SemanticsProperties,
+    /// Whether the annotations form a node, those of the descendants merge into it.
+    pub(crate) container: bool,
+    pub(crate) explicit_child_nodes: bool,
+    /// Whether the descendants merge into a single node, boundaries too.
+    pub(crate) merges_descendants: bool,
+    /// Whether the descendants are left out of the semantics tree.
+    pub(crate) excludes_descendants: bool,
+}
+
+impl RenderSemanticsAnnotations {
+    pub fn new(properties: SemanticsProperties) -> Self {
+        RenderSemanticsAnnotations {
+            state: Default::default(),
+            size: Size::zero(),
+            child: None,
+            properties,
+            container: false,
+            explicit_child_nodes: false,
+            merges_descendants: false,
+            excludes_descendants: false,
+        }
+    }
+
+    pub fn with_child(mut self, child: impl RenderBox + 'static) -> Self {
+        self.set_child(Rc::new(RefCell::new(child)));
+        self
+    }
+
+    pub fn with_container(mut self, container: bool) -> Self {
+        self.container = container;
+        self
+    }
+
+    pub fn with_explicit_child_nodes(mut self, explicit_child_nodes: bool) -> Self {
+        self.explicit_child_nodes = explicit_child_nodes;
+        self
+    }
+
+    pub fn with_merges_descendants(mut self, merges_descendants: bool) -> Self {
+        self.merges_descendants = merges_descendants;
+        self
+    }
+
+    pub fn with_excludes_descendants(mut self, excludes_descendants: bool) -> Self {
+        self.excludes_descendants = excludes_descendants;
+        self
+    }
+
+    pub fn set_child(&mut self, child: Rc<RefCell<dyn RenderBox>>) {
+        if matches!(&self.child, Some(c) if Rc::ptr_eq(c, &child)) {
+            return;
+        }
+        self.state.adopt(child.borrow().state());
+        self.child = Some(child);
+    }
+
+    pub fn set_properties(&mut self, properties: SemanticsProperties) {
+        self.properties = properties;
+    }
+
+    pub fn set_container(&mut self, container: bool) {
+        self.container = container;
+    }
+
+    pub fn set_explicit_child_nodes(&mut self, explicit_child_nodes: bool) {
+        self.explicit_child_nodes = explicit_child_nodes;
+    }
+
+    pub fn set_merges_descendants(&mut self, merges_descendants: bool) {
+        self.merges_descendants = merges_descendants;
+    }
+
+    pub fn set_excludes_descendants(&mut self, excludes_descendants: bool) {
+        self.excludes_descendants = excludes_descendants;
+    }
+}
+
+impl HitTestTarget for RenderSemanticsAnnotations {}
+
+impl RenderObject for RenderSemanticsAnnotations {
+    fn ty_id(&self) -> TypeId {
+        TypeId::of::<Self>()
+    }
+
+    fn ty_name(&self) -> &'static str {
+        type_name::<Self>()
+    }
+
+    fn state(&self) -> &Rc<RenderState> {
+        &self.state
+    }
+
+    fn paint(&self, context: &mut PaintContext, offset: Offset) {
+        if let Some(child) = &self.child {
+            context.paint_child(&*child.borrow(), offset);
+        }
+    }
+
+    fn hit_test(&self, position: Offset, result: &mut crate::gestures::HitTestResult) -> bool {
+        match &self.child {
+            Some(child) => {
+                let is_hit = child.borrow().hit_test(position, result);
+                if is_hit {
+                    result.add(HitTestEntry::new(child.clone()));
+                }
+                is_hit
+            }
+            None => false,
+        }
+    }
+
+    fn visit_children(&self, visitor: &mut dyn FnMut(&Rc<RefCell<dyn RenderBox>>)) {
+        if let Some(child) = &self.child {
+            visitor(child);
+        }
+    }
+}
+
+impl RenderBox for RenderSemanticsAnnotations {
+    fn perform_layout(&mut self, constraints: &BoxConstraints) {
+        self.size = match &self.child {
+            Some(child) => {
+                child.borrow_mut().layout(constraints, true);
+                child.borrow().size()
+            }
+            None => constraints.smallest(),
+        };
+    }
+
+    fn compute_distance_to_actual_baseline(&self, baseline: TextBaseline) -> Option<f32> {
+        self.child
+            .as_ref()
+            .and_then(|child| child.borrow().get_distance_to_actual_baseline(baseline))
+    }
+
+    fn compute_min_intrinsic_width(&self, height: f32) -> f32 {
+        self.child
+            .as_ref()
+            .map(|child| child.borrow().get_min_intrinsic_width(height))
+            .unwrap_or(0.0)
+    }
+
+    fn compute_max_intrinsic_width(&self, height: f32) -> f32 {
+        self.child
+            .as_ref()
+            .map(|child| child.borrow().get_max_intrinsic_width(height))
+            .unwrap_or(0.0)
+    }
+
+    fn compute_min_intrinsic_height(&self, width: f32) -> f32 {
+        self.child
+            .as_ref()
+            .map(|child| child.borrow().get_min_intrinsic_height(width))
+            .unwrap_or(0.0)
+    }
+
+    fn compute_max_intrinsic_height(&self, width: f32) -> f32 {
+        self.child
+            .as_ref()
+            .map(|child| child.borrow().get_max_intrinsic_height(width))
+            .unwrap_or(0.0)
+    }
+
+    fn perform_resize(&mut self, constraints: &BoxConstraints) {
+        self.size = match &self.child {
+            Some(child) => {
+                child.borrow_mut().perform_resize(constraints);
+                child.borrow().size()
+            }
+            None => constraints.smallest(),
+        };
+    }
+
+    fn size(&self) -> Size {
+        self.size
+    }
+
+    fn describe_semantics_configuration(&self, config: &mut SemanticsConfiguration) {
+        config.is_semantic_boundary = self.container;
+        config.explicit_child_nodes = self.explicit_child_nodes;
+        config.is_merging_semantics_of_descendants = self.merges_descendants;
+        config.excludes_descendants = self.excludes_descendants;
+        self.properties.apply(config);
+    }
+}
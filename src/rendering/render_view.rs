@@ -6,12 +6,19 @@ use std::{
 
 use crate::{
     gestures::{HitTestEntry, HitTestResult, HitTestTarget},
-    rendering::{BoxConstraints, Offset, PaintContext, RenderBox, RenderObject, Size},
+    rendering::{
+        BoxConstraints, Offset, PaintContext, RenderBox, RenderObject, RenderState, Size,
+        TextBaseline,
+    },
+    semantics::{SemanticsConfiguration, SemanticsRole},
 };
 
 #[derive(Debug)]
 pub struct RenderView {
     // RenderObject
+    pub(crate) state: Rc<RenderState>,
+
+    // RenderBox
     pub(crate) size: Size,
 
     pub(crate) child: Option<Rc<RefCell<dyn RenderBox>>>,
@@ -20,10 +27,19 @@ pub struct RenderView {
 impl RenderView {
     pub fn new() -> Self {
         RenderView {
+            state: Rc::new(RenderState::repaint_boundary()),
             size: Size::zero(),
             child: None,
         }
     }
+
+    pub fn set_child(&mut self, child: Rc<RefCell<dyn RenderBox>>) {
+        if matches!(&self.child, Some(c) if Rc::ptr_eq(c, &child)) {
+            return;
+        }
+        self.state.adopt(child.borrow().state());
+        self.child = Some(child);
+    }
 }
 
 impl HitTestTarget for RenderView {}
@@ -37,9 +53,13 @@ impl RenderObject for RenderView {
         type_name::<Self>()
     }
 
+    fn state(&self) -> &Rc<RenderState> {
+        &self.state
+    }
+
     fn paint(&self, context: &mut PaintContext, offset: Offset) {
         if let Some(child) = &self.child {
-            child.borrow().paint(context, offset);
+            context.paint_child(&*child.borrow(), offset);
         }
     }
 
@@ -55,6 +75,12 @@ impl RenderObject for RenderView {
             false
         }
     }
+
+    fn visit_children(&self, visitor: &mut dyn FnMut(&Rc<RefCell<dyn RenderBox>>)) {
+        if let Some(child) = &self.child {
+            visitor(child);
+        }
+    }
 }
 
 impl RenderBox for RenderView {
@@ -68,6 +94,40 @@ impl RenderBox for RenderView {
         };
     }
 
+    fn compute_distance_to_actual_baseline(&self, baseline: TextBaseline) -> Option<f32> {
+        self.child
+            .as_ref()
+            .and_then(|child| child.borrow().get_distance_to_actual_baseline(baseline))
+    }
+
+    fn compute_min_intrinsic_width(&self, height: f32) -> f32 {
+        self.child
+            .as_ref()
+            .map(|child| child.borrow().get_min_intrinsic_width(height))
+            .unwrap_or(0.0)
+    }
+
+    fn compute_max_intrinsic_width(&self, height: f32) -> f32 {
+        self.child
+            .as_ref()
+            .map(|child| child.borrow().get_max_intrinsic_width(height))
+            .unwrap_or(0.0)
+    }
+
+    fn compute_min_intrinsic_height(&self, width: f32) -> f32 {
+        self.child
+            .as_ref()
+            .map(|child| child.borrow().get_min_intrinsic_height(width))
+            .unwrap_or(0.0)
+    }
+
+    fn compute_max_intrinsic_height(&self, width: f32) -> f32 {
+        self.child
+            .as_ref()
+            .map(|child| child.borrow().get_max_intrinsic_height(width))
+            .unwrap_or(0.0)
+    }
+
     fn perform_resize(&mut self, constraints: &BoxConstraints) {
         if let Some(child) = &mut self.child {
             child.borrow_mut().perform_resize(constraints);
@@ -78,4 +138,10 @@ impl RenderBox for RenderView {
     fn size(&self) -> Size {
         self.size
     }
+
+    fn describe_semantics_configuration(&self, config: &mut SemanticsConfiguration) {
+        config.is_semantic_boundary = true;
+        config.explicit_child_nodes = true;
+        config.role = SemanticsRole::Window;
+    }
 }
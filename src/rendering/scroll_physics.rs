@@ -0,0 +1,280 @@
+use std::{fmt::Debug, rc::Rc};
+
+use crate::{
+    physics::{
+        BoundedFrictionSimulation, ScrollSpringSimulation, Simulation, SpringDescription, Tolerance,
+    },
+    rendering::{BouncingScrollSimulation, SCROLL_DRAG},
+};
+
+/// Where a scroll offset is within the content it scrolls.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScrollMetrics {
+    pub pixels: f32,
+    pub min_scroll_extent: f32,
+    pub max_scroll_extent: f32,
+    /// Extent of the viewport along its main axis.
+    pub viewport_dimension: f32,
+}
+
+impl ScrollMetrics {
+    /// Whether the offset is scrolled beyond the content.
+    pub fn out_of_range(&self) -> bool {
+        self.pixels < self.min_scroll_extent || self.pixels > self.max_scroll_extent
+    }
+
+    /// Whether the offset is scrolled to the start or the end of the content.
+    pub fn at_edge(&self) -> bool {
+        self.pixels == self.min_scroll_extent || self.pixels == self.max_scroll_extent
+    }
+}
+
+/// How a scroll offset responds to the user: how far drags move it, how far past the
+/// content it may go and how it moves on after a fling.
+///
+/// Offsets passed to the physics follow [`ViewportOffset::apply_user_offset`]: a
+/// positive offset moves the content forward and the scroll offset back. Velocities
+/// follow the scroll offset, in pixels per second.
+///
+/// [`ViewportOffset::apply_user_offset`]: crate::rendering::ViewportOffset::apply_user_offset
+pub trait ScrollPhysics: Debug {
+    /// The distance the content moves for a drag by `offset`.
+    fn apply_physics_to_user_offset(&self, metrics: &ScrollMetrics, offset: f32) -> f32 {
+        offset
+    }
+
+    /// The part of a move of the scroll offset to `value` that goes past what the physics
+    /// allow, zero to allow all of it.
+    fn apply_boundary_conditions(&self, metrics: &ScrollMetrics, value: f32) -> f32 {
+        0.0
+    }
+
+    /// The motion of the scroll offset after the user let go of it at `velocity`, `None`
+    /// to stay where it is.
+    fn create_ballistic_simulation(
+        &self,
+        metrics: &ScrollMetrics,
+        velocity: f32,
+    ) -> Option<Box<dyn Simulation>> {
+        None
+    }
+
+    /// The spring pulling the scroll offset back into the content.
+    fn spring(&self) -> SpringDescription {
+        SpringDescription::with_damping_ratio(0.5, 100.0, 1.1)
+    }
+
+    /// When a ballistic simulation is done, within a pixel and at less than a pixel per
+    /// 50ms.
+    fn tolerance(&self) -> Tolerance {
+        Tolerance::new(1.0, 1e-3, 20.0)
+    }
+}
+
+/// Stops the scroll offset at the edges of the content, flings slow down by friction.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ClampingScrollPhysics;
+
+impl ScrollPhysics for ClampingScrollPhysics {
+    fn apply_boundary_conditions(&self, metrics: &ScrollMetrics, value: f32) -> f32 {
+        let ScrollMetrics {
+            pixels,
+            min_scroll_extent: min,
+            max_scroll_extent: max,
+            ..
+        } = *metrics;
+        if value < pixels && pixels <= min {
+            // underscroll
+            value - pixels
+        } else if max <= pixels && pixels < value {
+            // overscroll
+            value - pixels
+        } else if value < min && min < pixels {
+            // hit the start edge
+            value - min
+        } else if pixels < max && max < value {
+            // hit the end edge
+            value - max
+        } else {
+            0.0
+        }
+    }
+
+    fn create_ballistic_simulation(
+        &self,
+        metrics: &ScrollMetrics,
+        velocity: f32,
+    ) -> Option<Box<dyn Simulation>> {
+        let tolerance = self.tolerance();
+        if metrics.out_of_range() {
+            let end = metrics
+                .pixels
+                .clamp(metrics.min_scroll_extent, metrics.max_scroll_extent);
+            let simulation =
+                ScrollSpringSimulation::new(self.spring(), metrics.pixels, end, velocity.min(0.0))
+                    .with_tolerance(tolerance);
+            return Some(Box::new(simulation));
+        }
+        if velocity.abs() < tolerance.velocity
+            || (velocity > 0.0 && metrics.pixels >= metrics.max_scroll_extent)
+            || (velocity < 0.0 && metrics.pixels <= metrics.min_scroll_extent)
+        {
+            return None;
+        }
+        let simulation = BoundedFrictionSimulation::new(
+            SCROLL_DRAG,
+            metrics.pixels,
+            velocity,
+            metrics.min_scroll_extent,
+            metrics.max_scroll_extent,
+        )
+        .with_tolerance(tolerance);
+        Some(Box::new(simulation))
+    }
+}
+
+/// Lets the scroll offset go past the edges of the content, with growing resistance,
+/// and springs it back once the user lets go.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BouncingScrollPhysics;
+
+impl BouncingScrollPhysics {
+    /// The resistance of a drag scrolled `overscroll_fraction` of the viewport past the
+    /// content.
+    pub fn friction_factor(overscroll_fraction: f32) -> f32 {
+        0.52 * (1.0 - overscroll_fraction).powi(2)
+    }
+
+    /// Slows down the part of a drag by `delta` that stays `extent_outside` of the
+    /// content or further by `friction`.
+    fn apply_friction(extent_outside: f32, delta: f32, friction: f32) -> f32 {
+        if extent_outside <= 0.0 {
+            return delta;
+        }
+        let delta_to_limit = extent_outside / friction;
+        if delta < delta_to_limit {
+            delta * friction
+        } else {
+            extent_outside + delta - delta_to_limit
+        }
+    }
+}
+
+impl ScrollPhysics for BouncingScrollPhysics {
+    fn apply_physics_to_user_offset(&self, metrics: &ScrollMetrics, offset: f32) -> f32 {
+        if !metrics.out_of_range() {
+            return offset;
+        }
+        let overscroll_past_start = (metrics.min_scroll_extent - metrics.pixels).max(0.0);
+        let overscroll_past_end = (metrics.pixels - metrics.max_scroll_extent).max(0.0);
+        let overscroll_past = overscroll_past_start.max(overscroll_past_end);
+        // moving back towards the content
+        let easing = (overscroll_past_start > 0.0 && offset < 0.0)
+            || (overscroll_past_end > 0.0 && offset > 0.0);
+        let friction = if easing {
+            Self::friction_factor((overscroll_past - offset.abs()) / metrics.viewport_dimension)
+        } else {
+            Self::friction_factor(overscroll_past / metrics.viewport_dimension)
+        };
+
+        offset.signum() * Self::apply_friction(overscroll_past, offset.abs(), friction)
+    }
+
+    fn create_ballistic_simulation(
+        &self,
+        metrics: &ScrollMetrics,
+        velocity: f32,
+    ) -> Option<Box<dyn Simulation>> {
+        let tolerance = self.tolerance();
+        if velocity.abs() < tolerance.velocity && !metrics.out_of_range() {
+            return None;
+        }
+        Some(Box::new(BouncingScrollSimulation::new(
+            self.spring(),
+            metrics.pixels,
+            velocity,
+            metrics.min_scroll_extent,
+            metrics.max_scroll_extent,
+            tolerance,
+        )))
+    }
+}
+
+/// Settles the scroll offset on a page, a page being the extent of the viewport.
+///
+/// Flings go to the next page in their direction, other drags settle on the nearest
+/// page. Drags past the edges behave as with the parent physics.
+#[derive(Debug, Clone)]
+pub struct PageScrollPhysics {
+    parent: Rc<dyn ScrollPhysics>,
+}
+
+impl PageScrollPhysics {
+    pub fn new() -> Self {
+        PageScrollPhysics {
+            parent: Rc::new(ClampingScrollPhysics),
+        }
+    }
+
+    pub fn with_parent(mut self, parent: impl ScrollPhysics + 'static) -> Self {
+        self.parent = Rc::new(parent);
+        self
+    }
+
+    fn target_pixels(&self, metrics: &ScrollMetrics, velocity: f32) -> f32 {
+        let tolerance = self.tolerance();
+        let mut page = metrics.pixels / metrics.viewport_dimension;
+        if velocity < -tolerance.velocity {
+            page -= 0.5;
+        } else if velocity > tolerance.velocity {
+            page += 0.5;
+        }
+        (page.round() * metrics.viewport_dimension)
+            .clamp(metrics.min_scroll_extent, metrics.max_scroll_extent)
+    }
+}
+
+impl Default for PageScrollPhysics {
+    fn default() -> Self {
+        PageScrollPhysics::new()
+    }
+}
+
+impl ScrollPhysics for PageScrollPhysics {
+    fn apply_physics_to_user_offset(&self, metrics: &ScrollMetrics, offset: f32) -> f32 {
+        self.parent.apply_physics_to_user_offset(metrics, offset)
+    }
+
+    fn apply_boundary_conditions(&self, metrics: &ScrollMetrics, value: f32) -> f32 {
+        self.parent.apply_boundary_conditions(metrics, value)
+    }
+
+    fn create_ballistic_simulation(
+        &self,
+        metrics: &ScrollMetrics,
+        velocity: f32,
+    ) -> Option<Box<dyn Simulation>> {
+        if (velocity <= 0.0 && metrics.pixels <= metrics.min_scroll_extent)
+            || (velocity >= 0.0 && metrics.pixels >= metrics.max_scroll_extent)
+            || metrics.viewport_dimension == 0.0
+        {
+            return self.parent.create_ballistic_simulation(metrics, velocity);
+        }
+        let target = self.target_pixels(metrics, velocity);
+        if target == metrics.pixels {
+            return None;
+        }
+        let simulation =
+            ScrollSpringSimulation::new(self.spring(), metrics.pixels, target, velocity)
+                .with_tolerance(self.tolerance());
+        Some(Box::new(simulation))
+    }
+
+    fn spring(&self) -> SpringDescription {
+        self.parent.spring()
+    }
+
+    fn tolerance(&self) -> Tolerance {
+        self.parent.tolerance()
+    }
+}
@@ -0,0 +1,97 @@
+use crate::physics::{
+    FrictionSimulation, ScrollSpringSimulation, Simulation, SpringDescription, Tolerance,
+};
+
+/// Drag of the friction slowing down a fling, the velocity left after a second.
+pub const SCROLL_DRAG: f32 = 0.135;
+
+/// Speed a fling hands over to the spring pulling it back once it leaves the content.
+const MAX_SPRING_TRANSFER_VELOCITY: f32 = 5000.0;
+
+/// A fling slowed down by friction that springs back to `leading_extent` or
+/// `trailing_extent` once it overscrolls them, or right away when it starts beyond them.
+#[derive(Debug)]
+pub struct BouncingScrollSimulation {
+    friction: Option<FrictionSimulation>,
+    spring: Option<ScrollSpringSimulation>,
+    /// When the friction hands over to the spring.
+    spring_time: f32,
+    tolerance: Tolerance,
+}
+
+impl BouncingScrollSimulation {
+    pub fn new(
+        spring: SpringDescription,
+        position: f32,
+        velocity: f32,
+        leading_extent: f32,
+        trailing_extent: f32,
+        tolerance: Tolerance,
+    ) -> Self {
+        let spring_to = |start: f32, end: f32, velocity: f32| {
+            let velocity =
+                velocity.clamp(-MAX_SPRING_TRANSFER_VELOCITY, MAX_SPRING_TRANSFER_VELOCITY);
+            Some(
+                ScrollSpringSimulation::new(spring, start, end, velocity).with_tolerance(tolerance),
+            )
+        };
+        let (friction, spring, spring_time) = if position < leading_extent {
+            (None, spring_to(position, leading_extent, velocity), 0.0)
+        } else if position > trailing_extent {
+            (None, spring_to(position, trailing_extent, velocity), 0.0)
+        } else {
+            let friction =
+                FrictionSimulation::new(SCROLL_DRAG, position, velocity).with_tolerance(tolerance);
+            let final_x = friction.final_x();
+            let (spring, spring_time) = if velocity > 0.0 && final_x > trailing_extent {
+                let time = friction.time_at_x(trailing_extent);
+                let spring = spring_to(trailing_extent, trailing_extent, friction.dx(time));
+                (spring, time)
+            } else if velocity < 0.0 && final_x < leading_extent {
+                let time = friction.time_at_x(leading_extent);
+                let spring = spring_to(leading_extent, leading_extent, friction.dx(time));
+                (spring, time)
+            } else {
+                (None, f32::INFINITY)
+            };
+            (Some(friction), spring, spring_time)
+        };
+        BouncingScrollSimulation {
+            friction,
+            spring,
+            spring_time,
+            tolerance,
+        }
+    }
+
+    /// The simulation running at `time` and the time into it.
+    fn simulation(&self, time: f32) -> (&dyn Simulation, f32) {
+        match (&self.friction, &self.spring) {
+            (Some(friction), _) if time <= self.spring_time => (friction, time),
+            (_, Some(spring)) => (spring, time - self.spring_time),
+            (Some(friction), None) => (friction, time),
+            (None, None) => unreachable!(),
+        }
+    }
+}
+
+impl Simulation for BouncingScrollSimulation {
+    fn x(&self, time: f32) -> f32 {
+        let (simulation, time) = self.simulation(time);
+        simulation.x(time)
+    }
+
+    fn dx(&self, time: f32) -> f32 {
+        let (simulation, time) = self.simulation(time);
+        simulation.dx(time)
+    }
+
+    fn is_done(&self, time: f32) -> bool {
+        let (simulation, time) = self.simulation(time);
+        simulation.is_done(time)
+    }
+
+    fn tolerance(&self) -> Tolerance {
+        self.tolerance
+    }
+}
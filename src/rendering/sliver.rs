@@ -1,8 +1,15 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{
+    any::{type_name, TypeId},
+    cell::RefCell,
+    rc::Rc,
+};
 
 use crate::{
-    gestures::HitTestTarget,
-    rendering::{Axis, AxisDirection, BoxConstraints, RenderBox, RenderObject, ScrollDirection},
+    gestures::{HitTestEntry, HitTestResult, HitTestTarget},
+    rendering::{
+        Axis, AxisDirection, BoxConstraints, Offset, PaintContext, RenderBox, RenderObject,
+        RenderState, ScrollDirection,
+    },
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -39,6 +46,39 @@ impl SliverConstraints {
         (to.clamp(a, b) - from.clamp(a, b)).clamp(0.0, self.remaining_paint_extent)
     }
 
+    /// Offset of a box child within the painted region of a sliver, given the distance of
+    /// the leading edge of the child past the leading painted edge and its extent.
+    pub fn child_paint_offset(
+        &self,
+        paint_extent: f32,
+        main_axis_position: f32,
+        child_extent: f32,
+    ) -> Offset {
+        match self.axis_direction {
+            AxisDirection::Up => Offset::new(0.0, paint_extent - main_axis_position - child_extent),
+            AxisDirection::Right => Offset::new(main_axis_position, 0.0),
+            AxisDirection::Down => Offset::new(0.0, main_axis_position),
+            AxisDirection::Left => {
+                Offset::new(paint_extent - main_axis_position - child_extent, 0.0)
+            }
+        }
+    }
+
+    /// Whether `position`, relative to the painted region of a sliver with `geometry`,
+    /// is within the extent the sliver is hit in.
+    pub fn is_within_hit_test_extent(&self, geometry: &SliverGeometry, position: Offset) -> bool {
+        let (main_axis_position, cross_axis_position) = match self.axis_direction {
+            AxisDirection::Up => (geometry.paint_extent - position.y, position.x),
+            AxisDirection::Right => (position.x, position.y),
+            AxisDirection::Down => (position.y, position.x),
+            AxisDirection::Left => (geometry.paint_extent - position.x, position.y),
+        };
+        main_axis_position >= 0.0
+            && main_axis_position < geometry.hit_test_extent
+            && cross_axis_position >= 0.0
+            && cross_axis_position < self.cross_axis_extent
+    }
+
     pub fn calculate_cache_offset(&self, from: f32, to: f32) -> f32 {
         debug_assert!(from <= to);
         let a = self.scroll_offset + self.cache_origin;
@@ -78,11 +118,20 @@ pub struct SliverGeometry {
     pub has_visual_overflow: bool,
     pub cache_extent: f32,
     pub hit_test_extent: f32,
+    /// Asks the viewport to move its scroll offset by this amount and lay out again,
+    /// e.g. when children before the scroll offset turned out to have another extent.
+    pub scroll_offset_correction: Option<f32>,
 }
 
+/// A render object laid out by a viewport along its scroll axis.
+///
+/// Slivers are painted and hit tested at the offset of their painted region within the
+/// viewport, [`RenderObject::hit_test`] receives positions relative to it.
 pub trait RenderSliver: RenderObject {
     fn layout(&mut self, constraints: &SliverConstraints) {
-        self.perform_layout(constraints)
+        self.perform_layout(constraints);
+        self.state().clear_needs_layout();
+        self.state().mark_needs_paint();
     }
 
     fn perform_layout(&mut self, constraints: &SliverConstraints);
@@ -92,39 +141,98 @@ pub trait RenderSliver: RenderObject {
     fn geometry(&self) -> &SliverGeometry;
 }
 
+/// A sliver containing a single box child.
 #[derive(Debug)]
 pub struct RenderSliverToBoxAdapter {
-    geometry: SliverGeometry,
-    child: Rc<RefCell<dyn RenderBox>>,
+    // RenderObject
+    pub(crate) state: Rc<RenderState>,
+
+    // RenderSliver
+    pub(crate) constraints: Option<SliverConstraints>,
+    pub(crate) geometry: SliverGeometry,
+
+    // RenderSliverToBoxAdapter
+    pub(crate) child: Rc<RefCell<dyn RenderBox>>,
+}
+
+impl RenderSliverToBoxAdapter {
+    pub fn new(child: Rc<RefCell<dyn RenderBox>>) -> Self {
+        let state: Rc<RenderState> = Default::default();
+        state.adopt(child.borrow().state());
+        RenderSliverToBoxAdapter {
+            state,
+            constraints: None,
+            geometry: Default::default(),
+            child,
+        }
+    }
+
+    /// Offset of the child within the painted region of this sliver.
+    fn child_paint_offset(&self, constraints: &SliverConstraints) -> Offset {
+        let child_extent = self.child.borrow().size().main_size(constraints.axis());
+        constraints.child_paint_offset(
+            self.geometry.paint_extent,
+            -constraints.scroll_offset,
+            child_extent,
+        )
+    }
 }
 
 impl HitTestTarget for RenderSliverToBoxAdapter {}
 
 impl RenderObject for RenderSliverToBoxAdapter {
-    fn ty_id(&self) -> std::any::TypeId {
-        todo!()
+    fn ty_id(&self) -> TypeId {
+        TypeId::of::<Self>()
     }
 
     fn ty_name(&self) -> &'static str {
-        todo!()
+        type_name::<Self>()
     }
 
-    fn paint(&self, context: &mut super::PaintContext, offset: super::Offset) {
-        todo!()
+    fn state(&self) -> &Rc<RenderState> {
+        &self.state
+    }
+
+    fn paint(&self, context: &mut PaintContext, offset: Offset) {
+        match &self.constraints {
+            Some(constraints) if self.geometry.visible => {
+                let child_offset = self.child_paint_offset(constraints);
+                context.paint_child(&*self.child.borrow(), offset + child_offset);
+            }
+            _ => {}
+        }
+    }
+
+    fn hit_test(&self, position: Offset, result: &mut HitTestResult) -> bool {
+        let constraints = match &self.constraints {
+            Some(constraints) => constraints,
+            None => return false,
+        };
+        if !constraints.is_within_hit_test_extent(&self.geometry, position) {
+            return false;
+        }
+        let child_offset = self.child_paint_offset(constraints);
+        result.add_with_paint_offset(child_offset, position, |result, transformed| {
+            let is_hit = self.child.borrow().hit_test(transformed, result);
+            if is_hit {
+                result.add(HitTestEntry::new(self.child.clone()));
+            }
+            is_hit
+        })
+    }
+
+    fn visit_children(&self, visitor: &mut dyn FnMut(&Rc<RefCell<dyn RenderBox>>)) {
+        visitor(&self.child);
     }
 }
 
 impl RenderSliver for RenderSliverToBoxAdapter {
     fn perform_layout(&mut self, constraints: &SliverConstraints) {
+        self.constraints = Some(constraints.clone());
         let box_constraints = &constraints.into();
         self.child.borrow_mut().layout(box_constraints, true);
 
-        let child_size = self.child.borrow().size();
-        let child_extent = match constraints.axis() {
-            Axis::Horizontal => child_size.width,
-            Axis::Vertical => child_size.height,
-        };
-
+        let child_extent = self.child.borrow().size().main_size(constraints.axis());
         let painted_child_size = constraints.calculate_paint_offset(0.0, child_extent);
         let cache_extent = constraints.calculate_cache_offset(0.0, child_extent);
         let has_visual_overflow =
@@ -138,17 +246,18 @@ impl RenderSliver for RenderSliverToBoxAdapter {
             max_paint_extent: child_extent,
             max_scroll_obstruction_extent: 0.0,
             visible: painted_child_size > 0.0,
-            has_visual_overflow: has_visual_overflow,
-            cache_extent: cache_extent,
+            has_visual_overflow,
+            cache_extent,
             hit_test_extent: painted_child_size,
+            scroll_offset_correction: None,
         };
     }
 
     fn perform_resize(&mut self, constraints: &SliverConstraints) {
-        todo!()
+        self.perform_layout(constraints);
     }
 
     fn geometry(&self) -> &SliverGeometry {
-        todo!()
+        &self.geometry
     }
 }
@@ -1,48 +1,391 @@
 use std::{
     any::{type_name, TypeId},
     cell::RefCell,
+    collections::VecDeque,
+    fmt::{self, Debug},
     rc::Rc,
 };
 
 use crate::{
-    gestures::HitTestTarget,
+    gestures::{HitTestEntry, HitTestResult, HitTestTarget},
     rendering::{
-        Axis, AxisDirection, BoxConstraints, GrowthDirection, Offset, RenderBox, RenderObject,
-        RenderSliver, ScrollDirection, Size, SliverConstraints,
+        Axis, BoxConstraints, Offset, PaintContext, RenderBox, RenderObject, RenderSliver,
+        RenderState, SliverConstraints, SliverGeometry,
     },
 };
 
+/// Tolerance of the scroll offsets summed up from the extents of the children.
+const PRECISION_ERROR_TOLERANCE: f32 = 1e-3;
+
+/// Supplies the children of a [`RenderSliverList`] on demand.
+pub trait SliverChildDelegate: Debug {
+    /// Builds the child at `index`, `None` past the end of the list.
+    fn build(&self, index: usize) -> Option<Rc<RefCell<dyn RenderBox>>>;
+
+    /// The number of children, if known ahead of building them.
+    fn child_count(&self) -> Option<usize> {
+        None
+    }
+}
+
+/// A delegate building each child with a closure.
+pub struct SliverChildBuilderDelegate {
+    builder: Box<dyn Fn(usize) -> Option<Rc<RefCell<dyn RenderBox>>>>,
+    child_count: Option<usize>,
+}
+
+impl SliverChildBuilderDelegate {
+    pub fn new<F>(builder: F) -> Self
+    where
+        F: 'static + Fn(usize) -> Option<Rc<RefCell<dyn RenderBox>>>,
+    {
+        SliverChildBuilderDelegate {
+            builder: Box::new(builder),
+            child_count: None,
+        }
+    }
+
+    pub fn with_child_count(mut self, child_count: usize) -> Self {
+        self.child_count = Some(child_count);
+        self
+    }
+}
+
+impl Debug for SliverChildBuilderDelegate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SliverChildBuilderDelegate")
+            .field("child_count", &self.child_count)
+            .finish()
+    }
+}
+
+impl SliverChildDelegate for SliverChildBuilderDelegate {
+    fn build(&self, index: usize) -> Option<Rc<RefCell<dyn RenderBox>>> {
+        match self.child_count {
+            Some(child_count) if index >= child_count => None,
+            _ => (self.builder)(index),
+        }
+    }
+
+    fn child_count(&self) -> Option<usize> {
+        self.child_count
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SliverListChild {
+    /// Scroll offset of the leading edge of the child within the list.
+    pub layout_offset: f32,
+    pub inner: Rc<RefCell<dyn RenderBox>>,
+}
+
+/// A sliver placing box children one after the other along the scroll axis, building
+/// them lazily from a [`SliverChildDelegate`].
+///
+/// Only the children overlapping the visible and cached region are kept, the others are
+/// dropped as they scroll out and built again when they come back. With an `item_extent`
+/// every child gets that extent, so the children of any scroll offset are found without
+/// laying out those before them.
 #[derive(Debug)]
 pub struct RenderSliverList {
-    // RenderBox
-    pub(crate) size: Size,
+    // RenderObject
+    pub(crate) state: Rc<RenderState>,
+
+    // RenderSliver
+    pub(crate) constraints: Option<SliverConstraints>,
+    pub(crate) geometry: SliverGeometry,
 
     // RenderSliverList
-    pub(crate) direction: Axis,
-    pub(crate) cache_extent: f32,
-    pub(crate) children: Vec<Rc<RefCell<dyn RenderSliver>>>,
+    pub(crate) delegate: Rc<dyn SliverChildDelegate>,
+    pub(crate) item_extent: Option<f32>,
+    pub(crate) first_index: usize,
+    pub(crate) children: VecDeque<SliverListChild>,
+    /// The number of children, once the end of the list was reached.
+    pub(crate) child_count: Option<usize>,
 }
 
 impl RenderSliverList {
-    pub fn new(direction: Axis, cache_extent: f32) -> Self {
+    pub fn new(delegate: Rc<dyn SliverChildDelegate>) -> Self {
         RenderSliverList {
-            size: Size::zero(),
-            direction,
-            cache_extent,
-            children: Vec::new(),
+            state: Default::default(),
+            constraints: None,
+            geometry: Default::default(),
+            delegate,
+            item_extent: None,
+            first_index: 0,
+            children: VecDeque::new(),
+            child_count: None,
         }
     }
 
-    pub fn with_child(mut self, child: Rc<RefCell<dyn RenderSliver>>) -> Self {
-        self.children.push(child);
+    pub fn with_item_extent(mut self, item_extent: f32) -> Self {
+        self.item_extent = Some(item_extent);
         self
     }
+
+    /// Replaces the delegate, dropping the children built by the previous one.
+    pub fn set_delegate(&mut self, delegate: Rc<dyn SliverChildDelegate>) {
+        if Rc::ptr_eq(&self.delegate, &delegate) {
+            return;
+        }
+        self.delegate = delegate;
+        self.children.clear();
+        self.child_count = None;
+        self.mark_needs_layout();
+    }
+
+    pub fn set_item_extent(&mut self, item_extent: Option<f32>) {
+        if self.item_extent != item_extent {
+            self.item_extent = item_extent;
+            self.mark_needs_layout();
+        }
+    }
+
+    /// Indices of the children currently built.
+    pub fn child_indices(&self) -> std::ops::Range<usize> {
+        self.first_index..self.first_index + self.children.len()
+    }
+
+    fn build_child(&self, index: usize) -> Option<Rc<RefCell<dyn RenderBox>>> {
+        let child = self.delegate.build(index)?;
+        self.state.adopt(child.borrow().state());
+        Some(child)
+    }
+
+    /// Lays out `child` and returns its extent along `axis`.
+    fn layout_child(
+        child: &Rc<RefCell<dyn RenderBox>>,
+        constraints: &BoxConstraints,
+        axis: Axis,
+    ) -> f32 {
+        child.borrow_mut().layout(constraints, true);
+        child.borrow().size().main_size(axis)
+    }
+
+    fn child_extent(&self, child: &SliverListChild, axis: Axis) -> f32 {
+        match self.item_extent {
+            Some(item_extent) => item_extent,
+            None => child.inner.borrow().size().main_size(axis),
+        }
+    }
+
+    /// The number of children of a delegate that does not know it, found by building
+    /// children below `end`, which is past the last one.
+    fn search_child_count(&self, end: usize) -> usize {
+        let (mut low, mut high) = (0, end);
+        while low < high {
+            let middle = low + (high - low) / 2;
+            if self.delegate.build(middle).is_some() {
+                low = middle + 1;
+            } else {
+                high = middle;
+            }
+        }
+        low
+    }
+
+    /// Builds and lays out the children of `[scroll_offset, target_end_scroll_offset)` for
+    /// fixed extent children, returns the number of children if the end of the list was
+    /// reached.
+    fn layout_fixed_extent(
+        &mut self,
+        item_extent: f32,
+        constraints: &BoxConstraints,
+        scroll_offset: f32,
+        target_end_scroll_offset: f32,
+    ) -> Option<usize> {
+        let first_index = (scroll_offset / item_extent).floor() as usize;
+        let target_last_index = ((target_end_scroll_offset / item_extent).ceil() as usize)
+            .saturating_sub(1)
+            .max(first_index);
+
+        // drop the children out of the new range, keep those still in it
+        let mut kept: VecDeque<_> = std::mem::take(&mut self.children)
+            .into_iter()
+            .zip(self.first_index..)
+            .filter(|(_, index)| (first_index..=target_last_index).contains(index))
+            .map(|(child, _)| child)
+            .collect();
+        let kept_first_index = first_index.max(self.first_index);
+        let kept_indices = kept_first_index..kept_first_index + kept.len();
+
+        self.first_index = first_index;
+        for index in first_index..=target_last_index {
+            let inner = if kept_indices.contains(&index) {
+                kept.pop_front().expect("kept child").inner
+            } else {
+                match self.build_child(index) {
+                    Some(inner) => inner,
+                    None if index == first_index => {
+                        // scrolled past the end of the list
+                        return Some(self.search_child_count(index));
+                    }
+                    None => return Some(index),
+                }
+            };
+            inner.borrow_mut().layout(constraints, true);
+            self.children.push_back(SliverListChild {
+                layout_offset: index as f32 * item_extent,
+                inner,
+            });
+        }
+        None
+    }
+
+    /// Builds and lays out the children of `[scroll_offset, target_end_scroll_offset)`
+    /// starting from those kept from the previous layout. Returns the number of children
+    /// if the end of the list was reached, or the scroll offset correction needed when the
+    /// children before the scroll offset turned out to have another extent than assumed.
+    fn layout_variable_extent(
+        &mut self,
+        constraints: &BoxConstraints,
+        axis: Axis,
+        scroll_offset: f32,
+        target_end_scroll_offset: f32,
+    ) -> Result<Option<usize>, f32> {
+        if self.children.is_empty() {
+            self.first_index = 0;
+            match self.build_child(0) {
+                Some(inner) => self.children.push_back(SliverListChild {
+                    layout_offset: 0.0,
+                    inner,
+                }),
+                None => return Ok(Some(0)),
+            }
+        }
+
+        // lay out the kept children, they may have changed extent
+        let mut layout_offset = self.children[0].layout_offset;
+        for child in self.children.iter_mut() {
+            child.layout_offset = layout_offset;
+            layout_offset += RenderSliverList::layout_child(&child.inner, constraints, axis);
+        }
+
+        // build the children between the scroll offset and the first kept child, at the
+        // start of the list down to the first child
+        while self.first_index > 0
+            && (self.children[0].layout_offset > scroll_offset
+                || scroll_offset < PRECISION_ERROR_TOLERANCE)
+        {
+            let index = self.first_index - 1;
+            let inner = match self.build_child(index) {
+                Some(inner) => inner,
+                None => {
+                    // the list shrank under the kept children, start it over from its first
+                    // child and scroll back to it
+                    self.children.clear();
+                    self.child_count = None;
+                    if scroll_offset > PRECISION_ERROR_TOLERANCE {
+                        return Err(-scroll_offset);
+                    }
+                    return self.layout_variable_extent(
+                        constraints,
+                        axis,
+                        scroll_offset,
+                        target_end_scroll_offset,
+                    );
+                }
+            };
+            let extent = RenderSliverList::layout_child(&inner, constraints, axis);
+            let layout_offset = self.children[0].layout_offset - extent;
+            self.children.push_front(SliverListChild {
+                layout_offset,
+                inner,
+            });
+            self.first_index = index;
+            self.collect_trailing_garbage(target_end_scroll_offset);
+            if layout_offset < -PRECISION_ERROR_TOLERANCE {
+                // the children before turned out larger than those kept, make room for
+                // them by scrolling, once more for each child until the start is reached
+                return Err(self.shift_children(-layout_offset));
+            }
+        }
+        let first_layout_offset = self.children[0].layout_offset;
+        if self.first_index == 0 && first_layout_offset.abs() > PRECISION_ERROR_TOLERANCE {
+            // the first child has to start the list
+            return Err(self.shift_children(-first_layout_offset));
+        }
+
+        // build the children up to the target end, dropping those before the scroll offset
+        let mut child_count = None;
+        loop {
+            while self.children.len() > 1 {
+                let first = &self.children[0];
+                if first.layout_offset + self.child_extent(first, axis) > scroll_offset {
+                    break;
+                }
+                self.children.pop_front();
+                self.first_index += 1;
+            }
+            let last = self.children.back().expect("last child");
+            let end_scroll_offset = last.layout_offset + self.child_extent(last, axis);
+            if end_scroll_offset >= target_end_scroll_offset {
+                break;
+            }
+            let index = self.first_index + self.children.len();
+            match self.build_child(index) {
+                Some(inner) => {
+                    RenderSliverList::layout_child(&inner, constraints, axis);
+                    self.children.push_back(SliverListChild {
+                        layout_offset: end_scroll_offset,
+                        inner,
+                    });
+                }
+                None => {
+                    child_count = Some(index);
+                    break;
+                }
+            }
+        }
+
+        self.collect_trailing_garbage(target_end_scroll_offset);
+        Ok(child_count)
+    }
+
+    /// Moves the children by `delta` along the scroll axis, returns it as the scroll offset
+    /// correction keeping them in place on screen.
+    fn shift_children(&mut self, delta: f32) -> f32 {
+        for child in self.children.iter_mut() {
+            child.layout_offset += delta;
+        }
+        delta
+    }
+
+    /// Drops the children starting past `target_end_scroll_offset`, keeping at least one.
+    fn collect_trailing_garbage(&mut self, target_end_scroll_offset: f32) {
+        while self.children.len() > 1
+            && self.children.back().expect("last child").layout_offset >= target_end_scroll_offset
+        {
+            self.children.pop_back();
+        }
+    }
+
+    /// Total scroll extent, extrapolated from the built children when the number of
+    /// children is known but not all of them were laid out.
+    fn estimate_max_scroll_offset(&self, axis: Axis, child_count: Option<usize>) -> f32 {
+        let child_count = child_count
+            .or_else(|| self.delegate.child_count())
+            .or(self.child_count);
+        let child_count = match (self.item_extent, child_count) {
+            (Some(item_extent), Some(child_count)) => return item_extent * child_count as f32,
+            (_, Some(child_count)) => child_count,
+            (_, None) => return f32::INFINITY,
+        };
+        let (first, last) = match (self.children.front(), self.children.back()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return 0.0,
+        };
+        let end_scroll_offset = last.layout_offset + self.child_extent(last, axis);
+        let remaining = child_count.saturating_sub(self.first_index + self.children.len());
+        let average_extent = (end_scroll_offset - first.layout_offset) / self.children.len() as f32;
+        end_scroll_offset + average_extent * remaining as f32
+    }
 }
 
 impl HitTestTarget for RenderSliverList {}
 
 impl RenderObject for RenderSliverList {
-    fn ty_id(&self) -> std::any::TypeId {
+    fn ty_id(&self) -> TypeId {
         TypeId::of::<Self>()
     }
 
@@ -50,60 +393,152 @@ impl RenderObject for RenderSliverList {
         type_name::<Self>()
     }
 
-    fn paint(&self, context: &mut super::PaintContext, offset: Offset) {
-        todo!()
+    fn state(&self) -> &Rc<RenderState> {
+        &self.state
     }
-}
 
-impl RenderBox for RenderSliverList {
-    fn size(&self) -> Size {
-        self.size
+    fn paint(&self, context: &mut PaintContext, offset: Offset) {
+        let constraints = match &self.constraints {
+            Some(constraints) => constraints,
+            None => return,
+        };
+        let axis = constraints.axis();
+        for child in &self.children {
+            let main_axis_position = child.layout_offset - constraints.scroll_offset;
+            let extent = self.child_extent(child, axis);
+            if main_axis_position < constraints.remaining_paint_extent
+                && main_axis_position + extent > 0.0
+            {
+                let child_offset = constraints.child_paint_offset(
+                    self.geometry.paint_extent,
+                    main_axis_position,
+                    extent,
+                );
+                context.paint_child(&*child.inner.borrow(), offset + child_offset);
+            }
+        }
     }
 
-    fn perform_layout(&mut self, constraints: &BoxConstraints) {
-        self.size = constraints.biggest();
-        if self.size.is_empty() {
-            return;
+    fn hit_test(&self, position: Offset, result: &mut HitTestResult) -> bool {
+        let constraints = match &self.constraints {
+            Some(constraints) => constraints,
+            None => return false,
+        };
+        let axis = constraints.axis();
+        if !constraints.is_within_hit_test_extent(&self.geometry, position) {
+            return false;
+        }
+        for child in self.children.iter().rev() {
+            let main_axis_position = child.layout_offset - constraints.scroll_offset;
+            let child_offset = constraints.child_paint_offset(
+                self.geometry.paint_extent,
+                main_axis_position,
+                self.child_extent(child, axis),
+            );
+            let is_hit =
+                result.add_with_paint_offset(child_offset, position, |result, transformed| {
+                    let is_child_hit = child.inner.borrow().hit_test(transformed, result);
+                    if is_child_hit {
+                        result.add(HitTestEntry::new(child.inner.clone()));
+                    }
+                    is_child_hit
+                });
+            if is_hit {
+                return true;
+            }
         }
+        false
+    }
 
-        // if first layout
-        let sliver_constraints = match self.direction {
-            Axis::Horizontal => SliverConstraints {
-                axis_direction: AxisDirection::Left,
-                growth_direction: GrowthDirection::Forward,
-                scroll_direction: ScrollDirection::Idle,
-                scroll_offset: 0.0,
-                preceding_scroll_extent: 0.0,
-                overlap: 0.0,
-                remaining_paint_extent: self.size.width,
-                cross_axis_extent: self.size.height,
-                cross_axis_direction: AxisDirection::Down,
-                viewport_main_axis_extent: self.size.width,
-                remaining_cache_extent: self.cache_extent,
-                cache_origin: 0.0,
-            },
-            Axis::Vertical => SliverConstraints {
-                axis_direction: AxisDirection::Down,
-                growth_direction: GrowthDirection::Forward,
-                scroll_direction: ScrollDirection::Idle,
-                scroll_offset: 0.0,
-                preceding_scroll_extent: 0.0,
-                overlap: 0.0,
-                remaining_paint_extent: self.size.height,
-                cross_axis_extent: self.size.width,
-                cross_axis_direction: AxisDirection::Right,
-                viewport_main_axis_extent: self.size.height,
-                remaining_cache_extent: self.cache_extent,
-                cache_origin: 0.0,
-            },
+    fn visit_children(&self, visitor: &mut dyn FnMut(&Rc<RefCell<dyn RenderBox>>)) {
+        for child in &self.children {
+            visitor(&child.inner);
+        }
+    }
+}
+
+impl RenderSliver for RenderSliverList {
+    fn perform_layout(&mut self, constraints: &SliverConstraints) {
+        self.constraints = Some(constraints.clone());
+        let axis = constraints.axis();
+        let scroll_offset = constraints.scroll_offset + constraints.cache_origin;
+        let target_end_scroll_offset = scroll_offset + constraints.remaining_cache_extent;
+
+        let child_count = match self.item_extent {
+            Some(item_extent) => {
+                let child_constraints = match axis {
+                    Axis::Horizontal => {
+                        BoxConstraints::tight((item_extent, constraints.cross_axis_extent))
+                    }
+                    Axis::Vertical => {
+                        BoxConstraints::tight((constraints.cross_axis_extent, item_extent))
+                    }
+                };
+                self.layout_fixed_extent(
+                    item_extent,
+                    &child_constraints,
+                    scroll_offset,
+                    target_end_scroll_offset,
+                )
+            }
+            None => {
+                let child_constraints = constraints.into();
+                match self.layout_variable_extent(
+                    &child_constraints,
+                    axis,
+                    scroll_offset,
+                    target_end_scroll_offset,
+                ) {
+                    Ok(child_count) => child_count,
+                    Err(correction) => {
+                        self.geometry = SliverGeometry {
+                            scroll_offset_correction: Some(correction),
+                            ..Default::default()
+                        };
+                        return;
+                    }
+                }
+            }
         };
 
-        for child in self.children.iter_mut() {
-            child.borrow_mut().layout(&sliver_constraints);
+        let (leading_scroll_offset, trailing_scroll_offset) =
+            match (self.children.front(), self.children.back()) {
+                (Some(first), Some(last)) => (
+                    first.layout_offset,
+                    last.layout_offset + self.child_extent(last, axis),
+                ),
+                _ => (0.0, 0.0),
+            };
+        if child_count.is_some() {
+            self.child_count = child_count;
         }
+        let estimated_max_scroll_offset = self.estimate_max_scroll_offset(axis, child_count);
+        let paint_extent =
+            constraints.calculate_paint_offset(leading_scroll_offset, trailing_scroll_offset);
+        let cache_extent =
+            constraints.calculate_cache_offset(leading_scroll_offset, trailing_scroll_offset);
+        let target_end_scroll_offset_for_paint =
+            constraints.scroll_offset + constraints.remaining_paint_extent;
+
+        self.geometry = SliverGeometry {
+            scroll_extent: estimated_max_scroll_offset,
+            paint_extent,
+            layout_extent: paint_extent,
+            max_paint_extent: estimated_max_scroll_offset,
+            visible: paint_extent > 0.0,
+            has_visual_overflow: trailing_scroll_offset > target_end_scroll_offset_for_paint
+                || constraints.scroll_offset > 0.0,
+            cache_extent,
+            hit_test_extent: paint_extent,
+            ..Default::default()
+        };
+    }
+
+    fn perform_resize(&mut self, constraints: &SliverConstraints) {
+        self.perform_layout(constraints);
     }
 
-    fn perform_resize(&mut self, constraints: &BoxConstraints) {
-        todo!()
+    fn geometry(&self) -> &SliverGeometry {
+        &self.geometry
     }
 }
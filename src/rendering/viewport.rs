@@ -0,0 +1,380 @@
+use std::{
+    any::{type_name, TypeId},
+    cell::RefCell,
+    rc::Rc,
+};
+
+use crate::{
+    gestures::{
+        DragCallbacks, DragGestureRecognizer, GestureRecognizer, HitTestEntry, HitTestResult,
+        HitTestTarget, PointerEvent, PointerEventKind,
+    },
+    rendering::{
+        Axis, AxisDirection, BoxConstraints, Clip, GrowthDirection, Offset, PaintContext,
+        RenderBox, RenderObject, RenderSliver, RenderState, Size, SliverConstraints,
+        SliverGeometry, ViewportOffset,
+    },
+};
+
+/// Extent painted ahead of and behind the visible region by default, in pixels.
+pub const DEFAULT_CACHE_EXTENT: f32 = 250.0;
+
+/// Layout attempts after which the viewport gives up on scroll offset corrections.
+const MAX_LAYOUT_CYCLES: usize = 10;
+
+/// A box showing a window onto its sliver children, scrolled by a [`ViewportOffset`].
+///
+/// The slivers are laid out one after the other along the axis direction and receive
+/// the part of the scroll offset, visible region and cache extent that is left after
+/// the slivers before them. Dragging along the axis and scrolling the mouse wheel over
+/// the viewport moves the offset, flinging it lets the offset move on by itself.
+#[derive(Debug)]
+pub struct RenderViewport {
+    // RenderObject
+    pub(crate) state: Rc<RenderState>,
+
+    // RenderBox
+    pub(crate) size: Size,
+
+    // RenderViewport
+    pub(crate) axis_direction: AxisDirection,
+    pub(crate) cross_axis_direction: AxisDirection,
+    pub(crate) offset: Rc<dyn ViewportOffset>,
+    pub(crate) cache_extent: f32,
+    pub(crate) clip_behavior: Clip,
+    pub(crate) children: Vec<Rc<RefCell<dyn RenderSliver>>>,
+    pub(crate) paint_offsets: Vec<Offset>,
+    pub(crate) max_scroll_extent: f32,
+    pub(crate) has_visual_overflow: bool,
+    pub(crate) drag: Rc<DragGestureRecognizer>,
+}
+
+impl RenderViewport {
+    pub fn new(axis_direction: AxisDirection, offset: Rc<dyn ViewportOffset>) -> Self {
+        let state: Rc<RenderState> = Default::default();
+        offset.attach(&state);
+        let cross_axis_direction = match Axis::from(axis_direction) {
+            Axis::Horizontal => AxisDirection::Down,
+            Axis::Vertical => AxisDirection::Right,
+        };
+        RenderViewport {
+            state,
+            size: Size::zero(),
+            axis_direction,
+            cross_axis_direction,
+            drag: RenderViewport::drag_recognizer(axis_direction, &offset),
+            offset,
+            cache_extent: DEFAULT_CACHE_EXTENT,
+            clip_behavior: Clip::HardEdge,
+            children: Vec::new(),
+            paint_offsets: Vec::new(),
+            max_scroll_extent: 0.0,
+            has_visual_overflow: false,
+        }
+    }
+
+    pub fn with_cache_extent(mut self, cache_extent: f32) -> Self {
+        self.cache_extent = cache_extent;
+        self
+    }
+
+    pub fn with_clip_behavior(mut self, clip_behavior: Clip) -> Self {
+        self.clip_behavior = clip_behavior;
+        self
+    }
+
+    pub fn with_child(mut self, child: Rc<RefCell<dyn RenderSliver>>) -> Self {
+        self.state.adopt(child.borrow().state());
+        self.children.push(child);
+        self
+    }
+
+    pub fn offset(&self) -> &Rc<dyn ViewportOffset> {
+        &self.offset
+    }
+
+    pub fn set_offset(&mut self, offset: Rc<dyn ViewportOffset>) {
+        if Rc::ptr_eq(&self.offset, &offset) {
+            return;
+        }
+        offset.attach(&self.state);
+        self.drag = RenderViewport::drag_recognizer(self.axis_direction, &offset);
+        self.offset = offset;
+        self.mark_needs_layout();
+    }
+
+    pub fn set_axis_direction(&mut self, axis_direction: AxisDirection) {
+        if self.axis_direction == axis_direction {
+            return;
+        }
+        self.axis_direction = axis_direction;
+        self.drag = RenderViewport::drag_recognizer(axis_direction, &self.offset);
+        self.mark_needs_layout();
+    }
+
+    pub fn set_cache_extent(&mut self, cache_extent: f32) {
+        if self.cache_extent != cache_extent {
+            self.cache_extent = cache_extent;
+            self.mark_needs_layout();
+        }
+    }
+
+    /// Extent of the content beyond the viewport, as of the last layout.
+    pub fn max_scroll_extent(&self) -> f32 {
+        self.max_scroll_extent
+    }
+
+    fn drag_recognizer(
+        axis_direction: AxisDirection,
+        offset: &Rc<dyn ViewportOffset>,
+    ) -> Rc<DragGestureRecognizer> {
+        let sign = if axis_direction.is_reversed() {
+            -1.0
+        } else {
+            1.0
+        };
+        let (on_down, on_update, on_end, on_cancel) = (
+            offset.clone(),
+            offset.clone(),
+            offset.clone(),
+            offset.clone(),
+        );
+        let callbacks = DragCallbacks::default()
+            .with_on_down(move |_| on_down.hold())
+            .with_on_update(move |details| {
+                if let Some(delta) = details.primary_delta {
+                    on_update.apply_user_offset(sign * delta);
+                }
+            })
+            .with_on_end(move |details| {
+                let velocity = details.primary_velocity.unwrap_or(0.0);
+                // the scroll offset moves against the content
+                on_end.clone().go_ballistic(-sign * velocity);
+            })
+            .with_on_cancel(move |_| on_cancel.clone().go_ballistic(0.0));
+        Rc::new(DragGestureRecognizer::new(
+            Axis::from(axis_direction),
+            callbacks,
+        ))
+    }
+
+    fn axis(&self) -> Axis {
+        self.axis_direction.into()
+    }
+
+    /// Position of the painted region of a sliver laid out at `layout_offset`.
+    fn paint_offset(&self, layout_offset: f32, geometry: &SliverGeometry) -> Offset {
+        match self.axis_direction {
+            AxisDirection::Up => Offset::new(
+                0.0,
+                self.size.height - layout_offset - geometry.paint_extent,
+            ),
+            AxisDirection::Right => Offset::new(layout_offset, 0.0),
+            AxisDirection::Down => Offset::new(0.0, layout_offset),
+            AxisDirection::Left => {
+                Offset::new(self.size.width - layout_offset - geometry.paint_extent, 0.0)
+            }
+        }
+    }
+
+    /// Lays out the slivers for the scroll offset `pixels`, returns the scroll offset
+    /// correction asked for by a sliver, zero if none.
+    fn attempt_layout(
+        &mut self,
+        main_axis_extent: f32,
+        cross_axis_extent: f32,
+        pixels: f32,
+    ) -> f32 {
+        self.max_scroll_extent = 0.0;
+        self.has_visual_overflow = false;
+        self.paint_offsets.clear();
+
+        let center_offset = -pixels;
+        let full_cache_extent = main_axis_extent + 2.0 * self.cache_extent;
+        let mut scroll_offset = pixels.max(0.0);
+        let mut layout_offset = center_offset.max(0.0);
+        let initial_layout_offset = layout_offset;
+        let mut max_paint_offset = layout_offset + (-center_offset).min(0.0);
+        let remaining_paint_extent =
+            (main_axis_extent - center_offset).clamp(0.0, main_axis_extent);
+        let mut remaining_cache_extent =
+            (full_cache_extent - (center_offset + self.cache_extent)).clamp(0.0, full_cache_extent);
+        let mut cache_origin = center_offset.clamp(-self.cache_extent, 0.0);
+        let mut preceding_scroll_extent = 0.0;
+        let scroll_direction = self.offset.user_scroll_direction();
+
+        for child in self.children.clone() {
+            let sliver_scroll_offset = scroll_offset.max(0.0);
+            let corrected_cache_origin = cache_origin.max(-sliver_scroll_offset);
+            let cache_extent_correction = cache_origin - corrected_cache_origin;
+
+            let constraints = SliverConstraints {
+                axis_direction: self.axis_direction,
+                growth_direction: GrowthDirection::Forward,
+                scroll_direction,
+                scroll_offset: sliver_scroll_offset,
+                preceding_scroll_extent,
+                overlap: max_paint_offset - layout_offset,
+                remaining_paint_extent: (remaining_paint_extent - layout_offset
+                    + initial_layout_offset)
+                    .max(0.0),
+                cross_axis_extent,
+                cross_axis_direction: self.cross_axis_direction,
+                viewport_main_axis_extent: main_axis_extent,
+                remaining_cache_extent: (remaining_cache_extent + cache_extent_correction).max(0.0),
+                cache_origin: corrected_cache_origin,
+            };
+            child.borrow_mut().layout(&constraints);
+            let geometry = child.borrow().geometry().clone();
+            if let Some(correction) = geometry.scroll_offset_correction {
+                return correction;
+            }
+
+            let effective_layout_offset = layout_offset + geometry.paint_origin;
+            let child_layout_offset = if geometry.visible || scroll_offset > 0.0 {
+                effective_layout_offset
+            } else {
+                -scroll_offset + initial_layout_offset
+            };
+            self.paint_offsets
+                .push(self.paint_offset(child_layout_offset, &geometry));
+
+            max_paint_offset =
+                (effective_layout_offset + geometry.paint_extent).max(max_paint_offset);
+            scroll_offset -= geometry.scroll_extent;
+            preceding_scroll_extent += geometry.scroll_extent;
+            layout_offset += geometry.layout_extent;
+            if geometry.cache_extent != 0.0 {
+                remaining_cache_extent -= geometry.cache_extent - cache_extent_correction;
+                cache_origin = (corrected_cache_origin + geometry.cache_extent).min(0.0);
+            }
+
+            self.max_scroll_extent += geometry.scroll_extent;
+            self.has_visual_overflow |= geometry.has_visual_overflow;
+        }
+        0.0
+    }
+
+    fn paint_contents(&self, context: &mut PaintContext, offset: Offset) {
+        // earlier slivers paint on top of later ones, e.g. pinned headers
+        for (child, paint_offset) in self.children.iter().zip(&self.paint_offsets).rev() {
+            let child = child.borrow();
+            if child.geometry().visible {
+                context.paint_child(&*child, offset + *paint_offset);
+            }
+        }
+    }
+}
+
+impl HitTestTarget for RenderViewport {
+    fn handle_event(&mut self, event: PointerEvent, entry: HitTestEntry) {
+        if let PointerEventKind::Scroll { scroll_delta } = event.kind {
+            let delta = match self.axis_direction {
+                AxisDirection::Up => -scroll_delta.y,
+                AxisDirection::Right => scroll_delta.x,
+                AxisDirection::Down => scroll_delta.y,
+                AxisDirection::Left => -scroll_delta.x,
+            };
+            self.offset.apply_user_offset(-delta);
+            self.offset.clone().go_ballistic(0.0);
+        }
+    }
+
+    fn gesture_recognizers(&self) -> Vec<Rc<dyn GestureRecognizer>> {
+        vec![self.drag.clone()]
+    }
+}
+
+impl RenderObject for RenderViewport {
+    fn ty_id(&self) -> TypeId {
+        TypeId::of::<Self>()
+    }
+
+    fn ty_name(&self) -> &'static str {
+        type_name::<Self>()
+    }
+
+    fn state(&self) -> &Rc<RenderState> {
+        &self.state
+    }
+
+    fn paint(&self, context: &mut PaintContext, offset: Offset) {
+        if self.children.is_empty() {
+            return;
+        }
+        if self.has_visual_overflow {
+            context.push_clip_rect(offset, self.size, self.clip_behavior, |context| {
+                self.paint_contents(context, offset)
+            });
+        } else {
+            self.paint_contents(context, offset);
+        }
+    }
+
+    fn hit_test(&self, position: Offset, result: &mut HitTestResult) -> bool {
+        if !self.size.contains(position) {
+            return false;
+        }
+        for (child, paint_offset) in self.children.iter().zip(&self.paint_offsets) {
+            let child = child.borrow();
+            if child.geometry().visible
+                && result.add_with_paint_offset(*paint_offset, position, |result, transformed| {
+                    child.hit_test(transformed, result)
+                })
+            {
+                break;
+            }
+        }
+        // the viewport scrolls wherever it is hit, not only over its slivers
+        true
+    }
+
+    fn visit_slivers(&self, visitor: &mut dyn FnMut(&Rc<RefCell<dyn RenderSliver>>)) {
+        for child in &self.children {
+            visitor(child);
+        }
+    }
+}
+
+impl RenderBox for RenderViewport {
+    fn size(&self) -> Size {
+        self.size
+    }
+
+    fn flush_layout(&mut self) {
+        // slivers are not relayout boundaries, so any dirty descendant relays out the viewport
+        let state = self.state.clone();
+        if state.needs_layout() || state.take_child_needs_layout() {
+            if let Some(constraints) = state.constraints() {
+                self.perform_layout(&constraints);
+                state.clear_needs_layout();
+                state.mark_needs_paint();
+            }
+        }
+    }
+
+    fn perform_layout(&mut self, constraints: &BoxConstraints) {
+        self.size = constraints.biggest();
+        let main_axis_extent = self.size.main_size(self.axis());
+        let cross_axis_extent = self.size.cross_size(self.axis());
+        self.offset.apply_viewport_dimension(main_axis_extent);
+
+        for _ in 0..MAX_LAYOUT_CYCLES {
+            let correction =
+                self.attempt_layout(main_axis_extent, cross_axis_extent, self.offset.pixels());
+            if correction != 0.0 {
+                self.offset.correct_by(correction);
+            } else if self
+                .offset
+                .apply_content_dimensions(0.0, (self.max_scroll_extent - main_axis_extent).max(0.0))
+            {
+                return;
+            }
+        }
+        // gave up on corrections, the last attempt stands
+    }
+
+    fn perform_resize(&mut self, constraints: &BoxConstraints) {
+        self.size = constraints.biggest();
+    }
+}
@@ -0,0 +1,318 @@
+use std::{
+    cell::{Cell, RefCell},
+    fmt::Debug,
+    rc::{Rc, Weak},
+    time::Duration,
+};
+
+use crate::{
+    physics::Simulation,
+    rendering::{
+        ClampingScrollPhysics, RenderState, ScrollDirection, ScrollMetrics, ScrollPhysics,
+    },
+    scheduler::{SchedulerBinding, Ticker, TickerCallback},
+};
+
+/// How far the content of a viewport is scrolled.
+///
+/// The viewport reports its dimensions during layout, the offset may answer by moving,
+/// e.g. to stay within the content, which makes the viewport lay out again.
+pub trait ViewportOffset: Debug {
+    /// Pixels the content is scrolled by along the axis direction of the viewport.
+    fn pixels(&self) -> f32;
+
+    /// The direction the user is scrolling the content in.
+    fn user_scroll_direction(&self) -> ScrollDirection {
+        ScrollDirection::Idle
+    }
+
+    /// Relayouts the viewport owning `state` whenever the offset moves.
+    fn attach(&self, state: &Rc<RenderState>) {}
+
+    /// Called during layout with the extent of the viewport along its main axis.
+    fn apply_viewport_dimension(&self, viewport_dimension: f32) {}
+
+    /// Called during layout with the range the content can be scrolled in, returns
+    /// `false` if the offset moved in response and the viewport has to lay out again.
+    fn apply_content_dimensions(&self, min_scroll_extent: f32, max_scroll_extent: f32) -> bool {
+        true
+    }
+
+    /// Moves the offset during layout without relayouting the viewport, used when a
+    /// sliver asks for a scroll offset correction.
+    fn correct_by(&self, correction: f32);
+
+    /// Moves the offset to `pixels`.
+    fn jump_to(&self, pixels: f32);
+
+    /// Moves the content by `delta` along the axis direction on behalf of the user,
+    /// i.e. scrolls by `-delta` pixels. Ignored by offsets the user cannot scroll.
+    fn apply_user_offset(&self, delta: f32) {}
+
+    /// Stops the offset where it is, e.g. when the user touches the content during a
+    /// fling.
+    fn hold(&self) {}
+
+    /// Lets the offset move on by itself after the user stopped scrolling, `velocity`
+    /// being the speed of `pixels` in pixels per second, zero unless the content was
+    /// flung.
+    fn go_ballistic(self: Rc<Self>, velocity: f32) {}
+}
+
+/// An offset the user cannot scroll.
+#[derive(Debug, Default)]
+pub struct FixedViewportOffset {
+    pixels: Cell<f32>,
+}
+
+impl FixedViewportOffset {
+    pub fn new(pixels: f32) -> Self {
+        FixedViewportOffset {
+            pixels: Cell::new(pixels),
+        }
+    }
+
+    pub fn zero() -> Self {
+        FixedViewportOffset::new(0.0)
+    }
+}
+
+impl ViewportOffset for FixedViewportOffset {
+    fn pixels(&self) -> f32 {
+        self.pixels.get()
+    }
+
+    fn correct_by(&self, correction: f32) {
+        self.pixels.set(self.pixels.get() + correction);
+    }
+
+    fn jump_to(&self, pixels: f32) {}
+}
+
+/// The offset of a scrollable viewport, moved by the user within the limits of its
+/// [`ScrollPhysics`], [`ClampingScrollPhysics`] by default.
+///
+/// Once the user lets go, the offset moves on as the physics simulate it with the frames
+/// of a scheduler, the one of the frame the position is created in unless given one.
+/// Without a scheduler it stops right away, within the content.
+#[derive(Debug)]
+pub struct ScrollPosition {
+    pixels: Cell<f32>,
+    min_scroll_extent: Cell<Option<f32>>,
+    max_scroll_extent: Cell<Option<f32>>,
+    viewport_dimension: Cell<Option<f32>>,
+    user_scroll_direction: Cell<ScrollDirection>,
+    viewports: RefCell<Vec<Weak<RenderState>>>,
+    physics: Rc<dyn ScrollPhysics>,
+    scheduler: Option<Rc<SchedulerBinding>>,
+    ballistic: RefCell<Option<Rc<Ticker>>>,
+}
+
+impl ScrollPosition {
+    pub fn new(initial_pixels: f32) -> Self {
+        ScrollPosition {
+            pixels: Cell::new(initial_pixels),
+            min_scroll_extent: Cell::new(None),
+            max_scroll_extent: Cell::new(None),
+            viewport_dimension: Cell::new(None),
+            user_scroll_direction: Cell::new(ScrollDirection::Idle),
+            viewports: RefCell::new(Vec::new()),
+            physics: Rc::new(ClampingScrollPhysics),
+            scheduler: SchedulerBinding::current(),
+            ballistic: RefCell::new(None),
+        }
+    }
+
+    pub fn with_physics(mut self, physics: impl ScrollPhysics + 'static) -> Self {
+        self.physics = Rc::new(physics);
+        self
+    }
+
+    pub fn with_scheduler(mut self, scheduler: &Rc<SchedulerBinding>) -> Self {
+        self.scheduler = Some(scheduler.clone());
+        self
+    }
+
+    pub fn physics(&self) -> &Rc<dyn ScrollPhysics> {
+        &self.physics
+    }
+
+    pub fn min_scroll_extent(&self) -> Option<f32> {
+        self.min_scroll_extent.get()
+    }
+
+    pub fn max_scroll_extent(&self) -> Option<f32> {
+        self.max_scroll_extent.get()
+    }
+
+    pub fn viewport_dimension(&self) -> Option<f32> {
+        self.viewport_dimension.get()
+    }
+
+    /// The metrics of the position, once a viewport reported its dimensions.
+    pub fn metrics(&self) -> Option<ScrollMetrics> {
+        Some(ScrollMetrics {
+            pixels: self.pixels.get(),
+            min_scroll_extent: self.min_scroll_extent.get()?,
+            max_scroll_extent: self.max_scroll_extent.get()?,
+            viewport_dimension: self.viewport_dimension.get()?,
+        })
+    }
+
+    /// Whether the user is scrolling, or the offset moves on after they let go.
+    pub fn is_scrolling(&self) -> bool {
+        self.user_scroll_direction.get() != ScrollDirection::Idle
+            || self.ballistic.borrow().is_some()
+    }
+
+    /// `pixels` clamped to the scrollable range, if known.
+    fn clamp(&self, pixels: f32) -> f32 {
+        match (self.min_scroll_extent.get(), self.max_scroll_extent.get()) {
+            (Some(min), Some(max)) => pixels.clamp(min, max),
+            _ => pixels,
+        }
+    }
+
+    /// Moves to `pixels` as far as the physics allow, returns the overscroll they did not.
+    fn set_pixels(&self, pixels: f32) -> f32 {
+        if pixels == self.pixels.get() {
+            return 0.0;
+        }
+        let overscroll = self.metrics().map_or(0.0, |metrics| {
+            self.physics.apply_boundary_conditions(&metrics, pixels)
+        });
+        self.force_pixels(pixels - overscroll);
+        overscroll
+    }
+
+    fn force_pixels(&self, pixels: f32) {
+        if self.pixels.replace(pixels) == pixels {
+            return;
+        }
+        self.viewports.borrow_mut().retain(|viewport| {
+            if let Some(viewport) = viewport.upgrade() {
+                viewport.mark_needs_layout();
+                true
+            } else {
+                false
+            }
+        });
+    }
+
+    fn tick_ballistic(&self, simulation: &dyn Simulation, elapsed: Duration) {
+        let time = elapsed.as_secs_f32();
+        let previous = self.pixels.get();
+        let overscroll = self.set_pixels(simulation.x(time));
+        let pixels = self.pixels.get();
+        if pixels != previous {
+            self.user_scroll_direction.set(if pixels < previous {
+                ScrollDirection::Forward
+            } else {
+                ScrollDirection::Reverse
+            });
+        }
+        if overscroll != 0.0 || simulation.is_done(time) {
+            self.go_idle();
+        }
+    }
+
+    fn go_idle(&self) {
+        if let Some(ticker) = self.ballistic.take() {
+            ticker.stop();
+        }
+        self.user_scroll_direction.set(ScrollDirection::Idle);
+    }
+}
+
+impl ViewportOffset for ScrollPosition {
+    fn pixels(&self) -> f32 {
+        self.pixels.get()
+    }
+
+    fn user_scroll_direction(&self) -> ScrollDirection {
+        self.user_scroll_direction.get()
+    }
+
+    fn attach(&self, state: &Rc<RenderState>) {
+        self.viewports.borrow_mut().push(Rc::downgrade(state));
+    }
+
+    fn apply_viewport_dimension(&self, viewport_dimension: f32) {
+        self.viewport_dimension.set(Some(viewport_dimension));
+    }
+
+    fn apply_content_dimensions(&self, min_scroll_extent: f32, max_scroll_extent: f32) -> bool {
+        self.min_scroll_extent.set(Some(min_scroll_extent));
+        self.max_scroll_extent.set(Some(max_scroll_extent));
+        let pixels = self.pixels.get();
+        let clamped = self.clamp(pixels);
+        // overscrolling while the user scrolls is up to the physics
+        if clamped != pixels && !self.is_scrolling() {
+            // content shrunk below the offset, the viewport is in layout already
+            self.pixels.set(clamped);
+            return false;
+        }
+        true
+    }
+
+    fn correct_by(&self, correction: f32) {
+        self.pixels.set(self.pixels.get() + correction);
+    }
+
+    fn jump_to(&self, pixels: f32) {
+        self.go_idle();
+        self.force_pixels(pixels);
+    }
+
+    fn apply_user_offset(&self, delta: f32) {
+        if delta == 0.0 {
+            return;
+        }
+        if let Some(ticker) = self.ballistic.take() {
+            ticker.stop();
+        }
+        self.user_scroll_direction.set(if delta > 0.0 {
+            ScrollDirection::Forward
+        } else {
+            ScrollDirection::Reverse
+        });
+        let delta = self.metrics().map_or(delta, |metrics| {
+            self.physics.apply_physics_to_user_offset(&metrics, delta)
+        });
+        self.set_pixels(self.pixels.get() - delta);
+    }
+
+    fn hold(&self) {
+        self.go_idle();
+    }
+
+    fn go_ballistic(self: Rc<Self>, velocity: f32) {
+        self.go_idle();
+        let simulation = self
+            .metrics()
+            .and_then(|metrics| self.physics.create_ballistic_simulation(&metrics, velocity));
+        match (simulation, &self.scheduler) {
+            (Some(simulation), Some(scheduler)) => {
+                let simulation_direction = simulation.dx(0.0);
+                let this = Rc::downgrade(&self);
+                let ticker = Ticker::new(
+                    scheduler,
+                    TickerCallback::new(move |elapsed| {
+                        if let Some(position) = this.upgrade() {
+                            position.tick_ballistic(&*simulation, elapsed);
+                        }
+                    }),
+                );
+                self.user_scroll_direction.set(match simulation_direction {
+                    d if d < 0.0 => ScrollDirection::Forward,
+                    d if d > 0.0 => ScrollDirection::Reverse,
+                    _ => ScrollDirection::Idle,
+                });
+                ticker.start();
+                self.ballistic.replace(Some(ticker));
+            }
+            (Some(_), None) => self.force_pixels(self.clamp(self.pixels.get())),
+            (None, _) => {}
+        }
+    }
+}
@@ -0,0 +1,199 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::{BTreeMap, HashSet},
+    fmt,
+    rc::Rc,
+    time::Duration,
+};
+
+use crate::scheduler::{Clock, SystemClock};
+
+thread_local! {
+    static CURRENT: RefCell<Option<Rc<SchedulerBinding>>> = const { RefCell::new(None) };
+}
+
+/// Called with the time the frame began at.
+#[derive(Clone)]
+pub struct FrameCallback(Rc<dyn Fn(Duration)>);
+
+impl FrameCallback {
+    pub fn new<F>(f: F) -> Self
+    where
+        F: 'static + Fn(Duration),
+    {
+        FrameCallback(Rc::new(f))
+    }
+
+    pub fn call(&self, timestamp: Duration) {
+        (self.0)(timestamp)
+    }
+}
+
+impl fmt::Debug for FrameCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("FrameCallback")
+    }
+}
+
+/// What the scheduler is running of a frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchedulerPhase {
+    /// Between frames.
+    Idle,
+    /// Running the transient callbacks, e.g. ticking the animations.
+    TransientCallbacks,
+    /// Building, laying out and painting the frame, and running the persistent callbacks.
+    PersistentCallbacks,
+    /// Running the callbacks of the frame just drawn.
+    PostFrameCallbacks,
+}
+
+/// Schedules frames and runs the callbacks of each, in three phases:
+///
+/// - the transient callbacks, e.g. of tickers, asked for the next frame only,
+/// - the persistent callbacks, run with every frame after it is built,
+/// - the post-frame callbacks, run once after the next frame.
+///
+/// The platform, or a test, begins a frame with [`SchedulerBinding::handle_begin_frame`]
+/// when one is scheduled, draws it, and ends it with
+/// [`SchedulerBinding::handle_draw_frame`]. Frames begin at the time of the clock.
+#[derive(Debug)]
+pub struct SchedulerBinding {
+    clock: Rc<dyn Clock>,
+    phase: Cell<SchedulerPhase>,
+    has_scheduled_frame: Cell<bool>,
+    current_frame_timestamp: Cell<Option<Duration>>,
+    next_callback_id: Cell<usize>,
+    transient_callbacks: RefCell<BTreeMap<usize, FrameCallback>>,
+    /// The transient callbacks canceled while the callbacks of the frame run.
+    removed_ids: RefCell<HashSet<usize>>,
+    persistent_callbacks: RefCell<Vec<FrameCallback>>,
+    post_frame_callbacks: RefCell<Vec<FrameCallback>>,
+}
+
+impl SchedulerBinding {
+    pub fn new(clock: Rc<dyn Clock>) -> Rc<Self> {
+        Rc::new(SchedulerBinding {
+            clock,
+            phase: Cell::new(SchedulerPhase::Idle),
+            has_scheduled_frame: Cell::new(false),
+            current_frame_timestamp: Cell::new(None),
+            next_callback_id: Cell::new(0),
+            transient_callbacks: RefCell::new(BTreeMap::new()),
+            removed_ids: RefCell::new(HashSet::new()),
+            persistent_callbacks: RefCell::new(Vec::new()),
+            post_frame_callbacks: RefCell::new(Vec::new()),
+        })
+    }
+
+    /// A scheduler on the clock of the system.
+    pub fn system() -> Rc<Self> {
+        SchedulerBinding::new(Rc::new(SystemClock::new()))
+    }
+
+    /// The scheduler of the frame being built, for the widgets that animate. `None`
+    /// outside of [`SchedulerBinding::scope`].
+    pub fn current() -> Option<Rc<SchedulerBinding>> {
+        CURRENT.with(|current| current.borrow().clone())
+    }
+
+    /// Makes this the current scheduler while `f` runs, e.g. while a frame is built.
+    pub fn scope<R>(self: &Rc<Self>, f: impl FnOnce() -> R) -> R {
+        let previous = CURRENT.with(|current| current.replace(Some(self.clone())));
+        let result = f();
+        CURRENT.with(|current| *current.borrow_mut() = previous);
+        result
+    }
+
+    pub fn clock(&self) -> &Rc<dyn Clock> {
+        &self.clock
+    }
+
+    pub fn phase(&self) -> SchedulerPhase {
+        self.phase.get()
+    }
+
+    /// Whether a frame is to be drawn, the platform asks before drawing one.
+    pub fn has_scheduled_frame(&self) -> bool {
+        self.has_scheduled_frame.get()
+    }
+
+    pub fn schedule_frame(&self) {
+        self.has_scheduled_frame.set(true);
+    }
+
+    /// The time the current frame began at, `None` between frames.
+    pub fn current_frame_timestamp(&self) -> Option<Duration> {
+        self.current_frame_timestamp.get()
+    }
+
+    /// Schedules a frame and calls `callback` when it begins, returns the id to cancel it
+    /// with. A callback scheduled while the transient callbacks run waits for the next
+    /// frame.
+    pub fn schedule_frame_callback(&self, callback: FrameCallback) -> usize {
+        self.schedule_frame();
+        let id = self.next_callback_id.get() + 1;
+        self.next_callback_id.set(id);
+        self.transient_callbacks.borrow_mut().insert(id, callback);
+        id
+    }
+
+    pub fn cancel_frame_callback_with_id(&self, id: usize) {
+        self.transient_callbacks.borrow_mut().remove(&id);
+        self.removed_ids.borrow_mut().insert(id);
+    }
+
+    /// Number of transient callbacks waiting for the next frame.
+    pub fn transient_callback_count(&self) -> usize {
+        self.transient_callbacks.borrow().len()
+    }
+
+    /// Calls `callback` with every frame, it does not schedule frames.
+    pub fn add_persistent_frame_callback(&self, callback: FrameCallback) {
+        self.persistent_callbacks.borrow_mut().push(callback);
+    }
+
+    /// Calls `callback` once the next frame is drawn, it does not schedule a frame.
+    pub fn add_post_frame_callback(&self, callback: FrameCallback) {
+        self.post_frame_callbacks.borrow_mut().push(callback);
+    }
+
+    /// Begins a frame at the time of the clock and runs its transient callbacks.
+    pub fn handle_begin_frame(&self) {
+        let timestamp = self.clock.now();
+        self.current_frame_timestamp.set(Some(timestamp));
+        self.has_scheduled_frame.set(false);
+
+        self.phase.set(SchedulerPhase::TransientCallbacks);
+        let callbacks = std::mem::take(&mut *self.transient_callbacks.borrow_mut());
+        for (id, callback) in callbacks {
+            // a callback may cancel those after it
+            if !self.removed_ids.borrow().contains(&id) {
+                callback.call(timestamp);
+            }
+        }
+        self.removed_ids.borrow_mut().clear();
+        self.phase.set(SchedulerPhase::PersistentCallbacks);
+    }
+
+    /// Runs the persistent callbacks, then the post-frame callbacks, and ends the frame.
+    pub fn handle_draw_frame(&self) {
+        let timestamp = match self.current_frame_timestamp.get() {
+            Some(timestamp) => timestamp,
+            None => self.clock.now(),
+        };
+        self.phase.set(SchedulerPhase::PersistentCallbacks);
+        let callbacks = self.persistent_callbacks.borrow().clone();
+        for callback in callbacks {
+            callback.call(timestamp);
+        }
+
+        self.phase.set(SchedulerPhase::PostFrameCallbacks);
+        let callbacks = std::mem::take(&mut *self.post_frame_callbacks.borrow_mut());
+        for callback in callbacks {
+            callback.call(timestamp);
+        }
+        self.phase.set(SchedulerPhase::Idle);
+        self.current_frame_timestamp.set(None);
+    }
+}
@@ -0,0 +1,63 @@
+use std::{
+    cell::Cell,
+    fmt::Debug,
+    time::{Duration, Instant},
+};
+
+/// The time frames begin at, and animations and tickers measure.
+pub trait Clock: Debug {
+    /// Time since an arbitrary origin, the same for all calls.
+    fn now(&self) -> Duration;
+}
+
+/// The monotonic clock of the system, counting from its creation.
+#[derive(Debug)]
+pub struct SystemClock {
+    origin: Instant,
+}
+
+impl SystemClock {
+    pub fn new() -> Self {
+        SystemClock {
+            origin: Instant::now(),
+        }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        SystemClock::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.origin.elapsed()
+    }
+}
+
+/// A clock that only moves when told to, so that animations are deterministic in tests.
+#[derive(Debug, Default)]
+pub struct FakeClock {
+    now: Cell<Duration>,
+}
+
+impl FakeClock {
+    pub fn new() -> Self {
+        FakeClock::default()
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        self.now.set(self.now.get() + duration);
+    }
+
+    pub fn set(&self, now: Duration) {
+        self.now.set(now);
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> Duration {
+        self.now.get()
+    }
+}
@@ -0,0 +1,8 @@
+mod clock;
+pub use clock::*;
+
+mod binding;
+pub use binding::*;
+
+mod ticker;
+pub use ticker::*;
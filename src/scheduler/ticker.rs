@@ -0,0 +1,149 @@
+use std::{
+    cell::Cell,
+    fmt,
+    rc::{Rc, Weak},
+    time::Duration,
+};
+
+use crate::scheduler::{FrameCallback, SchedulerBinding, SchedulerPhase};
+
+/// Called with the time elapsed since the ticker started.
+#[derive(Clone)]
+pub struct TickerCallback(Rc<dyn Fn(Duration)>);
+
+impl TickerCallback {
+    pub fn new<F>(f: F) -> Self
+    where
+        F: 'static + Fn(Duration),
+    {
+        TickerCallback(Rc::new(f))
+    }
+
+    pub fn call(&self, elapsed: Duration) {
+        (self.0)(elapsed)
+    }
+}
+
+impl fmt::Debug for TickerCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("TickerCallback")
+    }
+}
+
+/// Calls its callback once per frame while active, with the time elapsed since it started.
+///
+/// A muted ticker stays active and its time keeps running, but it neither ticks nor
+/// schedules frames, e.g. for an animation offscreen. Dropping a ticker cancels its frame
+/// callback.
+#[derive(Debug)]
+pub struct Ticker {
+    scheduler: Rc<SchedulerBinding>,
+    on_tick: TickerCallback,
+    this: Weak<Ticker>,
+    active: Cell<bool>,
+    muted: Cell<bool>,
+    start_time: Cell<Option<Duration>>,
+    callback_id: Cell<Option<usize>>,
+}
+
+impl Ticker {
+    pub fn new(scheduler: &Rc<SchedulerBinding>, on_tick: TickerCallback) -> Rc<Self> {
+        Rc::new_cyclic(|this| Ticker {
+            scheduler: scheduler.clone(),
+            on_tick,
+            this: this.clone(),
+            active: Cell::new(false),
+            muted: Cell::new(false),
+            start_time: Cell::new(None),
+            callback_id: Cell::new(None),
+        })
+    }
+
+    /// Whether the ticker was started and not stopped, muted or not.
+    pub fn is_active(&self) -> bool {
+        self.active.get()
+    }
+
+    /// Whether the ticker calls its callback with the next frames.
+    pub fn is_ticking(&self) -> bool {
+        self.active.get() && !self.muted.get()
+    }
+
+    pub fn muted(&self) -> bool {
+        self.muted.get()
+    }
+
+    pub fn set_muted(&self, muted: bool) {
+        self.muted.set(muted);
+        if muted {
+            self.unschedule_tick();
+        } else if self.should_schedule_tick() {
+            self.schedule_tick();
+        }
+    }
+
+    /// Starts ticking with the next frame. Started in the middle of a frame, the time
+    /// elapsed counts from the beginning of that frame.
+    pub fn start(&self) {
+        assert!(!self.active.get(), "a ticker was started twice");
+        self.active.set(true);
+        let start_time = match self.scheduler.phase() {
+            SchedulerPhase::Idle => None,
+            _ => self.scheduler.current_frame_timestamp(),
+        };
+        self.start_time.set(start_time);
+        if self.should_schedule_tick() {
+            self.schedule_tick();
+        }
+    }
+
+    /// Stops ticking, a ticker started again counts its time from zero.
+    pub fn stop(&self) {
+        if !self.active.get() {
+            return;
+        }
+        self.active.set(false);
+        self.start_time.set(None);
+        self.unschedule_tick();
+    }
+
+    fn should_schedule_tick(&self) -> bool {
+        self.is_ticking() && self.callback_id.get().is_none()
+    }
+
+    fn tick(&self, timestamp: Duration) {
+        self.callback_id.set(None);
+        let start_time = self.start_time.get().unwrap_or(timestamp);
+        self.start_time.set(Some(start_time));
+        self.on_tick.call(timestamp.saturating_sub(start_time));
+
+        // the callback may have stopped the ticker
+        if self.should_schedule_tick() {
+            self.schedule_tick();
+        }
+    }
+
+    fn schedule_tick(&self) {
+        let this = self.this.clone();
+        let id = self
+            .scheduler
+            .schedule_frame_callback(FrameCallback::new(move |timestamp| {
+                if let Some(ticker) = this.upgrade() {
+                    ticker.tick(timestamp);
+                }
+            }));
+        self.callback_id.set(Some(id));
+    }
+
+    fn unschedule_tick(&self) {
+        if let Some(id) = self.callback_id.take() {
+            self.scheduler.cancel_frame_callback_with_id(id);
+        }
+    }
+}
+
+impl Drop for Ticker {
+    fn drop(&mut self) {
+        self.unschedule_tick();
+    }
+}
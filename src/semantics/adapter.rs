@@ -0,0 +1,189 @@
+use std::collections::HashMap;
+
+use accesskit::{
+    Action, ActionData, ActionRequest, Node, NodeId, Rect, Role, Toggled, Tree, TreeUpdate,
+};
+
+use crate::semantics::{
+    SemanticsAction, SemanticsNode, SemanticsNodeId, SemanticsOwner, SemanticsRole,
+};
+
+/// Turns semantics trees into AccessKit tree updates for the platform adapter of a window.
+///
+/// An update only carries the nodes that changed since the previous one, a node removed
+/// is left out with its parent sent again without it. The first update, and the first
+/// after [`AccessKitAdapter::reset`], carries the whole tree.
+#[derive(Debug, Default)]
+pub struct AccessKitAdapter {
+    /// The nodes sent, as the platform adapter knows them.
+    nodes: HashMap<NodeId, Node>,
+    root: Option<NodeId>,
+}
+
+impl AccessKitAdapter {
+    pub fn new() -> Self {
+        AccessKitAdapter::default()
+    }
+
+    /// Forgets the nodes sent, e.g. when an assistive technology asks for the whole tree.
+    pub fn reset(&mut self) {
+        self.nodes.clear();
+        self.root = None;
+    }
+
+    /// The update bringing the tree sent last to the tree at `root`.
+    pub fn update(&mut self, root: &SemanticsNode) -> TreeUpdate {
+        let mut nodes = HashMap::new();
+        let mut focus = None;
+        collect(root, &mut nodes, &mut focus);
+
+        let root_id = node_id(root.id);
+        let tree = match self.root {
+            Some(id) if id == root_id => None,
+            _ => {
+                let mut tree = Tree::new(root_id);
+                tree.toolkit_name = Some("oxui".to_string());
+                Some(tree)
+            }
+        };
+        let mut changed: Vec<_> = nodes
+            .iter()
+            .filter(|(id, node)| self.nodes.get(*id) != Some(*node))
+            .map(|(id, node)| (*id, node.clone()))
+            .collect();
+        // sorted, updates of the same trees are equal
+        changed.sort_by_key(|(id, _)| *id);
+
+        self.nodes = nodes;
+        self.root = Some(root_id);
+        TreeUpdate {
+            nodes: changed,
+            tree,
+            focus: focus.unwrap_or(root_id),
+        }
+    }
+}
+
+impl SemanticsOwner {
+    /// Carries out the action an assistive technology requests through AccessKit,
+    /// returns whether the node has it.
+    pub fn perform_action_request(&self, request: &ActionRequest) -> bool {
+        let action = match semantics_action(request.action) {
+            Some(action) => action,
+            None => return false,
+        };
+        let argument = match &request.data {
+            Some(ActionData::Value(value)) => Some(value.as_ref()),
+            _ => None,
+        };
+        self.perform_action(SemanticsNodeId(request.target.0), action, argument)
+    }
+}
+
+fn collect(node: &SemanticsNode, nodes: &mut HashMap<NodeId, Node>, focus: &mut Option<NodeId>) {
+    let id = node_id(node.id);
+    if node.is_focused {
+        *focus = Some(id);
+    }
+    nodes.insert(id, accesskit_node(node));
+    for child in &node.children {
+        collect(child, nodes, focus);
+    }
+}
+
+fn node_id(id: SemanticsNodeId) -> NodeId {
+    NodeId(id.0)
+}
+
+fn accesskit_node(node: &SemanticsNode) -> Node {
+    let mut accesskit_node = Node::new(role(node.role));
+    if !node.label.is_empty() {
+        // the text of a label is its value
+        match node.role {
+            SemanticsRole::Text => accesskit_node.set_value(node.label.as_str()),
+            _ => accesskit_node.set_label(node.label.as_str()),
+        }
+    }
+    if let Some(value) = &node.value {
+        accesskit_node.set_value(value.as_str());
+    }
+    for action in node
+        .actions
+        .iter()
+        .filter_map(|action| accesskit_action(*action))
+    {
+        accesskit_node.add_action(action);
+    }
+    if node.is_enabled == Some(false) {
+        accesskit_node.set_disabled();
+    }
+    match node.is_checked {
+        Some(true) => accesskit_node.set_toggled(Toggled::True),
+        Some(false) => accesskit_node.set_toggled(Toggled::False),
+        None => {}
+    }
+    let rect = node.rect;
+    accesskit_node.set_bounds(Rect::new(
+        rect.left as f64,
+        rect.top as f64,
+        rect.right as f64,
+        rect.bottom as f64,
+    ));
+    let children: Vec<_> = node
+        .children
+        .iter()
+        .map(|child| node_id(child.id))
+        .collect();
+    accesskit_node.set_children(children);
+    accesskit_node
+}
+
+fn role(role: SemanticsRole) -> Role {
+    match role {
+        SemanticsRole::Generic => Role::GenericContainer,
+        SemanticsRole::Window => Role::Window,
+        SemanticsRole::Text => Role::Label,
+        SemanticsRole::Header => Role::Heading,
+        SemanticsRole::Button => Role::Button,
+        SemanticsRole::Link => Role::Link,
+        SemanticsRole::CheckBox => Role::CheckBox,
+        SemanticsRole::Slider => Role::Slider,
+        SemanticsRole::TextField => Role::TextInput,
+        SemanticsRole::Image => Role::Image,
+        SemanticsRole::ScrollView => Role::ScrollView,
+    }
+}
+
+fn accesskit_action(action: SemanticsAction) -> Option<Action> {
+    let action = match action {
+        SemanticsAction::Tap => Action::Click,
+        SemanticsAction::LongPress => Action::ShowContextMenu,
+        SemanticsAction::Focus => Action::Focus,
+        SemanticsAction::SetText => Action::SetValue,
+        SemanticsAction::Increase => Action::Increment,
+        SemanticsAction::Decrease => Action::Decrement,
+        SemanticsAction::ScrollUp => Action::ScrollUp,
+        SemanticsAction::ScrollDown => Action::ScrollDown,
+        SemanticsAction::ScrollLeft => Action::ScrollLeft,
+        SemanticsAction::ScrollRight => Action::ScrollRight,
+        SemanticsAction::Dismiss => return None,
+    };
+    Some(action)
+}
+
+fn semantics_action(action: Action) -> Option<SemanticsAction> {
+    let action = match action {
+        Action::Click => SemanticsAction::Tap,
+        Action::ShowContextMenu => SemanticsAction::LongPress,
+        Action::Focus => SemanticsAction::Focus,
+        Action::SetValue => SemanticsAction::SetText,
+        Action::Increment => SemanticsAction::Increase,
+        Action::Decrement => SemanticsAction::Decrease,
+        Action::ScrollUp => SemanticsAction::ScrollUp,
+        Action::ScrollDown => SemanticsAction::ScrollDown,
+        Action::ScrollLeft => SemanticsAction::ScrollLeft,
+        Action::ScrollRight => SemanticsAction::ScrollRight,
+        _ => return None,
+    };
+    Some(action)
+}
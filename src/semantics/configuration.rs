@@ -0,0 +1,249 @@
+use std::{fmt, rc::Rc};
+
+/// What a semantics node is to assistive technologies, e.g. a screen reader.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SemanticsRole {
+    /// A node without a role of its own, e.g. grouping others.
+    #[default]
+    Generic,
+    Window,
+    Text,
+    Header,
+    Button,
+    Link,
+    CheckBox,
+    Slider,
+    TextField,
+    Image,
+    ScrollView,
+}
+
+impl SemanticsRole {
+    /// The name of the role in the serialized tree.
+    pub fn name(&self) -> &'static str {
+        match self {
+            SemanticsRole::Generic => "generic",
+            SemanticsRole::Window => "window",
+            SemanticsRole::Text => "text",
+            SemanticsRole::Header => "header",
+            SemanticsRole::Button => "button",
+            SemanticsRole::Link => "link",
+            SemanticsRole::CheckBox => "check_box",
+            SemanticsRole::Slider => "slider",
+            SemanticsRole::TextField => "text_field",
+            SemanticsRole::Image => "image",
+            SemanticsRole::ScrollView => "scroll_view",
+        }
+    }
+}
+
+/// What assistive technologies can ask a semantics node to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SemanticsAction {
+    Tap,
+    LongPress,
+    /// Moves the keyboard focus to the node.
+    Focus,
+    /// Replaces the text of the node, e.g. of a text field.
+    SetText,
+    Increase,
+    Decrease,
+    ScrollUp,
+    ScrollDown,
+    ScrollLeft,
+    ScrollRight,
+    Dismiss,
+}
+
+impl SemanticsAction {
+    /// The name of the action in the serialized tree.
+    pub fn name(&self) -> &'static str {
+        match self {
+            SemanticsAction::Tap => "tap",
+            SemanticsAction::LongPress => "long_press",
+            SemanticsAction::Focus => "focus",
+            SemanticsAction::SetText => "set_text",
+            SemanticsAction::Increase => "increase",
+            SemanticsAction::Decrease => "decrease",
+            SemanticsAction::ScrollUp => "scroll_up",
+            SemanticsAction::ScrollDown => "scroll_down",
+            SemanticsAction::ScrollLeft => "scroll_left",
+            SemanticsAction::ScrollRight => "scroll_right",
+            SemanticsAction::Dismiss => "dismiss",
+        }
+    }
+}
+
+/// Carries out a semantics action, receiving the text of a [`SemanticsAction::SetText`].
+#[derive(Clone)]
+pub struct SemanticsActionHandler(Rc<dyn Fn(Option<String>)>);
+
+impl SemanticsActionHandler {
+    pub fn new<F>(f: F) -> Self
+    where
+        F: 'static + Fn(Option<String>),
+    {
+        SemanticsActionHandler(Rc::new(f))
+    }
+
+    pub fn call(&self, argument: Option<String>) {
+        (self.0)(argument)
+    }
+}
+
+impl fmt::Debug for SemanticsActionHandler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SemanticsActionHandler")
+    }
+}
+
+/// The semantics a render object describes of itself, see
+/// [`RenderBox::describe_semantics_configuration`](crate::rendering::RenderBox::describe_semantics_configuration).
+///
+/// A boundary forms a node, the annotations of the descendants that are not boundaries
+/// merge into it while they are compatible, e.g. the label of a text into its button. The
+/// annotations of an object that is not a boundary merge into the nearest boundary
+/// above.
+#[derive(Debug, Clone, Default)]
+pub struct SemanticsConfiguration {
+    pub is_semantic_boundary: bool,
+    /// Whether the annotations below a boundary form nodes of their own instead of
+    /// merging into it.
+    pub explicit_child_nodes: bool,
+    /// Whether all descendants, boundaries too, merge into the node of this object.
+    pub is_merging_semantics_of_descendants: bool,
+    /// Whether the descendants are left out of the tree.
+    pub excludes_descendants: bool,
+
+    pub role: SemanticsRole,
+    pub label: String,
+    pub value: Option<String>,
+    pub is_focusable: bool,
+    pub is_focused: bool,
+    pub is_enabled: Option<bool>,
+    pub is_checked: Option<bool>,
+    actions: Vec<(SemanticsAction, SemanticsActionHandler)>,
+}
+
+impl SemanticsConfiguration {
+    pub fn new() -> Self {
+        SemanticsConfiguration::default()
+    }
+
+    /// Adds `action`, replacing its previous handler.
+    pub fn add_action(&mut self, action: SemanticsAction, handler: SemanticsActionHandler) {
+        self.actions.retain(|(a, _)| *a != action);
+        self.actions.push((action, handler));
+    }
+
+    pub fn has_action(&self, action: SemanticsAction) -> bool {
+        self.actions.iter().any(|(a, _)| *a == action)
+    }
+
+    pub fn actions(&self) -> impl Iterator<Item = SemanticsAction> + '_ {
+        self.actions.iter().map(|(action, _)| *action)
+    }
+
+    pub(crate) fn take_handlers(&mut self) -> Vec<(SemanticsAction, SemanticsActionHandler)> {
+        std::mem::take(&mut self.actions)
+    }
+
+    /// Whether the object has anything to tell, objects without annotations only group
+    /// their descendants.
+    pub fn is_annotated(&self) -> bool {
+        self.role != SemanticsRole::Generic
+            || !self.label.is_empty()
+            || self.value.is_some()
+            || self.is_focusable
+            || self.is_enabled.is_some()
+            || self.is_checked.is_some()
+            || !self.actions.is_empty()
+    }
+
+    /// Whether `other` can merge into this configuration without one of them losing
+    /// what it tells, e.g. two values or two tap actions cannot.
+    pub fn is_compatible_with(&self, other: &SemanticsConfiguration) -> bool {
+        if !self.is_annotated() || !other.is_annotated() {
+            return true;
+        }
+        let both_roles =
+            self.role != SemanticsRole::Generic && other.role != SemanticsRole::Generic;
+        !(both_roles
+            || (self.value.is_some() && other.value.is_some())
+            || (self.is_focusable && other.is_focusable)
+            || (self.is_checked.is_some() && other.is_checked.is_some())
+            || other.actions().any(|action| self.has_action(action)))
+    }
+
+    /// Merges the annotations of `other` into this configuration, labels are joined by
+    /// lines and the annotations set first are kept.
+    pub fn absorb(&mut self, other: SemanticsConfiguration) {
+        if self.role == SemanticsRole::Generic {
+            self.role = other.role;
+        }
+        if !other.label.is_empty() {
+            if !self.label.is_empty() {
+                self.label.push('\n');
+            }
+            self.label.push_str(&other.label);
+        }
+        if self.value.is_none() {
+            self.value = other.value;
+        }
+        self.is_focusable |= other.is_focusable;
+        self.is_focused |= other.is_focused;
+        self.is_enabled = self.is_enabled.or(other.is_enabled);
+        self.is_checked = self.is_checked.or(other.is_checked);
+        for (action, handler) in other.actions {
+            if !self.has_action(action) {
+                self.actions.push((action, handler));
+            }
+        }
+    }
+}
+
+/// The annotations a [`Semantics`](crate::widgets::Semantics) widget gives its child.
+#[derive(Debug, Clone, Default)]
+pub struct SemanticsProperties {
+    pub role: Option<SemanticsRole>,
+    pub label: Option<String>,
+    pub value: Option<String>,
+    pub enabled: Option<bool>,
+    pub checked: Option<bool>,
+    pub on_tap: Option<SemanticsActionHandler>,
+    pub on_long_press: Option<SemanticsActionHandler>,
+    pub on_increase: Option<SemanticsActionHandler>,
+    pub on_decrease: Option<SemanticsActionHandler>,
+}
+
+impl SemanticsProperties {
+    /// Adds the properties set to `config`.
+    pub fn apply(&self, config: &mut SemanticsConfiguration) {
+        if let Some(role) = self.role {
+            config.role = role;
+        }
+        if let Some(label) = &self.label {
+            config.label = label.clone();
+        }
+        if let Some(value) = &self.value {
+            config.value = Some(value.clone());
+        }
+        if self.enabled.is_some() {
+            config.is_enabled = self.enabled;
+        }
+        if self.checked.is_some() {
+            config.is_checked = self.checked;
+        }
+        let handlers = [
+            (SemanticsAction::Tap, &self.on_tap),
+            (SemanticsAction::LongPress, &self.on_long_press),
+            (SemanticsAction::Increase, &self.on_increase),
+            (SemanticsAction::Decrease, &self.on_decrease),
+        ];
+        for (action, handler) in handlers {
+            if let Some(handler) = handler {
+                config.add_action(action, handler.clone());
+            }
+        }
+    }
+}
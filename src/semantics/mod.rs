@@ -0,0 +1,13 @@
+mod configuration;
+pub use configuration::*;
+
+mod node;
+pub use node::*;
+
+mod owner;
+pub use owner::*;
+
+#[cfg(feature = "accesskit")]
+mod adapter;
+#[cfg(feature = "accesskit")]
+pub use adapter::*;
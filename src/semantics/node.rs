@@ -0,0 +1,132 @@
+use std::fmt::{self, Write};
+
+use skia_safe::Rect;
+
+use crate::semantics::{SemanticsAction, SemanticsRole};
+
+/// Identifies a semantics node, it stays the same across updates as long as the render
+/// object forming the node is in the tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SemanticsNodeId(pub u64);
+
+impl fmt::Display for SemanticsNodeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+/// A node of the semantics tree, what assistive technologies know of a part of the UI.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SemanticsNode {
+    pub id: SemanticsNodeId,
+    pub role: SemanticsRole,
+    pub label: String,
+    pub value: Option<String>,
+    /// The actions the node can carry out, sorted.
+    pub actions: Vec<SemanticsAction>,
+    pub is_focusable: bool,
+    pub is_focused: bool,
+    pub is_enabled: Option<bool>,
+    pub is_checked: Option<bool>,
+    /// Where the node is on screen, transforms painted by its ancestors are not applied.
+    pub rect: Rect,
+    pub children: Vec<SemanticsNode>,
+}
+
+impl SemanticsNode {
+    /// The node `id` in this subtree.
+    pub fn find(&self, id: SemanticsNodeId) -> Option<&SemanticsNode> {
+        if self.id == id {
+            return Some(self);
+        }
+        self.children.iter().find_map(|child| child.find(id))
+    }
+
+    /// The first node in this subtree, in depth-first order, labelled `label`.
+    pub fn find_by_label(&self, label: &str) -> Option<&SemanticsNode> {
+        if self.label == label {
+            return Some(self);
+        }
+        self.children
+            .iter()
+            .find_map(|child| child.find_by_label(label))
+    }
+
+    /// Visits this node and its descendants in depth-first order.
+    pub fn visit(&self, visitor: &mut dyn FnMut(&SemanticsNode)) {
+        visitor(self);
+        for child in &self.children {
+            child.visit(visitor);
+        }
+    }
+
+    /// The subtree as text, a line for each node indented by its depth, e.g.
+    ///
+    /// ```text
+    /// #1 window rect=0,0 200x100
+    ///   #2 button "OK" actions=[tap, focus] flags=[focusable] rect=0,0 40x20
+    /// ```
+    ///
+    /// The text only changes when the tree does, tests compare it.
+    pub fn to_tree_string(&self) -> String {
+        let mut text = String::new();
+        self.write_tree(&mut text, 0);
+        text
+    }
+
+    fn write_tree(&self, text: &mut String, depth: usize) {
+        let _ = writeln!(text, "{:indent$}{}", "", self, indent = depth * 2);
+        for child in &self.children {
+            child.write_tree(text, depth + 1);
+        }
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        let mut flags = Vec::new();
+        if self.is_focusable {
+            flags.push("focusable");
+        }
+        if self.is_focused {
+            flags.push("focused");
+        }
+        match self.is_enabled {
+            Some(true) => flags.push("enabled"),
+            Some(false) => flags.push("disabled"),
+            None => {}
+        }
+        match self.is_checked {
+            Some(true) => flags.push("checked"),
+            Some(false) => flags.push("unchecked"),
+            None => {}
+        }
+        flags
+    }
+}
+
+impl fmt::Display for SemanticsNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.id, self.role.name())?;
+        if !self.label.is_empty() {
+            write!(f, " {:?}", self.label)?;
+        }
+        if let Some(value) = &self.value {
+            write!(f, " value={:?}", value)?;
+        }
+        if !self.actions.is_empty() {
+            let actions: Vec<_> = self.actions.iter().map(|action| action.name()).collect();
+            write!(f, " actions=[{}]", actions.join(", "))?;
+        }
+        let flags = self.flags();
+        if !flags.is_empty() {
+            write!(f, " flags=[{}]", flags.join(", "))?;
+        }
+        write!(
+            f,
+            " rect={},{} {}x{}",
+            self.rect.left,
+            self.rect.top,
+            self.rect.width(),
+            self.rect.height()
+        )
+    }
+}
@@ -0,0 +1,317 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    rc::{Rc, Weak},
+};
+
+use skia_safe::Rect;
+
+use crate::{
+    rendering::{RenderBox, RenderState},
+    semantics::{
+        SemanticsAction, SemanticsActionHandler, SemanticsConfiguration, SemanticsNode,
+        SemanticsNodeId, SemanticsRole,
+    },
+};
+
+/// What a subtree gives the node of its nearest boundary ancestor.
+enum Fragment {
+    /// Annotations to merge into that node, or to form a node of their own when they
+    /// cannot.
+    Config {
+        state: Rc<RenderState>,
+        rect: Rect,
+        config: SemanticsConfiguration,
+    },
+    Node(PendingNode),
+}
+
+/// A node before pruning, it gets its id once kept.
+struct PendingNode {
+    state: Rc<RenderState>,
+    rect: Rect,
+    config: SemanticsConfiguration,
+    children: Vec<PendingNode>,
+}
+
+impl PendingNode {
+    /// Merges this node and all its descendants into `config`.
+    fn merge_into(self, config: &mut SemanticsConfiguration) {
+        config.absorb(self.config);
+        for child in self.children {
+            child.merge_into(config);
+        }
+    }
+}
+
+/// Builds the semantics tree from the configurations the render objects describe, and
+/// carries out the actions assistive technologies ask its nodes for.
+///
+/// Like the focus tree, the semantics tree is rebuilt from the render tree after each
+/// paint. A node keeps its id while the render object forming it stays in the tree.
+/// Nodes outside the root and nodes without annotations are pruned, the children of the
+/// latter take their place.
+#[derive(Default)]
+pub struct SemanticsOwner {
+    root: Option<SemanticsNode>,
+    ids: HashMap<*const RenderState, (Weak<RenderState>, SemanticsNodeId)>,
+    next_id: u64,
+    handlers: HashMap<(SemanticsNodeId, SemanticsAction), SemanticsActionHandler>,
+}
+
+impl SemanticsOwner {
+    pub fn new() -> Self {
+        SemanticsOwner::default()
+    }
+
+    /// The tree of the last update.
+    pub fn root_node(&self) -> Option<&SemanticsNode> {
+        self.root.as_ref()
+    }
+
+    /// Rebuilds the tree from the render tree at `root`, laid out and painted.
+    pub fn update(&mut self, root: &Rc<RefCell<dyn RenderBox>>) {
+        let mut fragments = collect(root);
+        let root_node = match fragments.pop() {
+            Some(Fragment::Node(node)) if fragments.is_empty() => node,
+            last => {
+                // the root forms a node whatever it describes
+                fragments.extend(last);
+                let render_box = root.borrow();
+                let mut config = SemanticsConfiguration::new();
+                config.explicit_child_nodes = true;
+                form_node(render_box.state(), bounds(&*render_box), config, fragments)
+            }
+        };
+
+        let mut ids = IdAllocator {
+            previous: std::mem::take(&mut self.ids),
+            ids: HashMap::new(),
+            next_id: self.next_id,
+        };
+        self.handlers.clear();
+        let visible = root_node.rect;
+        let mut nodes = Vec::new();
+        self.finish(root_node, visible, true, &mut ids, &mut nodes);
+        self.root = nodes.pop();
+        self.ids = ids.ids;
+        self.next_id = ids.next_id;
+    }
+
+    /// Asks the node `id` to carry out `action`, with the text of a
+    /// [`SemanticsAction::SetText`]. Returns whether the node has the action.
+    pub fn perform_action(
+        &self,
+        id: SemanticsNodeId,
+        action: SemanticsAction,
+        argument: Option<&str>,
+    ) -> bool {
+        match self.handlers.get(&(id, action)) {
+            Some(handler) => {
+                handler.call(argument.map(str::to_string));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Prunes `node` and pushes what is left of it to `nodes`, giving the nodes kept their
+    /// ids in depth-first order and registering the handlers of their actions.
+    fn finish(
+        &mut self,
+        node: PendingNode,
+        visible: Rect,
+        is_root: bool,
+        ids: &mut IdAllocator,
+        nodes: &mut Vec<SemanticsNode>,
+    ) {
+        if !is_root && !intersects(&node.rect, &visible) {
+            return;
+        }
+        let mut config = node.config;
+        if !is_root && !config.is_annotated() {
+            for child in node.children {
+                self.finish(child, visible, false, ids, nodes);
+            }
+            return;
+        }
+
+        let id = ids.id(&node.state);
+        let mut children = Vec::new();
+        for child in node.children {
+            self.finish(child, visible, false, ids, &mut children);
+        }
+        let mut actions = Vec::new();
+        for (action, handler) in config.take_handlers() {
+            actions.push(action);
+            self.handlers.insert((id, action), handler);
+        }
+        actions.sort();
+        // a node telling nothing but its label is text, e.g. of a paragraph
+        let is_text = !config.label.is_empty()
+            && actions.is_empty()
+            && config.value.is_none()
+            && !config.is_focusable
+            && config.is_enabled.is_none()
+            && config.is_checked.is_none();
+        let role = match config.role {
+            SemanticsRole::Generic if is_text => SemanticsRole::Text,
+            role => role,
+        };
+        nodes.push(SemanticsNode {
+            id,
+            role,
+            label: config.label,
+            value: config.value,
+            actions,
+            is_focusable: config.is_focusable,
+            is_focused: config.is_focused,
+            is_enabled: config.is_enabled,
+            is_checked: config.is_checked,
+            rect: node.rect,
+            children,
+        });
+    }
+}
+
+/// Gives the render objects forming nodes the ids they had in the previous update.
+struct IdAllocator {
+    previous: HashMap<*const RenderState, (Weak<RenderState>, SemanticsNodeId)>,
+    ids: HashMap<*const RenderState, (Weak<RenderState>, SemanticsNodeId)>,
+    next_id: u64,
+}
+
+impl IdAllocator {
+    fn id(&mut self, state: &Rc<RenderState>) -> SemanticsNodeId {
+        // the weak reference keeps the address from being reused by another object
+        let key = Rc::as_ptr(state);
+        let entry = match self.previous.remove(&key) {
+            Some(entry) => entry,
+            None => {
+                self.next_id += 1;
+                (Rc::downgrade(state), SemanticsNodeId(self.next_id))
+            }
+        };
+        let id = entry.1;
+        self.ids.insert(key, entry);
+        id
+    }
+}
+
+fn collect(object: &Rc<RefCell<dyn RenderBox>>) -> Vec<Fragment> {
+    let render_box = object.borrow();
+    let mut config = SemanticsConfiguration::new();
+    render_box.describe_semantics_configuration(&mut config);
+    if let Some(node) = render_box.focus_node() {
+        if node.can_request_focus() && !node.is_scope() {
+            config.is_focusable = true;
+            config.is_focused = node.has_primary_focus();
+            let handler = SemanticsActionHandler::new(move |_| node.request_focus());
+            config.add_action(SemanticsAction::Focus, handler);
+        }
+    }
+
+    let mut fragments = Vec::new();
+    if !config.excludes_descendants {
+        render_box.visit_children(&mut |child| fragments.extend(collect(child)));
+        render_box.visit_slivers(&mut |sliver| {
+            sliver
+                .borrow()
+                .visit_children(&mut |child| fragments.extend(collect(child)))
+        });
+    }
+
+    let state = render_box.state().clone();
+    let rect = bounds(&*render_box);
+    if config.is_merging_semantics_of_descendants {
+        let mut node = PendingNode {
+            state,
+            rect,
+            config,
+            children: Vec::new(),
+        };
+        for fragment in fragments {
+            match fragment {
+                Fragment::Config { config, .. } => node.config.absorb(config),
+                Fragment::Node(child) => child.merge_into(&mut node.config),
+            }
+        }
+        vec![Fragment::Node(node)]
+    } else if config.is_semantic_boundary {
+        vec![Fragment::Node(form_node(&state, rect, config, fragments))]
+    } else if config.is_annotated() {
+        // the compatible annotations below travel up with those of this object
+        let mut passed = Vec::new();
+        for fragment in fragments {
+            match fragment {
+                Fragment::Config { config: other, .. } if config.is_compatible_with(&other) => {
+                    config.absorb(other)
+                }
+                fragment => passed.push(fragment),
+            }
+        }
+        passed.insert(
+            0,
+            Fragment::Config {
+                state,
+                rect,
+                config,
+            },
+        );
+        passed
+    } else {
+        fragments
+    }
+}
+
+/// The node of a boundary, with the annotations below merged in or as children.
+fn form_node(
+    state: &Rc<RenderState>,
+    rect: Rect,
+    config: SemanticsConfiguration,
+    fragments: Vec<Fragment>,
+) -> PendingNode {
+    let mut node = PendingNode {
+        state: state.clone(),
+        rect,
+        config,
+        children: Vec::new(),
+    };
+    for fragment in fragments {
+        match fragment {
+            Fragment::Config { config, .. }
+                if !node.config.explicit_child_nodes && node.config.is_compatible_with(&config) =>
+            {
+                node.config.absorb(config)
+            }
+            Fragment::Config {
+                state,
+                rect,
+                config,
+            } => node.children.push(PendingNode {
+                state,
+                rect,
+                config,
+                children: Vec::new(),
+            }),
+            Fragment::Node(child) => node.children.push(child),
+        }
+    }
+    node
+}
+
+fn bounds(render_box: &dyn RenderBox) -> Rect {
+    let offset = render_box.state().global_paint_offset();
+    let size = render_box.size();
+    Rect::from_xywh(offset.x, offset.y, size.width, size.height)
+}
+
+/// Whether `rect` has an area and some of it is in `visible`.
+fn intersects(rect: &Rect, visible: &Rect) -> bool {
+    rect.left < rect.right
+        && rect.top < rect.bottom
+        && rect.left < visible.right
+        && visible.left < rect.right
+        && rect.top < visible.bottom
+        && visible.top < rect.bottom
+}
@@ -0,0 +1,155 @@
+use std::{
+    any::{Any, TypeId},
+    cell::Cell,
+    collections::HashMap,
+    fmt,
+    marker::PhantomData,
+    rc::Rc,
+};
+
+/// What the user wants done, e.g. saving, whatever the key or button asking for it.
+///
+/// A shortcut maps keys to an intent, the nearest action bound to its type carries it out.
+pub trait Intent: Any + fmt::Debug {}
+
+/// Carries out intents of type `T`.
+pub trait Action<T: Intent> {
+    /// Whether the action can carry out `intent` now, a disabled action leaves the key of
+    /// a shortcut to the enclosing nodes.
+    fn is_enabled(&self, intent: &T) -> bool {
+        true
+    }
+
+    fn invoke(&self, intent: &T);
+}
+
+/// An action calling a closure, enabled until disabled.
+pub struct CallbackAction<T> {
+    on_invoke: Rc<dyn Fn(&T)>,
+    enabled: Cell<bool>,
+}
+
+impl<T> CallbackAction<T> {
+    pub fn new<F>(on_invoke: F) -> Self
+    where
+        F: 'static + Fn(&T),
+    {
+        CallbackAction {
+            on_invoke: Rc::new(on_invoke),
+            enabled: Cell::new(true),
+        }
+    }
+
+    pub fn with_enabled(self, enabled: bool) -> Self {
+        self.enabled.set(enabled);
+        self
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.set(enabled);
+    }
+}
+
+impl<T: Intent> Action<T> for CallbackAction<T> {
+    fn is_enabled(&self, _: &T) -> bool {
+        self.enabled.get()
+    }
+
+    fn invoke(&self, intent: &T) {
+        (self.on_invoke)(intent)
+    }
+}
+
+impl<T> fmt::Debug for CallbackAction<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CallbackAction")
+            .field("enabled", &self.enabled.get())
+            .finish()
+    }
+}
+
+/// An action of any intent type, called with intents of its own type only.
+pub(crate) trait AnyAction {
+    fn is_enabled(&self, intent: &dyn Intent) -> bool;
+
+    fn invoke(&self, intent: &dyn Intent);
+}
+
+struct BoundAction<T, A> {
+    action: Rc<A>,
+    intent: PhantomData<T>,
+}
+
+impl<T, A> AnyAction for BoundAction<T, A>
+where
+    T: Intent,
+    A: Action<T>,
+{
+    fn is_enabled(&self, intent: &dyn Intent) -> bool {
+        let intent = (intent as &dyn Any).downcast_ref::<T>();
+        intent.map_or(false, |intent| self.action.is_enabled(intent))
+    }
+
+    fn invoke(&self, intent: &dyn Intent) {
+        if let Some(intent) = (intent as &dyn Any).downcast_ref::<T>() {
+            self.action.invoke(intent);
+        }
+    }
+}
+
+/// Actions by the type of intent they carry out.
+#[derive(Clone, Default)]
+pub struct ActionMap {
+    actions: HashMap<TypeId, Rc<dyn AnyAction>>,
+}
+
+impl ActionMap {
+    pub fn new() -> Self {
+        ActionMap::default()
+    }
+
+    /// Binds `action` to the intents of type `T`, keep a clone of it to disable it later.
+    pub fn with<T, A>(mut self, action: Rc<A>) -> Self
+    where
+        T: Intent,
+        A: 'static + Action<T>,
+    {
+        self.insert(action);
+        self
+    }
+
+    /// Binds a closure to the intents of type `T`.
+    pub fn with_callback<T: Intent>(self, on_invoke: impl Fn(&T) + 'static) -> Self {
+        self.with(Rc::new(CallbackAction::new(on_invoke)))
+    }
+
+    pub fn insert<T, A>(&mut self, action: Rc<A>)
+    where
+        T: Intent,
+        A: 'static + Action<T>,
+    {
+        let action = BoundAction {
+            action,
+            intent: PhantomData::<T>,
+        };
+        self.actions.insert(TypeId::of::<T>(), Rc::new(action));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+
+    /// The action bound to the type of `intent`.
+    pub(crate) fn find(&self, intent: &dyn Intent) -> Option<Rc<dyn AnyAction>> {
+        let ty_id = (intent as &dyn Any).type_id();
+        self.actions.get(&ty_id).cloned()
+    }
+}
+
+impl fmt::Debug for ActionMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ActionMap")
+            .field("len", &self.actions.len())
+            .finish()
+    }
+}
@@ -0,0 +1,650 @@
+use std::{
+    cell::{Cell, RefCell},
+    fmt,
+    rc::{Rc, Weak},
+};
+
+use skia_safe::Rect;
+
+use crate::{
+    gestures::HitTestTarget,
+    rendering::RenderBox,
+    services::{ActionMap, ImeEvent, Intent, KeyEvent, LogicalKey, ShortcutMap},
+};
+
+/// What a key handler did with an event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyEventResult {
+    /// The event was handled, it stops bubbling.
+    Handled,
+    /// The event was not handled, it bubbles to the enclosing node.
+    Ignored,
+    /// The event was not handled and should not bubble further, e.g. so a text field
+    /// gets a key a shortcut of its ancestors would take.
+    SkipRemainingHandlers,
+}
+
+#[derive(Clone)]
+pub struct KeyEventCallback(Rc<dyn Fn(&KeyEvent) -> KeyEventResult>);
+
+impl KeyEventCallback {
+    pub fn new<F>(f: F) -> Self
+    where
+        F: 'static + Fn(&KeyEvent) -> KeyEventResult,
+    {
+        KeyEventCallback(Rc::new(f))
+    }
+
+    pub fn call(&self, event: &KeyEvent) -> KeyEventResult {
+        (self.0)(event)
+    }
+}
+
+impl fmt::Debug for KeyEventCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("KeyEventCallback")
+    }
+}
+
+#[derive(Clone)]
+pub struct FocusChangeCallback(Rc<dyn Fn(bool)>);
+
+impl FocusChangeCallback {
+    pub fn new<F>(f: F) -> Self
+    where
+        F: 'static + Fn(bool),
+    {
+        FocusChangeCallback(Rc::new(f))
+    }
+
+    pub fn call(&self, has_focus: bool) {
+        (self.0)(has_focus)
+    }
+}
+
+impl fmt::Debug for FocusChangeCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("FocusChangeCallback")
+    }
+}
+
+/// A node of the focus tree, owned by the render object that can take the keyboard focus.
+///
+/// The tree mirrors the render tree: the parent of a node is the node of the nearest
+/// ancestor render object that has one, it is rebuilt by [`FocusManager::update_tree`]
+/// after each paint. Key events go to the node with the primary focus and bubble to the
+/// root until a node handles them.
+///
+/// A scope groups its descendants for traversal and remembers which of them was focused
+/// last, focusing a scope focuses that node again.
+pub struct FocusNode {
+    debug_label: Option<String>,
+    is_scope: bool,
+    can_request_focus: Cell<bool>,
+    skip_traversal: Cell<bool>,
+    on_key: RefCell<Option<KeyEventCallback>>,
+    on_focus_change: RefCell<Option<FocusChangeCallback>>,
+    shortcuts: RefCell<ShortcutMap>,
+    actions: RefCell<ActionMap>,
+
+    // focus tree
+    manager: RefCell<Weak<FocusManager>>,
+    parent: RefCell<Weak<FocusNode>>,
+    children: RefCell<Vec<Weak<FocusNode>>>,
+    attached: Cell<bool>,
+    target: RefCell<Option<Weak<RefCell<dyn HitTestTarget>>>>,
+    rect: Cell<Rect>,
+
+    // focus state
+    has_focus: Cell<bool>,
+    /// The node focused last in this scope.
+    focused_child: RefCell<Weak<FocusNode>>,
+    /// Focus requested before the node was attached.
+    requested: Cell<bool>,
+}
+
+impl FocusNode {
+    pub fn new() -> Self {
+        FocusNode {
+            debug_label: None,
+            is_scope: false,
+            can_request_focus: Cell::new(true),
+            skip_traversal: Cell::new(false),
+            on_key: RefCell::new(None),
+            on_focus_change: RefCell::new(None),
+            shortcuts: RefCell::new(ShortcutMap::new()),
+            actions: RefCell::new(ActionMap::new()),
+            manager: RefCell::new(Weak::new()),
+            parent: RefCell::new(Weak::new()),
+            children: RefCell::new(Vec::new()),
+            attached: Cell::new(false),
+            target: RefCell::new(None),
+            rect: Cell::new(Rect::default()),
+            has_focus: Cell::new(false),
+            focused_child: RefCell::new(Weak::new()),
+            requested: Cell::new(false),
+        }
+    }
+
+    /// A node scoping the traversal of its descendants.
+    pub fn scope() -> Self {
+        FocusNode {
+            is_scope: true,
+            ..FocusNode::new()
+        }
+    }
+
+    pub fn with_debug_label(mut self, debug_label: impl Into<String>) -> Self {
+        self.debug_label = Some(debug_label.into());
+        self
+    }
+
+    pub fn with_can_request_focus(self, can_request_focus: bool) -> Self {
+        self.can_request_focus.set(can_request_focus);
+        self
+    }
+
+    pub fn with_skip_traversal(self, skip_traversal: bool) -> Self {
+        self.skip_traversal.set(skip_traversal);
+        self
+    }
+
+    pub fn with_on_key(self, on_key: impl Fn(&KeyEvent) -> KeyEventResult + 'static) -> Self {
+        self.set_on_key(Some(KeyEventCallback::new(on_key)));
+        self
+    }
+
+    pub fn with_on_focus_change(self, on_focus_change: impl Fn(bool) + 'static) -> Self {
+        self.set_on_focus_change(Some(FocusChangeCallback::new(on_focus_change)));
+        self
+    }
+
+    pub fn with_shortcuts(self, shortcuts: ShortcutMap) -> Self {
+        self.set_shortcuts(shortcuts);
+        self
+    }
+
+    pub fn with_actions(self, actions: ActionMap) -> Self {
+        self.set_actions(actions);
+        self
+    }
+
+    pub fn debug_label(&self) -> Option<&str> {
+        self.debug_label.as_deref()
+    }
+
+    pub fn is_scope(&self) -> bool {
+        self.is_scope
+    }
+
+    pub fn can_request_focus(&self) -> bool {
+        self.can_request_focus.get()
+    }
+
+    /// Whether the node can be focused by a pointer or a request but not by traversal.
+    pub fn skip_traversal(&self) -> bool {
+        self.skip_traversal.get()
+    }
+
+    pub fn set_can_request_focus(&self, can_request_focus: bool) {
+        self.can_request_focus.set(can_request_focus);
+    }
+
+    pub fn set_skip_traversal(&self, skip_traversal: bool) {
+        self.skip_traversal.set(skip_traversal);
+    }
+
+    /// Handles the key events reaching this node, before its render object.
+    pub fn set_on_key(&self, on_key: Option<KeyEventCallback>) {
+        *self.on_key.borrow_mut() = on_key;
+    }
+
+    /// Called when this node or one of its descendants gains or loses the focus.
+    pub fn set_on_focus_change(&self, on_focus_change: Option<FocusChangeCallback>) {
+        *self.on_focus_change.borrow_mut() = on_focus_change;
+    }
+
+    /// Shortcuts for the key events reaching this node, after its key callback.
+    pub fn set_shortcuts(&self, shortcuts: ShortcutMap) {
+        *self.shortcuts.borrow_mut() = shortcuts;
+    }
+
+    /// Actions for the intents of the shortcuts of this node and its descendants.
+    pub fn set_actions(&self, actions: ActionMap) {
+        *self.actions.borrow_mut() = actions;
+    }
+
+    /// Whether this node or one of its descendants has the primary focus.
+    pub fn has_focus(&self) -> bool {
+        self.has_focus.get()
+    }
+
+    /// Whether this node receives the key events first.
+    pub fn has_primary_focus(&self) -> bool {
+        self.manager
+            .borrow()
+            .upgrade()
+            .and_then(|manager| manager.primary_focus())
+            .map_or(false, |primary| std::ptr::eq(&*primary, self))
+    }
+
+    /// The enclosing node, `None` for the root.
+    pub fn parent(&self) -> Option<Rc<FocusNode>> {
+        self.parent.borrow().upgrade()
+    }
+
+    pub fn children(&self) -> Vec<Rc<FocusNode>> {
+        self.children
+            .borrow()
+            .iter()
+            .filter_map(|child| child.upgrade())
+            .collect()
+    }
+
+    /// Whether the last update of the focus tree found this node in the render tree.
+    pub fn is_attached(&self) -> bool {
+        self.attached.get()
+    }
+
+    /// The bounds of the render object of this node on screen, as last painted.
+    pub fn rect(&self) -> Rect {
+        self.rect.get()
+    }
+
+    /// The nearest scope enclosing this node, not the node itself.
+    pub fn enclosing_scope(&self) -> Option<Rc<FocusNode>> {
+        let mut parent = self.parent();
+        while let Some(node) = parent {
+            if node.is_scope {
+                return Some(node);
+            }
+            parent = node.parent();
+        }
+        None
+    }
+
+    /// Moves the primary focus to this node, or for a scope to the node focused last in
+    /// it. The change applies with the next event or frame, a node not attached yet is
+    /// focused once it is.
+    pub fn request_focus(self: &Rc<Self>) {
+        match self.manager.borrow().upgrade() {
+            Some(manager) if self.attached.get() => manager.request(self),
+            _ => self.requested.set(true),
+        }
+    }
+
+    /// Moves the primary focus from this node, or its focused descendant, to the enclosing
+    /// scope.
+    pub fn unfocus(&self) {
+        if !self.has_focus.get() {
+            return;
+        }
+        let manager = self.manager.borrow().upgrade();
+        if let (Some(manager), Some(scope)) = (manager, self.enclosing_scope()) {
+            *scope.focused_child.borrow_mut() = Weak::new();
+            manager.request(&scope);
+        }
+    }
+
+    /// The render object of this node as the target of key events.
+    fn target(&self) -> Option<Rc<RefCell<dyn HitTestTarget>>> {
+        self.target
+            .borrow()
+            .as_ref()
+            .and_then(|target| target.upgrade())
+    }
+
+    fn handle_key_event(&self, event: &KeyEvent) -> KeyEventResult {
+        let on_key = self.on_key.borrow().clone();
+        let result = on_key.map_or(KeyEventResult::Ignored, |on_key| on_key.call(event));
+        if result != KeyEventResult::Ignored {
+            return result;
+        }
+        let intent = self.shortcuts.borrow().find(event);
+        let manager = self.manager.borrow().upgrade();
+        if let (Some(intent), Some(manager)) = (intent, manager) {
+            if manager.invoke_action(&*intent) {
+                return KeyEventResult::Handled;
+            }
+        }
+        match self.target() {
+            Some(target) if target.borrow_mut().handle_key_event(event) => KeyEventResult::Handled,
+            _ => KeyEventResult::Ignored,
+        }
+    }
+
+    fn set_has_focus(&self, has_focus: bool) {
+        if self.has_focus.replace(has_focus) == has_focus {
+            return;
+        }
+        let on_focus_change = self.on_focus_change.borrow().clone();
+        if let Some(on_focus_change) = on_focus_change {
+            on_focus_change.call(has_focus);
+        }
+        if let Some(target) = self.target() {
+            target.borrow_mut().handle_focus_change(has_focus);
+        }
+    }
+
+    /// Whether traversal visits this node.
+    fn is_traversable(&self) -> bool {
+        self.can_request_focus.get() && !self.skip_traversal.get()
+    }
+}
+
+impl Default for FocusNode {
+    fn default() -> Self {
+        FocusNode::new()
+    }
+}
+
+impl fmt::Debug for FocusNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FocusNode")
+            .field("debug_label", &self.debug_label)
+            .field("is_scope", &self.is_scope)
+            .field("has_focus", &self.has_focus.get())
+            .field("rect", &self.rect.get())
+            .finish()
+    }
+}
+
+/// Owns the focus tree and routes key and input method events to its focused node.
+///
+/// Focus changes requested while handling an event apply once it is handled, so a
+/// render object can request the focus from its own handlers.
+#[derive(Debug)]
+pub struct FocusManager {
+    root: Rc<FocusNode>,
+    primary_focus: RefCell<Weak<FocusNode>>,
+    /// The nodes that have the focus, the primary focus first.
+    focus_chain: RefCell<Vec<Weak<FocusNode>>>,
+    requested: RefCell<Weak<FocusNode>>,
+    nodes: RefCell<Vec<Weak<FocusNode>>>,
+}
+
+impl FocusManager {
+    pub fn new() -> Rc<Self> {
+        Rc::new_cyclic(|manager| {
+            let root = Rc::new(FocusNode::scope().with_debug_label("root"));
+            *root.manager.borrow_mut() = manager.clone();
+            root.attached.set(true);
+            FocusManager {
+                root,
+                primary_focus: RefCell::new(Weak::new()),
+                focus_chain: RefCell::new(Vec::new()),
+                requested: RefCell::new(Weak::new()),
+                nodes: RefCell::new(Vec::new()),
+            }
+        })
+    }
+
+    /// The scope enclosing all the nodes.
+    pub fn root_scope(&self) -> &Rc<FocusNode> {
+        &self.root
+    }
+
+    pub fn primary_focus(&self) -> Option<Rc<FocusNode>> {
+        self.primary_focus.borrow().upgrade()
+    }
+
+    /// Rebuilds the focus tree from the nodes of the render objects under `root`, with
+    /// their bounds as last painted.
+    ///
+    /// Requests of the nodes attached for the first time apply, and a focused node no
+    /// longer in the tree passes the focus to its nearest ancestor still in it.
+    pub fn update_tree(self: &Rc<Self>, root: &Rc<RefCell<dyn RenderBox>>) {
+        for node in self.nodes.take() {
+            if let Some(node) = node.upgrade() {
+                node.attached.set(false);
+            }
+        }
+        self.root.children.borrow_mut().clear();
+        self.attach(root, &self.root.clone());
+
+        let primary = self.primary_focus();
+        if let Some(primary) = primary.filter(|node| !node.attached.get()) {
+            let mut ancestor = primary.parent();
+            while let Some(node) = ancestor.clone().filter(|node| !node.attached.get()) {
+                ancestor = node.parent();
+            }
+            let ancestor = ancestor.unwrap_or_else(|| self.root.clone());
+            let requested = self.requested.borrow().upgrade();
+            if requested.map_or(true, |node| !node.attached.get()) {
+                self.request(&ancestor);
+            }
+        }
+        self.apply_focus_changes();
+    }
+
+    fn attach(self: &Rc<Self>, object: &Rc<RefCell<dyn RenderBox>>, parent: &Rc<FocusNode>) {
+        let render_box = object.borrow();
+        let parent = match render_box.focus_node() {
+            Some(node) => {
+                let offset = render_box.state().global_paint_offset();
+                let size = render_box.size();
+                let target: Rc<RefCell<dyn HitTestTarget>> = object.clone();
+                *node.target.borrow_mut() = Some(Rc::downgrade(&target));
+                node.rect
+                    .set(Rect::from_xywh(offset.x, offset.y, size.width, size.height));
+                *node.manager.borrow_mut() = Rc::downgrade(self);
+                *node.parent.borrow_mut() = Rc::downgrade(parent);
+                node.children.borrow_mut().clear();
+                node.attached.set(true);
+                parent.children.borrow_mut().push(Rc::downgrade(&node));
+                self.nodes.borrow_mut().push(Rc::downgrade(&node));
+                if node.requested.take() {
+                    self.request(&node);
+                }
+                node
+            }
+            None => parent.clone(),
+        };
+        render_box.visit_children(&mut |child| self.attach(child, &parent));
+        render_box.visit_slivers(&mut |sliver| {
+            sliver
+                .borrow()
+                .visit_children(&mut |child| self.attach(child, &parent))
+        });
+    }
+
+    fn request(&self, node: &Rc<FocusNode>) {
+        *self.requested.borrow_mut() = Rc::downgrade(node);
+    }
+
+    /// Moves the primary focus to the node requesting it last, telling the nodes which
+    /// gain or lose the focus.
+    pub fn apply_focus_changes(&self) {
+        let mut node = match self.requested.take().upgrade() {
+            Some(node) => node,
+            None => return,
+        };
+        while node.is_scope {
+            let focused_child = node.focused_child.borrow().upgrade();
+            match focused_child {
+                Some(child) if child.attached.get() => node = child,
+                _ => break,
+            }
+        }
+        if !node.attached.get() || !node.can_request_focus.get() {
+            return;
+        }
+
+        let mut chain = vec![node.clone()];
+        while let Some(parent) = chain.last().unwrap().parent() {
+            chain.push(parent);
+        }
+        for scope in chain.iter().skip(1).filter(|scope| scope.is_scope) {
+            *scope.focused_child.borrow_mut() = Rc::downgrade(&node);
+        }
+        *self.primary_focus.borrow_mut() = Rc::downgrade(&node);
+
+        let old_chain: Vec<_> = self
+            .focus_chain
+            .replace(chain.iter().map(Rc::downgrade).collect())
+            .iter()
+            .filter_map(|node| node.upgrade())
+            .collect();
+        let contains = |chain: &[Rc<FocusNode>], node: &Rc<FocusNode>| {
+            chain.iter().any(|n| Rc::ptr_eq(n, node))
+        };
+        for lost in old_chain.iter().filter(|n| !contains(&chain, n)) {
+            lost.set_has_focus(false);
+        }
+        for gained in chain.iter().filter(|n| !contains(&old_chain, n)) {
+            gained.set_has_focus(true);
+        }
+    }
+
+    /// Dispatches `event` to the primary focus and its ancestors, from the innermost
+    /// outwards, until one handles it. Returns whether one did.
+    ///
+    /// A node handles an event with its key callback first, then with the action of the
+    /// shortcut it triggers, then with its render object.
+    /// Tab and Shift+Tab no node handles move the focus in reading order.
+    pub fn handle_key_event(&self, event: &KeyEvent) -> bool {
+        self.apply_focus_changes();
+        let mut node = self.primary_focus().or_else(|| Some(self.root.clone()));
+        while let Some(current) = node {
+            match current.handle_key_event(event) {
+                KeyEventResult::Handled => return true,
+                KeyEventResult::SkipRemainingHandlers => return false,
+                KeyEventResult::Ignored => node = current.parent(),
+            }
+        }
+        let modifiers = event.modifiers;
+        if event.is_down()
+            && event.logical_key == LogicalKey::Tab
+            && !(modifiers.control || modifiers.alt || modifiers.meta)
+        {
+            return if modifiers.shift {
+                self.previous_focus()
+            } else {
+                self.next_focus()
+            };
+        }
+        false
+    }
+
+    /// Focuses the next node in reading order in the scope of the primary focus, wrapping
+    /// around. Returns whether there is a node to focus.
+    pub fn next_focus(&self) -> bool {
+        self.move_focus(true)
+    }
+
+    /// Focuses the previous node in reading order in the scope of the primary focus,
+    /// wrapping around. Returns whether there is a node to focus.
+    pub fn previous_focus(&self) -> bool {
+        self.move_focus(false)
+    }
+
+    fn move_focus(&self, forward: bool) -> bool {
+        self.apply_focus_changes();
+        let primary = self.primary_focus();
+        let scope = primary
+            .as_ref()
+            .and_then(|node| node.enclosing_scope())
+            .unwrap_or_else(|| self.root.clone());
+        let mut candidates = Vec::new();
+        traversal_candidates(&scope, &mut candidates);
+        let order = reading_order(candidates);
+        if order.is_empty() {
+            return false;
+        }
+
+        let index = primary.and_then(|primary| order.iter().position(|n| Rc::ptr_eq(n, &primary)));
+        let next = match (index, forward) {
+            (Some(index), true) => (index + 1) % order.len(),
+            (Some(index), false) => (index + order.len() - 1) % order.len(),
+            (None, true) => 0,
+            (None, false) => order.len() - 1,
+        };
+        self.request(&order[next]);
+        self.apply_focus_changes();
+        true
+    }
+
+    /// Invokes the action bound to the type of `intent` by the nearest node from the
+    /// primary focus up that binds one, returns whether it was enabled.
+    ///
+    /// A disabled action hides those further up, the intent is not carried out.
+    pub fn invoke_action(&self, intent: &dyn Intent) -> bool {
+        let mut node = self.primary_focus().or_else(|| Some(self.root.clone()));
+        while let Some(current) = node {
+            let action = current.actions.borrow().find(intent);
+            if let Some(action) = action {
+                if !action.is_enabled(intent) {
+                    return false;
+                }
+                action.invoke(intent);
+                return true;
+            }
+            node = current.parent();
+        }
+        false
+    }
+
+    /// Dispatches `event` to the primary focus if it is a text input client, returns
+    /// whether it is.
+    pub fn handle_ime_event(&self, event: &ImeEvent) -> bool {
+        self.apply_focus_changes();
+        let target = match self.primary_focus().and_then(|node| node.target()) {
+            Some(target) => target,
+            None => return false,
+        };
+        let mut target = target.borrow_mut();
+        match target.text_input_client() {
+            Some(client) => {
+                client.handle_ime_event(event);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The caret of the focused text input client on screen, where the platform places
+    /// the candidate window of its input method.
+    pub fn ime_caret_rect(&self) -> Option<Rect> {
+        let node = self.primary_focus()?;
+        let target = node.target()?;
+        let rect = target.borrow_mut().text_input_client()?.caret_rect();
+        let origin = node.rect();
+        Some(rect.with_offset((origin.left, origin.top)))
+    }
+}
+
+/// The nodes traversal visits in `scope`, a nested scope is visited as a single node.
+fn traversal_candidates(scope: &FocusNode, candidates: &mut Vec<Rc<FocusNode>>) {
+    for child in scope.children() {
+        if child.is_traversable() {
+            candidates.push(child.clone());
+        }
+        if !child.is_scope {
+            traversal_candidates(&child, candidates);
+        }
+    }
+}
+
+/// Sorts `nodes` in reading order: rows from top to bottom, each from left to right.
+///
+/// A row starts with the top-most node left and takes the nodes overlapping it
+/// vertically, so boxes of different heights side by side read as one row.
+fn reading_order(mut nodes: Vec<Rc<FocusNode>>) -> Vec<Rc<FocusNode>> {
+    let mut sorted = Vec::with_capacity(nodes.len());
+    while !nodes.is_empty() {
+        let first = nodes
+            .iter()
+            .min_by(|a, b| a.rect().top.total_cmp(&b.rect().top))
+            .unwrap()
+            .clone();
+        let band = first.rect();
+        let (mut row, rest): (Vec<_>, Vec<_>) = nodes.into_iter().partition(|node| {
+            let rect = node.rect();
+            Rc::ptr_eq(node, &first) || (rect.top < band.bottom && rect.bottom > band.top)
+        });
+        row.sort_by(|a, b| a.rect().left.total_cmp(&b.rect().left));
+        sorted.extend(row);
+        nodes = rest;
+    }
+    sorted
+}
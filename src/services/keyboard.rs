@@ -0,0 +1,134 @@
+use std::ops::BitOr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyEventKind {
+    /// The key was pressed.
+    Down,
+    /// The key is held down and the platform repeats it.
+    Repeat,
+    /// The key was released.
+    Up,
+}
+
+/// The meaning of a key in the current keyboard layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LogicalKey {
+    /// A key producing a character, letters in lower case whatever the modifiers.
+    Character(char),
+    Enter,
+    Tab,
+    Backspace,
+    Delete,
+    Escape,
+    ArrowLeft,
+    ArrowRight,
+    ArrowUp,
+    ArrowDown,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Shift,
+    Control,
+    Alt,
+    Meta,
+}
+
+/// The key on the keyboard whatever the layout, the scancode the platform reports, e.g. to
+/// bind the keys in the position of WASD on any layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PhysicalKey(pub u32);
+
+impl PhysicalKey {
+    /// A key the platform reported no scancode for, e.g. a synthesized event.
+    pub const UNIDENTIFIED: PhysicalKey = PhysicalKey(0);
+}
+
+/// The modifier keys held down, combined with `|`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub struct Modifiers {
+    pub shift: bool,
+    pub control: bool,
+    pub alt: bool,
+    pub meta: bool,
+}
+
+impl Modifiers {
+    pub const NONE: Modifiers = Modifiers {
+        shift: false,
+        control: false,
+        alt: false,
+        meta: false,
+    };
+    pub const SHIFT: Modifiers = Modifiers {
+        shift: true,
+        ..Modifiers::NONE
+    };
+    pub const CONTROL: Modifiers = Modifiers {
+        control: true,
+        ..Modifiers::NONE
+    };
+    pub const ALT: Modifiers = Modifiers {
+        alt: true,
+        ..Modifiers::NONE
+    };
+    pub const META: Modifiers = Modifiers {
+        meta: true,
+        ..Modifiers::NONE
+    };
+}
+
+impl BitOr for Modifiers {
+    type Output = Modifiers;
+
+    fn bitor(self, rhs: Modifiers) -> Modifiers {
+        Modifiers {
+            shift: self.shift || rhs.shift,
+            control: self.control || rhs.control,
+            alt: self.alt || rhs.alt,
+            meta: self.meta || rhs.meta,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyEvent {
+    pub kind: KeyEventKind,
+    pub logical_key: LogicalKey,
+    pub physical_key: PhysicalKey,
+    pub modifiers: Modifiers,
+    /// The text the key produces, if any, e.g. `"A"` for the `a` key with shift.
+    pub character: Option<String>,
+}
+
+impl KeyEvent {
+    pub fn new(kind: KeyEventKind, logical_key: LogicalKey) -> Self {
+        KeyEvent {
+            kind,
+            logical_key,
+            physical_key: PhysicalKey::UNIDENTIFIED,
+            modifiers: Modifiers::NONE,
+            character: None,
+        }
+    }
+
+    pub fn with_physical_key(mut self, physical_key: PhysicalKey) -> Self {
+        self.physical_key = physical_key;
+        self
+    }
+
+    pub fn with_modifiers(mut self, modifiers: Modifiers) -> Self {
+        self.modifiers = modifiers;
+        self
+    }
+
+    pub fn with_character(mut self, character: impl Into<String>) -> Self {
+        self.character = Some(character.into());
+        self
+    }
+
+    /// Whether the key went down or repeats, i.e. the key is acting.
+    pub fn is_down(&self) -> bool {
+        self.kind != KeyEventKind::Up
+    }
+}
@@ -0,0 +1,20 @@
+mod keyboard;
+pub use keyboard::*;
+
+mod focus;
+pub use focus::*;
+
+mod text_editing;
+pub use text_editing::*;
+
+mod text_input;
+pub use text_input::*;
+
+mod raw_keyboard;
+pub use raw_keyboard::*;
+
+mod actions;
+pub use actions::*;
+
+mod shortcuts;
+pub use shortcuts::*;
@@ -0,0 +1,157 @@
+use std::collections::HashSet;
+
+use winit::event::{ElementState, ModifiersState, VirtualKeyCode, WindowEvent};
+
+use crate::services::{ImeEvent, KeyEvent, KeyEventKind, LogicalKey, Modifiers, PhysicalKey};
+
+/// The keyboard input of a window event.
+#[derive(Debug, Clone, PartialEq)]
+pub enum KeyboardInput {
+    Key(KeyEvent),
+    Ime(ImeEvent),
+}
+
+/// Turns the keyboard events of a winit window into key and input method events.
+///
+/// winit reports a key held down as presses without releases, the presses after the
+/// first become repeats. The text typed arrives apart from the keys, as the commits of
+/// an input method.
+#[derive(Debug, Default)]
+pub struct RawKeyboard {
+    modifiers: Modifiers,
+    pressed: HashSet<u32>,
+}
+
+impl RawKeyboard {
+    pub fn new() -> Self {
+        RawKeyboard::default()
+    }
+
+    /// The modifier keys held down.
+    pub fn modifiers(&self) -> Modifiers {
+        self.modifiers
+    }
+
+    /// The keyboard input of `event`, `None` for other events and keys oxui does not know.
+    pub fn handle_window_event(&mut self, event: &WindowEvent) -> Option<KeyboardInput> {
+        match event {
+            WindowEvent::ModifiersChanged(state) => {
+                self.modifiers = modifiers(*state);
+                None
+            }
+            WindowEvent::KeyboardInput { input, .. } => {
+                let kind = match input.state {
+                    ElementState::Pressed if !self.pressed.insert(input.scancode) => {
+                        KeyEventKind::Repeat
+                    }
+                    ElementState::Pressed => KeyEventKind::Down,
+                    ElementState::Released => {
+                        self.pressed.remove(&input.scancode);
+                        KeyEventKind::Up
+                    }
+                };
+                let logical_key = logical_key(input.virtual_keycode?)?;
+                let event = KeyEvent::new(kind, logical_key)
+                    .with_physical_key(PhysicalKey(input.scancode))
+                    .with_modifiers(self.modifiers);
+                Some(KeyboardInput::Key(event))
+            }
+            WindowEvent::ReceivedCharacter(c) => {
+                ImeEvent::from_received_character(*c).map(KeyboardInput::Ime)
+            }
+            WindowEvent::Focused(false) => {
+                // the keys released in another window are never reported
+                self.pressed.clear();
+                None
+            }
+            _ => None,
+        }
+    }
+}
+
+fn modifiers(state: ModifiersState) -> Modifiers {
+    Modifiers {
+        shift: state.shift(),
+        control: state.ctrl(),
+        alt: state.alt(),
+        meta: state.logo(),
+    }
+}
+
+fn logical_key(code: VirtualKeyCode) -> Option<LogicalKey> {
+    use VirtualKeyCode::*;
+
+    let key = match code {
+        Key1 | Numpad1 => LogicalKey::Character('1'),
+        Key2 | Numpad2 => LogicalKey::Character('2'),
+        Key3 | Numpad3 => LogicalKey::Character('3'),
+        Key4 | Numpad4 => LogicalKey::Character('4'),
+        Key5 | Numpad5 => LogicalKey::Character('5'),
+        Key6 | Numpad6 => LogicalKey::Character('6'),
+        Key7 | Numpad7 => LogicalKey::Character('7'),
+        Key8 | Numpad8 => LogicalKey::Character('8'),
+        Key9 | Numpad9 => LogicalKey::Character('9'),
+        Key0 | Numpad0 => LogicalKey::Character('0'),
+        A => LogicalKey::Character('a'),
+        B => LogicalKey::Character('b'),
+        C => LogicalKey::Character('c'),
+        D => LogicalKey::Character('d'),
+        E => LogicalKey::Character('e'),
+        F => LogicalKey::Character('f'),
+        G => LogicalKey::Character('g'),
+        H => LogicalKey::Character('h'),
+        I => LogicalKey::Character('i'),
+        J => LogicalKey::Character('j'),
+        K => LogicalKey::Character('k'),
+        L => LogicalKey::Character('l'),
+        M => LogicalKey::Character('m'),
+        N => LogicalKey::Character('n'),
+        O => LogicalKey::Character('o'),
+        P => LogicalKey::Character('p'),
+        Q => LogicalKey::Character('q'),
+        R => LogicalKey::Character('r'),
+        S => LogicalKey::Character('s'),
+        T => LogicalKey::Character('t'),
+        U => LogicalKey::Character('u'),
+        V => LogicalKey::Character('v'),
+        W => LogicalKey::Character('w'),
+        X => LogicalKey::Character('x'),
+        Y => LogicalKey::Character('y'),
+        Z => LogicalKey::Character('z'),
+        Space => LogicalKey::Character(' '),
+        Minus | NumpadSubtract => LogicalKey::Character('-'),
+        Equals | NumpadEquals => LogicalKey::Character('='),
+        Plus | NumpadAdd => LogicalKey::Character('+'),
+        Asterisk | NumpadMultiply => LogicalKey::Character('*'),
+        Slash | NumpadDivide => LogicalKey::Character('/'),
+        Period | NumpadDecimal => LogicalKey::Character('.'),
+        Comma | NumpadComma => LogicalKey::Character(','),
+        Semicolon => LogicalKey::Character(';'),
+        Colon => LogicalKey::Character(':'),
+        Apostrophe => LogicalKey::Character('\''),
+        Grave => LogicalKey::Character('`'),
+        Backslash => LogicalKey::Character('\\'),
+        LBracket => LogicalKey::Character('['),
+        RBracket => LogicalKey::Character(']'),
+        At => LogicalKey::Character('@'),
+        Return | NumpadEnter => LogicalKey::Enter,
+        Tab => LogicalKey::Tab,
+        Back => LogicalKey::Backspace,
+        Delete => LogicalKey::Delete,
+        Escape => LogicalKey::Escape,
+        Left => LogicalKey::ArrowLeft,
+        Right => LogicalKey::ArrowRight,
+        Up => LogicalKey::ArrowUp,
+        Down => LogicalKey::ArrowDown,
+        Home => LogicalKey::Home,
+        End => LogicalKey::End,
+        PageUp => LogicalKey::PageUp,
+        PageDown => LogicalKey::PageDown,
+        LShift | RShift => LogicalKey::Shift,
+        LControl | RControl => LogicalKey::Control,
+        LAlt | RAlt => LogicalKey::Alt,
+        LWin | RWin => LogicalKey::Meta,
+        _ => return None,
+    };
+    Some(key)
+}
@@ -0,0 +1,77 @@
+use std::rc::Rc;
+
+use crate::services::{Intent, KeyEvent, KeyEventKind, LogicalKey, Modifiers};
+
+/// A key pressed with exactly some modifiers, e.g. Ctrl+S but not Ctrl+Shift+S.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SingleActivator {
+    pub key: LogicalKey,
+    pub modifiers: Modifiers,
+    /// Whether the key held down triggers it again as the platform repeats it.
+    pub include_repeats: bool,
+}
+
+impl SingleActivator {
+    pub fn new(key: LogicalKey) -> Self {
+        SingleActivator {
+            key,
+            modifiers: Modifiers::NONE,
+            include_repeats: true,
+        }
+    }
+
+    pub fn with_modifiers(mut self, modifiers: Modifiers) -> Self {
+        self.modifiers = modifiers;
+        self
+    }
+
+    pub fn with_include_repeats(mut self, include_repeats: bool) -> Self {
+        self.include_repeats = include_repeats;
+        self
+    }
+
+    /// Whether `event` triggers the shortcut.
+    pub fn accepts(&self, event: &KeyEvent) -> bool {
+        let kind_accepted = match event.kind {
+            KeyEventKind::Down => true,
+            KeyEventKind::Repeat => self.include_repeats,
+            KeyEventKind::Up => false,
+        };
+        kind_accepted && event.logical_key == self.key && event.modifiers == self.modifiers
+    }
+}
+
+/// Shortcuts from the keys that trigger them to the intents they ask for.
+#[derive(Debug, Clone, Default)]
+pub struct ShortcutMap {
+    shortcuts: Vec<(SingleActivator, Rc<dyn Intent>)>,
+}
+
+impl ShortcutMap {
+    pub fn new() -> Self {
+        ShortcutMap::default()
+    }
+
+    /// Maps `activator` to `intent`, replacing the intent it was mapped to.
+    pub fn with(mut self, activator: SingleActivator, intent: impl Intent) -> Self {
+        self.insert(activator, intent);
+        self
+    }
+
+    pub fn insert(&mut self, activator: SingleActivator, intent: impl Intent) {
+        self.shortcuts.retain(|(a, _)| *a != activator);
+        self.shortcuts.push((activator, Rc::new(intent)));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shortcuts.is_empty()
+    }
+
+    /// The intent of the shortcut `event` triggers.
+    pub fn find(&self, event: &KeyEvent) -> Option<Rc<dyn Intent>> {
+        self.shortcuts
+            .iter()
+            .find(|(activator, _)| activator.accepts(event))
+            .map(|(_, intent)| intent.clone())
+    }
+}
@@ -0,0 +1,345 @@
+use std::{
+    cell::RefCell,
+    ops::Range,
+    rc::{Rc, Weak},
+};
+
+use crate::rendering::RenderState;
+
+/// Number of UTF-16 code units of `text`, the unit text positions are measured in.
+pub fn utf16_len(text: &str) -> usize {
+    text.chars().map(char::len_utf16).sum()
+}
+
+/// The byte index of the UTF-16 `offset` in `text`, clamped to its length.
+fn byte_index(text: &str, offset: usize) -> usize {
+    let mut position = 0;
+    for (index, c) in text.char_indices() {
+        if position >= offset {
+            return index;
+        }
+        position += c.len_utf16();
+    }
+    text.len()
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// A range of text between `base`, where the selection started, and `extent`, where it
+/// ends and the caret is. Collapsed to a caret when both are the same.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub struct TextSelection {
+    pub base: usize,
+    pub extent: usize,
+}
+
+impl TextSelection {
+    pub fn new(base: usize, extent: usize) -> Self {
+        TextSelection { base, extent }
+    }
+
+    pub fn collapsed(offset: usize) -> Self {
+        TextSelection::new(offset, offset)
+    }
+
+    pub fn is_collapsed(&self) -> bool {
+        self.base == self.extent
+    }
+
+    pub fn start(&self) -> usize {
+        self.base.min(self.extent)
+    }
+
+    pub fn end(&self) -> usize {
+        self.base.max(self.extent)
+    }
+
+    /// This selection with the caret moved to `offset`, keeping the base.
+    pub fn extend_to(&self, offset: usize) -> Self {
+        TextSelection::new(self.base, offset)
+    }
+}
+
+/// Text being edited and its selection, positions are in UTF-16 code units.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TextEditingValue {
+    pub text: String,
+    pub selection: TextSelection,
+    /// The text an input method is still composing, replaced when it commits.
+    pub composing: Option<Range<usize>>,
+}
+
+impl TextEditingValue {
+    /// `text` with the caret at its end.
+    pub fn new(text: impl Into<String>) -> Self {
+        let text = text.into();
+        let selection = TextSelection::collapsed(utf16_len(&text));
+        TextEditingValue {
+            text,
+            selection,
+            composing: None,
+        }
+    }
+
+    pub fn with_selection(mut self, selection: TextSelection) -> Self {
+        self.selection = selection;
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        utf16_len(&self.text)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    pub fn selected_text(&self) -> &str {
+        self.slice(self.selection.start(), self.selection.end())
+    }
+
+    /// The text between the positions `start` and `end`.
+    pub fn slice(&self, start: usize, end: usize) -> &str {
+        &self.text[byte_index(&self.text, start)..byte_index(&self.text, end)]
+    }
+
+    /// The chars of the text with their UTF-16 start offset.
+    fn chars(&self) -> impl DoubleEndedIterator<Item = (usize, char)> + '_ {
+        let mut offset = 0;
+        let chars: Vec<_> = self
+            .text
+            .chars()
+            .map(|c| {
+                let start = offset;
+                offset += c.len_utf16();
+                (start, c)
+            })
+            .collect();
+        chars.into_iter()
+    }
+
+    /// The offset of the char boundary before `offset`.
+    pub fn previous_char_boundary(&self, offset: usize) -> usize {
+        self.chars()
+            .rev()
+            .find(|(start, _)| *start < offset)
+            .map(|(start, _)| start)
+            .unwrap_or(0)
+    }
+
+    /// The offset of the char boundary after `offset`.
+    pub fn next_char_boundary(&self, offset: usize) -> usize {
+        self.chars()
+            .find(|(start, c)| start + c.len_utf16() > offset)
+            .map(|(start, c)| start + c.len_utf16())
+            .unwrap_or_else(|| self.len())
+    }
+
+    /// The start of the word before `offset`, skipping the spaces and punctuation
+    /// in between.
+    pub fn previous_word_boundary(&self, offset: usize) -> usize {
+        let mut boundary = 0;
+        let mut in_word = false;
+        for (start, c) in self.chars().rev().skip_while(|(start, _)| *start >= offset) {
+            if is_word_char(c) {
+                in_word = true;
+            } else if in_word {
+                return boundary;
+            }
+            boundary = start;
+        }
+        boundary
+    }
+
+    /// The end of the word after `offset`, skipping the spaces and punctuation in
+    /// between.
+    pub fn next_word_boundary(&self, offset: usize) -> usize {
+        let mut in_word = false;
+        for (start, c) in self.chars().skip_while(|(start, _)| *start < offset) {
+            if is_word_char(c) {
+                in_word = true;
+            } else if in_word {
+                return start;
+            }
+        }
+        self.len()
+    }
+
+    /// Replaces the selection with `text`, leaving the caret after it.
+    pub fn replace_selection(&self, text: &str) -> TextEditingValue {
+        self.replace(self.selection.start(), self.selection.end(), text)
+    }
+
+    /// Replaces the text between `start` and `end` with `text`, leaving the caret after it.
+    pub fn replace(&self, start: usize, end: usize, text: &str) -> TextEditingValue {
+        let mut value = self.text.clone();
+        value.replace_range(
+            byte_index(&self.text, start)..byte_index(&self.text, end),
+            text,
+        );
+        TextEditingValue {
+            text: value,
+            selection: TextSelection::collapsed(start + utf16_len(text)),
+            composing: None,
+        }
+    }
+
+    /// Replaces the composing region, or the selection when not composing, with `text`
+    /// an input method is composing. `selection` is relative to `text`, the caret goes
+    /// after it when `None`.
+    pub fn compose(&self, text: &str, selection: Option<(usize, usize)>) -> TextEditingValue {
+        let range = self
+            .composing
+            .clone()
+            .unwrap_or_else(|| self.selection.start()..self.selection.end());
+        let mut value = self.replace(range.start, range.end, text);
+        let len = utf16_len(text);
+        if let Some((base, extent)) = selection {
+            value.selection =
+                TextSelection::new(range.start + base.min(len), range.start + extent.min(len));
+        }
+        value.composing = (len > 0).then(|| range.start..range.start + len);
+        value
+    }
+
+    /// This value without the text being composed, as it was before composing started.
+    pub fn without_composing(&self) -> TextEditingValue {
+        match &self.composing {
+            Some(range) => self.replace(range.start, range.end, ""),
+            None => self.clone(),
+        }
+    }
+
+    /// Deletes the selection, or the char or word before the caret.
+    pub fn delete_backward(&self, by_word: bool) -> TextEditingValue {
+        if !self.selection.is_collapsed() {
+            return self.replace_selection("");
+        }
+        let end = self.selection.extent;
+        let start = if by_word {
+            self.previous_word_boundary(end)
+        } else {
+            self.previous_char_boundary(end)
+        };
+        self.replace(start, end, "")
+    }
+
+    /// Deletes the selection, or the char or word after the caret.
+    pub fn delete_forward(&self, by_word: bool) -> TextEditingValue {
+        if !self.selection.is_collapsed() {
+            return self.replace_selection("");
+        }
+        let start = self.selection.extent;
+        let end = if by_word {
+            self.next_word_boundary(start)
+        } else {
+            self.next_char_boundary(start)
+        };
+        self.replace(start, end, "")
+    }
+}
+
+/// The values a text field had before its edits, to undo and redo them.
+#[derive(Debug, Clone, Default)]
+pub struct UndoHistory {
+    undo: Vec<TextEditingValue>,
+    redo: Vec<TextEditingValue>,
+}
+
+impl UndoHistory {
+    /// Records the value replaced by an edit, forgetting the edits undone before.
+    pub fn record(&mut self, previous: TextEditingValue) {
+        self.undo.push(previous);
+        self.redo.clear();
+    }
+
+    /// The value before the last edit, `current` can be restored by a redo.
+    pub fn undo(&mut self, current: TextEditingValue) -> Option<TextEditingValue> {
+        let previous = self.undo.pop()?;
+        self.redo.push(current);
+        Some(previous)
+    }
+
+    /// The value the last undo replaced, `current` can be restored by an undo.
+    pub fn redo(&mut self, current: TextEditingValue) -> Option<TextEditingValue> {
+        let next = self.redo.pop()?;
+        self.undo.push(current);
+        Some(next)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+}
+
+/// The value of a text field, shared between the field and the app.
+///
+/// The fields attached to it lay out again whenever the value changes.
+#[derive(Debug, Default)]
+pub struct TextEditingController {
+    value: RefCell<TextEditingValue>,
+    editables: RefCell<Vec<Weak<RenderState>>>,
+}
+
+impl TextEditingController {
+    pub fn new(text: impl Into<String>) -> Self {
+        TextEditingController {
+            value: RefCell::new(TextEditingValue::new(text)),
+            ..Default::default()
+        }
+    }
+
+    pub fn value(&self) -> TextEditingValue {
+        self.value.borrow().clone()
+    }
+
+    pub fn text(&self) -> String {
+        self.value.borrow().text.clone()
+    }
+
+    pub fn selection(&self) -> TextSelection {
+        self.value.borrow().selection
+    }
+
+    pub fn set_value(&self, value: TextEditingValue) {
+        if *self.value.borrow() == value {
+            return;
+        }
+        *self.value.borrow_mut() = value;
+        self.editables.borrow_mut().retain(|editable| {
+            if let Some(editable) = editable.upgrade() {
+                editable.mark_needs_layout();
+                true
+            } else {
+                false
+            }
+        });
+    }
+
+    /// Replaces the text, leaving the caret at its end.
+    pub fn set_text(&self, text: impl Into<String>) {
+        self.set_value(TextEditingValue::new(text));
+    }
+
+    pub fn set_selection(&self, selection: TextSelection) {
+        let value = self.value().with_selection(selection);
+        self.set_value(value);
+    }
+
+    /// Relayouts the editable owning `state` whenever the value changes.
+    pub fn attach(&self, state: &Rc<RenderState>) {
+        self.editables.borrow_mut().push(Rc::downgrade(state));
+    }
+
+    pub fn detach(&self, state: &Rc<RenderState>) {
+        self.editables
+            .borrow_mut()
+            .retain(|editable| editable.as_ptr() != Rc::as_ptr(state));
+    }
+}
@@ -0,0 +1,121 @@
+use skia_safe::Rect;
+
+/// Text input from an input method, e.g. composing CJK text from its reading.
+///
+/// Positions are in UTF-16 code units, converted from those of the platform.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImeEvent {
+    /// The input method is ready to compose text.
+    Enabled,
+
+    /// The text being composed, and the selection within it, `None` to hide the caret.
+    /// Empty text ends the composition without committing it.
+    Preedit(String, Option<(usize, usize)>),
+
+    /// The text composed, it replaces the text being composed.
+    Commit(String),
+
+    /// The input method stopped, the text being composed is dropped.
+    Disabled,
+}
+
+impl ImeEvent {
+    /// The commit of a char winit reports as `ReceivedCharacter`, `None` for the control
+    /// chars keys report too, e.g. backspace.
+    pub fn from_received_character(c: char) -> Option<ImeEvent> {
+        (!c.is_control()).then(|| ImeEvent::Commit(c.to_string()))
+    }
+}
+
+/// A render object editing text an input method composes, e.g. a text field.
+///
+/// The focused target that is a client receives the input method events.
+pub trait TextInputClient {
+    /// Replaces the text being composed, or the selection when not composing, with `text`.
+    /// `selection` is relative to `text`.
+    fn update_composing(&mut self, text: &str, selection: Option<(usize, usize)>);
+
+    /// Replaces the text being composed, or the selection when not composing, with `text`
+    /// and ends the composition.
+    fn commit(&mut self, text: &str);
+
+    /// The caret in the coordinates of the client, the platform shows its candidate
+    /// window next to it.
+    fn caret_rect(&self) -> Rect;
+
+    fn handle_ime_event(&mut self, event: &ImeEvent) {
+        match event {
+            ImeEvent::Enabled => {}
+            ImeEvent::Preedit(text, selection) => self.update_composing(text, *selection),
+            ImeEvent::Commit(text) => self.commit(text),
+            ImeEvent::Disabled => self.update_composing("", None),
+        }
+    }
+}
+
+/// An input method driven by code, to test text input without a platform.
+///
+/// It composes the reading typed into it, and sends the events a platform input method
+/// would to `dispatch`.
+pub struct FakeIme<F> {
+    dispatch: F,
+    composing: String,
+}
+
+impl<F> FakeIme<F>
+where
+    F: FnMut(ImeEvent),
+{
+    pub fn new(mut dispatch: F) -> Self {
+        dispatch(ImeEvent::Enabled);
+        FakeIme {
+            dispatch,
+            composing: String::new(),
+        }
+    }
+
+    /// The text being composed.
+    pub fn composing(&self) -> &str {
+        &self.composing
+    }
+
+    /// Adds `reading` to the composition one char at a time, e.g. the pinyin of a word.
+    pub fn type_reading(&mut self, reading: &str) {
+        for c in reading.chars() {
+            self.composing.push(c);
+            self.preedit();
+        }
+    }
+
+    /// Removes the last char of the composition.
+    pub fn backspace(&mut self) {
+        if self.composing.pop().is_some() {
+            self.preedit();
+        }
+    }
+
+    /// Commits `text`, e.g. the candidate picked for the reading, ending the composition.
+    pub fn commit(&mut self, text: &str) {
+        self.composing.clear();
+        self.preedit();
+        (self.dispatch)(ImeEvent::Commit(text.to_string()));
+    }
+
+    /// Ends the composition without committing it.
+    pub fn cancel(&mut self) {
+        self.composing.clear();
+        self.preedit();
+    }
+
+    /// Stops the input method, dropping the composition.
+    pub fn disable(mut self) {
+        (self.dispatch)(ImeEvent::Disabled);
+    }
+
+    /// Sends the composition with the caret at its end.
+    fn preedit(&mut self) {
+        let caret = self.composing.encode_utf16().count();
+        let selection = (!self.composing.is_empty()).then_some((caret, caret));
+        (self.dispatch)(ImeEvent::Preedit(self.composing.clone(), selection));
+    }
+}
@@ -0,0 +1,178 @@
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+use crate::{
+    animation::{AnimationController, AnimationStatus},
+    rendering::{BoxConstraints, Size},
+    scheduler::{FakeClock, SchedulerBinding},
+    tests::common::{assert_near, ms, WidgetTester},
+    widgets::ConstrainedBox,
+};
+
+/// Draws frames of a scheduler on a fake clock.
+struct Frames {
+    clock: Rc<FakeClock>,
+    scheduler: Rc<SchedulerBinding>,
+}
+
+impl Frames {
+    fn new() -> Self {
+        let clock = Rc::new(FakeClock::new());
+        Frames {
+            scheduler: SchedulerBinding::new(clock.clone()),
+            clock,
+        }
+    }
+
+    fn controller(&self, duration: Duration) -> Rc<AnimationController> {
+        Rc::new(AnimationController::new(&self.scheduler).with_duration(duration))
+    }
+
+    fn pump(&self, duration: Duration) {
+        self.clock.advance(duration);
+        self.scheduler.handle_begin_frame();
+        self.scheduler.handle_draw_frame();
+    }
+}
+
+#[test]
+fn test_forward_and_reverse() {
+    let frames = Frames::new();
+    let controller = frames.controller(ms(100));
+    let statuses = Rc::new(RefCell::new(Vec::new()));
+    let log = statuses.clone();
+    controller.add_status_listener(move |status| log.borrow_mut().push(status));
+    assert_eq!(controller.status(), AnimationStatus::Dismissed);
+
+    controller.forward();
+    assert_eq!(controller.status(), AnimationStatus::Forward);
+    frames.pump(ms(0));
+    frames.pump(ms(25));
+    assert_near(controller.value(), 0.25, 1e-4);
+    assert!((controller.velocity() - 10.0).abs() < 0.1);
+    frames.pump(ms(75));
+    assert_eq!(controller.value(), 1.0);
+    // done after the duration has passed
+    frames.pump(ms(16));
+    assert_eq!(controller.status(), AnimationStatus::Completed);
+    assert!(!controller.is_animating());
+    assert_eq!(controller.velocity(), 0.0);
+
+    controller.reverse();
+    frames.pump(ms(0));
+    frames.pump(ms(50));
+    assert_near(controller.value(), 0.5, 1e-4);
+    frames.pump(ms(60));
+    assert_eq!(controller.value(), 0.0);
+    assert_eq!(controller.status(), AnimationStatus::Dismissed);
+    assert_eq!(
+        *statuses.borrow(),
+        vec![
+            AnimationStatus::Forward,
+            AnimationStatus::Completed,
+            AnimationStatus::Reverse,
+            AnimationStatus::Dismissed,
+        ]
+    );
+}
+
+#[test]
+fn test_animating_from_the_middle_takes_the_rest_of_the_duration() {
+    let frames = Frames::new();
+    let controller = Rc::new(
+        AnimationController::new(&frames.scheduler)
+            .with_duration(ms(100))
+            .with_reverse_duration(ms(200))
+            .with_value(0.5),
+    );
+    assert_eq!(controller.status(), AnimationStatus::Forward);
+    controller.reverse();
+    frames.pump(ms(0));
+    frames.pump(ms(50));
+    assert_near(controller.value(), 0.25, 1e-4);
+
+    controller.animate_to(0.75, None);
+    frames.pump(ms(0));
+    frames.pump(ms(25));
+    assert_near(controller.value(), 0.5, 1e-4);
+    frames.pump(ms(50));
+    assert_eq!(controller.value(), 0.75);
+}
+
+#[test]
+fn test_listeners_are_notified_of_each_value() {
+    let frames = Frames::new();
+    let controller = frames.controller(ms(32));
+    let values = Rc::new(RefCell::new(Vec::new()));
+    let (log, notified) = (values.clone(), controller.clone());
+    let id = controller.add_listener(move || log.borrow_mut().push(notified.value()));
+    controller.forward();
+    frames.pump(ms(0));
+    frames.pump(ms(16));
+    controller.remove_listener(id);
+    frames.pump(ms(16));
+    assert_eq!(*values.borrow(), vec![0.0, 0.5]);
+
+    controller.set_value(2.0);
+    assert_eq!(controller.value(), 1.0);
+    assert_eq!(controller.status(), AnimationStatus::Completed);
+    assert!(!controller.is_animating());
+}
+
+#[test]
+fn test_repeat_in_reverse() {
+    let frames = Frames::new();
+    let controller = frames.controller(ms(100));
+    controller.repeat(None, None, true, None);
+    frames.pump(ms(0));
+    frames.pump(ms(40));
+    assert_near(controller.value(), 0.4, 1e-4);
+    frames.pump(ms(100));
+    assert_near(controller.value(), 0.6, 1e-4);
+    assert!(controller.velocity() < 0.0);
+    frames.pump(ms(100));
+    assert_near(controller.value(), 0.4, 1e-4);
+    assert!(controller.is_animating());
+
+    controller.stop();
+    assert!(!controller.is_animating());
+    assert_eq!(frames.scheduler.transient_callback_count(), 0);
+}
+
+#[test]
+fn test_fling_settles_at_the_bounds() {
+    let frames = Frames::new();
+    let controller = frames.controller(ms(100));
+    controller.set_value(0.5);
+    controller.fling(2.0);
+    assert_eq!(controller.status(), AnimationStatus::Forward);
+    let mut pumped = Duration::ZERO;
+    while controller.is_animating() {
+        assert!(pumped < Duration::from_secs(1), "fling never settled");
+        frames.pump(ms(16));
+        pumped += ms(16);
+    }
+    assert_eq!(controller.value(), 1.0);
+    assert_eq!(controller.status(), AnimationStatus::Completed);
+
+    controller.fling(-1.0);
+    frames.pump(ms(0));
+    frames.pump(ms(16));
+    assert!(controller.velocity() < 0.0);
+    assert_eq!(controller.status(), AnimationStatus::Reverse);
+}
+
+#[test]
+fn test_tester_pumps_frames_until_the_animation_settles() {
+    let mut tester = WidgetTester::new(Size::new(100.0, 100.0));
+    tester.pump_widget(ConstrainedBox {
+        constraints: BoxConstraints::tight(Size::new(10.0, 10.0)),
+    });
+    let controller = Rc::new(AnimationController::new(tester.scheduler()).with_duration(ms(160)));
+    controller.forward();
+
+    // the first frame starts the animation, one after its duration sees it done
+    let frames = tester.pump_and_settle(ms(16), Duration::from_secs(10));
+    assert_eq!(frames, 12);
+    assert_eq!(tester.now(), ms(192));
+    assert_eq!(controller.status(), AnimationStatus::Completed);
+}
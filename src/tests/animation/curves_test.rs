@@ -0,0 +1,94 @@
+use crate::animation::{Cubic, Curve, Curves, SpringCurve};
+use crate::physics::SpringDescription;
+use crate::tests::common::assert_near;
+
+fn curves() -> Vec<(&'static str, Box<dyn Curve>)> {
+    vec![
+        ("linear", Box::new(Curves::LINEAR)),
+        ("ease", Box::new(Curves::EASE)),
+        ("ease in", Box::new(Curves::EASE_IN)),
+        ("ease out", Box::new(Curves::EASE_OUT)),
+        ("ease in out", Box::new(Curves::EASE_IN_OUT)),
+        ("fast out slow in", Box::new(Curves::FAST_OUT_SLOW_IN)),
+        ("elastic in", Box::new(Curves::ELASTIC_IN)),
+        ("elastic out", Box::new(Curves::ELASTIC_OUT)),
+        ("elastic in out", Box::new(Curves::ELASTIC_IN_OUT)),
+        ("bounce in", Box::new(Curves::BOUNCE_IN)),
+        ("bounce out", Box::new(Curves::BOUNCE_OUT)),
+        ("bounce in out", Box::new(Curves::BOUNCE_IN_OUT)),
+        ("spring", Box::new(Curves::SPRING)),
+    ]
+}
+
+/// The progress of an animation, strictly between its ends.
+fn samples() -> Vec<f32> {
+    (1..100).map(|i| i as f32 / 100.0).collect()
+}
+
+#[test]
+fn test_curves_start_at_zero_and_end_at_one() {
+    for (name, curve) in curves() {
+        assert_eq!(curve.transform(0.0), 0.0, "{}", name);
+        assert_eq!(curve.transform(1.0), 1.0, "{}", name);
+        // continuous at the ends
+        assert_near(curve.transform(1e-4), 0.0, 0.01);
+        assert_near(curve.transform(1.0 - 1e-4), 1.0, 0.01);
+    }
+}
+
+#[test]
+fn test_cubic_bezier() {
+    // a bezier with its control points on the diagonal is linear
+    let linear = Cubic::new(0.25, 0.25, 0.75, 0.75);
+    for t in [0.1, 0.3, 0.5, 0.9] {
+        assert_near(linear.transform(t), t, 2e-3);
+    }
+    // symmetric around the middle
+    assert_near(Curves::EASE_IN_OUT.transform(0.5), 0.5, 2e-3);
+    assert_near(
+        Curves::EASE_IN_OUT.transform(0.2) + Curves::EASE_IN_OUT.transform(0.8),
+        1.0,
+        2e-3,
+    );
+    assert!(Curves::EASE_IN.transform(0.5) < 0.5);
+    assert!(Curves::EASE_OUT.transform(0.5) > 0.5);
+}
+
+#[test]
+fn test_elastic_and_bounce() {
+    assert_near(Curves::BOUNCE_OUT.transform(0.5), 0.765625, 1e-6);
+    assert_near(Curves::BOUNCE_IN.transform(0.5), 1.0 - 0.765625, 1e-6);
+    assert_near(Curves::BOUNCE_IN_OUT.transform(0.5), 0.5, 1e-6);
+    let samples = samples();
+    // bounces stay in the bounds, elastic curves leave them
+    let bounce_out = samples.iter().map(|t| Curves::BOUNCE_OUT.transform(*t));
+    assert!(bounce_out.clone().all(|x| (0.0..=1.0).contains(&x)));
+    let elastic_out = samples.iter().map(|t| Curves::ELASTIC_OUT.transform(*t));
+    assert!(elastic_out.clone().any(|x| x > 1.0));
+    let elastic_in = samples.iter().map(|t| Curves::ELASTIC_IN.transform(*t));
+    assert!(elastic_in.clone().any(|x| x < 0.0));
+    let elastic_in_out = samples.iter().map(|t| Curves::ELASTIC_IN_OUT.transform(*t));
+    assert!(elastic_in_out.clone().any(|x| x < 0.0));
+    assert!(elastic_in_out.clone().any(|x| x > 1.0));
+}
+
+#[test]
+fn test_spring_curves() {
+    let samples = samples();
+    assert!(samples.iter().any(|t| Curves::SPRING.transform(*t) > 1.0));
+
+    for ratio in [1.0, 2.0] {
+        let curve = SpringCurve::new(SpringDescription::with_damping_ratio(1.0, 100.0, ratio));
+        let mut previous = 0.0;
+        for t in samples.iter().copied() {
+            let value = curve.transform(t);
+            assert!(
+                value >= previous && value <= 1.0,
+                "ratio {} at {}",
+                ratio,
+                t
+            );
+            previous = value;
+        }
+    }
+}
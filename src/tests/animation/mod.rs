@@ -0,0 +1,4 @@
+mod animation_controller_test;
+mod curves_test;
+mod spring_simulation_test;
+mod tween_test;
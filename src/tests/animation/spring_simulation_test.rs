@@ -0,0 +1,42 @@
+use crate::physics::{Simulation, SpringDescription, SpringSimulation, Tolerance};
+use crate::tests::common::assert_near;
+
+#[test]
+fn test_springs_start_where_released() {
+    for ratio in [0.5, 1.0, 2.0] {
+        let spring = SpringDescription::with_damping_ratio(1.0, 100.0, ratio);
+        let simulation = SpringSimulation::new(spring, 0.0, 10.0, 5.0);
+        assert_near(simulation.x(0.0), 0.0, 1e-4);
+        assert_near(simulation.dx(0.0), 5.0, 1e-3);
+        assert!(!simulation.is_done(0.0));
+        assert_near(simulation.x(5.0), 10.0, 1e-3);
+        assert!(simulation.is_done(5.0), "ratio {}", ratio);
+    }
+}
+
+#[test]
+fn test_underdamped_spring_overshoots() {
+    let underdamped = SpringDescription::with_damping_ratio(1.0, 100.0, 0.2);
+    let critical = SpringDescription::with_damping_ratio(1.0, 100.0, 1.0);
+    let (mut overshot, mut critical_overshot) = (false, false);
+    for step in 0..100 {
+        let t = step as f32 * 0.02;
+        overshot |= SpringSimulation::new(underdamped, 0.0, 1.0, 0.0).x(t) > 1.0 + 1e-3;
+        critical_overshot |= SpringSimulation::new(critical, 0.0, 1.0, 0.0).x(t) > 1.0 + 1e-3;
+    }
+    assert!(overshot);
+    assert!(!critical_overshot);
+}
+
+#[test]
+fn test_tolerance_decides_when_done() {
+    let spring = SpringDescription::with_damping_ratio(1.0, 100.0, 1.0);
+    let strict = SpringSimulation::new(spring, 0.0, 1.0, 0.0);
+    let loose = strict
+        .clone()
+        .with_tolerance(Tolerance::new(0.1, 1e-3, f32::INFINITY));
+    let t = 0.4;
+    assert!(loose.is_done(t));
+    assert!(!strict.is_done(t));
+    assert_eq!(loose.tolerance().distance, 0.1);
+}
@@ -0,0 +1,69 @@
+use std::rc::Rc;
+
+use crate::{
+    animation::{AnimationController, Curve, Curves, Tween},
+    rendering::{BoxConstraints, Offset, Size},
+    scheduler::{FakeClock, SchedulerBinding},
+    ui::Color,
+};
+
+#[test]
+fn test_tweens_interpolate_their_values() {
+    assert_eq!(Tween::new(10.0, 20.0).transform(0.25), 12.5);
+    assert_eq!(Tween::new(10.0, 20.0).transform(1.5), 25.0);
+    assert_eq!(
+        Tween::new(Offset::new(0.0, 10.0), Offset::new(10.0, 0.0)).transform(0.5),
+        Offset::new(5.0, 5.0)
+    );
+    assert_eq!(
+        Tween::new(Size::new(10.0, 10.0), Size::new(20.0, 30.0)).transform(0.5),
+        Size::new(15.0, 20.0)
+    );
+    assert_eq!(
+        Tween::new(Color::from_argb(0xff, 0, 0x10, 0xff), Color::WHITE).transform(0.5),
+        Color::from_argb(0xff, 0x80, 0x88, 0xff)
+    );
+    let transparent = Tween::new(Color::BLACK, Color::TRANSPARENT);
+    assert_eq!(transparent.transform(1.0), Color::TRANSPARENT);
+    assert_eq!(transparent.transform(0.5).a(), 0x80);
+}
+
+#[test]
+fn test_box_constraints_keep_their_infinite_bounds() {
+    let tween = Tween::new(
+        BoxConstraints::tight(Size::new(10.0, 10.0)).with_max_height(f32::INFINITY),
+        BoxConstraints::tight(Size::new(30.0, 20.0)).with_max_height(f32::INFINITY),
+    );
+    let constraints = tween.transform(0.5);
+    assert_eq!((constraints.min_width, constraints.max_width), (20.0, 20.0));
+    assert_eq!(constraints.min_height, 15.0);
+    assert_eq!(constraints.max_height, f32::INFINITY);
+
+    // between an unbounded and a bounded constraint, the bound jumps halfway
+    let tween = Tween::new(
+        BoxConstraints::default(),
+        BoxConstraints::tight(Size::new(40.0, 20.0)),
+    );
+    let constraints = tween.transform(0.25);
+    assert_eq!(
+        (constraints.min_width, constraints.max_width),
+        (10.0, f32::INFINITY)
+    );
+    let constraints = tween.transform(0.5);
+    assert_eq!((constraints.min_width, constraints.max_width), (20.0, 40.0));
+    assert_eq!(
+        (constraints.min_height, constraints.max_height),
+        (10.0, 20.0)
+    );
+}
+
+#[test]
+fn test_tween_evaluates_a_controller_along_a_curve() {
+    let scheduler = SchedulerBinding::new(Rc::new(FakeClock::new()));
+    let controller = AnimationController::new(&scheduler).with_value(0.5);
+    let tween = Tween::new(100.0, 200.0);
+    assert_eq!(tween.evaluate(&controller), 150.0);
+    let eased = tween.evaluate_curved(&controller, &Curves::EASE_IN);
+    assert_eq!(eased, tween.transform(Curves::EASE_IN.transform(0.5)));
+    assert!(eased < 150.0);
+}
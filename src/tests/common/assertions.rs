@@ -0,0 +1,14 @@
+use std::time::Duration;
+
+pub fn ms(millis: u64) -> Duration {
+    Duration::from_millis(millis)
+}
+
+pub fn assert_near(actual: f32, expected: f32, epsilon: f32) {
+    assert!(
+        (actual - expected).abs() < epsilon,
+        "{} is not {}",
+        actual,
+        expected
+    );
+}
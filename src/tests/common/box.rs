@@ -1,18 +1,51 @@
-use std::any::{type_name, TypeId};
+use std::{
+    any::{type_name, TypeId},
+    cell::Cell,
+    rc::Rc,
+};
 
-use crate::rendering::{BoxConstraints, RenderBox, RenderObject, Size};
+use crate::{
+    gestures::{HitTestEntry, HitTestResult, HitTestTarget, PointerEvent},
+    rendering::{
+        BoxConstraints, Offset, PaintContext, RenderBox, RenderObject, RenderState, Size,
+        TextBaseline,
+    },
+};
 
 #[derive(Debug)]
 pub struct RenderSizedBox {
+    pub(crate) state: Rc<RenderState>,
     pub(crate) size: Size,
+    pub(crate) baseline: Option<f32>,
+    pub(crate) layout_count: usize,
+    pub(crate) paint_count: Cell<usize>,
+    pub(crate) intrinsic_count: Cell<usize>,
+    pub(crate) events: Vec<PointerEvent>,
 }
 
 impl RenderSizedBox {
     pub fn new(width: f32, height: f32) -> Self {
         RenderSizedBox {
+            state: Default::default(),
             size: Size::new(width, height),
+            baseline: None,
+            layout_count: 0,
+            paint_count: Cell::new(0),
+            intrinsic_count: Cell::new(0),
+            events: Vec::new(),
         }
     }
+
+    pub fn with_baseline(mut self, baseline: f32) -> Self {
+        self.baseline = Some(baseline);
+        self
+    }
+}
+
+impl HitTestTarget for RenderSizedBox {
+    fn handle_event(&mut self, event: PointerEvent, entry: HitTestEntry) {
+        self.events.push(event);
+    }
 }
 
 impl RenderObject for RenderSizedBox {
@@ -23,10 +56,25 @@ impl RenderObject for RenderSizedBox {
     fn ty_name(&self) -> &'static str {
         type_name::<Self>()
     }
+
+    fn state(&self) -> &Rc<RenderState> {
+        &self.state
+    }
+
+    fn paint(&self, context: &mut PaintContext, offset: Offset) {
+        self.paint_count.set(self.paint_count.get() + 1);
+        context.draw_rect(offset, self.size);
+    }
+
+    fn hit_test(&self, position: Offset, result: &mut HitTestResult) -> bool {
+        self.size.contains(position)
+    }
 }
 
 impl RenderBox for RenderSizedBox {
-    fn perform_layout(&mut self, constraints: &BoxConstraints) {}
+    fn perform_layout(&mut self, constraints: &BoxConstraints) {
+        self.layout_count += 1;
+    }
 
     fn perform_resize(&mut self, constraints: &BoxConstraints) {
         todo!()
@@ -35,4 +83,28 @@ impl RenderBox for RenderSizedBox {
     fn size(&self) -> Size {
         self.size
     }
+
+    fn compute_distance_to_actual_baseline(&self, baseline: TextBaseline) -> Option<f32> {
+        self.baseline
+    }
+
+    fn compute_min_intrinsic_width(&self, height: f32) -> f32 {
+        self.intrinsic_count.set(self.intrinsic_count.get() + 1);
+        self.size.width
+    }
+
+    fn compute_max_intrinsic_width(&self, height: f32) -> f32 {
+        self.intrinsic_count.set(self.intrinsic_count.get() + 1);
+        self.size.width
+    }
+
+    fn compute_min_intrinsic_height(&self, width: f32) -> f32 {
+        self.intrinsic_count.set(self.intrinsic_count.get() + 1);
+        self.size.height
+    }
+
+    fn compute_max_intrinsic_height(&self, width: f32) -> f32 {
+        self.intrinsic_count.set(self.intrinsic_count.get() + 1);
+        self.size.height
+    }
 }
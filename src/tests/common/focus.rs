@@ -0,0 +1,52 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    gestures::{GestureBinding, PointerEvent, PointerEventKind},
+    rendering::{BoxConstraints, Offset, PaintContext, RenderBox, RenderView, Size},
+    services::FocusManager,
+};
+
+/// A view laid out, painted and with its focus tree attached, as after a frame.
+pub struct FocusTree {
+    pub root: Rc<RefCell<dyn RenderBox>>,
+    pub manager: Rc<FocusManager>,
+    gestures: GestureBinding,
+}
+
+impl FocusTree {
+    pub fn new(child: impl RenderBox + 'static, size: Size) -> Self {
+        FocusTree::from_child(Rc::new(RefCell::new(child)), size)
+    }
+
+    /// A tree of `child` kept by the caller too, e.g. to replace its children.
+    pub fn from_child(child: Rc<RefCell<dyn RenderBox>>, size: Size) -> Self {
+        let mut view = RenderView::new();
+        view.set_child(child);
+        let root: Rc<RefCell<dyn RenderBox>> = Rc::new(RefCell::new(view));
+        let tree = FocusTree {
+            root,
+            manager: FocusManager::new(),
+            gestures: GestureBinding::new(),
+        };
+        tree.layout(size);
+        tree
+    }
+
+    /// Lays out and paints the view at `size`, then updates the focus tree.
+    pub fn layout(&self, size: Size) {
+        self.root
+            .borrow_mut()
+            .layout(&BoxConstraints::tight(size), false);
+        PaintContext::flush_paint(&*self.root.borrow(), size);
+        self.manager.update_tree(&self.root);
+    }
+
+    /// Taps at `position` and applies the focus changes it requested.
+    pub fn tap(&mut self, position: Offset) {
+        for kind in [PointerEventKind::Down, PointerEventKind::Up] {
+            let event = PointerEvent::new(kind, 0, position);
+            self.gestures.handle_event(&self.root, event);
+        }
+        self.manager.apply_focus_changes();
+    }
+}
@@ -0,0 +1,247 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use skia_safe::{
+    image::CachingHint, AlphaType, ColorType, Data, EncodedImageFormat, Image, ImageInfo,
+};
+
+/// Regenerates the goldens instead of comparing against them when set, e.g.
+/// `OXUI_UPDATE_GOLDENS=1 cargo test`.
+pub const UPDATE_GOLDENS_ENV: &str = "OXUI_UPDATE_GOLDENS";
+
+/// The pixels of a frame, RGBA with unpremultiplied alpha, row after row.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GoldenImage {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<u8>,
+}
+
+impl GoldenImage {
+    /// An image filled with `color`.
+    pub fn new(width: usize, height: usize, color: [u8; 4]) -> Self {
+        GoldenImage {
+            width,
+            height,
+            pixels: color.repeat(width * height),
+        }
+    }
+
+    pub fn from_image(image: &Image) -> Self {
+        let (width, height) = (image.width() as usize, image.height() as usize);
+        let mut pixels = vec![0; width * height * 4];
+        let read = image.read_pixels(
+            &GoldenImage::info(width, height),
+            &mut pixels,
+            width * 4,
+            (0, 0),
+            CachingHint::Disallow,
+        );
+        assert!(read, "cannot read the pixels of the image");
+        GoldenImage {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    pub fn decode_png(bytes: &[u8]) -> Option<Self> {
+        Image::from_encoded(Data::new_copy(bytes)).map(|image| GoldenImage::from_image(&image))
+    }
+
+    pub fn encode_png(&self) -> Vec<u8> {
+        let info = GoldenImage::info(self.width, self.height);
+        Image::from_raster_data(&info, Data::new_copy(&self.pixels), self.width * 4)
+            .and_then(|image| image.encode_to_data(EncodedImageFormat::PNG))
+            .expect("encode png")
+            .as_bytes()
+            .to_vec()
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> [u8; 4] {
+        let i = (y * self.width + x) * 4;
+        [
+            self.pixels[i],
+            self.pixels[i + 1],
+            self.pixels[i + 2],
+            self.pixels[i + 3],
+        ]
+    }
+
+    pub fn set_pixel(&mut self, x: usize, y: usize, color: [u8; 4]) {
+        let i = (y * self.width + x) * 4;
+        self.pixels[i..i + 4].copy_from_slice(&color);
+    }
+
+    /// Compares the pixels with those of `expected`, of the same size. A pixel matches when
+    /// none of its channels differs by more than `tolerance`.
+    pub fn compare(&self, expected: &GoldenImage, tolerance: u8) -> GoldenDiff {
+        assert_eq!((self.width, self.height), (expected.width, expected.height));
+        // the mismatches in red over the expected image faded
+        let mut image = GoldenImage::new(self.width, self.height, [0; 4]);
+        let mut mismatched = 0;
+        let mut max_difference = 0;
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let (actual, wanted) = (self.pixel(x, y), expected.pixel(x, y));
+                let difference = actual
+                    .iter()
+                    .zip(wanted.iter())
+                    .map(|(a, b)| a.abs_diff(*b))
+                    .max()
+                    .unwrap_or(0);
+                max_difference = max_difference.max(difference);
+                if difference > tolerance {
+                    mismatched += 1;
+                    image.set_pixel(x, y, [0xff, 0, 0, 0xff]);
+                } else {
+                    image.set_pixel(x, y, [wanted[0], wanted[1], wanted[2], wanted[3] / 4]);
+                }
+            }
+        }
+        GoldenDiff {
+            mismatched,
+            max_difference,
+            image,
+        }
+    }
+
+    fn info(width: usize, height: usize) -> ImageInfo {
+        ImageInfo::new(
+            (width as i32, height as i32),
+            ColorType::RGBA8888,
+            AlphaType::Unpremul,
+            None,
+        )
+    }
+}
+
+/// How a frame differs from its golden.
+#[derive(Debug)]
+pub struct GoldenDiff {
+    /// Number of pixels differing by more than the tolerance.
+    pub mismatched: usize,
+    /// The largest difference of a channel.
+    pub max_difference: u8,
+    /// The mismatched pixels in red, over the golden faded.
+    pub image: GoldenImage,
+}
+
+/// A checked-in PNG a frame is compared against, see [`WidgetTester::snapshot`].
+///
+/// On a mismatch the frame and the diff are written to `failures` next to the golden.
+/// With [`UPDATE_GOLDENS_ENV`] set the frame replaces the golden instead.
+///
+/// [`WidgetTester::snapshot`]: crate::tests::common::WidgetTester::snapshot
+pub struct GoldenFile {
+    name: String,
+    directory: PathBuf,
+    tolerance: u8,
+    update: bool,
+}
+
+impl GoldenFile {
+    pub fn new(name: impl Into<String>) -> Self {
+        GoldenFile {
+            name: name.into(),
+            directory: Path::new(env!("CARGO_MANIFEST_DIR")).join("src/tests/goldens"),
+            tolerance: 0,
+            update: env::var_os(UPDATE_GOLDENS_ENV).is_some(),
+        }
+    }
+
+    pub fn with_directory(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.directory = directory.into();
+        self
+    }
+
+    /// How much a channel of a pixel may differ from the golden, e.g. for anti-aliasing
+    /// that differs between platforms.
+    pub fn with_tolerance(mut self, tolerance: u8) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn with_update(mut self, update: bool) -> Self {
+        self.update = update;
+        self
+    }
+
+    pub fn path(&self) -> PathBuf {
+        self.directory.join(format!("{}.png", self.name))
+    }
+
+    /// Where the frame and the diff of a mismatch are written.
+    pub fn failure_path(&self, suffix: &str) -> PathBuf {
+        self.directory
+            .join("failures")
+            .join(format!("{}_{}.png", self.name, suffix))
+    }
+
+    /// Compares `actual` with the golden, or writes it as the golden when updating.
+    pub fn check(&self, actual: &GoldenImage) -> Result<(), String> {
+        let path = self.path();
+        if self.update {
+            write(&path, &actual.encode_png());
+            return Ok(());
+        }
+        let expected = match fs::read(&path) {
+            Ok(bytes) => GoldenImage::decode_png(&bytes)
+                .ok_or_else(|| format!("{} is not a png", path.display()))?,
+            Err(_) => {
+                return Err(format!(
+                    "no golden at {}, set {} to create it",
+                    path.display(),
+                    UPDATE_GOLDENS_ENV
+                ))
+            }
+        };
+
+        let actual_path = self.failure_path("actual");
+        if (actual.width, actual.height) != (expected.width, expected.height) {
+            write(&actual_path, &actual.encode_png());
+            return Err(format!(
+                "{} is {}x{}, the frame is {}x{}, see {}",
+                path.display(),
+                expected.width,
+                expected.height,
+                actual.width,
+                actual.height,
+                actual_path.display()
+            ));
+        }
+        let diff = actual.compare(&expected, self.tolerance);
+        if diff.mismatched == 0 {
+            return Ok(());
+        }
+        let diff_path = self.failure_path("diff");
+        write(&actual_path, &actual.encode_png());
+        write(&diff_path, &diff.image.encode_png());
+        Err(format!(
+            "{}/{} pixels differ from {} by up to {} (tolerance {}), see {} and {}",
+            diff.mismatched,
+            actual.width * actual.height,
+            path.display(),
+            diff.max_difference,
+            self.tolerance,
+            actual_path.display(),
+            diff_path.display()
+        ))
+    }
+
+    /// Panics unless `actual` matches the golden.
+    pub fn assert_matches(&self, actual: &GoldenImage) {
+        if let Err(message) = self.check(actual) {
+            panic!("golden {} mismatch: {}", self.name, message);
+        }
+    }
+}
+
+fn write(path: &Path, bytes: &[u8]) {
+    if let Some(directory) = path.parent() {
+        fs::create_dir_all(directory).expect("create golden directory");
+    }
+    fs::write(path, bytes).expect("write golden");
+}
@@ -3,3 +3,18 @@ pub use image::*;
 
 mod r#box;
 pub use r#box::RenderSizedBox;
+
+mod sliver;
+pub use sliver::RenderSizedSliver;
+
+mod focus;
+pub use focus::FocusTree;
+
+mod golden;
+pub use golden::{GoldenFile, GoldenImage, UPDATE_GOLDENS_ENV};
+
+mod widget_tester;
+pub use widget_tester::{Finder, WidgetTester};
+
+mod assertions;
+pub use assertions::{assert_near, ms};
@@ -0,0 +1,97 @@
+use std::{
+    any::{type_name, TypeId},
+    cell::RefCell,
+    rc::Rc,
+};
+
+use crate::{
+    gestures::{HitTestResult, HitTestTarget},
+    rendering::{
+        Axis, Offset, PaintContext, RenderObject, RenderSliver, RenderState, SliverConstraints,
+        SliverGeometry,
+    },
+};
+
+/// A sliver of fixed scroll extent recording its constraints, paints and hits.
+#[derive(Debug)]
+pub struct RenderSizedSliver {
+    pub(crate) state: Rc<RenderState>,
+    pub(crate) extent: f32,
+    pub(crate) constraints: Option<SliverConstraints>,
+    pub(crate) geometry: SliverGeometry,
+    pub(crate) paints: RefCell<Vec<Offset>>,
+    pub(crate) hits: RefCell<Vec<Offset>>,
+}
+
+impl RenderSizedSliver {
+    pub fn new(extent: f32) -> Self {
+        RenderSizedSliver {
+            state: Default::default(),
+            extent,
+            constraints: None,
+            geometry: Default::default(),
+            paints: RefCell::new(Vec::new()),
+            hits: RefCell::new(Vec::new()),
+        }
+    }
+}
+
+impl HitTestTarget for RenderSizedSliver {}
+
+impl RenderObject for RenderSizedSliver {
+    fn ty_id(&self) -> TypeId {
+        TypeId::of::<Self>()
+    }
+
+    fn ty_name(&self) -> &'static str {
+        type_name::<Self>()
+    }
+
+    fn state(&self) -> &Rc<RenderState> {
+        &self.state
+    }
+
+    fn paint(&self, context: &mut PaintContext, offset: Offset) {
+        self.paints.borrow_mut().push(offset);
+    }
+
+    fn hit_test(&self, position: Offset, result: &mut HitTestResult) -> bool {
+        let main_axis_position = match self.constraints.as_ref().map(|c| c.axis()) {
+            Some(Axis::Horizontal) => position.x,
+            _ => position.y,
+        };
+        let is_hit =
+            main_axis_position >= 0.0 && main_axis_position < self.geometry.hit_test_extent;
+        if is_hit {
+            self.hits.borrow_mut().push(position);
+        }
+        is_hit
+    }
+}
+
+impl RenderSliver for RenderSizedSliver {
+    fn perform_layout(&mut self, constraints: &SliverConstraints) {
+        let paint_extent = constraints.calculate_paint_offset(0.0, self.extent);
+        self.geometry = SliverGeometry {
+            scroll_extent: self.extent,
+            paint_extent,
+            layout_extent: paint_extent,
+            max_paint_extent: self.extent,
+            visible: paint_extent > 0.0,
+            has_visual_overflow: self.extent > constraints.remaining_paint_extent
+                || constraints.scroll_offset > 0.0,
+            cache_extent: constraints.calculate_cache_offset(0.0, self.extent),
+            hit_test_extent: paint_extent,
+            ..Default::default()
+        };
+        self.constraints = Some(constraints.clone());
+    }
+
+    fn perform_resize(&mut self, constraints: &SliverConstraints) {
+        todo!()
+    }
+
+    fn geometry(&self) -> &SliverGeometry {
+        &self.geometry
+    }
+}
@@ -0,0 +1,290 @@
+use std::{any::TypeId, cell::RefCell, fmt, rc::Rc, time::Duration};
+
+use compose_rt::Recomposer;
+use skia_safe::{Color, Rect, Surface};
+
+use crate::{
+    gestures::{PointerEvent, PointerEventKind, PAN_SLOP},
+    rendering::{Offset, PipelineOwner, RenderBox, Size},
+    scheduler::{Clock, FakeClock, SchedulerBinding},
+    semantics::SemanticsConfiguration,
+    services::{ImeEvent, KeyEvent, KeyEventKind, LogicalKey, Modifiers},
+    tests::common::GoldenImage,
+    widgets::{Key, Widget},
+};
+
+/// Matches render objects of the tree a [`WidgetTester`] pumped.
+pub enum Finder {
+    /// Render objects of a type, e.g. `RenderParagraph` for a `Text`.
+    Type(TypeId, &'static str),
+    /// Render objects telling `text` as their label or value, e.g. a paragraph or a text
+    /// field.
+    Text(String),
+    /// The render objects of the widgets with a key.
+    Key(Key),
+}
+
+impl Finder {
+    pub fn by_type<T: 'static>() -> Self {
+        Finder::Type(TypeId::of::<T>(), std::any::type_name::<T>())
+    }
+
+    pub fn text(text: impl Into<String>) -> Self {
+        Finder::Text(text.into())
+    }
+
+    pub fn by_key(key: impl Into<Key>) -> Self {
+        Finder::Key(key.into())
+    }
+
+    /// The render objects matched in the tree at `root`, in paint order.
+    pub fn evaluate(&self, root: &Rc<RefCell<dyn RenderBox>>) -> Vec<Rc<RefCell<dyn RenderBox>>> {
+        let mut found = Vec::new();
+        self.collect(root, &mut found);
+        found
+    }
+
+    fn collect(
+        &self,
+        object: &Rc<RefCell<dyn RenderBox>>,
+        found: &mut Vec<Rc<RefCell<dyn RenderBox>>>,
+    ) {
+        let render_box = object.borrow();
+        if self.matches(&*render_box) {
+            found.push(object.clone());
+        }
+        render_box.visit_children(&mut |child| self.collect(child, found));
+        render_box.visit_slivers(&mut |sliver| {
+            sliver
+                .borrow()
+                .visit_children(&mut |child| self.collect(child, found))
+        });
+    }
+
+    fn matches(&self, render_box: &dyn RenderBox) -> bool {
+        match self {
+            Finder::Type(ty_id, _) => render_box.ty_id() == *ty_id,
+            Finder::Text(text) => {
+                let mut config = SemanticsConfiguration::new();
+                render_box.describe_semantics_configuration(&mut config);
+                config.label == *text || config.value.as_deref() == Some(text.as_str())
+            }
+            Finder::Key(key) => render_box.state().key().as_ref() == Some(key),
+        }
+    }
+}
+
+impl fmt::Display for Finder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Finder::Type(_, name) => write!(f, "type {}", name),
+            Finder::Text(text) => write!(f, "text {:?}", text),
+            Finder::Key(key) => write!(f, "key {:?}", key.as_str()),
+        }
+    }
+}
+
+/// Pumps widgets into a headless window, a raster surface driven by a fake clock, and
+/// plays the input of a user on them.
+///
+/// Nothing happens between frames but what the test asks for: events are dispatched at
+/// the time of the clock, and a frame is composed, laid out and painted on each pump.
+pub struct WidgetTester {
+    size: Size,
+    device_pixel_ratio: f32,
+    recomposer: Recomposer,
+    pipeline: Option<PipelineOwner>,
+    surface: Surface,
+    clock: Rc<FakeClock>,
+    scheduler: Rc<SchedulerBinding>,
+}
+
+impl WidgetTester {
+    pub fn new(size: Size) -> Self {
+        let clock = Rc::new(FakeClock::new());
+        WidgetTester {
+            size,
+            device_pixel_ratio: 1.0,
+            recomposer: Recomposer::new(1000),
+            pipeline: None,
+            surface: WidgetTester::create_surface(size, 1.0),
+            scheduler: SchedulerBinding::new(clock.clone()),
+            clock,
+        }
+    }
+
+    /// Paints the frames with `device_pixel_ratio` physical pixels to a logical one, the
+    /// layout and the input stay in logical pixels.
+    pub fn with_device_pixel_ratio(mut self, device_pixel_ratio: f32) -> Self {
+        self.device_pixel_ratio = device_pixel_ratio;
+        self.surface = WidgetTester::create_surface(self.size, device_pixel_ratio);
+        self
+    }
+
+    fn create_surface(size: Size, device_pixel_ratio: f32) -> Surface {
+        let width = (size.width * device_pixel_ratio).ceil() as i32;
+        let height = (size.height * device_pixel_ratio).ceil() as i32;
+        Surface::new_raster_n32_premul((width, height)).expect("raster surface")
+    }
+
+    /// Makes `widget` the root and pumps a frame, the render objects of the previous root
+    /// are kept where the new one composes the same widgets.
+    pub fn pump_widget<T>(&mut self, widget: T)
+    where
+        T: 'static + Widget,
+    {
+        match &mut self.pipeline {
+            Some(pipeline) => pipeline.set_root(widget),
+            None => {
+                let pipeline =
+                    PipelineOwner::new(self.size, widget).with_scheduler(self.scheduler.clone());
+                self.pipeline = Some(pipeline);
+            }
+        }
+        self.pump(Duration::ZERO);
+    }
+
+    /// Advances the clock by `duration`, firing the gesture deadlines passed, then ticks
+    /// the animations and composes, lays out and paints a frame.
+    pub fn pump(&mut self, duration: Duration) {
+        self.clock.advance(duration);
+        let pipeline = self.pipeline.as_mut().expect("no widget pumped");
+        pipeline.handle_deadlines(self.clock.now());
+        let canvas = self.surface.canvas();
+        canvas.clear(Color::WHITE);
+        canvas.save();
+        canvas.scale((self.device_pixel_ratio, self.device_pixel_ratio));
+        self.recomposer
+            .compose(|cx| pipeline.draw_frame(cx, canvas));
+        canvas.restore();
+    }
+
+    /// Pumps frames `duration` apart while one is scheduled, e.g. until the animations
+    /// end, and returns how many. Panics after `timeout` of frames.
+    pub fn pump_and_settle(&mut self, duration: Duration, timeout: Duration) -> usize {
+        let end = self.now() + timeout;
+        let mut count = 0;
+        while self.scheduler.has_scheduled_frame() {
+            assert!(
+                self.now() < end,
+                "frames still scheduled after {:?}",
+                timeout
+            );
+            self.pump(duration);
+            count += 1;
+        }
+        count
+    }
+
+    /// The time of the fake clock.
+    pub fn now(&self) -> Duration {
+        self.clock.now()
+    }
+
+    /// The scheduler of the frames pumped, on the fake clock.
+    pub fn scheduler(&self) -> &Rc<SchedulerBinding> {
+        &self.scheduler
+    }
+
+    pub fn pipeline(&mut self) -> &mut PipelineOwner {
+        self.pipeline.as_mut().expect("no widget pumped")
+    }
+
+    /// The surface the frames are painted on.
+    pub fn surface(&mut self) -> &mut Surface {
+        &mut self.surface
+    }
+
+    /// The pixels of the last frame, to compare against a
+    /// [`GoldenFile`](crate::tests::common::GoldenFile).
+    pub fn snapshot(&mut self) -> GoldenImage {
+        GoldenImage::from_image(&self.surface.image_snapshot())
+    }
+
+    /// The render objects `finder` matches in the last frame.
+    pub fn find(&self, finder: &Finder) -> Vec<Rc<RefCell<dyn RenderBox>>> {
+        let pipeline = self.pipeline.as_ref().expect("no widget pumped");
+        match pipeline.render_view() {
+            Some(view) => finder.evaluate(view),
+            None => Vec::new(),
+        }
+    }
+
+    /// The only render object `finder` matches, panics unless there is exactly one.
+    pub fn find_one(&self, finder: &Finder) -> Rc<RefCell<dyn RenderBox>> {
+        let mut found = self.find(finder);
+        match found.len() {
+            1 => found.remove(0),
+            n => panic!("expected one render object for {}, found {}", finder, n),
+        }
+    }
+
+    /// Where the last frame painted the render object `finder` matches, on screen.
+    pub fn rect(&self, finder: &Finder) -> Rect {
+        let object = self.find_one(finder);
+        let render_box = object.borrow();
+        let offset = render_box.state().global_paint_offset();
+        let size = render_box.size();
+        Rect::from_xywh(offset.x, offset.y, size.width, size.height)
+    }
+
+    /// The center of the render object `finder` matches, where input is played.
+    pub fn center(&self, finder: &Finder) -> Offset {
+        let rect = self.rect(finder);
+        Offset::new(
+            rect.left + rect.width() / 2.0,
+            rect.top + rect.height() / 2.0,
+        )
+    }
+
+    /// Taps the center of the render object `finder` matches.
+    pub fn tap(&mut self, finder: &Finder) {
+        let position = self.down(finder);
+        self.up(position);
+    }
+
+    /// Puts a pointer down on the center of the render object `finder` matches and keeps
+    /// it there until [`WidgetTester::up`], e.g. to pump frames during a long press.
+    pub fn down(&mut self, finder: &Finder) -> Offset {
+        let position = self.center(finder);
+        self.dispatch(PointerEventKind::Down, position);
+        position
+    }
+
+    /// Lifts the pointer put down at `position`.
+    pub fn up(&mut self, position: Offset) {
+        self.dispatch(PointerEventKind::Up, position);
+    }
+
+    /// Drags from the center of the render object `finder` matches by `offset`.
+    ///
+    /// The first move passes the slop of the drag recognizers, so that one of them
+    /// recognizes the drag, the second moves the rest of the way. A recognizer that wins
+    /// the arena against others does not report the slop.
+    pub fn drag(&mut self, finder: &Finder, offset: Offset) {
+        let start = self.center(finder);
+        let scale = ((PAN_SLOP + 1.0) / offset.distance()).min(1.0);
+        let slop = Offset::new(offset.x * scale, offset.y * scale);
+        self.dispatch(PointerEventKind::Down, start);
+        self.dispatch(PointerEventKind::Move, start + slop);
+        self.dispatch(PointerEventKind::Move, start + offset);
+        self.dispatch(PointerEventKind::Up, start + offset);
+    }
+
+    /// Taps the text field `finder` matches to focus it, then replaces its text with
+    /// `text` through the input method.
+    pub fn enter_text(&mut self, finder: &Finder, text: &str) {
+        self.tap(finder);
+        let pipeline = self.pipeline();
+        let select_all = KeyEvent::new(KeyEventKind::Down, LogicalKey::Character('a'))
+            .with_modifiers(Modifiers::CONTROL);
+        pipeline.handle_key_event(select_all);
+        let committed = pipeline.handle_ime_event(ImeEvent::Commit(text.to_string()));
+        assert!(committed, "{} is not a focused text input client", finder);
+    }
+
+    fn dispatch(&mut self, kind: PointerEventKind, position: Offset) {
+        let event = PointerEvent::new(kind, 0, position).with_timestamp(self.now());
+        self.pipeline().handle_event(event);
+    }
+}
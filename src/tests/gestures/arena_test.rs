@@ -0,0 +1,110 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::gestures::{GestureArenaManager, GestureArenaMember, GestureDisposition};
+
+#[derive(Debug)]
+struct TestMember {
+    name: &'static str,
+    log: Rc<RefCell<Vec<String>>>,
+}
+
+impl GestureArenaMember for TestMember {
+    fn accept_gesture(&self, pointer: usize) {
+        self.log.borrow_mut().push(format!("accept {}", self.name));
+    }
+
+    fn reject_gesture(&self, pointer: usize) {
+        self.log.borrow_mut().push(format!("reject {}", self.name));
+    }
+}
+
+fn members(
+    log: &Rc<RefCell<Vec<String>>>,
+) -> (Rc<dyn GestureArenaMember>, Rc<dyn GestureArenaMember>) {
+    let a = Rc::new(TestMember {
+        name: "a",
+        log: log.clone(),
+    });
+    let b = Rc::new(TestMember {
+        name: "b",
+        log: log.clone(),
+    });
+    (a, b)
+}
+
+#[test]
+fn test_sole_member_wins_on_close() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let (a, _) = members(&log);
+    let arena = Rc::new(GestureArenaManager::new());
+
+    arena.add(1, a);
+    assert!(log.borrow().is_empty());
+    arena.close(1);
+    assert!(log.borrow().is_empty());
+    arena.flush();
+    assert_eq!(*log.borrow(), vec!["accept a"]);
+}
+
+#[test]
+fn test_sweep_accepts_first_member() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let (a, b) = members(&log);
+    let arena = Rc::new(GestureArenaManager::new());
+
+    arena.add(1, a);
+    arena.add(1, b);
+    arena.close(1);
+    assert!(log.borrow().is_empty());
+    arena.sweep(1);
+    assert_eq!(*log.borrow(), vec!["accept a", "reject b"]);
+}
+
+#[test]
+fn test_eager_winner_wins_on_close() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let (a, b) = members(&log);
+    let arena = Rc::new(GestureArenaManager::new());
+
+    arena.add(1, a);
+    let entry = arena.add(1, b);
+    entry.resolve(GestureDisposition::Accepted);
+    assert!(log.borrow().is_empty());
+    arena.close(1);
+    assert_eq!(*log.borrow(), vec!["reject a", "accept b"]);
+
+    // resolved arenas ignore later resolutions
+    entry.resolve(GestureDisposition::Rejected);
+    assert_eq!(log.borrow().len(), 2);
+}
+
+#[test]
+fn test_rejection_leaves_last_member_the_winner() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let (a, b) = members(&log);
+    let arena = Rc::new(GestureArenaManager::new());
+
+    let entry = arena.add(1, a);
+    arena.add(1, b);
+    arena.close(1);
+    entry.resolve(GestureDisposition::Rejected);
+    assert_eq!(*log.borrow(), vec!["reject a"]);
+    arena.flush();
+    assert_eq!(*log.borrow(), vec!["reject a", "accept b"]);
+}
+
+#[test]
+fn test_hold_defers_sweep_until_release() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let (a, b) = members(&log);
+    let arena = Rc::new(GestureArenaManager::new());
+
+    arena.add(1, a);
+    arena.add(1, b);
+    arena.close(1);
+    arena.hold(1);
+    arena.sweep(1);
+    assert!(log.borrow().is_empty());
+    arena.release(1);
+    assert_eq!(*log.borrow(), vec!["accept a", "reject b"]);
+}
@@ -0,0 +1,106 @@
+use std::{cell::RefCell, f32::consts::FRAC_PI_2, rc::Rc};
+
+use crate::{
+    gestures::{
+        GestureBinding, HitTestEntry, HitTestResult, HitTestTarget, PointerEvent, PointerEventKind,
+    },
+    rendering::{Matrix4, Offset},
+    tests::common::RenderSizedBox,
+};
+
+fn assert_offset_eq(actual: Offset, expected: Offset) {
+    assert!(
+        (actual - expected).distance() < 1e-4,
+        "{:?} != {:?}",
+        actual,
+        expected
+    );
+}
+
+fn target() -> Rc<RefCell<RenderSizedBox>> {
+    Rc::new(RefCell::new(RenderSizedBox::new(10.0, 10.0)))
+}
+
+fn add(result: &mut HitTestResult, target: &Rc<RefCell<RenderSizedBox>>) {
+    let target: Rc<RefCell<dyn HitTestTarget>> = target.clone();
+    result.add(HitTestEntry::new(target));
+}
+
+#[test]
+fn test_entries_record_nested_transforms() {
+    let (outer, inner) = (target(), target());
+    let mut result = HitTestResult::new();
+    let position = Offset::new(30.0, 40.0);
+
+    let is_hit = result.add_with_paint_offset(Offset::new(10.0, 20.0), position, |result, p| {
+        assert_offset_eq(p, Offset::new(20.0, 20.0));
+        add(result, &outer);
+        // child painted scaled by 2 around its origin
+        result.add_with_paint_transform(Matrix4::diagonal3(2.0, 2.0, 1.0), p, |result, p| {
+            assert_offset_eq(p, Offset::new(10.0, 10.0));
+            add(result, &inner);
+            true
+        })
+    });
+    assert!(is_hit);
+
+    let path = result.path();
+    assert_offset_eq(
+        path[0].transform().unwrap().transform_point(position),
+        Offset::new(20.0, 20.0),
+    );
+    assert_offset_eq(
+        path[1].transform().unwrap().transform_point(position),
+        Offset::new(10.0, 10.0),
+    );
+}
+
+#[test]
+fn test_transforms_are_popped() {
+    let (a, b) = (target(), target());
+    let mut result = HitTestResult::new();
+    result.add_with_paint_offset(Offset::new(10.0, 0.0), Offset::zero(), |result, _| {
+        add(result, &a);
+        true
+    });
+    add(&mut result, &b);
+
+    assert!(result.path()[0].transform().is_some());
+    assert_eq!(result.path()[1].transform(), None);
+}
+
+#[test]
+fn test_singular_transform_is_not_hit() {
+    let mut result = HitTestResult::new();
+    let is_hit = result.add_with_paint_transform(
+        Matrix4::diagonal3(0.0, 1.0, 1.0),
+        Offset::zero(),
+        |_, _| panic!("hit tested a child with a singular transform"),
+    );
+    assert!(!is_hit);
+    assert!(result.path().is_empty());
+}
+
+#[test]
+fn test_dispatch_uses_local_position_under_rotation() {
+    let a = target();
+    let mut result = HitTestResult::new();
+    let position = Offset::new(5.0, 20.0);
+    // child painted at (20, 10) and rotated a quarter turn clockwise
+    let mut transform = Matrix4::translation(20.0, 10.0, 0.0);
+    transform.rotate_z(FRAC_PI_2);
+    result.add_with_paint_transform(transform, position, |result, p| {
+        add(result, &a);
+        true
+    });
+
+    let binding = GestureBinding::new();
+    binding.dispatch_event(
+        &PointerEvent::new(PointerEventKind::Down, 1, position),
+        &result,
+    );
+
+    let event = &a.borrow().events[0];
+    assert_eq!(event.position, position);
+    assert_offset_eq(event.local_position, Offset::new(10.0, 15.0));
+}
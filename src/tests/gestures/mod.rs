@@ -0,0 +1,5 @@
+mod arena_test;
+mod hit_test_test;
+mod pointer_test;
+mod recognizer_test;
+mod velocity_tracker_test;
@@ -0,0 +1,170 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    gestures::{GestureBinding, PointerEvent, PointerEventKind},
+    rendering::{FlexFit, Offset, RenderBox, RenderFlex, RenderFlexible},
+    tests::common::RenderSizedBox,
+};
+
+fn flexible(child: &Rc<RefCell<RenderSizedBox>>, offset: Offset) -> RenderFlexible {
+    RenderFlexible {
+        offset,
+        flex: 0,
+        fit: FlexFit::Tight,
+        inner: child.clone(),
+    }
+}
+
+fn setup() -> (
+    Rc<RefCell<dyn RenderBox>>,
+    Rc<RefCell<RenderSizedBox>>,
+    Rc<RefCell<RenderSizedBox>>,
+) {
+    let a = Rc::new(RefCell::new(RenderSizedBox::new(10.0, 10.0)));
+    let b = Rc::new(RefCell::new(RenderSizedBox::new(10.0, 10.0)));
+    let root = RenderFlex::default()
+        .with_child(flexible(&a, Offset::zero()))
+        .with_child(flexible(&b, Offset::new(10.0, 0.0)));
+    (Rc::new(RefCell::new(root)), a, b)
+}
+
+fn kinds(target: &Rc<RefCell<RenderSizedBox>>) -> Vec<PointerEventKind> {
+    target.borrow().events.iter().map(|e| e.kind).collect()
+}
+
+#[test]
+fn test_pointer_sequence_routes_to_down_target() {
+    let (root, a, b) = setup();
+    let mut binding = GestureBinding::new();
+
+    binding.handle_event(
+        &root,
+        PointerEvent::new(PointerEventKind::Down, 1, Offset::new(5.0, 5.0)),
+    );
+    assert!(binding.is_tracking(1));
+    // leaves `a` and moves over `b`
+    binding.handle_event(
+        &root,
+        PointerEvent::new(PointerEventKind::Move, 1, Offset::new(15.0, 5.0)),
+    );
+    binding.handle_event(
+        &root,
+        PointerEvent::new(PointerEventKind::Up, 1, Offset::new(15.0, 5.0)),
+    );
+    assert!(!binding.is_tracking(1));
+
+    assert_eq!(
+        kinds(&a),
+        vec![
+            PointerEventKind::Down,
+            PointerEventKind::Move,
+            PointerEventKind::Up
+        ]
+    );
+    assert!(b.borrow().events.is_empty());
+
+    // once released, further moves of the pointer are not routed
+    binding.handle_event(
+        &root,
+        PointerEvent::new(PointerEventKind::Move, 1, Offset::new(5.0, 5.0)),
+    );
+    assert_eq!(a.borrow().events.len(), 3);
+}
+
+#[test]
+fn test_local_position() {
+    let (root, a, b) = setup();
+    let mut binding = GestureBinding::new();
+
+    binding.handle_event(
+        &root,
+        PointerEvent::new(PointerEventKind::Down, 1, Offset::new(15.0, 5.0)),
+    );
+    binding.handle_event(
+        &root,
+        PointerEvent::new(PointerEventKind::Move, 1, Offset::new(25.0, 8.0)),
+    );
+
+    let events = &b.borrow().events;
+    assert_eq!(events[0].position, Offset::new(15.0, 5.0));
+    assert_eq!(events[0].local_position, Offset::new(5.0, 5.0));
+    assert_eq!(events[1].local_position, Offset::new(15.0, 8.0));
+    assert!(a.borrow().events.is_empty());
+}
+
+#[test]
+fn test_cancel_ends_sequence() {
+    let (root, a, _) = setup();
+    let mut binding = GestureBinding::new();
+
+    binding.handle_event(
+        &root,
+        PointerEvent::new(PointerEventKind::Down, 1, Offset::new(5.0, 5.0)),
+    );
+    binding.handle_event(
+        &root,
+        PointerEvent::new(PointerEventKind::Cancel, 1, Offset::new(5.0, 5.0)),
+    );
+    assert!(!binding.is_tracking(1));
+    assert_eq!(
+        kinds(&a),
+        vec![PointerEventKind::Down, PointerEventKind::Cancel]
+    );
+}
+
+#[test]
+fn test_multiple_pointers_are_tracked_independently() {
+    let (root, a, b) = setup();
+    let mut binding = GestureBinding::new();
+
+    binding.handle_event(
+        &root,
+        PointerEvent::new(PointerEventKind::Down, 1, Offset::new(5.0, 5.0)),
+    );
+    binding.handle_event(
+        &root,
+        PointerEvent::new(PointerEventKind::Down, 2, Offset::new(15.0, 5.0)),
+    );
+    binding.handle_event(
+        &root,
+        PointerEvent::new(PointerEventKind::Move, 2, Offset::new(5.0, 5.0)),
+    );
+    binding.handle_event(
+        &root,
+        PointerEvent::new(PointerEventKind::Up, 1, Offset::new(5.0, 5.0)),
+    );
+
+    assert_eq!(
+        kinds(&a),
+        vec![PointerEventKind::Down, PointerEventKind::Up]
+    );
+    assert_eq!(
+        kinds(&b),
+        vec![PointerEventKind::Down, PointerEventKind::Move]
+    );
+    assert!(b.borrow().events.iter().all(|e| e.pointer == 2));
+}
+
+#[test]
+fn test_hover_and_scroll_hit_test_each_event() {
+    let (root, a, b) = setup();
+    let mut binding = GestureBinding::new();
+
+    binding.handle_event(
+        &root,
+        PointerEvent::new(PointerEventKind::Hover, 1, Offset::new(5.0, 5.0)),
+    );
+    binding.handle_event(
+        &root,
+        PointerEvent::new(PointerEventKind::Hover, 1, Offset::new(15.0, 5.0)),
+    );
+    let scroll = PointerEventKind::Scroll {
+        scroll_delta: Offset::new(0.0, 20.0),
+    };
+    binding.handle_event(&root, PointerEvent::new(scroll, 1, Offset::new(15.0, 5.0)));
+
+    assert!(!binding.is_tracking(1));
+    assert_eq!(kinds(&a), vec![PointerEventKind::Hover]);
+    assert_eq!(kinds(&b), vec![PointerEventKind::Hover, scroll]);
+    assert_eq!(b.borrow().events[0].buttons, 0);
+}
@@ -0,0 +1,284 @@
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+use crate::{
+    gestures::{
+        DoubleTapCallbacks, DoubleTapGestureRecognizer, DragCallbacks, DragGestureRecognizer,
+        GestureBinding, HitTestBehavior, LongPressCallbacks, LongPressGestureRecognizer,
+        PointerEvent, PointerEventKind, ScaleCallbacks, ScaleGestureRecognizer, TapCallbacks,
+        TapGestureRecognizer,
+    },
+    rendering::{BoxConstraints, Offset, RenderBox, RenderGestureDetector, RenderView, Size},
+    tests::common::RenderSizedBox,
+};
+
+type Log = Rc<RefCell<Vec<String>>>;
+
+fn event(kind: PointerEventKind, pointer: usize, x: f32, y: f32, ms: u64) -> PointerEvent {
+    PointerEvent::new(kind, pointer, Offset::new(x, y)).with_timestamp(Duration::from_millis(ms))
+}
+
+fn logger(log: &Log, message: &'static str) -> impl Fn(()) + 'static {
+    let log = log.clone();
+    move |_| log.borrow_mut().push(message.to_string())
+}
+
+fn view(child: RenderGestureDetector) -> Rc<RefCell<dyn RenderBox>> {
+    let mut view = RenderView::new();
+    view.set_child(Rc::new(RefCell::new(child)));
+    view.layout(&BoxConstraints::tight(Size::new(100.0, 100.0)), false);
+    Rc::new(RefCell::new(view))
+}
+
+fn detector() -> RenderGestureDetector {
+    RenderGestureDetector::new(HitTestBehavior::Opaque)
+        .with_child(RenderSizedBox::new(100.0, 100.0))
+}
+
+fn tap_callbacks(log: &Log) -> TapCallbacks {
+    let down_log = log.clone();
+    TapCallbacks::default()
+        .with_on_tap_down(move |details| {
+            down_log
+                .borrow_mut()
+                .push(format!("tap down {:?}", details.local_position))
+        })
+        .with_on_tap(logger(log, "tap"))
+        .with_on_tap_cancel(logger(log, "tap cancel"))
+}
+
+fn drag_callbacks(log: &Log, name: &'static str) -> DragCallbacks {
+    let update_log = log.clone();
+    let start_log = log.clone();
+    DragCallbacks::default()
+        .with_on_start(move |_| start_log.borrow_mut().push(format!("{} start", name)))
+        .with_on_update(move |details| {
+            update_log
+                .borrow_mut()
+                .push(format!("{} update {:?}", name, details.primary_delta))
+        })
+        .with_on_end(move |_| {})
+        .with_on_cancel(logger(log, "drag cancel"))
+}
+
+#[test]
+fn test_tap() {
+    let log = Log::default();
+    let root = view(detector().with_recognizer(TapGestureRecognizer::new(tap_callbacks(&log))));
+    let mut binding = GestureBinding::new();
+
+    binding.handle_event(&root, event(PointerEventKind::Down, 1, 10.0, 20.0, 0));
+    binding.handle_event(&root, event(PointerEventKind::Move, 1, 12.0, 20.0, 10));
+    binding.handle_event(&root, event(PointerEventKind::Up, 1, 12.0, 20.0, 20));
+    assert_eq!(
+        *log.borrow(),
+        vec!["tap down Offset { x: 10.0, y: 20.0 }", "tap"]
+    );
+}
+
+#[test]
+fn test_tap_cancelled_beyond_slop() {
+    let log = Log::default();
+    let root = view(detector().with_recognizer(TapGestureRecognizer::new(tap_callbacks(&log))));
+    let mut binding = GestureBinding::new();
+
+    binding.handle_event(&root, event(PointerEventKind::Down, 1, 10.0, 20.0, 0));
+    binding.handle_event(&root, event(PointerEventKind::Move, 1, 50.0, 20.0, 10));
+    binding.handle_event(&root, event(PointerEventKind::Up, 1, 50.0, 20.0, 20));
+    assert_eq!(
+        *log.borrow(),
+        vec!["tap down Offset { x: 10.0, y: 20.0 }", "tap cancel"]
+    );
+}
+
+/// A tap target inside a vertically scrolling parent.
+fn button_in_list(log: &Log) -> Rc<RefCell<dyn RenderBox>> {
+    let button = detector().with_recognizer(TapGestureRecognizer::new(tap_callbacks(log)));
+    view(
+        RenderGestureDetector::new(HitTestBehavior::Opaque)
+            .with_child(button)
+            .with_recognizer(DragGestureRecognizer::vertical(drag_callbacks(
+                log, "vertical",
+            ))),
+    )
+}
+
+#[test]
+fn test_tap_in_list_wins_without_movement() {
+    let log = Log::default();
+    let root = button_in_list(&log);
+    let mut binding = GestureBinding::new();
+
+    binding.handle_event(&root, event(PointerEventKind::Down, 1, 10.0, 10.0, 0));
+    assert!(log.borrow().is_empty());
+    binding.handle_event(&root, event(PointerEventKind::Up, 1, 10.0, 10.0, 20));
+    assert_eq!(
+        *log.borrow(),
+        vec!["drag cancel", "tap down Offset { x: 10.0, y: 10.0 }", "tap"]
+    );
+}
+
+#[test]
+fn test_drag_in_list_beats_tap() {
+    let log = Log::default();
+    let root = button_in_list(&log);
+    let mut binding = GestureBinding::new();
+
+    binding.handle_event(&root, event(PointerEventKind::Down, 1, 10.0, 10.0, 0));
+    binding.handle_event(&root, event(PointerEventKind::Move, 1, 10.0, 40.0, 10));
+    binding.handle_event(&root, event(PointerEventKind::Move, 1, 10.0, 50.0, 20));
+    binding.handle_event(&root, event(PointerEventKind::Up, 1, 10.0, 50.0, 30));
+    assert_eq!(
+        *log.borrow(),
+        vec!["vertical start", "vertical update Some(10.0)"]
+    );
+}
+
+#[test]
+fn test_drag_axis_competition() {
+    let log = Log::default();
+    let root = view(
+        detector()
+            .with_recognizer(DragGestureRecognizer::horizontal(drag_callbacks(
+                &log,
+                "horizontal",
+            )))
+            .with_recognizer(DragGestureRecognizer::vertical(drag_callbacks(
+                &log, "vertical",
+            ))),
+    );
+    let mut binding = GestureBinding::new();
+
+    binding.handle_event(&root, event(PointerEventKind::Down, 1, 50.0, 50.0, 0));
+    binding.handle_event(&root, event(PointerEventKind::Move, 1, 30.0, 52.0, 10));
+    binding.handle_event(&root, event(PointerEventKind::Move, 1, 25.0, 60.0, 20));
+    binding.handle_event(&root, event(PointerEventKind::Up, 1, 25.0, 60.0, 30));
+    assert_eq!(
+        *log.borrow(),
+        vec![
+            "drag cancel",
+            "horizontal start",
+            "horizontal update Some(-5.0)"
+        ]
+    );
+}
+
+fn double_tap_detector(log: &Log) -> Rc<RefCell<dyn RenderBox>> {
+    view(
+        detector()
+            .with_recognizer(TapGestureRecognizer::new(
+                TapCallbacks::default().with_on_tap(logger(log, "tap")),
+            ))
+            .with_recognizer(DoubleTapGestureRecognizer::new(
+                DoubleTapCallbacks::default().with_on_double_tap(logger(log, "double tap")),
+            )),
+    )
+}
+
+#[test]
+fn test_double_tap() {
+    let log = Log::default();
+    let root = double_tap_detector(&log);
+    let mut binding = GestureBinding::new();
+
+    binding.handle_event(&root, event(PointerEventKind::Down, 1, 10.0, 10.0, 0));
+    binding.handle_event(&root, event(PointerEventKind::Up, 1, 10.0, 10.0, 50));
+    assert!(log.borrow().is_empty());
+    binding.handle_event(&root, event(PointerEventKind::Down, 2, 12.0, 10.0, 150));
+    binding.handle_event(&root, event(PointerEventKind::Up, 2, 12.0, 10.0, 200));
+    assert_eq!(*log.borrow(), vec!["double tap"]);
+
+    // nothing is pending anymore
+    binding.handle_deadlines(Duration::from_secs(1));
+    assert_eq!(log.borrow().len(), 1);
+}
+
+#[test]
+fn test_slow_taps_are_single_taps() {
+    let log = Log::default();
+    let root = double_tap_detector(&log);
+    let mut binding = GestureBinding::new();
+
+    binding.handle_event(&root, event(PointerEventKind::Down, 1, 10.0, 10.0, 0));
+    binding.handle_event(&root, event(PointerEventKind::Up, 1, 10.0, 10.0, 50));
+    binding.handle_deadlines(Duration::from_millis(200));
+    assert!(log.borrow().is_empty());
+    binding.handle_deadlines(Duration::from_millis(400));
+    assert_eq!(*log.borrow(), vec!["tap"]);
+
+    binding.handle_event(&root, event(PointerEventKind::Down, 2, 10.0, 10.0, 500));
+    binding.handle_event(&root, event(PointerEventKind::Up, 2, 10.0, 10.0, 550));
+    // the timeout expires with the next event
+    binding.handle_event(&root, event(PointerEventKind::Hover, 3, 10.0, 10.0, 900));
+    assert_eq!(*log.borrow(), vec!["tap", "tap"]);
+}
+
+#[test]
+fn test_long_press_beats_tap() {
+    let log = Log::default();
+    let root = view(
+        detector()
+            .with_recognizer(TapGestureRecognizer::new(tap_callbacks(&log)))
+            .with_recognizer(LongPressGestureRecognizer::new(
+                LongPressCallbacks::default().with_on_long_press(logger(&log, "long press")),
+            )),
+    );
+    let mut binding = GestureBinding::new();
+
+    binding.handle_event(&root, event(PointerEventKind::Down, 1, 10.0, 10.0, 0));
+    binding.handle_deadlines(Duration::from_millis(300));
+    binding.handle_deadlines(Duration::from_millis(600));
+    binding.handle_event(&root, event(PointerEventKind::Up, 1, 10.0, 10.0, 700));
+    assert_eq!(
+        *log.borrow(),
+        vec![
+            "tap down Offset { x: 10.0, y: 10.0 }",
+            "tap cancel",
+            "long press"
+        ]
+    );
+}
+
+#[test]
+fn test_short_press_is_not_long_press() {
+    let log = Log::default();
+    let root = view(
+        detector()
+            .with_recognizer(TapGestureRecognizer::new(tap_callbacks(&log)))
+            .with_recognizer(LongPressGestureRecognizer::new(
+                LongPressCallbacks::default().with_on_long_press(logger(&log, "long press")),
+            )),
+    );
+    let mut binding = GestureBinding::new();
+
+    binding.handle_event(&root, event(PointerEventKind::Down, 1, 10.0, 10.0, 0));
+    binding.handle_event(&root, event(PointerEventKind::Up, 1, 10.0, 10.0, 50));
+    binding.handle_deadlines(Duration::from_secs(1));
+    assert_eq!(
+        *log.borrow(),
+        vec!["tap down Offset { x: 10.0, y: 10.0 }", "tap"]
+    );
+}
+
+#[test]
+fn test_scale() {
+    let scales = Rc::new(RefCell::new(Vec::new()));
+    let updates = scales.clone();
+    let root = view(detector().with_recognizer(ScaleGestureRecognizer::new(
+        ScaleCallbacks::default().with_on_update(move |details| {
+            updates
+                .borrow_mut()
+                .push((details.scale, details.focal_point))
+        }),
+    )));
+    let mut binding = GestureBinding::new();
+
+    binding.handle_event(&root, event(PointerEventKind::Down, 1, 40.0, 50.0, 0));
+    binding.handle_event(&root, event(PointerEventKind::Down, 2, 60.0, 50.0, 0));
+    binding.handle_event(&root, event(PointerEventKind::Move, 1, 30.0, 50.0, 10));
+    binding.handle_event(&root, event(PointerEventKind::Move, 2, 70.0, 50.0, 10));
+
+    let scales = scales.borrow();
+    assert_eq!(scales.len(), 2);
+    assert_eq!(scales[0], (1.5, Offset::new(45.0, 50.0)));
+    assert_eq!(scales[1], (2.0, Offset::new(50.0, 50.0)));
+}
@@ -0,0 +1,112 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    gestures::{
+        DragCallbacks, DragEndDetails, DragGestureRecognizer, GestureBinding, HitTestBehavior,
+        LeastSquaresSolver, PointerEvent, PointerEventKind, VelocityTracker, MAX_FLING_VELOCITY,
+    },
+    rendering::{BoxConstraints, Offset, RenderBox, RenderGestureDetector, RenderView, Size},
+    tests::common::{assert_near, ms, RenderSizedBox},
+};
+
+#[test]
+fn test_least_squares_fits_polynomials() {
+    // y = 1 + 2x + 3x^2
+    let x = [-2.0, -1.0, 0.0, 1.0, 2.0];
+    let y: Vec<f32> = x.iter().map(|x| 1.0 + 2.0 * x + 3.0 * x * x).collect();
+    let w = [1.0; 5];
+    let fit = LeastSquaresSolver::new(&x, &y, &w).solve(2).unwrap();
+    for (actual, expected) in fit.coefficients.iter().zip([1.0, 2.0, 3.0]) {
+        assert_near(*actual, expected, 1e-4);
+    }
+    assert_near(fit.confidence, 1.0, 1e-6);
+
+    // a line through noisy samples fits with less confidence
+    let fit = LeastSquaresSolver::new(&x, &[0.0, 1.0, 1.0, 4.0, 4.0], &w)
+        .solve(1)
+        .unwrap();
+    assert!(fit.confidence < 1.0 && fit.coefficients[1] > 0.0);
+
+    // samples at the same x do not determine a slope
+    assert_eq!(
+        LeastSquaresSolver::new(&[1.0; 3], &[0.0, 1.0, 2.0], &[1.0; 3]).solve(1),
+        None
+    );
+}
+
+#[test]
+fn test_tracker_estimates_recent_motion() {
+    let mut tracker = VelocityTracker::new();
+    assert_eq!(tracker.get_velocity_estimate(), None);
+    tracker.add_position(ms(0), Offset::new(0.0, 0.0));
+    // a single sample does not move
+    assert_eq!(tracker.get_velocity().pixels_per_second, Offset::zero());
+
+    // slow at first, then 1000 pixels per second right and 500 down
+    for i in 1..=5 {
+        tracker.add_position(ms(i * 10), Offset::new(i as f32, 0.0));
+    }
+    for i in 1..=20 {
+        let t = 50 + i * 8;
+        tracker.add_position(ms(t), Offset::new(5.0 + i as f32 * 8.0, i as f32 * 4.0));
+    }
+    let estimate = tracker.get_velocity_estimate().unwrap();
+    assert_near(estimate.pixels_per_second.x, 1000.0, 1.0);
+    assert_near(estimate.pixels_per_second.y, 500.0, 1.0);
+    assert_near(estimate.confidence, 1.0, 1e-3);
+    // only the last 100ms count
+    assert_eq!(estimate.duration, ms(96));
+    assert_eq!(estimate.offset, Offset::new(96.0, 48.0));
+
+    // a pointer resting after moving has no velocity
+    tracker.add_position(ms(260), Offset::new(165.0, 80.0));
+    assert_eq!(tracker.get_velocity().pixels_per_second, Offset::zero());
+}
+
+fn drag_end(moves: &[(f32, u64)]) -> DragEndDetails {
+    let ends = Rc::new(RefCell::new(Vec::new()));
+    let log = ends.clone();
+    let callbacks =
+        DragCallbacks::default().with_on_end(move |details| log.borrow_mut().push(details));
+    let detector = RenderGestureDetector::new(HitTestBehavior::Opaque)
+        .with_child(RenderSizedBox::new(100.0, 400.0))
+        .with_recognizer(DragGestureRecognizer::vertical(callbacks));
+    let mut view = RenderView::new();
+    view.set_child(Rc::new(RefCell::new(detector)));
+    view.layout(&BoxConstraints::tight(Size::new(100.0, 400.0)), false);
+    let root: Rc<RefCell<dyn RenderBox>> = Rc::new(RefCell::new(view));
+
+    let mut binding = GestureBinding::new();
+    let event = |kind, y: f32, time: u64| {
+        PointerEvent::new(kind, 1, Offset::new(50.0, y)).with_timestamp(ms(time))
+    };
+    binding.handle_event(&root, event(PointerEventKind::Down, 10.0, 0));
+    for (y, time) in moves.iter().copied() {
+        binding.handle_event(&root, event(PointerEventKind::Move, y, time));
+    }
+    let (y, time) = moves.last().copied().unwrap();
+    binding.handle_event(&root, event(PointerEventKind::Up, y, time));
+    let end = ends.borrow().clone();
+    assert_eq!(end.len(), 1);
+    end[0]
+}
+
+#[test]
+fn test_drags_end_with_the_velocity_of_a_fling() {
+    // 2000 pixels per second down
+    let moves: Vec<_> = (1..=6).map(|i| (10.0 + i as f32 * 16.0, i * 8)).collect();
+    let details = drag_end(&moves);
+    assert_near(details.primary_velocity.unwrap(), 2000.0, 1.0);
+    assert_eq!(details.velocity.pixels_per_second.x, 0.0);
+
+    // too fast, clamped
+    let moves: Vec<_> = (1..=6).map(|i| (10.0 + i as f32 * 80.0, i * 8)).collect();
+    let details = drag_end(&moves);
+    assert_near(details.primary_velocity.unwrap(), MAX_FLING_VELOCITY, 1.0);
+
+    // a slow drag is no fling
+    let moves: Vec<_> = (1..=6).map(|i| (10.0 + i as f32 * 5.0, i * 100)).collect();
+    let details = drag_end(&moves);
+    assert_eq!(details.primary_velocity, Some(0.0));
+    assert_eq!(details.velocity.pixels_per_second, Offset::zero());
+}
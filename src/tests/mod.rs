@@ -1,2 +1,9 @@
+mod animation;
 mod common;
+mod gestures;
+mod painting;
 mod rendering;
+mod scheduler;
+mod semantics;
+mod services;
+mod widgets;
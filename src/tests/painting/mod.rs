@@ -0,0 +1 @@
+mod text_span_test;
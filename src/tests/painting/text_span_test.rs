@@ -0,0 +1,80 @@
+use crate::{
+    painting::{FontWeight, TextDecoration, TextSpan, TextStyle},
+    ui::Color,
+};
+
+fn span() -> TextSpan {
+    TextSpan::new("Read ")
+        .with_child(
+            TextSpan::new("the")
+                .with_style(TextStyle::new().with_font_weight(FontWeight::BOLD))
+                .with_child(" docs"),
+        )
+        .with_child(TextSpan::default().with_child(" now"))
+}
+
+#[test]
+fn test_plain_text() {
+    assert_eq!(span().to_plain_text(), "Read the docs now");
+}
+
+#[test]
+fn test_span_for_position() {
+    let span = span();
+    let texts: Vec<_> = [0, 4, 5, 7, 8, 12, 13, 16, 17]
+        .iter()
+        .map(|position| {
+            span.get_span_for_position(*position)
+                .and_then(|span| span.text.as_deref())
+        })
+        .collect();
+    assert_eq!(
+        texts,
+        vec![
+            Some("Read "),
+            Some("Read "),
+            Some("the"),
+            Some("the"),
+            Some(" docs"),
+            Some(" docs"),
+            Some(" now"),
+            Some(" now"),
+            None
+        ]
+    );
+}
+
+#[test]
+fn test_positions_are_utf16() {
+    // the emoji takes 2 code units
+    let span = TextSpan::new("\u{1F600}").with_child("a");
+    assert_eq!(
+        span.get_span_for_position(1).unwrap().text.as_deref(),
+        Some("\u{1F600}")
+    );
+    assert_eq!(
+        span.get_span_for_position(2).unwrap().text.as_deref(),
+        Some("a")
+    );
+}
+
+#[test]
+fn test_merge_overrides_set_properties() {
+    let base = TextStyle::new()
+        .with_color(Color::BLACK)
+        .with_font_size(14.0)
+        .with_font_family("Roboto");
+    let link = TextStyle::new()
+        .with_color(Color::from_argb(255, 0, 0, 255))
+        .with_decoration(TextDecoration::UNDERLINE | TextDecoration::OVERLINE)
+        .with_letter_spacing(1.0);
+
+    let merged = base.merge(&link);
+    assert_eq!(merged.color, Some(Color::from_argb(255, 0, 0, 255)));
+    assert_eq!(merged.font_size, Some(14.0));
+    assert_eq!(merged.font_families, vec!["Roboto".to_string()]);
+    assert_eq!(merged.letter_spacing, Some(1.0));
+    let decoration = merged.decoration.unwrap();
+    assert!(decoration.underline && decoration.overline && !decoration.line_through);
+    assert_eq!(link.merge(&TextStyle::new()), link);
+}
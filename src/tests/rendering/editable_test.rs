@@ -0,0 +1,209 @@
+use std::rc::Rc;
+
+use crate::{
+    gestures::HitTestTarget,
+    painting::TextStyle,
+    rendering::{BoxConstraints, Offset, RenderBox, RenderEditable, RenderObject, Size},
+    services::{
+        KeyEvent, KeyEventKind, LogicalKey, Modifiers, TextEditingController, TextSelection,
+    },
+    tests::common::FocusTree,
+};
+
+fn editable(text: &str) -> (RenderEditable, Rc<TextEditingController>) {
+    let controller = Rc::new(TextEditingController::new(text));
+    let editable =
+        RenderEditable::new(controller.clone()).with_style(TextStyle::new().with_font_size(20.0));
+    (editable, controller)
+}
+
+fn layout(editable: &mut RenderEditable, max_width: f32) {
+    editable.layout(&BoxConstraints::default().with_max_width(max_width), true);
+}
+
+fn key(logical_key: LogicalKey) -> KeyEvent {
+    KeyEvent::new(KeyEventKind::Down, logical_key)
+}
+
+fn type_text(editable: &mut RenderEditable, text: &str) {
+    for c in text.chars() {
+        let event = key(LogicalKey::Character(c)).with_character(c.to_string());
+        assert!(editable.handle_key_event(&event));
+    }
+}
+
+#[test]
+fn test_typing_and_deleting() {
+    let (mut editable, controller) = editable("");
+    type_text(&mut editable, "hello world");
+    assert_eq!(controller.text(), "hello world");
+    assert!(editable.state().needs_layout());
+
+    editable.handle_key_event(&key(LogicalKey::Backspace));
+    assert_eq!(controller.text(), "hello worl");
+    let control = Modifiers::CONTROL;
+    editable.handle_key_event(&key(LogicalKey::Backspace).with_modifiers(control));
+    assert_eq!(controller.text(), "hello ");
+
+    controller.set_selection(TextSelection::collapsed(0));
+    editable.handle_key_event(&key(LogicalKey::Delete).with_modifiers(control));
+    assert_eq!(controller.text(), " ");
+    // releasing a key and shortcuts do not type
+    let up = KeyEvent::new(KeyEventKind::Up, LogicalKey::Character('a')).with_character("a");
+    assert!(!editable.handle_key_event(&up));
+    let copy = key(LogicalKey::Character('c'))
+        .with_modifiers(control)
+        .with_character("c");
+    assert!(!editable.handle_key_event(&copy));
+    assert_eq!(controller.text(), " ");
+}
+
+#[test]
+fn test_resize_takes_the_smallest_size() {
+    let (mut editable, _) = editable("hello");
+    let constraints = BoxConstraints::default()
+        .with_min_width(20.0)
+        .with_max_width(100.0)
+        .with_max_height(50.0);
+    editable.perform_resize(&constraints);
+    assert_eq!(editable.size, Size::new(20.0, 0.0));
+}
+
+#[test]
+fn test_moving_and_extending_selection() {
+    let (mut editable, controller) = editable("one two three");
+    layout(&mut editable, 1000.0);
+    let shift = Modifiers::SHIFT;
+    let control = Modifiers::CONTROL;
+
+    editable.handle_key_event(&key(LogicalKey::Home));
+    assert_eq!(controller.selection(), TextSelection::collapsed(0));
+    editable.handle_key_event(&key(LogicalKey::ArrowRight).with_modifiers(control));
+    assert_eq!(controller.selection(), TextSelection::collapsed(3));
+    editable.handle_key_event(&key(LogicalKey::ArrowRight).with_modifiers(control | shift));
+    assert_eq!(controller.selection(), TextSelection::new(3, 7));
+    assert_eq!(controller.value().selected_text(), " two");
+
+    // typing replaces the selection
+    type_text(&mut editable, "-");
+    assert_eq!(controller.text(), "one- three");
+    editable.handle_key_event(&key(LogicalKey::ArrowLeft).with_modifiers(shift));
+    editable.handle_key_event(&key(LogicalKey::ArrowLeft).with_modifiers(shift));
+    assert_eq!(controller.selection(), TextSelection::new(4, 2));
+    // moving collapses it to the side moved to
+    editable.handle_key_event(&key(LogicalKey::ArrowLeft));
+    assert_eq!(controller.selection(), TextSelection::collapsed(2));
+
+    editable.handle_key_event(&key(LogicalKey::Character('a')).with_modifiers(control));
+    assert_eq!(controller.selection(), TextSelection::new(0, 10));
+    editable.handle_key_event(&key(LogicalKey::End));
+    assert_eq!(controller.selection(), TextSelection::collapsed(10));
+}
+
+#[test]
+fn test_moving_across_lines() {
+    let (editable, controller) = editable("first line\nsecond\nthird line");
+    let mut editable = editable.with_max_lines(None);
+    layout(&mut editable, 1000.0);
+    controller.set_selection(TextSelection::collapsed(8));
+
+    editable.handle_key_event(&key(LogicalKey::ArrowDown));
+    assert_eq!(
+        controller.selection(),
+        TextSelection::collapsed(17),
+        "end of 'second'"
+    );
+    editable.handle_key_event(&key(LogicalKey::ArrowDown));
+    assert_eq!(controller.selection(), TextSelection::collapsed(24));
+    editable.handle_key_event(&key(LogicalKey::Home));
+    assert_eq!(controller.selection(), TextSelection::collapsed(18));
+    editable.handle_key_event(&key(LogicalKey::ArrowUp));
+    assert_eq!(controller.selection(), TextSelection::collapsed(11));
+    editable.handle_key_event(&key(LogicalKey::ArrowUp));
+    editable.handle_key_event(&key(LogicalKey::ArrowUp));
+    assert_eq!(controller.selection(), TextSelection::collapsed(0));
+
+    editable.handle_key_event(&key(LogicalKey::Enter));
+    assert!(controller.text().starts_with("\nfirst"));
+    layout(&mut editable, 1000.0);
+    let caret = editable.caret_rect();
+    assert_eq!(caret.left, 0.0);
+    assert!(caret.top > 0.0, "the caret starts the second line");
+}
+
+#[test]
+fn test_undo_and_redo() {
+    let (mut editable, controller) = editable("");
+    let control = Modifiers::CONTROL;
+    type_text(&mut editable, "ab");
+    editable.handle_key_event(&key(LogicalKey::Backspace));
+    assert_eq!(controller.text(), "a");
+
+    let undo = key(LogicalKey::Character('z')).with_modifiers(control);
+    let redo = key(LogicalKey::Character('z')).with_modifiers(control | Modifiers::SHIFT);
+    editable.handle_key_event(&undo);
+    assert_eq!(controller.text(), "ab");
+    editable.handle_key_event(&undo);
+    editable.handle_key_event(&undo);
+    assert_eq!(controller.text(), "");
+    assert!(
+        editable.handle_key_event(&undo),
+        "nothing left, still handled"
+    );
+
+    editable.handle_key_event(&redo);
+    assert_eq!(controller.text(), "a");
+    editable.handle_key_event(&key(LogicalKey::Character('y')).with_modifiers(control));
+    assert_eq!(controller.value().selection, TextSelection::collapsed(2));
+    assert_eq!(controller.text(), "ab");
+}
+
+#[test]
+fn test_max_length_truncates_input() {
+    let (editable, controller) = editable("abc");
+    let mut editable = editable.with_max_length(5);
+    type_text(&mut editable, "def");
+    assert_eq!(controller.text(), "abcde");
+
+    // a selection makes room for what replaces it
+    controller.set_selection(TextSelection::new(0, 2));
+    type_text(&mut editable, "xyz");
+    assert_eq!(controller.text(), "xycde");
+}
+
+#[test]
+fn test_single_line_scrolls_to_caret() {
+    let (mut editable, controller) = editable("a single line much longer than its field");
+    layout(&mut editable, 100.0);
+    assert_eq!(editable.size.width, 100.0);
+    assert!(editable.needs_clipping);
+
+    // the caret at the end is kept in view
+    assert!(editable.scroll_offset > 0.0);
+    let caret = editable.caret_rect();
+    assert!(caret.left >= 0.0 && caret.right <= 100.0);
+
+    editable.handle_key_event(&key(LogicalKey::Home));
+    layout(&mut editable, 100.0);
+    assert_eq!(editable.scroll_offset, 0.0);
+    // enter does not break a single line
+    assert!(!editable.handle_key_event(&key(LogicalKey::Enter)));
+    assert_eq!(controller.text().lines().count(), 1);
+}
+
+#[test]
+fn test_tap_places_caret_and_focuses() {
+    let (editable, controller) = editable("hello");
+    let focus_node = editable.focus_node.clone();
+    let mut tree = FocusTree::new(editable, Size::new(200.0, 100.0));
+    assert!(!focus_node.has_focus());
+
+    // between the 'e' and the first 'l'
+    tree.tap(Offset::new(21.0, 5.0));
+    assert_eq!(controller.selection(), TextSelection::collapsed(2));
+    assert!(focus_node.has_primary_focus());
+
+    let event = key(LogicalKey::Character('y')).with_character("y");
+    assert!(tree.manager.handle_key_event(&event));
+    assert_eq!(controller.text(), "heyllo");
+}
@@ -1,6 +1,10 @@
-use crate::rendering::{
-    Axis, BoxConstraints, FlexFit, Flexible, RenderBox, RenderConstrainedBox, RenderFlex, Size,
-    TextDirection,
+use crate::{
+    rendering::{
+        Axis, BoxConstraints, CrossAxisAlignment, FlexFit, MainAxisAlignment, Offset, RenderBox,
+        RenderConstrainedBox, RenderFlex, RenderFlexible, Size, TextBaseline, TextDirection,
+        VerticalDirection,
+    },
+    tests::common::RenderSizedBox,
 };
 
 #[test]
@@ -30,8 +34,8 @@ fn test_vertical_overflow() {
         .with_child(RenderConstrainedBox::new(BoxConstraints::tight_for(
             None, 200.0,
         )))
-        .with_child(Flexible::new(
-            RenderConstrainedBox::new(BoxConstraints::expand(None, None)),
+        .with_child(RenderFlexible::new(
+            RenderConstrainedBox::new(BoxConstraints::expand_by(None, None)),
             1,
             FlexFit::Loose,
         ));
@@ -43,5 +47,270 @@ fn test_vertical_overflow() {
     flex.layout(&viewport, false);
 
     assert_eq!(flex.size(), Size::new(100.0, 100.0));
-    assert_eq!(flex.children[1].inner.size(), Size::new(100.0, 0.0));
+    assert_eq!(
+        flex.children[1].inner.borrow().size(),
+        Size::new(100.0, 0.0)
+    );
+}
+
+#[test]
+fn test_baseline_alignment() {
+    let mut flex = RenderFlex::default()
+        .with_text_direction(TextDirection::LTR)
+        .with_cross_axis_alignment(CrossAxisAlignment::Baseline)
+        .with_text_baseline(TextBaseline::Alphabetic)
+        .with_child(RenderSizedBox::new(30.0, 20.0).with_baseline(15.0))
+        .with_child(RenderSizedBox::new(30.0, 40.0).with_baseline(30.0))
+        .with_child(RenderSizedBox::new(30.0, 10.0));
+
+    flex.layout(
+        &BoxConstraints::default()
+            .with_max_width(200.0)
+            .with_max_height(200.0),
+        false,
+    );
+
+    // 30 above the shared baseline and 10 below it
+    assert_eq!(flex.size(), Size::new(200.0, 40.0));
+    assert_eq!(flex.children[0].offset, Offset::new(0.0, 15.0));
+    assert_eq!(flex.children[1].offset, Offset::new(30.0, 0.0));
+    assert_eq!(flex.children[2].offset, Offset::new(60.0, 0.0));
+    assert_eq!(
+        flex.get_distance_to_actual_baseline(TextBaseline::Alphabetic),
+        Some(30.0)
+    );
+}
+
+#[test]
+fn test_baseline_alignment_grows_cross_size() {
+    let mut flex = RenderFlex::default()
+        .with_text_direction(TextDirection::LTR)
+        .with_cross_axis_alignment(CrossAxisAlignment::Baseline)
+        .with_text_baseline(TextBaseline::Alphabetic)
+        .with_child(RenderSizedBox::new(30.0, 20.0).with_baseline(5.0))
+        .with_child(RenderSizedBox::new(30.0, 20.0).with_baseline(15.0));
+
+    flex.layout(
+        &BoxConstraints::default()
+            .with_max_width(200.0)
+            .with_max_height(200.0),
+        false,
+    );
+
+    // 15 above the baseline and 15 below it
+    assert_eq!(flex.size(), Size::new(200.0, 30.0));
+    assert_eq!(flex.children[0].offset, Offset::new(0.0, 10.0));
+    assert_eq!(flex.children[1].offset, Offset::new(30.0, 0.0));
+}
+
+#[test]
+fn test_column_baseline_is_first_child() {
+    let mut flex = RenderFlex::default()
+        .with_direction(Axis::Vertical)
+        .with_cross_axis_alignment(CrossAxisAlignment::Start)
+        .with_child(RenderSizedBox::new(30.0, 20.0))
+        .with_child(RenderSizedBox::new(30.0, 20.0).with_baseline(15.0))
+        .with_child(RenderSizedBox::new(30.0, 20.0).with_baseline(5.0));
+
+    flex.layout(
+        &BoxConstraints::default()
+            .with_max_width(200.0)
+            .with_max_height(200.0),
+        false,
+    );
+
+    assert_eq!(
+        flex.get_distance_to_actual_baseline(TextBaseline::Alphabetic),
+        Some(35.0)
+    );
+    assert_eq!(
+        flex.children[0]
+            .inner
+            .borrow()
+            .get_distance_to_baseline(TextBaseline::Alphabetic),
+        20.0
+    );
+}
+
+const MAIN_SIZES: [f32; 3] = [10.0, 20.0, 30.0];
+const CROSS_SIZES: [f32; 3] = [30.0, 20.0, 10.0];
+const MAIN_EXTENT: f32 = 120.0;
+const CROSS_EXTENT: f32 = 40.0;
+
+/// Where the children start along the main axis when it runs from the top left.
+const MAIN_AXIS_CASES: [(MainAxisAlignment, [f32; 3]); 6] = [
+    (MainAxisAlignment::Start, [0.0, 10.0, 30.0]),
+    (MainAxisAlignment::End, [60.0, 70.0, 90.0]),
+    (MainAxisAlignment::Center, [30.0, 40.0, 60.0]),
+    (MainAxisAlignment::SpaceBetween, [0.0, 40.0, 90.0]),
+    (MainAxisAlignment::SpaceAround, [10.0, 40.0, 80.0]),
+    (MainAxisAlignment::SpaceEvenly, [15.0, 40.0, 75.0]),
+];
+
+/// Where the children start along the cross axis when it runs from the top left and
+/// when it does not, and their cross sizes.
+type CrossAxisCase = (CrossAxisAlignment, [f32; 3], [f32; 3], [f32; 3]);
+
+const CROSS_AXIS_CASES: [CrossAxisCase; 4] = [
+    (
+        CrossAxisAlignment::Start,
+        [0.0, 0.0, 0.0],
+        [10.0, 20.0, 30.0],
+        CROSS_SIZES,
+    ),
+    (
+        CrossAxisAlignment::End,
+        [10.0, 20.0, 30.0],
+        [0.0, 0.0, 0.0],
+        CROSS_SIZES,
+    ),
+    (
+        CrossAxisAlignment::Center,
+        [5.0, 10.0, 15.0],
+        [5.0, 10.0, 15.0],
+        CROSS_SIZES,
+    ),
+    (
+        CrossAxisAlignment::Stretch,
+        [0.0, 0.0, 0.0],
+        [0.0, 0.0, 0.0],
+        [CROSS_EXTENT; 3],
+    ),
+];
+
+/// Lays out three children, as `MAIN_SIZES` by `CROSS_SIZES`, in a flex of
+/// `MAIN_EXTENT` by `CROSS_EXTENT`.
+fn lay_out(
+    direction: Axis,
+    main_axis_alignment: MainAxisAlignment,
+    cross_axis_alignment: CrossAxisAlignment,
+    text_direction: TextDirection,
+    vertical_direction: VerticalDirection,
+) -> RenderFlex {
+    let size = |main: f32, cross: f32| match direction {
+        Axis::Horizontal => Size::new(main, cross),
+        Axis::Vertical => Size::new(cross, main),
+    };
+    let flex = RenderFlex::default()
+        .with_direction(direction)
+        .with_main_axis_alignment(main_axis_alignment)
+        .with_cross_axis_alignment(cross_axis_alignment)
+        .with_text_direction(text_direction)
+        .with_vertical_direction(vertical_direction);
+    let mut flex = MAIN_SIZES
+        .iter()
+        .zip(CROSS_SIZES)
+        .fold(flex, |flex, (main, cross)| {
+            flex.with_child(RenderConstrainedBox::new(BoxConstraints::tight(size(
+                *main, cross,
+            ))))
+        });
+    flex.layout(
+        &BoxConstraints::tight(size(MAIN_EXTENT, CROSS_EXTENT)),
+        false,
+    );
+    flex
+}
+
+/// The main and cross positions of the children, and their cross sizes.
+fn positions(flex: &RenderFlex) -> (Vec<f32>, Vec<f32>, Vec<f32>) {
+    let mut main = Vec::new();
+    let mut cross = Vec::new();
+    let mut cross_sizes = Vec::new();
+    for child in flex.children.iter() {
+        let size = child.inner.borrow().size();
+        match flex.direction {
+            Axis::Horizontal => {
+                main.push(child.offset.x);
+                cross.push(child.offset.y);
+                cross_sizes.push(size.height);
+            }
+            Axis::Vertical => {
+                main.push(child.offset.y);
+                cross.push(child.offset.x);
+                cross_sizes.push(size.width);
+            }
+        }
+    }
+    (main, cross, cross_sizes)
+}
+
+#[test]
+fn test_alignment_matrix() {
+    for direction in [Axis::Horizontal, Axis::Vertical] {
+        for text_direction in [TextDirection::LTR, TextDirection::RTL] {
+            for vertical_direction in [VerticalDirection::Down, VerticalDirection::Up] {
+                // rows follow the text direction, columns the vertical one
+                let (main_from_top_left, cross_from_top_left) = match direction {
+                    Axis::Horizontal => (
+                        text_direction == TextDirection::LTR,
+                        vertical_direction == VerticalDirection::Down,
+                    ),
+                    Axis::Vertical => (
+                        vertical_direction == VerticalDirection::Down,
+                        text_direction == TextDirection::LTR,
+                    ),
+                };
+                for (main_axis_alignment, main_positions) in MAIN_AXIS_CASES {
+                    for (cross_axis_alignment, top_left, bottom_right, cross_sizes) in
+                        CROSS_AXIS_CASES
+                    {
+                        let flex = lay_out(
+                            direction,
+                            main_axis_alignment,
+                            cross_axis_alignment,
+                            text_direction,
+                            vertical_direction,
+                        );
+                        // a flipped main axis mirrors the children
+                        let main: Vec<f32> = if main_from_top_left {
+                            main_positions.to_vec()
+                        } else {
+                            main_positions
+                                .iter()
+                                .zip(MAIN_SIZES)
+                                .map(|(position, size)| MAIN_EXTENT - position - size)
+                                .collect()
+                        };
+                        let cross = if cross_from_top_left {
+                            top_left
+                        } else {
+                            bottom_right
+                        };
+                        assert_eq!(
+                            positions(&flex),
+                            (main, cross.to_vec(), cross_sizes.to_vec()),
+                            "{:?} {:?} {:?} {:?} {:?}",
+                            direction,
+                            main_axis_alignment,
+                            cross_axis_alignment,
+                            text_direction,
+                            vertical_direction
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_single_child_space_alignment() {
+    for (main_axis_alignment, position) in [
+        (MainAxisAlignment::SpaceBetween, 0.0),
+        (MainAxisAlignment::SpaceAround, 50.0),
+        (MainAxisAlignment::SpaceEvenly, 50.0),
+    ] {
+        let mut flex = RenderFlex::default()
+            .with_text_direction(TextDirection::LTR)
+            .with_main_axis_alignment(main_axis_alignment)
+            .with_child(RenderSizedBox::new(20.0, 10.0));
+        flex.layout(&BoxConstraints::tight((120.0, 10.0)), false);
+        assert_eq!(
+            flex.children[0].offset,
+            Offset::new(position, 0.0),
+            "{:?}",
+            main_axis_alignment
+        );
+    }
 }
@@ -0,0 +1,96 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    rendering::{
+        Axis, BoxConstraints, FlexFit, RenderBox, RenderConstrainedBox, RenderFlex, RenderFlexible,
+        RenderImage, RenderObject, RenderView,
+    },
+    tests::common::{create_image, RenderSizedBox},
+};
+
+#[test]
+fn test_flex_inflexible_children() {
+    let flex = RenderFlex::default()
+        .with_child(RenderSizedBox::new(10.0, 20.0))
+        .with_child(RenderSizedBox::new(30.0, 40.0));
+
+    assert_eq!(flex.get_min_intrinsic_width(f32::INFINITY), 40.0);
+    assert_eq!(flex.get_max_intrinsic_width(f32::INFINITY), 40.0);
+    assert_eq!(flex.get_min_intrinsic_height(f32::INFINITY), 40.0);
+    assert_eq!(flex.get_max_intrinsic_height(f32::INFINITY), 40.0);
+}
+
+#[test]
+fn test_flex_flexible_children() {
+    let flex = RenderFlex::default()
+        .with_direction(Axis::Vertical)
+        .with_child(RenderFlexible::new(
+            RenderSizedBox::new(5.0, 10.0),
+            1,
+            FlexFit::Loose,
+        ))
+        .with_child(RenderFlexible::new(
+            RenderSizedBox::new(15.0, 30.0),
+            2,
+            FlexFit::Loose,
+        ))
+        .with_child(RenderSizedBox::new(20.0, 5.0));
+
+    // largest flex fraction is 30 / 2, so 3 flex units need 45 plus 5 inflexible
+    assert_eq!(flex.get_max_intrinsic_height(f32::INFINITY), 50.0);
+    assert_eq!(flex.get_max_intrinsic_width(f32::INFINITY), 20.0);
+}
+
+#[test]
+fn test_constrained_box() {
+    let tight = RenderConstrainedBox::new(BoxConstraints::tight((10.0, 20.0)));
+    assert_eq!(tight.get_min_intrinsic_width(f32::INFINITY), 10.0);
+    assert_eq!(tight.get_max_intrinsic_height(f32::INFINITY), 20.0);
+
+    let mut loose = RenderConstrainedBox::new(
+        BoxConstraints::default()
+            .with_max_width(25.0)
+            .with_min_height(50.0),
+    );
+    loose.set_child(Rc::new(RefCell::new(RenderSizedBox::new(40.0, 30.0))));
+    assert_eq!(loose.get_max_intrinsic_width(f32::INFINITY), 25.0);
+    assert_eq!(loose.get_min_intrinsic_height(f32::INFINITY), 50.0);
+}
+
+#[test]
+fn test_image() {
+    let image = create_image(20, 10);
+    assert_eq!(image.get_max_intrinsic_width(f32::INFINITY), 20.0);
+    assert_eq!(image.get_max_intrinsic_width(30.0), 60.0);
+    assert_eq!(image.get_max_intrinsic_height(40.0), 20.0);
+    assert_eq!(image.get_min_intrinsic_width(f32::INFINITY), 0.0);
+
+    let image = RenderImage::new(50.0, None);
+    assert_eq!(image.get_min_intrinsic_width(f32::INFINITY), 50.0);
+    assert_eq!(image.get_max_intrinsic_height(f32::INFINITY), 0.0);
+}
+
+#[test]
+fn test_view_delegates_to_child() {
+    let mut view = RenderView::new();
+    assert_eq!(view.get_max_intrinsic_width(f32::INFINITY), 0.0);
+
+    view.set_child(Rc::new(RefCell::new(RenderSizedBox::new(10.0, 20.0))));
+    assert_eq!(view.get_max_intrinsic_width(f32::INFINITY), 10.0);
+    assert_eq!(view.get_min_intrinsic_height(f32::INFINITY), 20.0);
+}
+
+#[test]
+fn test_intrinsics_are_cached_until_relayout() {
+    let sized = RenderSizedBox::new(10.0, 20.0);
+    assert_eq!(sized.get_min_intrinsic_width(100.0), 10.0);
+    assert_eq!(sized.get_min_intrinsic_width(100.0), 10.0);
+    assert_eq!(sized.intrinsic_count.get(), 1);
+
+    sized.get_min_intrinsic_width(50.0);
+    assert_eq!(sized.intrinsic_count.get(), 2);
+
+    sized.mark_needs_layout();
+    sized.get_min_intrinsic_width(100.0);
+    assert_eq!(sized.intrinsic_count.get(), 3);
+}
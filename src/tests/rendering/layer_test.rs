@@ -0,0 +1,107 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    rendering::{
+        BoxConstraints, FlexFit, Offset, PaintContext, RenderBox, RenderFlex, RenderFlexible,
+        RenderObject, RenderRepaintBoundary, RenderView, Size,
+    },
+    tests::common::RenderSizedBox,
+};
+
+struct Tree {
+    view: RenderView,
+    boundary: Rc<RefCell<RenderRepaintBoundary>>,
+    inside: Rc<RefCell<RenderSizedBox>>,
+    outside: Rc<RefCell<RenderSizedBox>>,
+}
+
+fn build_tree() -> Tree {
+    let inside = Rc::new(RefCell::new(RenderSizedBox::new(10.0, 10.0)));
+    let outside = Rc::new(RefCell::new(RenderSizedBox::new(10.0, 10.0)));
+    let boundary = Rc::new(RefCell::new(RenderRepaintBoundary::new()));
+    boundary.borrow_mut().set_child(inside.clone());
+
+    let flex = RenderFlex::default()
+        .with_child(RenderFlexible {
+            offset: Offset::zero(),
+            flex: 0,
+            fit: FlexFit::Tight,
+            inner: boundary.clone(),
+        })
+        .with_child(RenderFlexible {
+            offset: Offset::zero(),
+            flex: 0,
+            fit: FlexFit::Tight,
+            inner: outside.clone(),
+        });
+    let mut view = RenderView::new();
+    view.set_child(Rc::new(RefCell::new(flex)));
+    view.layout(&BoxConstraints::tight(Size::new(100.0, 100.0)), false);
+
+    Tree {
+        view,
+        boundary,
+        inside,
+        outside,
+    }
+}
+
+#[test]
+fn test_first_paint_records_layers() {
+    let tree = build_tree();
+    PaintContext::flush_paint(&tree.view, Size::new(100.0, 100.0));
+
+    assert!(tree.view.state().layer().is_some());
+    assert!(tree.boundary.borrow().state().layer().is_some());
+    assert!(!tree.view.state().needs_paint());
+    assert!(!tree.boundary.borrow().state().needs_paint());
+    assert_eq!(tree.inside.borrow().paint_count.get(), 1);
+    assert_eq!(tree.outside.borrow().paint_count.get(), 1);
+}
+
+#[test]
+fn test_clean_tree_is_not_repainted() {
+    let tree = build_tree();
+    PaintContext::flush_paint(&tree.view, Size::new(100.0, 100.0));
+    PaintContext::flush_paint(&tree.view, Size::new(100.0, 100.0));
+
+    assert_eq!(tree.inside.borrow().paint_count.get(), 1);
+    assert_eq!(tree.outside.borrow().paint_count.get(), 1);
+}
+
+#[test]
+fn test_repaint_stops_at_boundary() {
+    let tree = build_tree();
+    PaintContext::flush_paint(&tree.view, Size::new(100.0, 100.0));
+    let view_layer = tree.view.state().layer().unwrap();
+    let boundary_layer = tree.boundary.borrow().state().layer().unwrap();
+
+    tree.inside.borrow().mark_needs_paint();
+    assert!(tree.boundary.borrow().state().needs_paint());
+    assert!(!tree.view.state().needs_paint());
+    assert!(tree.view.state().child_needs_paint());
+
+    PaintContext::flush_paint(&tree.view, Size::new(100.0, 100.0));
+    assert!(!tree.view.state().child_needs_paint());
+    assert_eq!(tree.inside.borrow().paint_count.get(), 2);
+    assert_eq!(tree.outside.borrow().paint_count.get(), 1);
+    assert!(Rc::ptr_eq(&tree.view.state().layer().unwrap(), &view_layer));
+    assert!(Rc::ptr_eq(
+        &tree.boundary.borrow().state().layer().unwrap(),
+        &boundary_layer
+    ));
+}
+
+#[test]
+fn test_repaint_outside_boundary_reuses_its_layer() {
+    let tree = build_tree();
+    PaintContext::flush_paint(&tree.view, Size::new(100.0, 100.0));
+
+    tree.outside.borrow().mark_needs_paint();
+    assert!(tree.view.state().needs_paint());
+    assert!(!tree.boundary.borrow().state().needs_paint());
+
+    PaintContext::flush_paint(&tree.view, Size::new(100.0, 100.0));
+    assert_eq!(tree.inside.borrow().paint_count.get(), 1);
+    assert_eq!(tree.outside.borrow().paint_count.get(), 2);
+}
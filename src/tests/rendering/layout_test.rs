@@ -0,0 +1,137 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    gestures::HitTestBehavior,
+    rendering::{
+        BoxConstraints, CrossAxisAlignment, FlexFit, Offset, RenderBox, RenderFlex, RenderFlexible,
+        RenderFocus, RenderGestureDetector, RenderObject, RenderOpacity,
+        RenderSemanticsAnnotations, RenderView, Size,
+    },
+    semantics::SemanticsProperties,
+    services::FocusNode,
+    tests::common::RenderSizedBox,
+};
+
+fn flexible(child: &Rc<RefCell<RenderSizedBox>>, flex: usize, fit: FlexFit) -> RenderFlexible {
+    RenderFlexible {
+        offset: Offset::zero(),
+        flex,
+        fit,
+        inner: child.clone(),
+    }
+}
+
+#[test]
+fn test_clean_tree_is_not_relaid_out() {
+    let a = Rc::new(RefCell::new(RenderSizedBox::new(10.0, 10.0)));
+    let b = Rc::new(RefCell::new(RenderSizedBox::new(10.0, 10.0)));
+    let mut flex = RenderFlex::default()
+        .with_child(flexible(&a, 0, FlexFit::Tight))
+        .with_child(flexible(&b, 0, FlexFit::Tight));
+
+    let constraints = BoxConstraints::tight(Size::new(100.0, 100.0));
+    flex.layout(&constraints, false);
+    flex.layout(&constraints, false);
+
+    assert_eq!(a.borrow().layout_count, 1);
+    assert_eq!(b.borrow().layout_count, 1);
+
+    flex.layout(&BoxConstraints::tight(Size::new(50.0, 50.0)), false);
+    assert_eq!(a.borrow().layout_count, 2);
+    assert_eq!(b.borrow().layout_count, 2);
+}
+
+#[test]
+fn test_dirty_child_marks_parent() {
+    let a = Rc::new(RefCell::new(RenderSizedBox::new(10.0, 10.0)));
+    let b = Rc::new(RefCell::new(RenderSizedBox::new(10.0, 10.0)));
+    let mut flex = RenderFlex::default()
+        .with_child(flexible(&a, 0, FlexFit::Tight))
+        .with_child(flexible(&b, 0, FlexFit::Tight));
+
+    let constraints = BoxConstraints::tight(Size::new(100.0, 100.0));
+    flex.layout(&constraints, false);
+    assert!(!a.borrow().state().is_relayout_boundary());
+
+    a.borrow().mark_needs_layout();
+    assert!(flex.state().needs_layout());
+
+    flex.layout(&constraints, false);
+    assert!(!flex.state().needs_layout());
+    assert_eq!(a.borrow().layout_count, 2);
+    assert_eq!(b.borrow().layout_count, 1);
+}
+
+#[test]
+fn test_relayout_boundary() {
+    let a = Rc::new(RefCell::new(RenderSizedBox::new(10.0, 10.0)));
+    let b = Rc::new(RefCell::new(RenderSizedBox::new(10.0, 10.0)));
+    let mut flex = RenderFlex::default()
+        .with_child(flexible(&a, 1, FlexFit::Tight))
+        .with_child(flexible(&b, 1, FlexFit::Tight));
+    flex.cross_axis_alignment = CrossAxisAlignment::Stretch;
+
+    let constraints = BoxConstraints::tight(Size::new(100.0, 100.0));
+    flex.layout(&constraints, false);
+    assert!(a.borrow().state().is_relayout_boundary());
+
+    a.borrow().mark_needs_layout();
+    assert!(!flex.state().needs_layout());
+    assert!(flex.state().child_needs_layout());
+
+    flex.layout(&constraints, false);
+    assert!(!flex.state().child_needs_layout());
+    assert_eq!(a.borrow().layout_count, 2);
+    assert_eq!(b.borrow().layout_count, 1);
+}
+
+#[test]
+fn test_set_children_marks_dirty_only_on_change() {
+    let a = Rc::new(RefCell::new(RenderSizedBox::new(10.0, 10.0)));
+    let mut flex = RenderFlex::default().with_child(flexible(&a, 0, FlexFit::Tight));
+    flex.layout(&BoxConstraints::tight(Size::new(100.0, 100.0)), false);
+
+    flex.set_children(vec![flexible(&a, 0, FlexFit::Tight)]);
+    assert!(!flex.state().needs_layout());
+
+    flex.set_children(vec![flexible(&a, 1, FlexFit::Tight)]);
+    assert!(flex.state().needs_layout());
+}
+
+#[test]
+fn test_view_resizes_through_a_gesture_detector() {
+    let mut view = RenderView::new();
+    view.set_child(Rc::new(RefCell::new(RenderGestureDetector::new(
+        HitTestBehavior::Opaque,
+    ))));
+    view.perform_resize(&BoxConstraints::tight(Size::new(100.0, 50.0)));
+    assert_eq!(view.size(), Size::new(100.0, 50.0));
+}
+
+#[test]
+fn test_view_resizes_through_a_focus() {
+    let mut view = RenderView::new();
+    view.set_child(Rc::new(RefCell::new(RenderFocus::new(Rc::new(
+        FocusNode::new(),
+    )))));
+    view.perform_resize(&BoxConstraints::tight(Size::new(100.0, 50.0)));
+    assert_eq!(view.size(), Size::new(100.0, 50.0));
+}
+
+#[test]
+fn test_view_resizes_through_semantics_annotations() {
+    let mut view = RenderView::new();
+    view.set_child(Rc::new(RefCell::new(RenderSemanticsAnnotations::new(
+        SemanticsProperties::default(),
+    ))));
+    view.perform_resize(&BoxConstraints::tight(Size::new(100.0, 50.0)));
+    assert_eq!(view.size(), Size::new(100.0, 50.0));
+}
+
+#[test]
+fn test_view_resizes_through_an_opacity() {
+    let mut view = RenderView::new();
+    view.set_child(Rc::new(RefCell::new(RenderOpacity::new(0.5))));
+    view.perform_resize(&BoxConstraints::tight(Size::new(100.0, 50.0)));
+    assert_eq!(view.size(), Size::new(100.0, 50.0));
+}
@@ -0,0 +1,47 @@
+use std::f32::consts::FRAC_PI_2;
+
+use crate::rendering::{Matrix4, Offset};
+
+fn assert_offset_eq(actual: Offset, expected: Offset) {
+    assert!(
+        (actual - expected).distance() < 1e-4,
+        "{:?} != {:?}",
+        actual,
+        expected
+    );
+}
+
+#[test]
+fn test_multiply_applies_right_hand_side_first() {
+    let m = Matrix4::translation(10.0, 0.0, 0.0) * Matrix4::diagonal3(2.0, 3.0, 1.0);
+    assert_offset_eq(
+        m.transform_point(Offset::new(1.0, 1.0)),
+        Offset::new(12.0, 3.0),
+    );
+
+    let mut n = Matrix4::translation(10.0, 0.0, 0.0);
+    n.scale(2.0, 3.0, 1.0);
+    assert_eq!(m, n);
+}
+
+#[test]
+fn test_rotation_z() {
+    let m = Matrix4::rotation_z(FRAC_PI_2);
+    assert_offset_eq(
+        m.transform_point(Offset::new(1.0, 0.0)),
+        Offset::new(0.0, 1.0),
+    );
+}
+
+#[test]
+fn test_invert() {
+    let mut m = Matrix4::translation(5.0, -3.0, 0.0);
+    m.rotate_z(0.3);
+    m.scale(2.0, 0.5, 1.0);
+    let inverse = m.invert().unwrap();
+    let point = Offset::new(7.0, 11.0);
+    assert_offset_eq(inverse.transform_point(m.transform_point(point)), point);
+    assert!((m.determinant() - 1.0).abs() < 1e-5);
+
+    assert_eq!(Matrix4::diagonal3(0.0, 1.0, 1.0).invert(), None);
+}
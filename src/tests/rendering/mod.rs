@@ -1,3 +1,12 @@
+mod editable_test;
 mod flex_test;
 mod image_test;
+mod intrinsic_test;
+mod layer_test;
+mod layout_test;
+mod matrix_test;
+mod paragraph_test;
+mod scroll_physics_test;
+mod sliver_list_test;
 mod slivers_test;
+mod viewport_test;
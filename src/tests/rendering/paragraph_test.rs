@@ -0,0 +1,232 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    gestures::{GestureBinding, HitTestEntry, HitTestTarget, PointerEvent, PointerEventKind},
+    painting::{TextSpan, TextStyle},
+    rendering::{
+        BoxConstraints, Offset, RenderBox, RenderObject, RenderParagraph, Size, TextBaseline,
+        TextOverflow,
+    },
+};
+
+const TEXT: &str = "The quick brown fox jumps over the lazy dog";
+
+fn paragraph() -> RenderParagraph {
+    RenderParagraph::new(TEXT).with_style(TextStyle::new().with_font_size(20.0))
+}
+
+fn layout(paragraph: &mut RenderParagraph, max_width: f32) {
+    paragraph.layout(&BoxConstraints::default().with_max_width(max_width), true);
+}
+
+/// Height of `TEXT` on a single line.
+fn line_height() -> f32 {
+    let mut single = paragraph();
+    layout(&mut single, f32::INFINITY);
+    single.size.height
+}
+
+#[test]
+fn test_breaks_lines_at_max_width() {
+    let mut paragraph = paragraph();
+    layout(&mut paragraph, f32::INFINITY);
+    assert_eq!(paragraph.line_count(), 1);
+    assert_eq!(
+        paragraph.size.width,
+        paragraph.get_max_intrinsic_width(f32::INFINITY)
+    );
+
+    layout(&mut paragraph, 100.0);
+    assert!(paragraph.size.width <= 100.0);
+    assert!(paragraph.line_count() > 1);
+    assert!(paragraph.size.height > line_height());
+    assert!(!paragraph.needs_clipping);
+}
+
+#[test]
+fn test_max_lines_with_ellipsis() {
+    let mut paragraph = paragraph()
+        .with_max_lines(1)
+        .with_overflow(TextOverflow::Ellipsis);
+    layout(&mut paragraph, 100.0);
+
+    assert_eq!(paragraph.line_count(), 1);
+    assert!(paragraph.did_exceed_max_lines());
+    assert_eq!(paragraph.size.height, line_height());
+    assert!(paragraph.size.width <= 100.0);
+    // the ellipsis marks the overflow instead of clipping
+    assert!(!paragraph.needs_clipping);
+
+    paragraph.set_overflow(TextOverflow::Clip);
+    layout(&mut paragraph, 100.0);
+    assert!(paragraph.needs_clipping);
+}
+
+#[test]
+fn test_without_soft_wrap_text_overflows() {
+    let mut paragraph = paragraph().with_soft_wrap(false);
+    layout(&mut paragraph, 100.0);
+
+    assert_eq!(paragraph.line_count(), 1);
+    assert_eq!(paragraph.size.width, 100.0);
+    assert!(paragraph.text_width > 100.0);
+    assert!(paragraph.needs_clipping);
+}
+
+#[test]
+fn test_baselines() {
+    let mut paragraph = paragraph();
+    layout(&mut paragraph, 100.0);
+
+    let alphabetic = paragraph
+        .get_distance_to_actual_baseline(TextBaseline::Alphabetic)
+        .unwrap();
+    let ideographic = paragraph
+        .get_distance_to_actual_baseline(TextBaseline::Ideographic)
+        .unwrap();
+    // baselines are those of the first line
+    assert!(alphabetic > 0.0 && alphabetic <= ideographic);
+    assert!(ideographic <= line_height());
+}
+
+#[test]
+fn test_intrinsic_dimensions() {
+    let paragraph = paragraph();
+    let min_width = paragraph.get_min_intrinsic_width(f32::INFINITY);
+    let max_width = paragraph.get_max_intrinsic_width(f32::INFINITY);
+    // the longest word against the whole text on a line
+    assert!(min_width > 0.0 && min_width < max_width);
+
+    assert_eq!(
+        paragraph.get_min_intrinsic_height(f32::INFINITY),
+        line_height()
+    );
+    assert!(paragraph.get_max_intrinsic_height(min_width) > line_height());
+}
+
+#[test]
+fn test_text_change_relayouts() {
+    let mut paragraph = paragraph();
+    layout(&mut paragraph, f32::INFINITY);
+    let width = paragraph.size.width;
+
+    paragraph.set_text(format!("{} {}", TEXT, TEXT));
+    assert!(paragraph.state().needs_layout());
+    layout(&mut paragraph, f32::INFINITY);
+    assert!(paragraph.size.width > width);
+}
+
+#[derive(Debug, Default)]
+struct Link {
+    events: Vec<PointerEventKind>,
+}
+
+impl HitTestTarget for Link {
+    fn handle_event(&mut self, event: PointerEvent, entry: HitTestEntry) {
+        self.events.push(event.kind);
+    }
+}
+
+#[test]
+fn test_resize_takes_the_smallest_size() {
+    let mut paragraph = paragraph();
+    let constraints = BoxConstraints::default()
+        .with_min_width(20.0)
+        .with_max_width(100.0)
+        .with_max_height(50.0);
+    paragraph.perform_resize(&constraints);
+    assert_eq!(paragraph.size, Size::new(20.0, 0.0));
+}
+
+#[test]
+fn test_letter_spacing_widens_text() {
+    let mut paragraph = paragraph();
+    layout(&mut paragraph, f32::INFINITY);
+    let width = paragraph.size.width;
+
+    paragraph.set_text(TextSpan::new(TEXT).with_style(TextStyle::new().with_letter_spacing(2.0)));
+    layout(&mut paragraph, f32::INFINITY);
+    assert!(paragraph.size.width > width);
+}
+
+#[test]
+fn test_span_for_offset() {
+    let text = TextSpan::default()
+        .with_child(TextSpan::new("docs").with_style(TextStyle::new().with_font_size(30.0)))
+        .with_child(TextSpan::new("\nread them all"));
+    let mut paragraph =
+        RenderParagraph::new(text).with_style(TextStyle::new().with_font_size(20.0));
+    layout(&mut paragraph, f32::INFINITY);
+    let first_line = paragraph
+        .get_distance_to_actual_baseline(TextBaseline::Alphabetic)
+        .unwrap();
+
+    let (span, index) = paragraph
+        .get_span_for_offset(Offset::new(1.0, first_line - 1.0))
+        .unwrap();
+    assert_eq!((span.text.as_deref(), index), (Some("docs"), 0));
+    let (span, index) = paragraph
+        .get_span_for_offset(Offset::new(1.0, paragraph.size.height - 1.0))
+        .unwrap();
+    assert_eq!((span.text.as_deref(), index), (Some("\nread them all"), 5));
+    // the first line ends before the second one
+    let past_docs = Offset::new(paragraph.size.width - 1.0, first_line - 1.0);
+    assert!(paragraph.get_span_for_offset(past_docs).is_none());
+}
+
+#[test]
+fn test_span_targets_receive_events() {
+    let link = Rc::new(RefCell::new(Link::default()));
+    let text = TextSpan::default()
+        .with_child(TextSpan::new("docs").with_target(link.clone()))
+        .with_child(" are here");
+    let paragraph = RenderParagraph::new(text).with_style(TextStyle::new().with_font_size(20.0));
+    let root: Rc<RefCell<dyn RenderBox>> = Rc::new(RefCell::new(paragraph));
+    root.borrow_mut()
+        .layout(&BoxConstraints::default().with_max_width(1000.0), false);
+    let size = root.borrow().size();
+    let mut binding = GestureBinding::new();
+
+    for (pointer, x) in [(0, 1.0), (1, size.width - 1.0)] {
+        let position = Offset::new(x, size.height / 2.0);
+        for kind in [PointerEventKind::Down, PointerEventKind::Up] {
+            binding.handle_event(&root, PointerEvent::new(kind, pointer, position));
+        }
+    }
+    // only the pointer on the link reached it
+    assert_eq!(
+        link.borrow().events,
+        vec![PointerEventKind::Down, PointerEventKind::Up]
+    );
+}
+
+#[test]
+fn test_span_targets_change_without_relayout() {
+    let text = |link: &Rc<RefCell<Link>>| {
+        TextSpan::default()
+            .with_child(TextSpan::new("docs").with_target(link.clone()))
+            .with_child(" are here")
+    };
+    let old_link = Rc::new(RefCell::new(Link::default()));
+    let new_link = Rc::new(RefCell::new(Link::default()));
+    let paragraph =
+        RenderParagraph::new(text(&old_link)).with_style(TextStyle::new().with_font_size(20.0));
+    let paragraph = Rc::new(RefCell::new(paragraph));
+    let root: Rc<RefCell<dyn RenderBox>> = paragraph.clone();
+    root.borrow_mut()
+        .layout(&BoxConstraints::default().with_max_width(1000.0), false);
+
+    paragraph.borrow_mut().set_text(text(&new_link));
+    assert!(!paragraph.borrow().state().needs_layout());
+
+    let position = Offset::new(1.0, paragraph.borrow().size.height / 2.0);
+    let mut binding = GestureBinding::new();
+    for kind in [PointerEventKind::Down, PointerEventKind::Up] {
+        binding.handle_event(&root, PointerEvent::new(kind, 0, position));
+    }
+    assert!(old_link.borrow().events.is_empty());
+    assert_eq!(
+        new_link.borrow().events,
+        vec![PointerEventKind::Down, PointerEventKind::Up]
+    );
+}
@@ -0,0 +1,270 @@
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+use crate::{
+    gestures::{GestureBinding, PointerEvent, PointerEventKind},
+    physics::{FrictionSimulation, Simulation},
+    rendering::{
+        AxisDirection, BouncingScrollPhysics, BoxConstraints, ClampingScrollPhysics, Offset,
+        PageScrollPhysics, RenderBox, RenderView, RenderViewport, ScrollDirection, ScrollMetrics,
+        ScrollPhysics, ScrollPosition, Size, ViewportOffset,
+    },
+    scheduler::{FakeClock, SchedulerBinding},
+    tests::common::{assert_near, RenderSizedSliver},
+};
+
+fn metrics(pixels: f32) -> ScrollMetrics {
+    ScrollMetrics {
+        pixels,
+        min_scroll_extent: 0.0,
+        max_scroll_extent: 600.0,
+        viewport_dimension: 200.0,
+    }
+}
+
+/// Where a simulation comes to rest, and when.
+fn settle(simulation: &dyn Simulation) -> (f32, f32) {
+    let mut time = 0.0;
+    while !simulation.is_done(time) {
+        time += 1.0 / 60.0;
+        assert!(time < 10.0, "{:?} does not settle", simulation);
+    }
+    (simulation.x(time), time)
+}
+
+#[test]
+fn test_friction_simulation() {
+    let friction = FrictionSimulation::new(0.135, 100.0, 400.0);
+    let final_x = friction.final_x();
+    assert_near(final_x, 100.0 + 400.0 / 0.135f32.ln().abs(), 1e-3);
+    assert_eq!(friction.x(0.0), 100.0);
+    assert_near(friction.dx(1.0), 400.0 * 0.135, 1e-3);
+
+    let time = friction.time_at_x(150.0);
+    assert_near(friction.x(time), 150.0, 1e-3);
+    assert_eq!(friction.time_at_x(final_x + 1.0), f32::INFINITY);
+    assert_eq!(friction.time_at_x(50.0), f32::INFINITY);
+    let (x, _) = settle(&friction);
+    assert_near(x, final_x, 1e-2);
+}
+
+#[test]
+fn test_clamping_physics_stop_at_the_edges() {
+    let physics = ClampingScrollPhysics;
+    assert_eq!(
+        physics.apply_boundary_conditions(&metrics(300.0), 350.0),
+        0.0
+    );
+    assert_eq!(
+        physics.apply_boundary_conditions(&metrics(580.0), 650.0),
+        50.0
+    );
+    assert_eq!(
+        physics.apply_boundary_conditions(&metrics(600.0), 650.0),
+        50.0
+    );
+    assert_eq!(
+        physics.apply_boundary_conditions(&metrics(20.0), -30.0),
+        -30.0
+    );
+
+    assert!(physics
+        .create_ballistic_simulation(&metrics(300.0), 0.0)
+        .is_none());
+    assert!(physics
+        .create_ballistic_simulation(&metrics(600.0), 1000.0)
+        .is_none());
+    let fling = physics
+        .create_ballistic_simulation(&metrics(300.0), 1000.0)
+        .unwrap();
+    assert_eq!(settle(&*fling).0, 600.0);
+}
+
+#[test]
+fn test_bouncing_physics_resist_overscroll() {
+    let physics = BouncingScrollPhysics;
+    assert_eq!(
+        physics.apply_physics_to_user_offset(&metrics(300.0), 10.0),
+        10.0
+    );
+    // a quarter of the viewport past the start, dragging further and back
+    let overscrolled = metrics(-50.0);
+    let further = physics.apply_physics_to_user_offset(&overscrolled, 10.0);
+    assert_near(
+        further,
+        10.0 * BouncingScrollPhysics::friction_factor(0.25),
+        1e-5,
+    );
+    let back = physics.apply_physics_to_user_offset(&overscrolled, -10.0);
+    assert_near(
+        back,
+        -10.0 * BouncingScrollPhysics::friction_factor(0.2),
+        1e-5,
+    );
+    // dragging back past the edge moves freely once inside
+    let back = physics.apply_physics_to_user_offset(&metrics(-5.0), -100.0);
+    assert!(back < -90.0);
+    assert_eq!(physics.apply_boundary_conditions(&overscrolled, -80.0), 0.0);
+
+    // springs back to the edge, from overscroll or from a fling past it
+    let spring = physics
+        .create_ballistic_simulation(&overscrolled, 0.0)
+        .unwrap();
+    assert_eq!(spring.x(0.0), -50.0);
+    assert_eq!(settle(&*spring).0, 0.0);
+    let fling = physics
+        .create_ballistic_simulation(&metrics(500.0), 2000.0)
+        .unwrap();
+    let mut time = 0.0;
+    let mut furthest: f32 = 0.0;
+    while !fling.is_done(time) {
+        furthest = furthest.max(fling.x(time));
+        time += 1.0 / 60.0;
+    }
+    assert!(furthest > 600.0);
+    assert_eq!(fling.x(time), 600.0);
+}
+
+#[test]
+fn test_page_physics_snap_to_pages() {
+    let physics = PageScrollPhysics::new();
+    let target = |pixels: f32, velocity: f32| {
+        physics
+            .create_ballistic_simulation(&metrics(pixels), velocity)
+            .map(|simulation| settle(&*simulation).0)
+    };
+    assert_eq!(target(230.0, 0.0), Some(200.0));
+    assert_eq!(target(370.0, 0.0), Some(400.0));
+    assert_eq!(target(230.0, 100.0), Some(400.0));
+    assert_eq!(target(370.0, -100.0), Some(200.0));
+    assert_eq!(target(200.0, 0.0), None);
+    // at the edge, as clamping physics
+    assert_eq!(target(600.0, 1000.0), None);
+}
+
+struct Fling {
+    clock: Rc<FakeClock>,
+    scheduler: Rc<SchedulerBinding>,
+    root: Rc<RefCell<dyn RenderBox>>,
+    position: Rc<ScrollPosition>,
+}
+
+impl Fling {
+    /// A vertical viewport, 200 high, over 450 pixels of content.
+    fn new(physics: impl ScrollPhysics + 'static) -> Self {
+        let clock = Rc::new(FakeClock::new());
+        let scheduler = SchedulerBinding::new(clock.clone());
+        let position = Rc::new(
+            ScrollPosition::new(0.0)
+                .with_physics(physics)
+                .with_scheduler(&scheduler),
+        );
+        let viewport = (0..3).fold(
+            RenderViewport::new(AxisDirection::Down, position.clone()),
+            |viewport, _| viewport.with_child(Rc::new(RefCell::new(RenderSizedSliver::new(150.0)))),
+        );
+        let mut view = RenderView::new();
+        view.set_child(Rc::new(RefCell::new(viewport)));
+        let root: Rc<RefCell<dyn RenderBox>> = Rc::new(RefCell::new(view));
+        let fling = Fling {
+            clock,
+            scheduler,
+            root,
+            position,
+        };
+        fling.layout();
+        fling
+    }
+
+    fn layout(&self) {
+        let constraints = BoxConstraints::tight(Size::new(100.0, 200.0));
+        self.root.borrow_mut().layout(&constraints, false);
+    }
+
+    /// Drags the content up by 96 pixels at 2000 pixels per second.
+    fn drag(&self) {
+        let mut binding = GestureBinding::new();
+        let event = |kind, y: f32, time: u64| {
+            PointerEvent::new(kind, 1, Offset::new(50.0, y))
+                .with_timestamp(Duration::from_millis(time))
+        };
+        binding.handle_event(&self.root, event(PointerEventKind::Down, 190.0, 0));
+        for i in 1..=6 {
+            let y = 190.0 - i as f32 * 16.0;
+            binding.handle_event(&self.root, event(PointerEventKind::Move, y, i * 8));
+        }
+        binding.handle_event(&self.root, event(PointerEventKind::Up, 94.0, 48));
+    }
+
+    /// Runs the frames of the fling, returns the scroll offsets and directions.
+    fn frames(&self) -> Vec<(f32, ScrollDirection)> {
+        let mut frames = Vec::new();
+        while self.scheduler.has_scheduled_frame() {
+            assert!(frames.len() < 600, "the fling does not settle");
+            self.clock.advance(Duration::from_millis(16));
+            self.scheduler.handle_begin_frame();
+            self.layout();
+            self.scheduler.handle_draw_frame();
+            frames.push((
+                self.position.pixels(),
+                self.position.user_scroll_direction(),
+            ));
+        }
+        frames
+    }
+}
+
+#[test]
+fn test_fling_slows_down_to_the_end_of_the_content() {
+    let fling = Fling::new(ClampingScrollPhysics);
+    fling.drag();
+    assert_eq!(fling.position.pixels(), 96.0);
+    assert!(fling.position.is_scrolling());
+    assert_eq!(
+        fling.position.user_scroll_direction(),
+        ScrollDirection::Reverse
+    );
+
+    let frames = fling.frames();
+    assert!(frames.windows(2).all(|w| w[0].0 <= w[1].0));
+    assert!(frames[..frames.len() - 1]
+        .iter()
+        .all(|(_, direction)| *direction == ScrollDirection::Reverse));
+    assert_eq!(*frames.last().unwrap(), (250.0, ScrollDirection::Idle));
+    assert!(!fling.position.is_scrolling());
+}
+
+#[test]
+fn test_fling_bounces_back_from_past_the_content() {
+    let fling = Fling::new(BouncingScrollPhysics);
+    fling.drag();
+    let frames = fling.frames();
+    // out of range through layouts, then back in the content moving forward
+    let furthest = frames.iter().map(|(pixels, _)| *pixels).fold(0.0, f32::max);
+    assert!(furthest > 250.0);
+    assert!(frames
+        .iter()
+        .any(|(pixels, direction)| *pixels > 250.0 && *direction == ScrollDirection::Forward));
+    assert_eq!(*frames.last().unwrap(), (250.0, ScrollDirection::Idle));
+}
+
+#[test]
+fn test_touch_holds_a_fling() {
+    let fling = Fling::new(ClampingScrollPhysics);
+    fling.drag();
+    fling.clock.advance(Duration::from_millis(16));
+    fling.scheduler.handle_begin_frame();
+    fling.scheduler.handle_draw_frame();
+    fling.clock.advance(Duration::from_millis(16));
+    fling.scheduler.handle_begin_frame();
+    fling.scheduler.handle_draw_frame();
+    let pixels = fling.position.pixels();
+    assert!(pixels > 96.0);
+
+    let down = PointerEvent::new(PointerEventKind::Down, 2, Offset::new(50.0, 100.0));
+    GestureBinding::new().handle_event(&fling.root, down);
+    assert!(!fling.position.is_scrolling());
+    assert!(fling
+        .frames()
+        .iter()
+        .all(|frame| *frame == (pixels, ScrollDirection::Idle)));
+}
@@ -0,0 +1,218 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    gestures::HitTestResult,
+    rendering::{
+        AxisDirection, BoxConstraints, Offset, PaintContext, RenderBox, RenderObject,
+        RenderSliverList, RenderViewport, ScrollPosition, Size, SliverChildBuilderDelegate,
+        ViewportOffset,
+    },
+    tests::common::RenderSizedBox,
+};
+
+type Built = Rc<RefCell<Vec<(usize, Rc<RefCell<RenderSizedBox>>)>>>;
+
+struct Harness {
+    viewport: Rc<RefCell<RenderViewport>>,
+    position: Rc<ScrollPosition>,
+    list: Rc<RefCell<RenderSliverList>>,
+    built: Built,
+}
+
+impl Harness {
+    /// A viewport of 100x200 with a cache extent of 50 showing a list of rows whose
+    /// heights are read from `heights` as they are built.
+    fn new(
+        heights: Rc<RefCell<Vec<f32>>>,
+        item_extent: Option<f32>,
+        child_count: Option<usize>,
+    ) -> Self {
+        let built: Built = Default::default();
+        let log = built.clone();
+        let mut delegate = SliverChildBuilderDelegate::new(move |index| {
+            let height = *heights.borrow().get(index)?;
+            let child = Rc::new(RefCell::new(RenderSizedBox::new(100.0, height)));
+            log.borrow_mut().push((index, child.clone()));
+            Some(child)
+        });
+        if let Some(child_count) = child_count {
+            delegate = delegate.with_child_count(child_count);
+        }
+        let mut list = RenderSliverList::new(Rc::new(delegate));
+        list.set_item_extent(item_extent);
+        let list = Rc::new(RefCell::new(list));
+        let position = Rc::new(ScrollPosition::new(0.0));
+        let viewport = RenderViewport::new(AxisDirection::Down, position.clone())
+            .with_cache_extent(50.0)
+            .with_child(list.clone());
+        Harness {
+            viewport: Rc::new(RefCell::new(viewport)),
+            position,
+            list,
+            built,
+        }
+    }
+
+    fn rows(count: usize, height: f32) -> Rc<RefCell<Vec<f32>>> {
+        Rc::new(RefCell::new(vec![height; count]))
+    }
+
+    fn layout(&self) {
+        self.viewport
+            .borrow_mut()
+            .layout(&BoxConstraints::tight(Size::new(100.0, 200.0)), false);
+    }
+
+    fn jump_to(&self, pixels: f32) {
+        self.position.jump_to(pixels);
+        self.layout();
+    }
+
+    fn built_count(&self) -> usize {
+        self.built.borrow().len()
+    }
+}
+
+#[test]
+fn test_builds_only_visible_and_cached_children() {
+    let harness = Harness::new(Harness::rows(100_000, 50.0), None, Some(100_000));
+    harness.layout();
+
+    let list = harness.list.borrow();
+    // the cache extent covers one more row after the 4 visible ones
+    assert_eq!(list.child_indices(), 0..5);
+    assert_eq!(harness.built_count(), 5);
+    assert!(list.geometry.visible);
+    assert_eq!(list.geometry.paint_extent, 200.0);
+    // the remaining rows are estimated from the average extent of the built ones
+    assert_eq!(list.geometry.scroll_extent, 5_000_000.0);
+    assert_eq!(harness.position.max_scroll_extent(), Some(4_999_800.0));
+}
+
+#[test]
+fn test_children_scrolled_out_are_dropped() {
+    let harness = Harness::new(Harness::rows(100_000, 50.0), None, Some(100_000));
+    harness.layout();
+    let first = Rc::downgrade(&harness.built.borrow()[0].1);
+    harness.built.borrow_mut().clear();
+
+    harness.jump_to(1000.0);
+    // [950, 1250) is visible or cached
+    assert_eq!(harness.list.borrow().child_indices(), 19..25);
+    assert!(first.upgrade().is_none());
+
+    // rows coming back are built again
+    harness.built.borrow_mut().clear();
+    harness.jump_to(0.0);
+    assert_eq!(harness.list.borrow().child_indices(), 0..5);
+    let indices: Vec<_> = harness.built.borrow().iter().map(|(i, _)| *i).collect();
+    assert_eq!(indices, (0..19).rev().collect::<Vec<_>>());
+}
+
+#[test]
+fn test_item_extent_skips_to_scroll_offset() {
+    let harness = Harness::new(Harness::rows(100_000, 50.0), Some(50.0), Some(100_000));
+    harness.layout();
+    harness.built.borrow_mut().clear();
+
+    harness.jump_to(4_000_000.0);
+    let list = harness.list.borrow();
+    assert_eq!(list.child_indices(), 79_999..80_005);
+    // only the rows of the new range were built
+    assert_eq!(harness.built_count(), 6);
+    assert_eq!(list.children[0].layout_offset, 3_999_950.0);
+    assert_eq!(list.geometry.scroll_extent, 5_000_000.0);
+}
+
+#[test]
+fn test_end_of_list_found_while_scrolling() {
+    let harness = Harness::new(Harness::rows(10, 50.0), None, None);
+    harness.layout();
+    assert_eq!(harness.list.borrow().geometry.scroll_extent, f32::INFINITY);
+
+    harness.jump_to(400.0);
+    // the builder ran out after 10 rows, the offset is clamped to the content
+    assert_eq!(harness.list.borrow().geometry.scroll_extent, 500.0);
+    assert_eq!(harness.position.pixels(), 300.0);
+    assert_eq!(harness.list.borrow().child_indices(), 5..10);
+
+    // a fixed extent list scrolled past its end searches for it
+    let harness = Harness::new(Harness::rows(10, 50.0), Some(50.0), None);
+    harness.layout();
+    harness.jump_to(5_000.0);
+    assert_eq!(harness.list.borrow().geometry.scroll_extent, 500.0);
+    assert_eq!(harness.position.pixels(), 300.0);
+}
+
+#[test]
+fn test_scroll_offset_corrected_for_changed_extents() {
+    let heights = Harness::rows(100, 50.0);
+    let harness = Harness::new(heights.clone(), None, Some(100));
+    harness.layout();
+    harness.jump_to(1000.0);
+
+    // the rows before the scroll offset are built again with twice the extent
+    for height in heights.borrow_mut()[..19].iter_mut() {
+        *height = 100.0;
+    }
+    harness.jump_to(0.0);
+
+    // going back from row 19 would place row 9 at -50 and row 8 at -100, the scroll
+    // offset moves by both instead, one row at a time
+    assert_eq!(harness.position.pixels(), 150.0);
+    let list = harness.list.borrow();
+    assert_eq!(list.child_indices(), 9..12);
+    assert_eq!(list.children[0].layout_offset, 100.0);
+}
+
+#[test]
+fn test_list_shrunk_while_scrolled_starts_over() {
+    let heights = Harness::rows(100, 50.0);
+    let harness = Harness::new(heights.clone(), None, None);
+    harness.layout();
+    harness.jump_to(1000.0);
+    assert_eq!(harness.list.borrow().child_indices(), 19..25);
+
+    // the rows before those kept are gone when scrolling back
+    heights.borrow_mut().truncate(6);
+    harness.jump_to(500.0);
+
+    // the scroll offset is corrected for the cached region to start at the first row
+    assert_eq!(harness.position.pixels(), 50.0);
+    assert_eq!(harness.list.borrow().child_indices(), 0..6);
+    assert_eq!(harness.list.borrow().children[0].layout_offset, 0.0);
+}
+
+#[test]
+fn test_paints_and_hit_tests_visible_children() {
+    let harness = Harness::new(Harness::rows(100, 50.0), None, Some(100));
+    harness.layout();
+    harness.jump_to(30.0);
+
+    let mut context = PaintContext::new(Size::new(100.0, 200.0));
+    harness
+        .viewport
+        .borrow()
+        .paint(&mut context, Offset::zero());
+    let painted: Vec<_> = harness
+        .built
+        .borrow()
+        .iter()
+        .filter(|(_, child)| child.borrow().paint_count.get() > 0)
+        .map(|(index, _)| *index)
+        .collect();
+    // row 5 is only cached, it starts past the visible region
+    assert_eq!(harness.list.borrow().child_indices(), 0..6);
+    assert_eq!(painted, vec![0, 1, 2, 3, 4]);
+
+    let mut result = HitTestResult::new();
+    let position = Offset::new(10.0, 40.0);
+    assert!(harness.viewport.borrow().hit_test(position, &mut result));
+    assert_eq!(result.path().len(), 1);
+    let local = result.path()[0]
+        .transform()
+        .unwrap()
+        .transform_point(position);
+    // row 1 spans [50, 100) of the scroll offset 70
+    assert_eq!(local, Offset::new(10.0, 20.0));
+}
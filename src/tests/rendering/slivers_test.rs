@@ -1,14 +1,153 @@
+use std::{cell::RefCell, rc::Rc};
+
 use crate::{
-    rendering::{Axis, RenderBox, RenderSliverList},
+    gestures::HitTestResult,
+    rendering::{
+        AxisDirection, BoxConstraints, FixedViewportOffset, Offset, PaintContext, RenderBox,
+        RenderObject, RenderSliverToBoxAdapter, RenderViewport, ScrollPosition, Size,
+        ViewportOffset,
+    },
     tests::common::RenderSizedBox,
 };
 
+struct Harness {
+    viewport: Rc<RefCell<RenderViewport>>,
+    position: Rc<ScrollPosition>,
+    boxes: Vec<Rc<RefCell<RenderSizedBox>>>,
+    adapters: Vec<Rc<RefCell<RenderSliverToBoxAdapter>>>,
+}
+
+impl Harness {
+    /// A viewport of 100x200 with a cache extent of 200 showing 5 boxes of 400 high.
+    fn new() -> Self {
+        let position = Rc::new(ScrollPosition::new(0.0));
+        let boxes: Vec<_> = (0..5)
+            .map(|_| Rc::new(RefCell::new(RenderSizedBox::new(100.0, 400.0))))
+            .collect();
+        let adapters: Vec<_> = boxes
+            .iter()
+            .map(|child| Rc::new(RefCell::new(RenderSliverToBoxAdapter::new(child.clone()))))
+            .collect();
+        let viewport = adapters.iter().fold(
+            RenderViewport::new(AxisDirection::Down, position.clone()).with_cache_extent(200.0),
+            |viewport, adapter| viewport.with_child(adapter.clone()),
+        );
+        Harness {
+            viewport: Rc::new(RefCell::new(viewport)),
+            position,
+            boxes,
+            adapters,
+        }
+    }
+
+    fn jump_to(&self, pixels: f32) {
+        self.position.jump_to(pixels);
+        self.viewport
+            .borrow_mut()
+            .layout(&BoxConstraints::tight(Size::new(100.0, 200.0)), false);
+    }
+}
+
+#[test]
+fn test_geometry_at_scroll_offsets() {
+    let harness = Harness::new();
+
+    // (pixels, sliver, scroll_offset, paint_extent, cache_extent, visible)
+    let expectations = [
+        (0.0, 0, 0.0, 200.0, 400.0, true),
+        (0.0, 1, 0.0, 0.0, 0.0, false),
+        (100.0, 0, 100.0, 200.0, 400.0, true),
+        (100.0, 1, 0.0, 0.0, 100.0, false),
+        (300.0, 0, 300.0, 100.0, 300.0, true),
+        (300.0, 1, 0.0, 100.0, 300.0, true),
+        (500.0, 0, 500.0, 0.0, 100.0, false),
+        (500.0, 1, 100.0, 200.0, 400.0, true),
+    ];
+    for (pixels, index, scroll_offset, paint_extent, cache_extent, visible) in expectations {
+        harness.jump_to(pixels);
+        let adapter = harness.adapters[index].borrow();
+        let constraints = adapter.constraints.clone().unwrap();
+        let geometry = &adapter.geometry;
+        let actual = (
+            constraints.scroll_offset,
+            geometry.paint_extent,
+            geometry.cache_extent,
+            geometry.visible,
+        );
+        assert_eq!(
+            actual,
+            (scroll_offset, paint_extent, cache_extent, visible),
+            "sliver {} at {}",
+            index,
+            pixels
+        );
+        assert_eq!(geometry.scroll_extent, 400.0);
+        assert_eq!(geometry.layout_extent, paint_extent);
+        assert_eq!(geometry.hit_test_extent, paint_extent);
+        // none of the boxes fits into the viewport
+        assert!(geometry.has_visual_overflow);
+    }
+}
+
 #[test]
-fn test_sliver_list() {
-    let list = RenderSliverList::new(Axis::Vertical, 200.0)
-        .with_child(RenderSizedBox::new(100.0, 400.0).into_sliver())
-        .with_child(RenderSizedBox::new(100.0, 400.0).into_sliver())
-        .with_child(RenderSizedBox::new(100.0, 400.0).into_sliver())
-        .with_child(RenderSizedBox::new(100.0, 400.0).into_sliver())
-        .with_child(RenderSizedBox::new(100.0, 400.0).into_sliver());
+fn test_paints_visible_children() {
+    let harness = Harness::new();
+    harness.jump_to(300.0);
+
+    let mut context = PaintContext::new(Size::new(100.0, 200.0));
+    harness
+        .viewport
+        .borrow()
+        .paint(&mut context, Offset::zero());
+    let paint_counts: Vec<_> = harness
+        .boxes
+        .iter()
+        .map(|child| child.borrow().paint_count.get())
+        .collect();
+    assert_eq!(paint_counts, vec![1, 1, 0, 0, 0]);
+}
+
+#[test]
+fn test_hit_tests_child_at_scroll_offset() {
+    let harness = Harness::new();
+    harness.jump_to(300.0);
+
+    // the first box is scrolled up by 300, the second one starts at 100
+    for (position, local) in [
+        (Offset::new(10.0, 50.0), Offset::new(10.0, 350.0)),
+        (Offset::new(10.0, 150.0), Offset::new(10.0, 50.0)),
+    ] {
+        let mut result = HitTestResult::new();
+        assert!(harness.viewport.borrow().hit_test(position, &mut result));
+        assert_eq!(result.path().len(), 1);
+        let transformed = result.path()[0]
+            .transform()
+            .unwrap()
+            .transform_point(position);
+        assert_eq!(transformed, local);
+    }
+
+    // positions past the hit test extent miss, even though the child extends there
+    let mut result = HitTestResult::new();
+    let adapter = harness.adapters[0].borrow();
+    assert!(adapter.hit_test(Offset::new(10.0, 50.0), &mut result));
+    assert!(!adapter.hit_test(Offset::new(10.0, 150.0), &mut result));
+    assert_eq!(result.path().len(), 1);
+}
+
+#[test]
+fn test_into_sliver() {
+    let sliver = RenderSizedBox::new(100.0, 400.0).into_sliver();
+    let mut viewport = RenderViewport::new(
+        AxisDirection::Down,
+        Rc::new(FixedViewportOffset::new(100.0)),
+    )
+    .with_child(sliver.clone());
+    viewport.layout(&BoxConstraints::tight(Size::new(100.0, 200.0)), false);
+
+    let sliver = sliver.borrow();
+    let geometry = sliver.geometry();
+    assert_eq!(geometry.scroll_extent, 400.0);
+    assert_eq!(geometry.paint_extent, 200.0);
+    assert!(geometry.visible);
 }
@@ -0,0 +1,202 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    gestures::{GestureBinding, PointerEvent, PointerEventKind},
+    rendering::{
+        AxisDirection, BoxConstraints, Offset, PaintContext, RenderBox, RenderObject, RenderView,
+        RenderViewport, ScrollDirection, ScrollPosition, Size, ViewportOffset,
+    },
+    tests::common::RenderSizedSliver,
+};
+
+fn setup(
+    axis_direction: AxisDirection,
+) -> (
+    Rc<RefCell<RenderViewport>>,
+    Rc<ScrollPosition>,
+    Vec<Rc<RefCell<RenderSizedSliver>>>,
+) {
+    let position = Rc::new(ScrollPosition::new(0.0));
+    let slivers: Vec<_> = (0..3)
+        .map(|_| Rc::new(RefCell::new(RenderSizedSliver::new(150.0))))
+        .collect();
+    let viewport = slivers.iter().fold(
+        RenderViewport::new(axis_direction, position.clone()).with_cache_extent(50.0),
+        |viewport, sliver| viewport.with_child(sliver.clone()),
+    );
+    let viewport = Rc::new(RefCell::new(viewport));
+    (viewport, position, slivers)
+}
+
+fn layout(viewport: &Rc<RefCell<RenderViewport>>) {
+    viewport
+        .borrow_mut()
+        .layout(&BoxConstraints::tight(Size::new(100.0, 200.0)), false);
+}
+
+#[test]
+fn test_slivers_share_visible_and_cache_extent() {
+    let (viewport, position, slivers) = setup(AxisDirection::Down);
+    layout(&viewport);
+
+    let constraints: Vec<_> = slivers
+        .iter()
+        .map(|s| s.borrow().constraints.clone().unwrap())
+        .collect();
+    assert_eq!(
+        constraints
+            .iter()
+            .map(|c| (
+                c.scroll_offset,
+                c.remaining_paint_extent,
+                c.remaining_cache_extent
+            ))
+            .collect::<Vec<_>>(),
+        vec![(0.0, 200.0, 250.0), (0.0, 50.0, 100.0), (0.0, 0.0, 0.0)]
+    );
+    assert_eq!(constraints[1].preceding_scroll_extent, 150.0);
+    assert_eq!(constraints[0].cross_axis_extent, 100.0);
+    assert_eq!(viewport.borrow().max_scroll_extent(), 450.0);
+    assert_eq!(position.max_scroll_extent(), Some(250.0));
+    assert_eq!(position.viewport_dimension(), Some(200.0));
+}
+
+#[test]
+fn test_scroll_offset_is_fed_to_slivers() {
+    let (viewport, position, slivers) = setup(AxisDirection::Down);
+    layout(&viewport);
+
+    position.jump_to(100.0);
+    assert!(viewport.borrow().state.needs_layout());
+    layout(&viewport);
+
+    let first = slivers[0].borrow().constraints.clone().unwrap();
+    assert_eq!(first.scroll_offset, 100.0);
+    assert_eq!(first.cache_origin, -50.0);
+    assert_eq!(slivers[0].borrow().geometry.paint_extent, 50.0);
+    let second = slivers[1].borrow().constraints.clone().unwrap();
+    assert_eq!(second.scroll_offset, 0.0);
+    assert_eq!(second.remaining_paint_extent, 150.0);
+    assert_eq!(
+        viewport.borrow().paint_offsets,
+        vec![
+            Offset::new(0.0, 0.0),
+            Offset::new(0.0, 50.0),
+            Offset::new(0.0, 200.0)
+        ]
+    );
+}
+
+#[test]
+fn test_offset_is_clamped_to_content() {
+    let (viewport, position, slivers) = setup(AxisDirection::Down);
+    layout(&viewport);
+
+    position.jump_to(1000.0);
+    layout(&viewport);
+    assert_eq!(position.pixels(), 250.0);
+    assert_eq!(
+        slivers[1]
+            .borrow()
+            .constraints
+            .clone()
+            .unwrap()
+            .scroll_offset,
+        100.0
+    );
+}
+
+#[test]
+fn test_reversed_axis_paints_from_the_end() {
+    let (viewport, position, slivers) = setup(AxisDirection::Up);
+    layout(&viewport);
+
+    assert_eq!(
+        viewport.borrow().paint_offsets[..2],
+        [Offset::new(0.0, 50.0), Offset::new(0.0, 0.0)]
+    );
+}
+
+#[test]
+fn test_wheel_and_drag_scroll() {
+    let (viewport, position, slivers) = setup(AxisDirection::Down);
+    let mut view = RenderView::new();
+    view.set_child(viewport.clone());
+    let root: Rc<RefCell<dyn RenderBox>> = Rc::new(RefCell::new(view));
+    let constraints = BoxConstraints::tight(Size::new(100.0, 200.0));
+    root.borrow_mut().layout(&constraints, false);
+    let mut binding = GestureBinding::new();
+
+    let scroll = PointerEventKind::Scroll {
+        scroll_delta: Offset::new(0.0, 30.0),
+    };
+    binding.handle_event(&root, PointerEvent::new(scroll, 0, Offset::new(50.0, 50.0)));
+    assert_eq!(position.pixels(), 30.0);
+    // a wheel scroll is over with its event
+    assert_eq!(position.user_scroll_direction(), ScrollDirection::Idle);
+
+    root.borrow_mut().layout(&constraints, false);
+    assert_eq!(
+        slivers[0]
+            .borrow()
+            .constraints
+            .clone()
+            .unwrap()
+            .scroll_offset,
+        30.0
+    );
+    // hit in the coordinates of the painted region of the sliver
+    assert_eq!(
+        *slivers[0].borrow().hits.borrow(),
+        vec![Offset::new(50.0, 50.0)]
+    );
+
+    // the drag is the only gesture and starts without slop
+    binding.handle_event(
+        &root,
+        PointerEvent::new(PointerEventKind::Down, 1, Offset::new(50.0, 150.0)),
+    );
+    binding.handle_event(
+        &root,
+        PointerEvent::new(PointerEventKind::Move, 1, Offset::new(50.0, 160.0)),
+    );
+    assert_eq!(position.user_scroll_direction(), ScrollDirection::Forward);
+    binding.handle_event(
+        &root,
+        PointerEvent::new(PointerEventKind::Up, 1, Offset::new(50.0, 160.0)),
+    );
+    assert_eq!(position.pixels(), 20.0);
+    assert_eq!(position.user_scroll_direction(), ScrollDirection::Idle);
+}
+
+#[test]
+fn test_paints_visible_slivers() {
+    let (viewport, position, slivers) = setup(AxisDirection::Down);
+    layout(&viewport);
+
+    let mut context = PaintContext::new(Size::new(100.0, 200.0));
+    viewport
+        .borrow()
+        .paint(&mut context, Offset::new(10.0, 10.0));
+    assert_eq!(
+        *slivers[0].borrow().paints.borrow(),
+        vec![Offset::new(10.0, 10.0)]
+    );
+    assert_eq!(
+        *slivers[1].borrow().paints.borrow(),
+        vec![Offset::new(10.0, 160.0)]
+    );
+    assert!(slivers[2].borrow().paints.borrow().is_empty());
+    // the content overflows, so it is clipped
+    assert_eq!(context.finish().len(), 1);
+}
+
+#[test]
+fn test_viewport_resizes_to_fill_its_constraints() {
+    let (viewport, _, _) = setup(AxisDirection::Down);
+    let constraints = BoxConstraints::default()
+        .with_max_width(100.0)
+        .with_max_height(200.0);
+    viewport.borrow_mut().perform_resize(&constraints);
+    assert_eq!(viewport.borrow().size(), Size::new(100.0, 200.0));
+}
@@ -0,0 +1,2 @@
+mod scheduler_test;
+mod ticker_test;
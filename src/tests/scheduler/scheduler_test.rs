@@ -0,0 +1,94 @@
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+use crate::scheduler::{FakeClock, FrameCallback, SchedulerBinding, SchedulerPhase};
+
+fn scheduler() -> (Rc<FakeClock>, Rc<SchedulerBinding>) {
+    let clock = Rc::new(FakeClock::new());
+    (clock.clone(), SchedulerBinding::new(clock))
+}
+
+fn frame(scheduler: &SchedulerBinding) {
+    scheduler.handle_begin_frame();
+    scheduler.handle_draw_frame();
+}
+
+#[test]
+fn test_transient_callbacks_run_once_at_the_frame_time() {
+    let (clock, scheduler) = scheduler();
+    assert!(!scheduler.has_scheduled_frame());
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let entries = log.clone();
+    scheduler.schedule_frame_callback(FrameCallback::new(move |timestamp| {
+        entries.borrow_mut().push(timestamp)
+    }));
+    assert!(scheduler.has_scheduled_frame());
+    assert_eq!(scheduler.transient_callback_count(), 1);
+
+    clock.advance(Duration::from_millis(16));
+    frame(&scheduler);
+    frame(&scheduler);
+    assert_eq!(*log.borrow(), vec![Duration::from_millis(16)]);
+    assert!(!scheduler.has_scheduled_frame());
+    assert_eq!(scheduler.transient_callback_count(), 0);
+}
+
+#[test]
+fn test_callback_canceled_during_the_frame_does_not_run() {
+    let (_, scheduler) = scheduler();
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let second = Rc::new(RefCell::new(0));
+
+    let (entries, canceled, binding) = (log.clone(), second.clone(), scheduler.clone());
+    scheduler.schedule_frame_callback(FrameCallback::new(move |_| {
+        entries.borrow_mut().push("first");
+        binding.cancel_frame_callback_with_id(*canceled.borrow());
+        // scheduled while the frame runs, it waits for the next one
+        let entries = entries.clone();
+        binding.schedule_frame_callback(FrameCallback::new(move |_| {
+            entries.borrow_mut().push("next")
+        }));
+    }));
+    let entries = log.clone();
+    *second.borrow_mut() = scheduler.schedule_frame_callback(FrameCallback::new(move |_| {
+        entries.borrow_mut().push("second")
+    }));
+
+    frame(&scheduler);
+    assert_eq!(*log.borrow(), vec!["first"]);
+    assert!(scheduler.has_scheduled_frame());
+    frame(&scheduler);
+    assert_eq!(*log.borrow(), vec!["first", "next"]);
+}
+
+#[test]
+fn test_phases_of_a_frame() {
+    let (_, scheduler) = scheduler();
+    let log = Rc::new(RefCell::new(Vec::new()));
+
+    let (entries, binding) = (log.clone(), scheduler.clone());
+    scheduler.schedule_frame_callback(FrameCallback::new(move |_| {
+        entries.borrow_mut().push(("transient", binding.phase()))
+    }));
+    let (entries, binding) = (log.clone(), scheduler.clone());
+    scheduler.add_persistent_frame_callback(FrameCallback::new(move |_| {
+        entries.borrow_mut().push(("persistent", binding.phase()))
+    }));
+    let (entries, binding) = (log.clone(), scheduler.clone());
+    scheduler.add_post_frame_callback(FrameCallback::new(move |_| {
+        entries.borrow_mut().push(("post", binding.phase()))
+    }));
+
+    frame(&scheduler);
+    frame(&scheduler);
+    assert_eq!(
+        *log.borrow(),
+        vec![
+            ("transient", SchedulerPhase::TransientCallbacks),
+            ("persistent", SchedulerPhase::PersistentCallbacks),
+            ("post", SchedulerPhase::PostFrameCallbacks),
+            ("persistent", SchedulerPhase::PersistentCallbacks),
+        ]
+    );
+    assert_eq!(scheduler.phase(), SchedulerPhase::Idle);
+    assert_eq!(scheduler.current_frame_timestamp(), None);
+}
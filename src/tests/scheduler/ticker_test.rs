@@ -0,0 +1,76 @@
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+use crate::{
+    scheduler::{FakeClock, SchedulerBinding, Ticker, TickerCallback},
+    tests::common::ms,
+};
+
+/// A ticker logging the time elapsed of its ticks.
+fn ticker(scheduler: &Rc<SchedulerBinding>) -> (Rc<Ticker>, Rc<RefCell<Vec<Duration>>>) {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let entries = log.clone();
+    let ticker = Ticker::new(
+        scheduler,
+        TickerCallback::new(move |elapsed| entries.borrow_mut().push(elapsed)),
+    );
+    (ticker, log)
+}
+
+fn frame(clock: &FakeClock, scheduler: &SchedulerBinding, duration: Duration) {
+    clock.advance(duration);
+    scheduler.handle_begin_frame();
+    scheduler.handle_draw_frame();
+}
+
+#[test]
+fn test_ticker_counts_from_its_first_frame() {
+    let clock = Rc::new(FakeClock::new());
+    let scheduler = SchedulerBinding::new(clock.clone());
+    let (ticker, log) = ticker(&scheduler);
+    clock.set(ms(100));
+    ticker.start();
+    assert!(ticker.is_ticking());
+    assert!(scheduler.has_scheduled_frame());
+
+    frame(&clock, &scheduler, ms(10));
+    frame(&clock, &scheduler, ms(16));
+    frame(&clock, &scheduler, ms(16));
+    assert_eq!(*log.borrow(), vec![ms(0), ms(16), ms(32)]);
+
+    ticker.stop();
+    assert!(!ticker.is_active());
+    assert_eq!(scheduler.transient_callback_count(), 0);
+    frame(&clock, &scheduler, ms(16));
+    assert_eq!(log.borrow().len(), 3);
+}
+
+#[test]
+fn test_muted_ticker_keeps_its_time() {
+    let clock = Rc::new(FakeClock::new());
+    let scheduler = SchedulerBinding::new(clock.clone());
+    let (ticker, log) = ticker(&scheduler);
+    ticker.start();
+    frame(&clock, &scheduler, ms(0));
+
+    ticker.set_muted(true);
+    assert!(ticker.is_active() && !ticker.is_ticking());
+    assert_eq!(scheduler.transient_callback_count(), 0);
+    frame(&clock, &scheduler, ms(16));
+
+    ticker.set_muted(false);
+    frame(&clock, &scheduler, ms(16));
+    assert_eq!(*log.borrow(), vec![ms(0), ms(32)]);
+}
+
+#[test]
+fn test_dropped_ticker_cancels_its_callback() {
+    let clock = Rc::new(FakeClock::new());
+    let scheduler = SchedulerBinding::new(clock.clone());
+    let (ticker, log) = ticker(&scheduler);
+    ticker.start();
+    assert_eq!(scheduler.transient_callback_count(), 1);
+    drop(ticker);
+    assert_eq!(scheduler.transient_callback_count(), 0);
+    frame(&clock, &scheduler, ms(16));
+    assert!(log.borrow().is_empty());
+}
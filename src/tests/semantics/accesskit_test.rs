@@ -0,0 +1,164 @@
+use std::{cell::RefCell, rc::Rc};
+
+use accesskit::{Action, ActionData, ActionRequest, NodeId, Rect, Role};
+
+use crate::{
+    painting::TextStyle,
+    rendering::{
+        Axis, CrossAxisAlignment, RenderEditable, RenderFlex, RenderParagraph,
+        RenderSemanticsAnnotations, Size, TextDirection,
+    },
+    semantics::{
+        AccessKitAdapter, SemanticsActionHandler, SemanticsOwner, SemanticsProperties,
+        SemanticsRole,
+    },
+    services::TextEditingController,
+    tests::common::FocusTree,
+};
+
+fn text(text: &str) -> RenderParagraph {
+    RenderParagraph::new(text).with_style(TextStyle::new().with_font_size(10.0))
+}
+
+fn column() -> RenderFlex {
+    RenderFlex::default()
+        .with_direction(Axis::Vertical)
+        .with_cross_axis_alignment(CrossAxisAlignment::Start)
+        .with_text_direction(TextDirection::LTR)
+}
+
+fn request(action: Action, target: NodeId, data: Option<ActionData>) -> ActionRequest {
+    ActionRequest {
+        action,
+        target,
+        data,
+    }
+}
+
+#[test]
+fn test_first_update_carries_the_whole_tree() {
+    let controller = Rc::new(TextEditingController::new("draft"));
+    let field = RenderEditable::new(controller).with_style(TextStyle::new().with_font_size(20.0));
+    let tree = FocusTree::new(
+        column().with_child(text("Hello")).with_child(field),
+        Size::new(200.0, 100.0),
+    );
+    let mut owner = SemanticsOwner::new();
+    owner.update(&tree.root);
+    let mut adapter = AccessKitAdapter::new();
+    let update = adapter.update(owner.root_node().unwrap());
+
+    let tree_info = update.tree.as_ref().unwrap();
+    assert_eq!(tree_info.root, NodeId(1));
+    assert_eq!(tree_info.toolkit_name.as_deref(), Some("oxui"));
+    assert_eq!(update.focus, NodeId(1));
+    let ids: Vec<_> = update.nodes.iter().map(|(id, _)| *id).collect();
+    assert_eq!(ids, vec![NodeId(1), NodeId(2), NodeId(3)]);
+
+    let (window, label, input) = (&update.nodes[0].1, &update.nodes[1].1, &update.nodes[2].1);
+    assert_eq!(window.role(), Role::Window);
+    assert_eq!(window.children(), &[NodeId(2), NodeId(3)]);
+    assert_eq!(window.bounds(), Some(Rect::new(0.0, 0.0, 200.0, 100.0)));
+    assert_eq!(label.role(), Role::Label);
+    assert_eq!(label.value(), Some("Hello"));
+    assert_eq!(label.bounds(), Some(Rect::new(0.0, 0.0, 25.0, 12.5)));
+    assert_eq!(input.role(), Role::TextInput);
+    assert_eq!(input.value(), Some("draft"));
+    assert_eq!(input.bounds(), Some(Rect::new(0.0, 12.5, 200.0, 37.5)));
+    for action in [Action::Click, Action::Focus, Action::SetValue] {
+        assert!(input.supports_action(action));
+    }
+    assert!(!label.supports_action(Action::Click));
+}
+
+#[test]
+fn test_updates_carry_the_changed_nodes_only() {
+    let controller = Rc::new(TextEditingController::new(""));
+    let field =
+        RenderEditable::new(controller.clone()).with_style(TextStyle::new().with_font_size(20.0));
+    let holder = Rc::new(RefCell::new(
+        column().with_child(text("Name")).with_child(field),
+    ));
+    let tree = FocusTree::from_child(holder.clone(), Size::new(200.0, 100.0));
+    let mut owner = SemanticsOwner::new();
+    owner.update(&tree.root);
+    let mut adapter = AccessKitAdapter::new();
+    adapter.update(owner.root_node().unwrap());
+
+    let update = adapter.update(owner.root_node().unwrap());
+    assert!(update.tree.is_none());
+    assert!(update.nodes.is_empty());
+
+    controller.set_text("Ada");
+    tree.layout(Size::new(200.0, 100.0));
+    owner.update(&tree.root);
+    let update = adapter.update(owner.root_node().unwrap());
+    assert!(update.tree.is_none());
+    assert_eq!(update.nodes.len(), 1);
+    assert_eq!(update.nodes[0].0, NodeId(3));
+    assert_eq!(update.nodes[0].1.value(), Some("Ada"));
+
+    // a node removed sends its parent again
+    let mut children = holder.borrow().children.clone();
+    children.remove(0);
+    holder.borrow_mut().set_children(children);
+    tree.layout(Size::new(200.0, 100.0));
+    owner.update(&tree.root);
+    let update = adapter.update(owner.root_node().unwrap());
+    let ids: Vec<_> = update.nodes.iter().map(|(id, _)| *id).collect();
+    assert_eq!(ids, vec![NodeId(1), NodeId(3)]);
+    assert_eq!(update.nodes[0].1.children(), &[NodeId(3)]);
+
+    adapter.reset();
+    let update = adapter.update(owner.root_node().unwrap());
+    assert!(update.tree.is_some());
+    assert_eq!(update.nodes.len(), 2);
+}
+
+#[test]
+fn test_action_requests_reach_the_widget_tree() {
+    let taps = Rc::new(RefCell::new(0));
+    let counter = taps.clone();
+    let properties = SemanticsProperties {
+        role: Some(SemanticsRole::Button),
+        on_tap: Some(SemanticsActionHandler::new(move |_| {
+            *counter.borrow_mut() += 1
+        })),
+        ..Default::default()
+    };
+    // the text labels the button
+    let button = RenderSemanticsAnnotations::new(properties)
+        .with_container(true)
+        .with_child(text("Send"));
+    let controller = Rc::new(TextEditingController::new(""));
+    let field = RenderEditable::new(controller.clone()).with_max_lines(1);
+    let field_node = field.focus_node.clone();
+    let tree = FocusTree::new(
+        column().with_child(button).with_child(field),
+        Size::new(200.0, 100.0),
+    );
+    let mut owner = SemanticsOwner::new();
+    owner.update(&tree.root);
+    let mut adapter = AccessKitAdapter::new();
+    let update = adapter.update(owner.root_node().unwrap());
+    let button_id = update.nodes[1].0;
+    let field_id = update.nodes[2].0;
+    assert_eq!(update.nodes[1].1.label(), Some("Send"));
+
+    assert!(owner.perform_action_request(&request(Action::Click, button_id, None)));
+    assert_eq!(*taps.borrow(), 1);
+    let value = Some(ActionData::Value("one\ntwo".into()));
+    assert!(owner.perform_action_request(&request(Action::SetValue, field_id, value)));
+    assert_eq!(controller.text(), "onetwo");
+    assert!(owner.perform_action_request(&request(Action::Focus, field_id, None)));
+    tree.manager.apply_focus_changes();
+    assert!(field_node.has_primary_focus());
+    assert!(!owner.perform_action_request(&request(Action::Collapse, button_id, None)));
+    assert!(!owner.perform_action_request(&request(Action::Click, NodeId(42), None)));
+
+    // the focus follows the focused node
+    tree.layout(Size::new(200.0, 100.0));
+    owner.update(&tree.root);
+    let update = adapter.update(owner.root_node().unwrap());
+    assert_eq!(update.focus, field_id);
+}
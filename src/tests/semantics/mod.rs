@@ -0,0 +1,3 @@
+#[cfg(feature = "accesskit")]
+mod accesskit_test;
+mod semantics_test;
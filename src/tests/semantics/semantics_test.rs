@@ -0,0 +1,209 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    gestures::HitTestBehavior,
+    painting::TextStyle,
+    rendering::{
+        Axis, CrossAxisAlignment, RenderEditable, RenderFlex, RenderFocus, RenderGestureDetector,
+        RenderParagraph, RenderSemanticsAnnotations, Size, TextDirection,
+    },
+    semantics::{
+        SemanticsAction, SemanticsActionHandler, SemanticsOwner, SemanticsProperties, SemanticsRole,
+    },
+    services::{FocusNode, TextEditingController},
+    tests::common::{FocusTree, RenderSizedBox},
+};
+
+fn text(text: &str) -> RenderParagraph {
+    RenderParagraph::new(text).with_style(TextStyle::new().with_font_size(10.0))
+}
+
+fn column() -> RenderFlex {
+    RenderFlex::default()
+        .with_direction(Axis::Vertical)
+        .with_cross_axis_alignment(CrossAxisAlignment::Start)
+        .with_text_direction(TextDirection::LTR)
+}
+
+fn annotations(properties: SemanticsProperties) -> RenderSemanticsAnnotations {
+    RenderSemanticsAnnotations::new(properties)
+}
+
+fn button(label: &str) -> SemanticsProperties {
+    SemanticsProperties {
+        role: Some(SemanticsRole::Button),
+        label: Some(label.to_string()),
+        ..Default::default()
+    }
+}
+
+fn tapped(log: &Rc<RefCell<Vec<String>>>, label: &'static str) -> RenderGestureDetector {
+    let log = log.clone();
+    RenderGestureDetector::new(HitTestBehavior::Opaque)
+        .with_on_semantics_tap(move |_| log.borrow_mut().push(label.to_string()))
+}
+
+fn tree_string(owner: &SemanticsOwner) -> String {
+    owner.root_node().unwrap().to_tree_string()
+}
+
+#[test]
+fn test_tree_serializes_roles_labels_values_and_bounds() {
+    let controller = Rc::new(TextEditingController::new("draft"));
+    let field = RenderEditable::new(controller).with_style(TextStyle::new().with_font_size(20.0));
+    let root = column().with_child(text("Hello")).with_child(field);
+    let tree = FocusTree::new(root, Size::new(200.0, 100.0));
+    let mut owner = SemanticsOwner::new();
+    owner.update(&tree.root);
+
+    assert_eq!(
+        tree_string(&owner),
+        "#1 window rect=0,0 200x100\n\
+         \x20 #2 text \"Hello\" rect=0,0 25x12.5\n\
+         \x20 #3 text_field value=\"draft\" actions=[tap, focus, set_text] \
+         flags=[focusable] rect=0,12.5 200x25\n"
+    );
+}
+
+#[test]
+fn test_boundaries_merge_compatible_descendants() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    // the texts and the tap merge into the button, the second tap cannot and keeps a node
+    let labels = RenderFlex::default()
+        .with_text_direction(TextDirection::LTR)
+        .with_child(text("Save"))
+        .with_child(text("all"))
+        .with_child(tapped(&log, "inner").with_child(text("?")));
+    let role = SemanticsProperties {
+        role: Some(SemanticsRole::Button),
+        ..Default::default()
+    };
+    let save = annotations(role)
+        .with_container(true)
+        .with_child(tapped(&log, "outer").with_child(labels));
+    let tree = FocusTree::new(column().with_child(save), Size::new(200.0, 100.0));
+    let mut owner = SemanticsOwner::new();
+    owner.update(&tree.root);
+
+    assert_eq!(
+        tree_string(&owner),
+        "#1 window rect=0,0 200x100\n\
+         \x20 #2 button \"Save\\nall\" actions=[tap] rect=0,0 200x12.5\n\
+         \x20   #3 generic \"?\" actions=[tap] rect=35,0 5x12.5\n"
+    );
+    let button = owner.root_node().unwrap().children[0].clone();
+    assert!(owner.perform_action(button.id, SemanticsAction::Tap, None));
+    assert!(owner.perform_action(button.children[0].id, SemanticsAction::Tap, None));
+    assert!(!owner.perform_action(button.id, SemanticsAction::LongPress, None));
+    assert_eq!(*log.borrow(), vec!["outer", "inner"]);
+}
+
+#[test]
+fn test_merging_and_excluding_descendants() {
+    let checked = SemanticsProperties {
+        checked: Some(true),
+        on_tap: Some(SemanticsActionHandler::new(|_| {})),
+        ..Default::default()
+    };
+    // a check box merging the text field labelling it, boundaries merge too
+    let controller = Rc::new(TextEditingController::new("note"));
+    let row = RenderFlex::default()
+        .with_text_direction(TextDirection::LTR)
+        .with_child(annotations(checked).with_child(RenderSizedBox::new(10.0, 10.0)))
+        .with_child(text("Remember"))
+        .with_child(RenderEditable::new(controller).with_max_lines(1));
+    let merged = annotations(Default::default())
+        .with_merges_descendants(true)
+        .with_child(row);
+    let hidden = annotations(Default::default())
+        .with_excludes_descendants(true)
+        .with_child(text("decoration"));
+    let tree = FocusTree::new(
+        column().with_child(merged).with_child(hidden),
+        Size::new(200.0, 100.0),
+    );
+    let mut owner = SemanticsOwner::new();
+    owner.update(&tree.root);
+
+    let root = owner.root_node().unwrap();
+    assert_eq!(root.children.len(), 1);
+    let merged = &root.children[0];
+    assert_eq!(merged.label, "Remember");
+    assert_eq!(merged.role, SemanticsRole::TextField);
+    assert_eq!(merged.value.as_deref(), Some("note"));
+    assert_eq!(merged.is_checked, Some(true));
+    assert!(merged.is_focusable);
+    assert!(merged.children.is_empty());
+    assert!(root.find_by_label("decoration").is_none());
+}
+
+#[test]
+fn test_invisible_and_empty_nodes_are_pruned() {
+    let empty = annotations(button("empty")).with_child(RenderSizedBox::new(0.0, 0.0));
+    // a container without annotations hoists its children
+    let group = annotations(Default::default())
+        .with_container(true)
+        .with_explicit_child_nodes(true)
+        .with_child(column().with_child(text("a")).with_child(text("b")));
+    let below = annotations(button("below")).with_child(RenderSizedBox::new(10.0, 10.0));
+    let root = column()
+        .with_child(empty)
+        .with_child(group)
+        .with_child(RenderSizedBox::new(10.0, 100.0))
+        .with_child(below);
+    let tree = FocusTree::new(root, Size::new(200.0, 100.0));
+    let mut owner = SemanticsOwner::new();
+    owner.update(&tree.root);
+
+    let root = owner.root_node().unwrap();
+    let labels: Vec<_> = root
+        .children
+        .iter()
+        .map(|node| node.label.as_str())
+        .collect();
+    assert_eq!(labels, vec!["a", "b"]);
+}
+
+#[test]
+fn test_ids_stay_stable_and_actions_reach_render_objects() {
+    let controller = Rc::new(TextEditingController::new(""));
+    let field = RenderEditable::new(controller.clone())
+        .with_style(TextStyle::new().with_font_size(20.0))
+        .with_max_length(5);
+    let field_node = field.focus_node.clone();
+    let other = Rc::new(FocusNode::new());
+    let holder = Rc::new(RefCell::new(
+        column()
+            .with_child(RenderFocus::new(other.clone()).with_child(text("other")))
+            .with_child(field),
+    ));
+    let tree = FocusTree::from_child(holder.clone(), Size::new(200.0, 100.0));
+    let mut owner = SemanticsOwner::new();
+    owner.update(&tree.root);
+    let root = owner.root_node().unwrap().clone();
+    let (other_id, field_id) = (root.children[0].id, root.children[1].id);
+
+    assert!(owner.perform_action(field_id, SemanticsAction::SetText, Some("hello world")));
+    assert!(owner.perform_action(field_id, SemanticsAction::Focus, None));
+    tree.manager.apply_focus_changes();
+    assert_eq!(controller.text(), "hello");
+    assert!(field_node.has_primary_focus());
+
+    // a new object forming a node gets a new id, the others keep theirs
+    let mut children = holder.borrow().children.clone();
+    children.insert(0, text("new").into());
+    holder.borrow_mut().set_children(children);
+    tree.layout(Size::new(200.0, 100.0));
+    owner.update(&tree.root);
+    let root = owner.root_node().unwrap();
+    let ids: Vec<_> = root.children.iter().map(|node| node.id).collect();
+    assert_eq!(ids[1..], [other_id, field_id]);
+    assert!(ids[0] > field_id);
+    let field = root.find(field_id).unwrap();
+    assert_eq!(field.value.as_deref(), Some("hello"));
+    assert!(field.is_focused && !root.find(other_id).unwrap().is_focused);
+
+    assert!(owner.perform_action(other_id, SemanticsAction::Focus, None));
+    tree.manager.apply_focus_changes();
+    assert!(other.has_primary_focus());
+}
@@ -0,0 +1,244 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    rendering::{Axis, CrossAxisAlignment, RenderFlex, RenderFocus, Size, TextDirection},
+    services::{
+        FocusNode, KeyEvent, KeyEventCallback, KeyEventKind, KeyEventResult, LogicalKey, Modifiers,
+        PhysicalKey,
+    },
+    tests::common::{FocusTree, RenderSizedBox},
+};
+
+type Log = Rc<RefCell<Vec<String>>>;
+
+/// A node logging the keys reaching it, `result` tells what it does with them.
+fn node(label: &'static str, log: &Log, result: fn(&KeyEvent) -> KeyEventResult) -> Rc<FocusNode> {
+    let log = log.clone();
+    let node = FocusNode::new()
+        .with_debug_label(label)
+        .with_on_key(move |event| {
+            log.borrow_mut()
+                .push(format!("{} {:?}", label, event.logical_key));
+            result(event)
+        });
+    Rc::new(node)
+}
+
+fn ignore(_: &KeyEvent) -> KeyEventResult {
+    KeyEventResult::Ignored
+}
+
+fn focus(node: &Rc<FocusNode>, width: f32, height: f32) -> RenderFocus {
+    RenderFocus::new(node.clone()).with_child(RenderSizedBox::new(width, height))
+}
+
+fn key(logical_key: LogicalKey) -> KeyEvent {
+    KeyEvent::new(KeyEventKind::Down, logical_key)
+}
+
+fn primary_label(tree: &FocusTree) -> Option<String> {
+    let primary = tree.manager.primary_focus()?;
+    primary.debug_label().map(str::to_string)
+}
+
+#[test]
+fn test_key_events_bubble_until_handled() {
+    let log = Log::default();
+    let outer = node("outer", &log, |_| KeyEventResult::Handled);
+    let middle = node("middle", &log, ignore);
+    let inner = node("inner", &log, |event| match event.logical_key {
+        LogicalKey::Escape => KeyEventResult::SkipRemainingHandlers,
+        _ => KeyEventResult::Ignored,
+    });
+    let tree = RenderFocus::new(outer.clone())
+        .with_child(RenderFocus::new(middle.clone()).with_child(focus(&inner, 10.0, 10.0)));
+    let tree = FocusTree::new(tree, Size::new(100.0, 100.0));
+
+    inner.request_focus();
+    let event = key(LogicalKey::Enter).with_physical_key(PhysicalKey(0x1c));
+    assert!(tree.manager.handle_key_event(&event));
+    assert!(inner.has_primary_focus());
+    assert!(outer.has_focus() && !outer.has_primary_focus());
+    assert_eq!(
+        *log.borrow(),
+        vec!["inner Enter", "middle Enter", "outer Enter"]
+    );
+
+    log.borrow_mut().clear();
+    assert!(!tree.manager.handle_key_event(&key(LogicalKey::Escape)));
+    assert_eq!(*log.borrow(), vec!["inner Escape"]);
+}
+
+#[test]
+fn test_key_events_carry_physical_keys() {
+    let keys = Rc::new(RefCell::new(Vec::new()));
+    let node = Rc::new(FocusNode::new().with_on_key({
+        let keys = keys.clone();
+        move |event| {
+            keys.borrow_mut().push((event.kind, event.physical_key));
+            KeyEventResult::Handled
+        }
+    }));
+    let tree = FocusTree::new(focus(&node, 10.0, 10.0), Size::new(100.0, 100.0));
+    node.request_focus();
+
+    // the key in the position of W on a QWERTY layout, whatever the layout
+    let w = PhysicalKey(0x11);
+    for kind in [KeyEventKind::Down, KeyEventKind::Repeat, KeyEventKind::Up] {
+        let event = KeyEvent::new(kind, LogicalKey::Character('z')).with_physical_key(w);
+        assert!(tree.manager.handle_key_event(&event));
+    }
+    assert_eq!(
+        *keys.borrow(),
+        vec![
+            (KeyEventKind::Down, w),
+            (KeyEventKind::Repeat, w),
+            (KeyEventKind::Up, w)
+        ]
+    );
+}
+
+#[test]
+fn test_focus_changes_notify_gained_and_lost_nodes() {
+    let log = Log::default();
+    let on_change = |label: &'static str| {
+        let log = log.clone();
+        move |has_focus| log.borrow_mut().push(format!("{} {}", label, has_focus))
+    };
+    let shared = Rc::new(FocusNode::new().with_on_focus_change(on_change("shared")));
+    let a = Rc::new(FocusNode::new().with_on_focus_change(on_change("a")));
+    let b = Rc::new(FocusNode::new().with_on_focus_change(on_change("b")));
+    let flex = RenderFlex::default()
+        .with_child(focus(&a, 10.0, 10.0))
+        .with_child(focus(&b, 10.0, 10.0));
+    let tree = FocusTree::new(
+        RenderFocus::new(shared.clone()).with_child(flex),
+        Size::new(100.0, 100.0),
+    );
+
+    a.request_focus();
+    tree.manager.apply_focus_changes();
+    b.request_focus();
+    assert!(a.has_primary_focus(), "applies with the next event");
+    tree.manager.apply_focus_changes();
+    assert_eq!(
+        *log.borrow(),
+        vec!["a true", "shared true", "a false", "b true"]
+    );
+
+    log.borrow_mut().clear();
+    b.unfocus();
+    tree.manager.apply_focus_changes();
+    assert_eq!(*log.borrow(), vec!["b false", "shared false"]);
+    assert!(tree.manager.root_scope().has_primary_focus());
+}
+
+#[test]
+fn test_tab_traverses_in_reading_order() {
+    let log = Log::default();
+    let (a, b, c) = (
+        node("a", &log, ignore),
+        node("b", &log, ignore),
+        node("c", &log, ignore),
+    );
+    let skipped = Rc::new(
+        FocusNode::new()
+            .with_debug_label("skipped")
+            .with_skip_traversal(true),
+    );
+    // b is taller and starts above a, both read as the first row
+    let first_row = RenderFlex::default()
+        .with_cross_axis_alignment(CrossAxisAlignment::End)
+        .with_child(focus(&a, 50.0, 20.0))
+        .with_child(focus(&b, 50.0, 40.0))
+        .with_child(focus(&skipped, 50.0, 40.0));
+    let column = RenderFlex::default()
+        .with_direction(Axis::Vertical)
+        .with_cross_axis_alignment(CrossAxisAlignment::Start)
+        .with_text_direction(TextDirection::LTR)
+        .with_child(focus(&c, 50.0, 20.0))
+        .with_child(first_row);
+    let tree = FocusTree::new(column, Size::new(200.0, 200.0));
+    assert_eq!((c.rect().left, c.rect().top), (0.0, 0.0));
+    assert_eq!((a.rect().top, b.rect().top), (40.0, 20.0));
+
+    let tab = key(LogicalKey::Tab);
+    let mut order = Vec::new();
+    for _ in 0..4 {
+        assert!(tree.manager.handle_key_event(&tab));
+        order.push(primary_label(&tree).unwrap());
+    }
+    assert_eq!(order, vec!["c", "a", "b", "c"]);
+    // the focused node saw the tab before it moved the focus
+    assert_eq!(log.borrow().len(), 3);
+
+    let shift_tab = tab.clone().with_modifiers(Modifiers::SHIFT);
+    tree.manager.handle_key_event(&shift_tab);
+    assert_eq!(primary_label(&tree).unwrap(), "b");
+    // a tab the focused node handles does not move the focus
+    b.set_on_key(Some(KeyEventCallback::new(|_| KeyEventResult::Handled)));
+    tree.manager.handle_key_event(&tab);
+    assert!(b.has_primary_focus());
+}
+
+#[test]
+fn test_scope_limits_traversal_and_restores_focus() {
+    let log = Log::default();
+    let (x, y, z) = (
+        node("x", &log, ignore),
+        node("y", &log, ignore),
+        node("z", &log, ignore),
+    );
+    let scope = Rc::new(FocusNode::scope().with_debug_label("scope"));
+    let dialog = RenderFlex::default()
+        .with_child(focus(&x, 10.0, 10.0))
+        .with_child(focus(&y, 10.0, 10.0));
+    let column = RenderFlex::default()
+        .with_direction(Axis::Vertical)
+        .with_child(RenderFocus::new(scope.clone()).with_child(dialog))
+        .with_child(focus(&z, 10.0, 10.0));
+    let tree = FocusTree::new(column, Size::new(100.0, 100.0));
+
+    x.request_focus();
+    let tab = key(LogicalKey::Tab);
+    tree.manager.handle_key_event(&tab);
+    assert!(y.has_primary_focus());
+    tree.manager.handle_key_event(&tab);
+    assert!(x.has_primary_focus(), "wraps around inside the scope");
+    tree.manager.handle_key_event(&tab);
+
+    z.request_focus();
+    tree.manager.apply_focus_changes();
+    assert!(!scope.has_focus());
+    // focusing the scope focuses the node focused in it last
+    scope.request_focus();
+    tree.manager.apply_focus_changes();
+    assert!(y.has_primary_focus());
+    assert!(scope.has_focus());
+}
+
+#[test]
+fn test_requests_wait_for_nodes_to_attach() {
+    let log = Log::default();
+    let (parent, a, b) = (
+        node("parent", &log, ignore),
+        node("a", &log, ignore),
+        node("b", &log, ignore),
+    );
+    a.request_focus();
+    let holder = Rc::new(RefCell::new(
+        RenderFocus::new(parent.clone()).with_child(focus(&a, 10.0, 10.0)),
+    ));
+    let tree = FocusTree::from_child(holder.clone(), Size::new(100.0, 100.0));
+    assert!(a.has_primary_focus());
+    assert_eq!(a.parent().unwrap().debug_label(), Some("parent"));
+
+    // a node removed from the tree passes the focus to its nearest ancestor left
+    holder
+        .borrow_mut()
+        .set_child(Rc::new(RefCell::new(focus(&b, 10.0, 10.0))));
+    tree.layout(Size::new(100.0, 100.0));
+    assert!(!a.is_attached() && !a.has_focus());
+    assert!(parent.has_primary_focus());
+    assert_eq!(parent.children().len(), 1);
+}
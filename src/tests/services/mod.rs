@@ -0,0 +1,4 @@
+mod focus_test;
+mod shortcuts_test;
+mod text_editing_test;
+mod text_input_test;
@@ -0,0 +1,205 @@
+use std::{cell::RefCell, rc::Rc};
+
+use winit::event::{
+    DeviceId, ElementState, KeyboardInput as WinitKeyboardInput, ModifiersState, VirtualKeyCode,
+    WindowEvent,
+};
+
+use crate::{
+    painting::TextStyle,
+    rendering::{RenderEditable, RenderFlex, RenderFocus, Size},
+    services::{
+        Action, ActionMap, CallbackAction, FocusNode, ImeEvent, Intent, KeyEvent, KeyEventKind,
+        KeyboardInput, LogicalKey, Modifiers, PhysicalKey, RawKeyboard, ShortcutMap,
+        SingleActivator, TextEditingController,
+    },
+    tests::common::{FocusTree, RenderSizedBox},
+};
+
+#[derive(Debug)]
+struct Save;
+impl Intent for Save {}
+
+#[derive(Debug)]
+struct Insert(&'static str);
+impl Intent for Insert {}
+
+/// Saves unless there is nothing to save.
+#[derive(Debug, Default)]
+struct SaveAction {
+    dirty: RefCell<bool>,
+    saves: RefCell<usize>,
+}
+
+impl Action<Save> for SaveAction {
+    fn is_enabled(&self, _: &Save) -> bool {
+        *self.dirty.borrow()
+    }
+
+    fn invoke(&self, _: &Save) {
+        *self.dirty.borrow_mut() = false;
+        *self.saves.borrow_mut() += 1;
+    }
+}
+
+fn ctrl(c: char) -> SingleActivator {
+    SingleActivator::new(LogicalKey::Character(c)).with_modifiers(Modifiers::CONTROL)
+}
+
+fn ctrl_key(c: char) -> KeyEvent {
+    KeyEvent::new(KeyEventKind::Down, LogicalKey::Character(c)).with_modifiers(Modifiers::CONTROL)
+}
+
+fn unfocusable(shortcuts: ShortcutMap, actions: ActionMap) -> Rc<FocusNode> {
+    let node = FocusNode::new()
+        .with_can_request_focus(false)
+        .with_shortcuts(shortcuts)
+        .with_actions(actions);
+    Rc::new(node)
+}
+
+#[test]
+fn test_activator_matches_exact_modifiers() {
+    let save = ctrl('s');
+    assert!(save.accepts(&ctrl_key('s')));
+    let with_shift = ctrl_key('s').with_modifiers(Modifiers::CONTROL | Modifiers::SHIFT);
+    assert!(!save.accepts(&with_shift));
+    assert!(!save.accepts(&KeyEvent::new(
+        KeyEventKind::Down,
+        LogicalKey::Character('s')
+    )));
+
+    let mut repeat = ctrl_key('s');
+    repeat.kind = KeyEventKind::Repeat;
+    assert!(save.accepts(&repeat));
+    assert!(!save.with_include_repeats(false).accepts(&repeat));
+    let mut up = ctrl_key('s');
+    up.kind = KeyEventKind::Up;
+    assert!(!save.accepts(&up));
+}
+
+#[test]
+fn test_shortcut_invokes_action_above_focused_field() {
+    let controller = Rc::new(TextEditingController::new("draft"));
+    let editable =
+        RenderEditable::new(controller.clone()).with_style(TextStyle::new().with_font_size(20.0));
+    let field_node = editable.focus_node.clone();
+    let save = Rc::new(SaveAction::default());
+    let actions = ActionMap::new().with(save.clone());
+    let shortcuts = ShortcutMap::new().with(ctrl('s'), Save);
+    // shortcuts below the actions, as an app declares its keys inside its document
+    let tree = RenderFocus::new(unfocusable(ShortcutMap::new(), actions)).with_child(
+        RenderFocus::new(unfocusable(shortcuts, ActionMap::new())).with_child(editable),
+    );
+    let tree = FocusTree::new(tree, Size::new(200.0, 100.0));
+    field_node.request_focus();
+
+    // nothing to save yet, the disabled action leaves the key to the others
+    assert!(!tree.manager.handle_key_event(&ctrl_key('s')));
+    *save.dirty.borrow_mut() = true;
+    assert!(tree.manager.handle_key_event(&ctrl_key('s')));
+    assert_eq!(*save.saves.borrow(), 1);
+    assert!(!*save.dirty.borrow());
+    // the field still types the key without the modifier
+    let s = KeyEvent::new(KeyEventKind::Down, LogicalKey::Character('s')).with_character("s");
+    assert!(tree.manager.handle_key_event(&s));
+    assert_eq!(controller.text(), "drafts");
+}
+
+#[test]
+fn test_nearest_action_and_scoped_shortcuts() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let insert = |label: &'static str| {
+        let log = log.clone();
+        ActionMap::new().with_callback(move |intent: &Insert| {
+            log.borrow_mut().push(format!("{} {}", label, intent.0))
+        })
+    };
+    let (left, right) = (Rc::new(FocusNode::new()), Rc::new(FocusNode::new()));
+    // only the left subtree has the shortcut, and its own action
+    let left_shortcuts = ShortcutMap::new()
+        .with(ctrl('i'), Insert("image"))
+        .with(ctrl('i'), Insert("link"));
+    let left_side = RenderFocus::new(unfocusable(left_shortcuts, insert("inner")))
+        .with_child(RenderFocus::new(left.clone()).with_child(RenderSizedBox::new(10.0, 10.0)));
+    let right_side = RenderFocus::new(right.clone()).with_child(RenderSizedBox::new(10.0, 10.0));
+    let row = RenderFlex::default()
+        .with_child(left_side)
+        .with_child(right_side);
+    let root = RenderFocus::new(unfocusable(ShortcutMap::new(), insert("outer"))).with_child(row);
+    let tree = FocusTree::new(root, Size::new(100.0, 100.0));
+
+    left.request_focus();
+    assert!(tree.manager.handle_key_event(&ctrl_key('i')));
+    assert_eq!(*log.borrow(), vec!["inner link"]);
+
+    right.request_focus();
+    assert!(!tree.manager.handle_key_event(&ctrl_key('i')));
+    // an intent invoked directly resolves from the focused node
+    assert!(tree.manager.invoke_action(&Insert("text")));
+    assert_eq!(*log.borrow(), vec!["inner link", "outer text"]);
+    assert!(!tree.manager.invoke_action(&Save));
+}
+
+#[test]
+fn test_callback_action_can_be_disabled() {
+    let action = CallbackAction::new(|_: &Save| {}).with_enabled(false);
+    assert!(!action.is_enabled(&Save));
+    action.set_enabled(true);
+    assert!(action.is_enabled(&Save));
+}
+
+#[allow(deprecated)]
+fn keyboard_input(state: ElementState, scancode: u32, key: VirtualKeyCode) -> WindowEvent<'static> {
+    WindowEvent::KeyboardInput {
+        device_id: unsafe { DeviceId::dummy() },
+        input: WinitKeyboardInput {
+            scancode,
+            state,
+            virtual_keycode: Some(key),
+            modifiers: ModifiersState::empty(),
+        },
+        is_synthetic: false,
+    }
+}
+
+#[test]
+fn test_raw_keyboard_converts_window_events() {
+    let mut keyboard = RawKeyboard::new();
+    let changed = WindowEvent::ModifiersChanged(ModifiersState::CTRL | ModifiersState::SHIFT);
+    assert_eq!(keyboard.handle_window_event(&changed), None);
+    assert_eq!(keyboard.modifiers(), Modifiers::CONTROL | Modifiers::SHIFT);
+
+    let press = keyboard_input(ElementState::Pressed, 0x1f, VirtualKeyCode::S);
+    let mut kinds = Vec::new();
+    for event in [
+        press.clone(),
+        press,
+        keyboard_input(ElementState::Released, 0x1f, VirtualKeyCode::S),
+    ] {
+        match keyboard.handle_window_event(&event) {
+            Some(KeyboardInput::Key(event)) => {
+                assert_eq!(event.logical_key, LogicalKey::Character('s'));
+                assert_eq!(event.physical_key, PhysicalKey(0x1f));
+                assert_eq!(event.modifiers, Modifiers::CONTROL | Modifiers::SHIFT);
+                kinds.push(event.kind);
+            }
+            other => panic!("{:?}", other),
+        }
+    }
+    assert_eq!(
+        kinds,
+        vec![KeyEventKind::Down, KeyEventKind::Repeat, KeyEventKind::Up]
+    );
+
+    // typed text arrives as commits, control chars as keys only
+    let typed = WindowEvent::ReceivedCharacter('S');
+    assert_eq!(
+        keyboard.handle_window_event(&typed),
+        Some(KeyboardInput::Ime(ImeEvent::Commit("S".to_string())))
+    );
+    let control = WindowEvent::ReceivedCharacter('\u{13}');
+    assert_eq!(keyboard.handle_window_event(&control), None);
+    let unknown = keyboard_input(ElementState::Pressed, 0x3b, VirtualKeyCode::F1);
+    assert_eq!(keyboard.handle_window_event(&unknown), None);
+}
@@ -0,0 +1,83 @@
+use std::rc::Rc;
+
+use crate::{
+    rendering::RenderState,
+    services::{TextEditingController, TextEditingValue, TextSelection, UndoHistory},
+};
+
+fn value(text: &str, caret: usize) -> TextEditingValue {
+    TextEditingValue::new(text).with_selection(TextSelection::collapsed(caret))
+}
+
+#[test]
+fn test_char_boundaries_skip_surrogate_pairs() {
+    // the emoji is two UTF-16 code units long
+    let value = value("a😀b", 0);
+    assert_eq!(value.len(), 4);
+    assert_eq!(value.next_char_boundary(1), 3);
+    assert_eq!(value.previous_char_boundary(3), 1);
+    assert_eq!(value.previous_char_boundary(0), 0);
+    assert_eq!(value.next_char_boundary(4), 4);
+}
+
+#[test]
+fn test_word_boundaries() {
+    let value = value("foo, bar_baz  qux", 0);
+    assert_eq!(value.next_word_boundary(0), 3);
+    assert_eq!(value.next_word_boundary(3), 12);
+    assert_eq!(value.next_word_boundary(12), 17);
+    assert_eq!(value.previous_word_boundary(17), 14);
+    assert_eq!(value.previous_word_boundary(14), 5);
+    assert_eq!(value.previous_word_boundary(5), 0);
+}
+
+#[test]
+fn test_replace_and_delete() {
+    let value = value("hello world", 5);
+    assert_eq!(value.replace_selection(","), self::value("hello, world", 6));
+    assert_eq!(value.delete_backward(false), self::value("hell world", 4));
+    assert_eq!(value.delete_forward(false), self::value("helloworld", 5));
+    assert_eq!(value.delete_backward(true), self::value(" world", 0));
+    assert_eq!(value.delete_forward(true), self::value("hello", 5));
+
+    let selected = value.with_selection(TextSelection::new(11, 6));
+    assert_eq!(selected.selected_text(), "world");
+    assert_eq!(selected.delete_backward(true), self::value("hello ", 6));
+}
+
+#[test]
+fn test_undo_history() {
+    let mut history = UndoHistory::default();
+    let (a, b, c) = (value("a", 1), value("ab", 2), value("abc", 3));
+    history.record(a.clone());
+    history.record(b.clone());
+
+    assert_eq!(history.undo(c.clone()), Some(b.clone()));
+    assert_eq!(history.undo(b.clone()), Some(a.clone()));
+    assert_eq!(history.undo(a.clone()), None);
+    assert_eq!(history.redo(a.clone()), Some(b.clone()));
+
+    // an edit forgets what was undone
+    history.record(b);
+    assert!(!history.can_redo());
+    assert!(history.can_undo());
+}
+
+#[test]
+fn test_controller_relayouts_attached_editables() {
+    let controller = TextEditingController::new("hello");
+    let state = Rc::new(RenderState::default());
+    controller.attach(&state);
+    state.clear_needs_layout();
+
+    controller.set_selection(TextSelection::collapsed(5));
+    assert!(!state.needs_layout(), "the value is unchanged");
+    controller.set_text("hello world");
+    assert!(state.needs_layout());
+    assert_eq!(controller.selection(), TextSelection::collapsed(11));
+
+    controller.detach(&state);
+    state.clear_needs_layout();
+    controller.set_text("bye");
+    assert!(!state.needs_layout());
+}
@@ -0,0 +1,124 @@
+use std::rc::Rc;
+
+use crate::{
+    painting::{TextDecoration, TextStyle},
+    rendering::{Axis, BoxConstraints, Offset, RenderBox, RenderEditable, RenderFlex, Size},
+    services::{
+        FakeIme, FocusManager, ImeEvent, TextEditingController, TextEditingValue, TextInputClient,
+        TextSelection,
+    },
+    tests::common::{FocusTree, RenderSizedBox},
+};
+
+#[test]
+fn test_compose_replaces_composing_region() {
+    let value = TextEditingValue::new("ab").with_selection(TextSelection::collapsed(1));
+    let value = value.compose("n", None);
+    assert_eq!(value.text, "anb");
+    assert_eq!(value.composing, Some(1..2));
+    let value = value.compose("ni", Some((0, 0)));
+    assert_eq!(value.text, "anib");
+    assert_eq!(value.composing, Some(1..3));
+    assert_eq!(value.selection, TextSelection::collapsed(1));
+
+    assert_eq!(value.without_composing().text, "ab");
+    let cancelled = value.compose("", None);
+    assert_eq!(cancelled.text, "ab");
+    assert_eq!(cancelled.composing, None);
+}
+
+#[test]
+fn test_received_characters_commit_text() {
+    assert_eq!(
+        ImeEvent::from_received_character('é'),
+        Some(ImeEvent::Commit("é".to_string()))
+    );
+    // backspace is handled as a key
+    assert_eq!(ImeEvent::from_received_character('\u{8}'), None);
+}
+
+#[test]
+fn test_composing_text_is_underlined() {
+    let controller = Rc::new(TextEditingController::new("ab"));
+    let mut editable = RenderEditable::new(controller.clone());
+    editable.update_composing("ni", None);
+    editable.layout(&BoxConstraints::default().with_max_width(200.0), true);
+
+    let composing = &editable.painter.text.children[1];
+    assert_eq!(composing.text.as_deref(), Some("ni"));
+    let underline = TextStyle::new().with_decoration(TextDecoration::UNDERLINE);
+    assert_eq!(composing.style, Some(underline));
+
+    editable.commit("你");
+    editable.layout(&BoxConstraints::default().with_max_width(200.0), true);
+    assert_eq!(editable.painter.text.to_plain_text(), "ab你");
+    assert!(editable.painter.text.children.is_empty());
+}
+
+/// A field below a 50 high box, focused by a tap.
+fn focused_field(controller: &Rc<TextEditingController>) -> FocusTree {
+    let editable = RenderEditable::new(controller.clone())
+        .with_style(TextStyle::new().with_font_size(20.0))
+        .with_max_length(4);
+    let flex = RenderFlex::default()
+        .with_direction(Axis::Vertical)
+        .with_child(RenderSizedBox::new(100.0, 50.0))
+        .with_child(editable);
+    let mut tree = FocusTree::new(flex, Size::new(200.0, 200.0));
+    tree.tap(Offset::new(150.0, 60.0));
+    tree
+}
+
+#[test]
+fn test_fake_ime_composes_into_focused_field() {
+    let controller = Rc::new(TextEditingController::new("a"));
+    let tree = focused_field(&controller);
+    let mut ime = FakeIme::new(|event| {
+        assert!(tree.manager.handle_ime_event(&event));
+    });
+
+    ime.type_reading("nihao");
+    assert_eq!(ime.composing(), "nihao");
+    let value = controller.value();
+    assert_eq!(value.text, "anihao");
+    assert_eq!(value.composing, Some(1..6));
+    assert_eq!(value.selection, TextSelection::collapsed(6));
+
+    ime.backspace();
+    assert_eq!(controller.text(), "aniha");
+    ime.commit("你好");
+    let value = controller.value();
+    assert_eq!(value.text, "a你好");
+    assert_eq!(
+        (value.composing, value.selection),
+        (None, TextSelection::collapsed(3))
+    );
+
+    // committed text is cut to the max length, composing text is not
+    ime.type_reading("shijie");
+    assert_eq!(controller.text(), "a你好shijie");
+    ime.commit("世界");
+    assert_eq!(controller.text(), "a你好世");
+
+    ime.type_reading("x");
+    ime.cancel();
+    assert_eq!(controller.text(), "a你好世");
+    ime.disable();
+}
+
+#[test]
+fn test_reports_caret_in_global_coordinates() {
+    let controller = Rc::new(TextEditingController::new("ab"));
+    let tree = focused_field(&controller);
+
+    let caret = tree.manager.ime_caret_rect().unwrap();
+    // the field is below the 50 high box, the caret after 2 chars 10 wide
+    assert_eq!((caret.left, caret.top), (20.0, 50.0));
+    let event = ImeEvent::Preedit("n".to_string(), None);
+    tree.manager.handle_ime_event(&event);
+    let caret = tree.manager.ime_caret_rect().unwrap();
+    assert_eq!((caret.left, caret.top), (30.0, 50.0));
+
+    assert!(FocusManager::new().ime_caret_rect().is_none());
+    assert!(!FocusManager::new().handle_ime_event(&ImeEvent::Enabled));
+}
@@ -0,0 +1,237 @@
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+use crate::{
+    gestures::{HitTestBehavior, TapCallbacks},
+    rendering::{
+        Axis, BoxConstraints, Clip, CrossAxisAlignment, MainAxisSize, RenderBox,
+        RenderConstrainedBox, RenderFlex, Size,
+    },
+    tests::common::{Finder, RenderSizedBox, WidgetTester},
+    widgets::{BuildContext, ConstrainedBox, Flex, Flexible, GestureDetector, Text, Widget},
+};
+
+/// Composes to the same render box each time.
+#[derive(Debug)]
+struct Leaf(Rc<RefCell<RenderSizedBox>>);
+
+impl Widget for Leaf {
+    #[track_caller]
+    fn create(&self, context: BuildContext) -> Rc<RefCell<dyn RenderBox>> {
+        context.memo(
+            |_| self.0.clone() as Rc<RefCell<dyn RenderBox>>,
+            |_| true,
+            |_| {},
+            |n| n.clone(),
+        )
+    }
+}
+
+/// A square box counting how often it is composed, comparable unless `hashed` is false.
+#[derive(Debug)]
+struct Counted {
+    extent: f32,
+    hashed: bool,
+    composed: Rc<Cell<usize>>,
+}
+
+impl Widget for Counted {
+    #[track_caller]
+    fn create(&self, context: BuildContext) -> Rc<RefCell<dyn RenderBox>> {
+        self.composed.set(self.composed.get() + 1);
+        ConstrainedBox {
+            constraints: BoxConstraints::tight(Size::new(self.extent, self.extent)),
+        }
+        .create(context)
+    }
+
+    fn configuration_hash(&self) -> Option<u64> {
+        self.hashed.then_some(self.extent.to_bits() as u64)
+    }
+}
+
+fn sized(width: f32, height: f32) -> Flexible {
+    ConstrainedBox {
+        constraints: BoxConstraints::tight(Size::new(width, height)),
+    }
+    .into()
+}
+
+/// A row of two boxes in a column, for the row to be sized by its children.
+fn row_in_column(direction: Axis, main_axis_size: MainAxisSize) -> Flex {
+    let row = Flex::builder()
+        .direction(direction)
+        .main_axis_size(main_axis_size)
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .children(vec![sized(20.0, 10.0), sized(30.0, 10.0)])
+        .build();
+    Flex::builder()
+        .direction(Axis::Vertical)
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .children(vec![row.into()])
+        .build()
+}
+
+/// The offsets of the boxes in the row, and the size of the row.
+fn layout(tester: &WidgetTester) -> (Vec<(f32, f32)>, (f32, f32)) {
+    let row = tester.find(&Finder::by_type::<RenderFlex>()).pop().unwrap();
+    let row = row.borrow();
+    let origin = row.state().global_paint_offset();
+    let boxes = tester
+        .find(&Finder::by_type::<RenderConstrainedBox>())
+        .iter()
+        .map(|b| {
+            let offset = b.borrow().state().global_paint_offset();
+            (offset.x - origin.x, offset.y - origin.y)
+        })
+        .collect();
+    let size = row.size();
+    (boxes, (size.width, size.height))
+}
+
+#[test]
+fn test_flex_applies_its_properties_to_the_render_flex() {
+    let mut tester = WidgetTester::new(Size::new(200.0, 100.0));
+    tester.pump_widget(row_in_column(Axis::Horizontal, MainAxisSize::Max));
+    assert_eq!(
+        layout(&tester),
+        (vec![(0.0, 0.0), (20.0, 0.0)], (200.0, 10.0))
+    );
+
+    tester.pump_widget(row_in_column(Axis::Horizontal, MainAxisSize::Min));
+    assert_eq!(
+        layout(&tester),
+        (vec![(0.0, 0.0), (20.0, 0.0)], (50.0, 10.0))
+    );
+
+    tester.pump_widget(row_in_column(Axis::Vertical, MainAxisSize::Min));
+    assert_eq!(
+        layout(&tester),
+        (vec![(0.0, 0.0), (0.0, 10.0)], (30.0, 20.0))
+    );
+}
+
+#[test]
+fn test_unchanged_flex_is_not_laid_out_or_painted_again() {
+    let leaf = Rc::new(RefCell::new(RenderSizedBox::new(20.0, 10.0)));
+    let row = |cross_axis_alignment, clip_behavior| {
+        Flex::builder()
+            .cross_axis_alignment(cross_axis_alignment)
+            .clip_behavior(clip_behavior)
+            .children(vec![Leaf(leaf.clone()).into()])
+            .build()
+    };
+    let counts = || {
+        let leaf = leaf.borrow();
+        (leaf.layout_count, leaf.paint_count.get())
+    };
+    let mut tester = WidgetTester::new(Size::new(200.0, 100.0));
+    tester.pump_widget(row(CrossAxisAlignment::Start, Clip::None));
+    assert_eq!(counts(), (1, 1));
+
+    tester.pump_widget(row(CrossAxisAlignment::Start, Clip::None));
+    assert_eq!(counts(), (1, 1));
+
+    // clipping only repaints
+    tester.pump_widget(row(CrossAxisAlignment::Start, Clip::HardEdge));
+    assert_eq!(counts(), (1, 2));
+
+    // stretching lays the child out again with tight constraints
+    tester.pump_widget(row(CrossAxisAlignment::Stretch, Clip::HardEdge));
+    assert_eq!(counts(), (2, 3));
+}
+
+#[test]
+fn test_flex_without_children_is_skipped() {
+    let mut tester = WidgetTester::new(Size::new(200.0, 100.0));
+    let empty = || Flex::builder().children(Vec::new()).build();
+    tester.pump_widget(empty());
+    let flex = tester.find_one(&Finder::by_type::<RenderFlex>());
+    tester.pump_widget(empty());
+    assert!(Rc::ptr_eq(
+        &flex,
+        &tester.find_one(&Finder::by_type::<RenderFlex>())
+    ));
+    assert!(!flex.borrow().state().needs_layout());
+}
+
+#[test]
+fn test_unchanged_flex_skips_its_children() {
+    let composed = Rc::new(Cell::new(0));
+    let row = |extents: [f32; 2], hashed| {
+        let children = extents.iter().map(|extent| {
+            let child = Counted {
+                extent: *extent,
+                hashed,
+                composed: composed.clone(),
+            };
+            child.into()
+        });
+        let row = Flex::builder().children(children.collect()).build();
+        Flex::builder()
+            .direction(Axis::Vertical)
+            .children(vec![row.into()])
+            .build()
+    };
+    let mut tester = WidgetTester::new(Size::new(200.0, 100.0));
+    tester.pump_widget(row([10.0, 20.0], true));
+    assert_eq!(composed.get(), 2);
+    let flexes = tester.find(&Finder::by_type::<RenderFlex>());
+
+    // neither flex nor their children are composed again
+    tester.pump_widget(row([10.0, 20.0], true));
+    assert_eq!(composed.get(), 2);
+    let same = tester.find(&Finder::by_type::<RenderFlex>());
+    assert!(flexes.iter().zip(&same).all(|(a, b)| Rc::ptr_eq(a, b)));
+    assert!(flexes
+        .iter()
+        .all(|flex| !flex.borrow().state().needs_layout()));
+
+    // a changed child recomposes its siblings
+    tester.pump_widget(row([10.0, 30.0], true));
+    assert_eq!(composed.get(), 4);
+    let row_size = tester.find(&Finder::by_type::<RenderFlex>())[1]
+        .borrow()
+        .size();
+    assert_eq!(row_size, Size::new(200.0, 30.0));
+
+    // children that cannot be compared are always composed
+    tester.pump_widget(row([10.0, 30.0], false));
+    assert_eq!(composed.get(), 6);
+    tester.pump_widget(row([10.0, 30.0], false));
+    assert_eq!(composed.get(), 8);
+}
+
+#[test]
+fn test_flex_with_uncomparable_children_is_recomposed() {
+    let composed = Rc::new(Cell::new(0));
+    let taps = Rc::new(Cell::new(0));
+    let column = |label: &str| {
+        let counted = Counted {
+            extent: 10.0,
+            hashed: true,
+            composed: composed.clone(),
+        };
+        let row = Flex::builder().children(vec![counted.into()]).build();
+        let taps = taps.clone();
+        let detector = GestureDetector::new(Text::new(label))
+            .with_behavior(HitTestBehavior::Opaque)
+            .with_tap(TapCallbacks::default().with_on_tap(move |_| taps.set(taps.get() + 1)));
+        Flex::builder()
+            .direction(Axis::Vertical)
+            .children(vec![row.into(), detector.into()])
+            .build()
+    };
+    let mut tester = WidgetTester::new(Size::new(200.0, 100.0));
+    tester.pump_widget(column("a"));
+    assert_eq!(composed.get(), 1);
+
+    // the column holding a callback has no hash, its comparable row still skips itself
+    tester.pump_widget(column("b"));
+    assert_eq!(composed.get(), 1);
+    assert!(tester.find(&Finder::text("a")).is_empty());
+    tester.tap(&Finder::text("b"));
+    assert_eq!(taps.get(), 1);
+}
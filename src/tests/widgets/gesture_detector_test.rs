@@ -0,0 +1,66 @@
+use std::{cell::Cell, rc::Rc};
+
+use crate::{
+    gestures::{DoubleTapCallbacks, HitTestBehavior, LongPressCallbacks},
+    painting::TextStyle,
+    rendering::Size,
+    tests::common::{ms, Finder, WidgetTester},
+    widgets::{GestureDetector, Text},
+};
+
+fn detector() -> GestureDetector {
+    let text = Text::new("target").with_style(TextStyle::new().with_font_size(10.0));
+    GestureDetector::new(text).with_behavior(HitTestBehavior::Opaque)
+}
+
+#[test]
+fn test_double_tap_survives_a_frame_between_the_taps() {
+    let double_taps = Rc::new(Cell::new(0));
+    let widget = || {
+        let double_taps = double_taps.clone();
+        let double_tap = DoubleTapCallbacks::default()
+            .with_on_double_tap(move |_| double_taps.set(double_taps.get() + 1));
+        detector().with_double_tap(double_tap)
+    };
+    let mut tester = WidgetTester::new(Size::new(100.0, 100.0));
+    tester.pump_widget(widget());
+    let target = Finder::text("target");
+
+    tester.tap(&target);
+    tester.pump(ms(100));
+    tester.pump_widget(widget());
+    tester.tap(&target);
+    assert_eq!(double_taps.get(), 1);
+}
+
+/// Long press callbacks counting the presses and their ends into `events`.
+fn count_long_presses(events: &Rc<Cell<(usize, usize)>>) -> LongPressCallbacks {
+    let (on_press, on_end) = (events.clone(), events.clone());
+    LongPressCallbacks::default()
+        .with_on_long_press(move |_| {
+            let (pressed, ended) = on_press.get();
+            on_press.set((pressed + 1, ended));
+        })
+        .with_on_long_press_end(move |_| {
+            let (pressed, ended) = on_end.get();
+            on_end.set((pressed, ended + 1));
+        })
+}
+
+#[test]
+fn test_long_press_survives_frames_during_the_hold() {
+    let first = Rc::new(Cell::new((0, 0)));
+    let second = Rc::new(Cell::new((0, 0)));
+    let mut tester = WidgetTester::new(Size::new(100.0, 100.0));
+    tester.pump_widget(detector().with_long_press(count_long_presses(&first)));
+
+    let position = tester.down(&Finder::text("target"));
+    tester.pump(ms(300));
+    // the press in progress reports to the callbacks of the latest composition
+    tester.pump_widget(detector().with_long_press(count_long_presses(&second)));
+    tester.pump(ms(300));
+    assert_eq!(second.get(), (1, 0));
+    tester.up(position);
+    assert_eq!(second.get(), (1, 1));
+    assert_eq!(first.get(), (0, 0));
+}
@@ -0,0 +1,96 @@
+use std::{env, fs, path::PathBuf};
+
+use crate::{
+    rendering::{Axis, BoxConstraints, Size},
+    tests::common::{GoldenFile, GoldenImage, WidgetTester, UPDATE_GOLDENS_ENV},
+    widgets::{ConstrainedBox, Flex},
+};
+
+const WHITE: [u8; 4] = [0xff; 4];
+
+/// A directory of goldens for a test alone, emptied.
+fn scratch_directory(test: &str) -> PathBuf {
+    let directory = env::temp_dir().join(format!("oxui-goldens-{}-{}", test, std::process::id()));
+    let _ = fs::remove_dir_all(&directory);
+    directory
+}
+
+fn sized(width: f32, height: f32) -> ConstrainedBox {
+    ConstrainedBox {
+        constraints: BoxConstraints::tight(Size::new(width, height)),
+    }
+}
+
+#[test]
+fn test_frame_matches_checked_in_golden() {
+    let mut tester = WidgetTester::new(Size::new(40.0, 30.0)).with_device_pixel_ratio(2.0);
+    tester.pump_widget(
+        Flex::builder()
+            .direction(Axis::Vertical)
+            .children(vec![sized(20.0, 10.0).into(), sized(40.0, 10.0).into()])
+            .build(),
+    );
+
+    let frame = tester.snapshot();
+    assert_eq!((frame.width, frame.height), (80, 60));
+    GoldenFile::new("constrained_boxes").assert_matches(&frame);
+}
+
+#[test]
+fn test_pixels_match_within_tolerance() {
+    let expected = GoldenImage::new(4, 2, WHITE);
+    let mut actual = expected.clone();
+    actual.set_pixel(1, 0, [0xfc, 0xff, 0xff, 0xff]);
+    actual.set_pixel(2, 1, [0xff, 0xf5, 0xff, 0xff]);
+
+    let diff = actual.compare(&expected, 3);
+    assert_eq!(diff.mismatched, 1);
+    assert_eq!(diff.max_difference, 10);
+    assert_eq!(diff.image.pixel(2, 1), [0xff, 0, 0, 0xff]);
+    assert_eq!(diff.image.pixel(1, 0), [0xff, 0xff, 0xff, 0x3f]);
+    assert_eq!(actual.compare(&expected, 10).mismatched, 0);
+}
+
+#[test]
+fn test_mismatch_writes_the_frame_and_the_diff() {
+    let directory = scratch_directory("mismatch");
+    let golden = |update| {
+        GoldenFile::new("square")
+            .with_directory(&directory)
+            .with_update(update)
+    };
+    let expected = GoldenImage::new(3, 3, WHITE);
+    golden(true).check(&expected).expect("golden written");
+    let golden = golden(false);
+    assert!(golden.check(&expected).is_ok());
+
+    let mut actual = expected.clone();
+    actual.set_pixel(1, 1, [0, 0, 0, 0xff]);
+    let message = golden.check(&actual).unwrap_err();
+    assert!(message.starts_with("1/9 pixels"), "{}", message);
+    let written = fs::read(golden.failure_path("actual")).unwrap();
+    assert_eq!(GoldenImage::decode_png(&written), Some(actual.clone()));
+    let diff = GoldenImage::decode_png(&fs::read(golden.failure_path("diff")).unwrap()).unwrap();
+    assert_eq!(diff.pixel(1, 1), [0xff, 0, 0, 0xff]);
+    let tolerant = GoldenFile::new("square")
+        .with_directory(&directory)
+        .with_update(false)
+        .with_tolerance(0xff);
+    assert!(tolerant.check(&actual).is_ok());
+
+    // a frame of another size is a mismatch too
+    let message = golden.check(&GoldenImage::new(2, 3, WHITE)).unwrap_err();
+    assert!(message.contains("is 3x3, the frame is 2x3"), "{}", message);
+    let _ = fs::remove_dir_all(&directory);
+}
+
+#[test]
+fn test_missing_golden_asks_for_regeneration() {
+    let directory = scratch_directory("missing");
+    let golden = GoldenFile::new("missing")
+        .with_directory(&directory)
+        .with_update(false);
+    let message = golden.check(&GoldenImage::new(1, 1, WHITE)).unwrap_err();
+    assert!(message.contains(UPDATE_GOLDENS_ENV), "{}", message);
+    assert!(!golden.path().exists());
+}
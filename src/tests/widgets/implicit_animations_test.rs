@@ -0,0 +1,107 @@
+use std::time::Duration;
+
+use crate::{
+    animation::Curves,
+    painting::TextStyle,
+    rendering::{Axis, BoxConstraints, RenderConstrainedBox, Size},
+    tests::common::{ms, Finder, WidgetTester},
+    widgets::{AnimatedConstrainedBox, AnimatedOpacity, Flex, Text},
+};
+
+fn animated_box(width: f32, height: f32) -> Flex {
+    let animated =
+        AnimatedConstrainedBox::new(BoxConstraints::tight(Size::new(width, height)), ms(100));
+    Flex::builder()
+        .direction(Axis::Vertical)
+        .children(vec![animated.into()])
+        .build()
+}
+
+fn box_size(tester: &WidgetTester) -> (f32, f32) {
+    let rect = tester.rect(&Finder::by_type::<RenderConstrainedBox>());
+    (rect.width(), rect.height())
+}
+
+#[test]
+fn test_constrained_box_animates_to_new_constraints() {
+    let mut tester = WidgetTester::new(Size::new(200.0, 200.0));
+    tester.pump_widget(animated_box(10.0, 10.0));
+    assert_eq!(box_size(&tester), (10.0, 10.0));
+    assert!(!tester.scheduler().has_scheduled_frame());
+
+    tester.pump_widget(animated_box(50.0, 30.0));
+    assert_eq!(box_size(&tester), (10.0, 10.0));
+    tester.pump(ms(50));
+    assert_eq!(box_size(&tester), (30.0, 20.0));
+    tester.pump(ms(50));
+    assert_eq!(box_size(&tester), (50.0, 30.0));
+    assert_eq!(tester.pump_and_settle(ms(16), ms(1000)), 1);
+
+    // recomposing with the same constraints does not animate
+    tester.pump_widget(animated_box(50.0, 30.0));
+    assert!(!tester.scheduler().has_scheduled_frame());
+}
+
+#[test]
+fn test_new_target_animates_from_the_current_value() {
+    let mut tester = WidgetTester::new(Size::new(200.0, 200.0));
+    tester.pump_widget(animated_box(10.0, 10.0));
+    tester.pump_widget(animated_box(50.0, 50.0));
+    tester.pump(ms(50));
+    assert_eq!(box_size(&tester), (30.0, 30.0));
+
+    tester.pump_widget(animated_box(10.0, 10.0));
+    assert_eq!(box_size(&tester), (30.0, 30.0));
+    tester.pump(ms(50));
+    assert_eq!(box_size(&tester), (20.0, 20.0));
+    tester.pump_and_settle(ms(16), ms(1000));
+    assert_eq!(box_size(&tester), (10.0, 10.0));
+}
+
+#[test]
+fn test_constrained_box_animates_from_unbounded_constraints() {
+    let animated = |constraints| {
+        let animated = AnimatedConstrainedBox::new(constraints, ms(100));
+        Flex::builder()
+            .direction(Axis::Vertical)
+            .children(vec![animated.into()])
+            .build()
+    };
+    let mut tester = WidgetTester::new(Size::new(200.0, 200.0));
+    tester.pump_widget(animated(BoxConstraints::default()));
+    assert_eq!(box_size(&tester), (0.0, 0.0));
+
+    tester.pump_widget(animated(BoxConstraints::tight(Size::new(50.0, 30.0))));
+    tester.pump(ms(25));
+    assert_eq!(box_size(&tester), (12.5, 7.5));
+    tester.pump(ms(25));
+    assert_eq!(box_size(&tester), (25.0, 15.0));
+    tester.pump_and_settle(ms(16), ms(1000));
+    assert_eq!(box_size(&tester), (50.0, 30.0));
+}
+
+#[test]
+fn test_opacity_fades_the_child_out_of_the_semantics_tree() {
+    let faded = |opacity| {
+        let text = Text::new("hello").with_style(TextStyle::new().with_font_size(10.0));
+        AnimatedOpacity::new(opacity, ms(200), text).with_curve(Curves::EASE_IN)
+    };
+    let mut tester = WidgetTester::new(Size::new(100.0, 100.0));
+    tester.pump_widget(faded(1.0));
+    tester.pipeline().set_semantics_enabled(true);
+    tester.pump(Duration::ZERO);
+    let visible = |tester: &mut WidgetTester| {
+        let tree = tester.pipeline().semantics_tree().unwrap();
+        tree.find_by_label("hello").is_some()
+    };
+    assert!(visible(&mut tester));
+
+    tester.pump_widget(faded(0.0));
+    tester.pump(ms(100));
+    assert!(visible(&mut tester));
+    let frames = tester.pump_and_settle(ms(50), ms(1000));
+    assert_eq!(frames, 3);
+    assert!(!visible(&mut tester));
+    // still hit, e.g. to fade back in on a tap
+    assert_eq!(tester.find(&Finder::text("hello")).len(), 1);
+}
@@ -0,0 +1,5 @@
+mod flex_test;
+mod gesture_detector_test;
+mod golden_test;
+mod implicit_animations_test;
+mod widget_tester_test;
@@ -0,0 +1,126 @@
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+    time::Duration,
+};
+
+use crate::{
+    gestures::{DragCallbacks, HitTestBehavior, TapCallbacks},
+    painting::TextStyle,
+    rendering::{Axis, BoxConstraints, Offset, RenderBox, RenderEditable, RenderParagraph, Size},
+    services::TextEditingController,
+    tests::common::{Finder, WidgetTester},
+    widgets::{
+        BuildContext, ConstrainedBox, Flex, Flexible, GestureDetector, KeyedSubtree, Text,
+        TextField, Widget,
+    },
+};
+
+fn text(data: &str) -> Text {
+    Text::new(data).with_style(TextStyle::new().with_font_size(10.0))
+}
+
+fn column(children: Vec<Flexible>) -> Flex {
+    Flex::builder()
+        .direction(Axis::Vertical)
+        .children(children)
+        .build()
+}
+
+/// Counts the taps on its text, in state kept across recompositions.
+#[derive(Debug)]
+struct Counter;
+
+impl Widget for Counter {
+    #[track_caller]
+    fn create(&self, context: BuildContext) -> Rc<RefCell<dyn RenderBox>> {
+        let count = context.state(|| Rc::new(Cell::new(0)));
+        let label = format!("count {}", count.get());
+        let tap = TapCallbacks::default().with_on_tap(move |_| count.set(count.get() + 1));
+        GestureDetector::new(text(&label))
+            .with_behavior(HitTestBehavior::Opaque)
+            .with_tap(tap)
+            .create(context)
+    }
+}
+
+#[test]
+fn test_pumping_widgets_keeps_their_render_objects() {
+    let mut tester = WidgetTester::new(Size::new(200.0, 100.0));
+    tester.pump_widget(column(vec![text("a").into(), text("b").into()]));
+    assert_eq!(tester.surface().width(), 200);
+    assert_eq!(tester.find(&Finder::by_type::<RenderParagraph>()).len(), 2);
+    let first = tester.find_one(&Finder::text("a"));
+    assert!(tester.find(&Finder::text("c")).is_empty());
+
+    tester.pump_widget(column(vec![text("c").into(), text("b").into()]));
+    let changed = tester.find_one(&Finder::text("c"));
+    assert!(Rc::ptr_eq(&first, &changed));
+    assert!(tester.find(&Finder::text("a")).is_empty());
+}
+
+#[test]
+fn test_finding_by_key() {
+    let mut tester = WidgetTester::new(Size::new(200.0, 100.0));
+    let tall = ConstrainedBox {
+        constraints: BoxConstraints::tight(Size::new(50.0, 40.0)),
+    };
+    tester.pump_widget(column(vec![
+        KeyedSubtree::new("label", text("a")).into(),
+        KeyedSubtree::new("box", tall).into(),
+    ]));
+
+    let label = tester.rect(&Finder::by_key("label"));
+    let tall = tester.rect(&Finder::by_key("box"));
+    assert_eq!((tall.width(), tall.height()), (50.0, 40.0));
+    assert!(tall.top >= label.bottom);
+    assert!(tester.find(&Finder::by_key("missing")).is_empty());
+}
+
+#[test]
+fn test_tapping_recomposes_with_the_new_state() {
+    let mut tester = WidgetTester::new(Size::new(200.0, 100.0));
+    tester.pump_widget(Counter);
+    tester.tap(&Finder::text("count 0"));
+    tester.tap(&Finder::text("count 0"));
+    // nothing is composed until the next pump
+    assert_eq!(tester.find(&Finder::text("count 0")).len(), 1);
+
+    tester.pump(Duration::from_millis(16));
+    assert_eq!(tester.now(), Duration::from_millis(16));
+    assert_eq!(tester.find(&Finder::text("count 2")).len(), 1);
+}
+
+#[test]
+fn test_dragging_reports_the_offset() {
+    let dragged = Rc::new(Cell::new(0.0));
+    let updates = dragged.clone();
+    let drag = DragCallbacks::default()
+        .with_on_update(move |details| updates.set(updates.get() + details.delta.x));
+    let mut tester = WidgetTester::new(Size::new(200.0, 100.0));
+    tester.pump_widget(
+        GestureDetector::new(text("handle"))
+            .with_behavior(HitTestBehavior::Opaque)
+            .with_horizontal_drag(drag),
+    );
+
+    tester.drag(&Finder::text("handle"), Offset::new(60.0, 0.0));
+    assert_eq!(dragged.get(), 60.0);
+}
+
+#[test]
+fn test_entering_text() {
+    let controller = Rc::new(TextEditingController::new("draft"));
+    let mut tester = WidgetTester::new(Size::new(200.0, 100.0));
+    tester.pump_widget(column(vec![
+        text("Name").into(),
+        KeyedSubtree::new("name", TextField::new(controller.clone())).into(),
+    ]));
+
+    tester.enter_text(&Finder::by_key("name"), "Ada");
+    assert_eq!(controller.text(), "Ada");
+    tester.pump(Default::default());
+    let field = tester.find_one(&Finder::text("Ada"));
+    let editable = tester.find_one(&Finder::by_type::<RenderEditable>());
+    assert!(Rc::ptr_eq(&field, &editable));
+}
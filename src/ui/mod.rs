@@ -1,2 +1,4 @@
 mod image;
 pub use image::*;
+
+pub use skia_safe::Color;
@@ -1,4 +1,10 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{
+    any::TypeId,
+    cell::RefCell,
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    rc::Rc,
+};
 
 use crate::{
     rendering::{BoxConstraints, RenderBox, RenderConstrainedBox},
@@ -24,8 +30,23 @@ impl Widget for ConstrainedBox {
         context.memo(
             |_| Rc::new(RefCell::new(RenderConstrainedBox::new(self.constraints))),
             |n| n.borrow().additional_constraints == self.constraints,
-            |n| n.borrow_mut().additional_constraints = self.constraints,
+            |n| n.borrow_mut().set_additional_constraints(self.constraints),
             |n| n.clone(),
         )
     }
+
+    fn configuration_hash(&self) -> Option<u64> {
+        let mut hasher = DefaultHasher::new();
+        TypeId::of::<Self>().hash(&mut hasher);
+        let constraints = &self.constraints;
+        for bound in [
+            constraints.min_width,
+            constraints.max_width,
+            constraints.min_height,
+            constraints.max_height,
+        ] {
+            bound.to_bits().hash(&mut hasher);
+        }
+        Some(hasher.finish())
+    }
 }
@@ -1,4 +1,10 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{
+    any::TypeId,
+    cell::RefCell,
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    rc::Rc,
+};
 use typed_builder::TypedBuilder;
 
 use crate::{
@@ -10,6 +16,11 @@ use crate::{
     widgets::{BuildContext, Widget},
 };
 
+/// Lays out its children in a row or a column.
+///
+/// Recomposed with the same configuration, children included, it skips its whole subtree.
+/// That takes every child to have a [`Widget::configuration_hash`], otherwise the children
+/// are recomposed, each skipping itself when unchanged.
 #[derive(Debug, TypedBuilder)]
 pub struct Flex {
     #[builder(default=Axis::Horizontal)]
@@ -39,18 +50,38 @@ pub struct Flex {
     pub children: Vec<Flexible>,
 }
 
+impl Flex {
+    /// Whether `flex` is configured as this widget, children aside.
+    fn matches(&self, flex: &RenderFlex) -> bool {
+        flex.direction == self.direction
+            && flex.main_axis_size == self.main_axis_size
+            && flex.main_axis_alignment == self.main_axis_alignment
+            && flex.cross_axis_alignment == self.cross_axis_alignment
+            && flex.vertical_direction == self.vertical_direction
+            && flex.text_direction == self.text_direction
+            && flex.text_baseline == self.text_baseline
+            && flex.clip_behavior == self.clip_behavior
+    }
+}
+
 impl Widget for Flex {
     #[track_caller]
     fn create(&self, context: BuildContext) -> Rc<RefCell<dyn RenderBox>> {
+        let hash = self.configuration_hash();
         context.group(
             |_| {
-                let flex = RenderFlex::default().with_direction(self.direction);
-                Rc::new(RefCell::new(flex))
-            },
-            |_| {
-                // TODO: condition to skip whole Flex?
-                false
+                let flex = RenderFlex::default()
+                    .with_direction(self.direction)
+                    .with_main_axis_size(self.main_axis_size)
+                    .with_main_axis_alignment(self.main_axis_alignment)
+                    .with_cross_axis_alignment(self.cross_axis_alignment)
+                    .with_vertical_direction(self.vertical_direction)
+                    .with_text_direction(self.text_direction)
+                    .with_text_baseline(self.text_baseline)
+                    .with_clip_behavior(self.clip_behavior);
+                (Rc::new(RefCell::new(flex)), None)
             },
+            |(n, composed)| hash.is_some() && *composed == hash && self.matches(&n.borrow()),
             |cx| {
                 let mut children = Vec::with_capacity(self.children.len());
                 for child in self.children.iter() {
@@ -58,14 +89,42 @@ impl Widget for Flex {
                 }
                 children
             },
-            |n, children| {
-                let mut flex = n.borrow_mut();
-                flex.children.clear();
-                flex.children = children;
+            |(n, composed), children| {
+                *composed = hash;
+                let mut n = n.borrow_mut();
+                n.set_direction(self.direction);
+                n.set_main_axis_size(self.main_axis_size);
+                n.set_main_axis_alignment(self.main_axis_alignment);
+                n.set_cross_axis_alignment(self.cross_axis_alignment);
+                n.set_vertical_direction(self.vertical_direction);
+                n.set_text_direction(self.text_direction);
+                n.set_text_baseline(self.text_baseline);
+                n.set_clip_behavior(self.clip_behavior);
+                n.set_children(children);
             },
-            |n| n.clone(),
+            |(n, _)| n.clone(),
         )
     }
+
+    fn configuration_hash(&self) -> Option<u64> {
+        let mut hasher = DefaultHasher::new();
+        TypeId::of::<Self>().hash(&mut hasher);
+        (
+            self.direction,
+            self.main_axis_size,
+            self.main_axis_alignment,
+            self.cross_axis_alignment,
+            self.vertical_direction,
+            self.text_direction,
+            self.text_baseline,
+            self.clip_behavior,
+        )
+            .hash(&mut hasher);
+        for child in self.children.iter() {
+            (child.flex, child.fit, child.child.configuration_hash()?).hash(&mut hasher);
+        }
+        Some(hasher.finish())
+    }
 }
 
 #[derive(Debug, TypedBuilder)]
@@ -0,0 +1,159 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    rendering::{RenderBox, RenderFocus},
+    services::{FocusChangeCallback, FocusNode, KeyEvent, KeyEventCallback, KeyEventResult},
+    widgets::{BuildContext, Widget},
+};
+
+/// Places its child in the focus tree, e.g. to handle the keys its descendants ignore.
+///
+/// Without a node the widget keeps one of its own, give it one to request the focus from
+/// the app. The callbacks set replace those of the node.
+#[derive(Debug)]
+pub struct Focus {
+    pub child: Box<dyn Widget>,
+    pub focus_node: Option<Rc<FocusNode>>,
+    pub on_key: Option<KeyEventCallback>,
+    pub on_focus_change: Option<FocusChangeCallback>,
+    pub can_request_focus: bool,
+    pub skip_traversal: bool,
+}
+
+impl Focus {
+    pub fn new<T>(child: T) -> Self
+    where
+        T: 'static + Widget,
+    {
+        Focus {
+            child: Box::new(child),
+            focus_node: None,
+            on_key: None,
+            on_focus_change: None,
+            can_request_focus: true,
+            skip_traversal: false,
+        }
+    }
+
+    pub fn with_focus_node(mut self, focus_node: Rc<FocusNode>) -> Self {
+        self.focus_node = Some(focus_node);
+        self
+    }
+
+    pub fn with_on_key(mut self, f: impl Fn(&KeyEvent) -> KeyEventResult + 'static) -> Self {
+        self.on_key = Some(KeyEventCallback::new(f));
+        self
+    }
+
+    pub fn with_on_focus_change(mut self, f: impl Fn(bool) + 'static) -> Self {
+        self.on_focus_change = Some(FocusChangeCallback::new(f));
+        self
+    }
+
+    pub fn with_can_request_focus(mut self, can_request_focus: bool) -> Self {
+        self.can_request_focus = can_request_focus;
+        self
+    }
+
+    /// Keeps the node out of Tab traversal, it can still be focused by a request.
+    pub fn with_skip_traversal(mut self, skip_traversal: bool) -> Self {
+        self.skip_traversal = skip_traversal;
+        self
+    }
+}
+
+impl Widget for Focus {
+    #[track_caller]
+    fn create(&self, context: BuildContext) -> Rc<RefCell<dyn RenderBox>> {
+        context.group(
+            |_| {
+                let node = self
+                    .focus_node
+                    .clone()
+                    .unwrap_or_else(|| Rc::new(FocusNode::new()));
+                Rc::new(RefCell::new(RenderFocus::new(node)))
+            },
+            |_| false,
+            |cx| self.child.create(cx),
+            |n, child| {
+                let mut n = n.borrow_mut();
+                n.set_child(child);
+                if let Some(focus_node) = &self.focus_node {
+                    n.set_focus_node(focus_node.clone());
+                }
+                let node = &n.focus_node;
+                node.set_can_request_focus(self.can_request_focus);
+                node.set_skip_traversal(self.skip_traversal);
+                if self.on_key.is_some() {
+                    node.set_on_key(self.on_key.clone());
+                }
+                if self.on_focus_change.is_some() {
+                    node.set_on_focus_change(self.on_focus_change.clone());
+                }
+            },
+            |n| n.clone(),
+        )
+    }
+}
+
+/// Scopes Tab traversal to the nodes of its descendants, and focuses the one focused last
+/// in it when it gets the focus.
+#[derive(Debug)]
+pub struct FocusScope {
+    pub child: Box<dyn Widget>,
+    pub focus_node: Option<Rc<FocusNode>>,
+    pub on_key: Option<KeyEventCallback>,
+}
+
+impl FocusScope {
+    pub fn new<T>(child: T) -> Self
+    where
+        T: 'static + Widget,
+    {
+        FocusScope {
+            child: Box::new(child),
+            focus_node: None,
+            on_key: None,
+        }
+    }
+
+    /// Uses `focus_node`, created with [`FocusNode::scope`], as the node of the scope.
+    pub fn with_focus_node(mut self, focus_node: Rc<FocusNode>) -> Self {
+        debug_assert!(focus_node.is_scope(), "the node of a scope is a scope");
+        self.focus_node = Some(focus_node);
+        self
+    }
+
+    pub fn with_on_key(mut self, f: impl Fn(&KeyEvent) -> KeyEventResult + 'static) -> Self {
+        self.on_key = Some(KeyEventCallback::new(f));
+        self
+    }
+}
+
+impl Widget for FocusScope {
+    #[track_caller]
+    fn create(&self, context: BuildContext) -> Rc<RefCell<dyn RenderBox>> {
+        context.group(
+            |_| {
+                let node = self
+                    .focus_node
+                    .clone()
+                    .unwrap_or_else(|| Rc::new(FocusNode::scope()));
+                Rc::new(RefCell::new(RenderFocus::new(node)))
+            },
+            |_| false,
+            |cx| self.child.create(cx),
+            |n, child| {
+                let mut n = n.borrow_mut();
+                n.set_child(child);
+                if let Some(focus_node) = &self.focus_node {
+                    n.set_focus_node(focus_node.clone());
+                }
+                if self.on_key.is_some() {
+                    n.focus_node.set_on_key(self.on_key.clone());
+                }
+            },
+            |n| n.clone(),
+        )
+    }
+}
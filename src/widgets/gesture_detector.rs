@@ -0,0 +1,234 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    gestures::{
+        DoubleTapCallbacks, DoubleTapGestureRecognizer, DragCallbacks, DragGestureRecognizer,
+        GestureRecognizer, HitTestBehavior, LongPressCallbacks, LongPressGestureRecognizer,
+        ScaleCallbacks, ScaleGestureRecognizer, TapCallbacks, TapGestureRecognizer,
+    },
+    rendering::{RenderBox, RenderGestureDetector},
+    widgets::{BuildContext, Widget},
+};
+
+/// Detects gestures on its child, with a recognizer for each group of callbacks set.
+///
+/// Recognizing a pan and a scale at the same time is redundant, a scale with a single
+/// pointer is a pan.
+#[derive(Debug)]
+pub struct GestureDetector {
+    pub child: Box<dyn Widget>,
+    pub behavior: HitTestBehavior,
+    pub tap: TapCallbacks,
+    pub double_tap: DoubleTapCallbacks,
+    pub long_press: LongPressCallbacks,
+    pub vertical_drag: DragCallbacks,
+    pub horizontal_drag: DragCallbacks,
+    pub pan: DragCallbacks,
+    pub scale: ScaleCallbacks,
+}
+
+impl GestureDetector {
+    pub fn new<T>(child: T) -> Self
+    where
+        T: 'static + Widget,
+    {
+        GestureDetector {
+            child: Box::new(child),
+            behavior: HitTestBehavior::DeferToChild,
+            tap: Default::default(),
+            double_tap: Default::default(),
+            long_press: Default::default(),
+            vertical_drag: Default::default(),
+            horizontal_drag: Default::default(),
+            pan: Default::default(),
+            scale: Default::default(),
+        }
+    }
+
+    pub fn with_behavior(mut self, behavior: HitTestBehavior) -> Self {
+        self.behavior = behavior;
+        self
+    }
+
+    pub fn with_tap(mut self, tap: TapCallbacks) -> Self {
+        self.tap = tap;
+        self
+    }
+
+    pub fn with_double_tap(mut self, double_tap: DoubleTapCallbacks) -> Self {
+        self.double_tap = double_tap;
+        self
+    }
+
+    pub fn with_long_press(mut self, long_press: LongPressCallbacks) -> Self {
+        self.long_press = long_press;
+        self
+    }
+
+    pub fn with_vertical_drag(mut self, vertical_drag: DragCallbacks) -> Self {
+        self.vertical_drag = vertical_drag;
+        self
+    }
+
+    pub fn with_horizontal_drag(mut self, horizontal_drag: DragCallbacks) -> Self {
+        self.horizontal_drag = horizontal_drag;
+        self
+    }
+
+    pub fn with_pan(mut self, pan: DragCallbacks) -> Self {
+        self.pan = pan;
+        self
+    }
+
+    pub fn with_scale(mut self, scale: ScaleCallbacks) -> Self {
+        self.scale = scale;
+        self
+    }
+}
+
+/// The recognizers of a detector, kept across recompositions so that gestures in progress,
+/// e.g. waiting for the second tap of a double tap, survive the frames drawn meanwhile.
+#[derive(Debug, Default)]
+struct Recognizers {
+    tap: Option<Rc<TapGestureRecognizer>>,
+    double_tap: Option<Rc<DoubleTapGestureRecognizer>>,
+    long_press: Option<Rc<LongPressGestureRecognizer>>,
+    vertical_drag: Option<Rc<DragGestureRecognizer>>,
+    horizontal_drag: Option<Rc<DragGestureRecognizer>>,
+    pan: Option<Rc<DragGestureRecognizer>>,
+    scale: Option<Rc<ScaleGestureRecognizer>>,
+}
+
+impl Recognizers {
+    /// Updates the callbacks of the recognizers in place, returns whether one was added or
+    /// removed as its group of callbacks was set or cleared.
+    fn update(&mut self, detector: &GestureDetector) -> bool {
+        let mut changed = false;
+        changed |= update_recognizer(
+            &mut self.tap,
+            &detector.tap,
+            detector.tap.is_empty(),
+            TapGestureRecognizer::new,
+            TapGestureRecognizer::set_callbacks,
+        );
+        changed |= update_recognizer(
+            &mut self.double_tap,
+            &detector.double_tap,
+            detector.double_tap.is_empty(),
+            DoubleTapGestureRecognizer::new,
+            DoubleTapGestureRecognizer::set_callbacks,
+        );
+        changed |= update_recognizer(
+            &mut self.long_press,
+            &detector.long_press,
+            detector.long_press.is_empty(),
+            LongPressGestureRecognizer::new,
+            LongPressGestureRecognizer::set_callbacks,
+        );
+        changed |= update_recognizer(
+            &mut self.vertical_drag,
+            &detector.vertical_drag,
+            detector.vertical_drag.is_empty(),
+            DragGestureRecognizer::vertical,
+            DragGestureRecognizer::set_callbacks,
+        );
+        changed |= update_recognizer(
+            &mut self.horizontal_drag,
+            &detector.horizontal_drag,
+            detector.horizontal_drag.is_empty(),
+            DragGestureRecognizer::horizontal,
+            DragGestureRecognizer::set_callbacks,
+        );
+        changed |= update_recognizer(
+            &mut self.pan,
+            &detector.pan,
+            detector.pan.is_empty(),
+            DragGestureRecognizer::pan,
+            DragGestureRecognizer::set_callbacks,
+        );
+        changed |= update_recognizer(
+            &mut self.scale,
+            &detector.scale,
+            detector.scale.is_empty(),
+            ScaleGestureRecognizer::new,
+            ScaleGestureRecognizer::set_callbacks,
+        );
+        changed
+    }
+
+    fn to_vec(&self) -> Vec<Rc<dyn GestureRecognizer>> {
+        let mut recognizers: Vec<Rc<dyn GestureRecognizer>> = Vec::new();
+        if let Some(tap) = &self.tap {
+            recognizers.push(tap.clone());
+        }
+        if let Some(double_tap) = &self.double_tap {
+            recognizers.push(double_tap.clone());
+        }
+        if let Some(long_press) = &self.long_press {
+            recognizers.push(long_press.clone());
+        }
+        if let Some(vertical_drag) = &self.vertical_drag {
+            recognizers.push(vertical_drag.clone());
+        }
+        if let Some(horizontal_drag) = &self.horizontal_drag {
+            recognizers.push(horizontal_drag.clone());
+        }
+        if let Some(pan) = &self.pan {
+            recognizers.push(pan.clone());
+        }
+        if let Some(scale) = &self.scale {
+            recognizers.push(scale.clone());
+        }
+        recognizers
+    }
+}
+
+/// Keeps `recognizer` while its callbacks are set, returns whether it was added or removed.
+fn update_recognizer<R, C: Clone>(
+    recognizer: &mut Option<Rc<R>>,
+    callbacks: &C,
+    is_empty: bool,
+    create: impl FnOnce(C) -> R,
+    set_callbacks: impl FnOnce(&R, C),
+) -> bool {
+    match (recognizer.as_ref(), is_empty) {
+        (Some(_), true) => {
+            *recognizer = None;
+            true
+        }
+        (Some(current), false) => {
+            set_callbacks(current, callbacks.clone());
+            false
+        }
+        (None, false) => {
+            *recognizer = Some(Rc::new(create(callbacks.clone())));
+            true
+        }
+        (None, true) => false,
+    }
+}
+
+impl Widget for GestureDetector {
+    #[track_caller]
+    fn create(&self, context: BuildContext) -> Rc<RefCell<dyn RenderBox>> {
+        context.group(
+            |_| {
+                let detector = RenderGestureDetector::new(self.behavior);
+                (Rc::new(RefCell::new(detector)), Recognizers::default())
+            },
+            |_| false,
+            |cx| self.child.create(cx),
+            |(n, recognizers), child| {
+                let mut n = n.borrow_mut();
+                n.set_child(child);
+                n.set_behavior(self.behavior);
+                if recognizers.update(self) {
+                    n.set_recognizers(recognizers.to_vec());
+                }
+                n.set_on_semantics_tap(self.tap.on_tap.clone());
+                n.set_on_semantics_long_press(self.long_press.on_long_press.clone());
+            },
+            |(n, _)| n.clone(),
+        )
+    }
+}
@@ -0,0 +1,166 @@
+use std::{
+    cell::{Cell, RefCell},
+    fmt,
+    rc::Rc,
+    time::Duration,
+};
+
+use crate::{
+    animation::{AnimationController, Curve, Lerp, Linear, Tween},
+    rendering::{BoxConstraints, RenderBox, RenderConstrainedBox, RenderOpacity},
+    scheduler::SchedulerBinding,
+    widgets::{BuildContext, Widget},
+};
+
+/// A value animated from where it is to each new target it is given, the state of an
+/// implicitly animated widget kept across recompositions.
+///
+/// The animation runs on the scheduler of the frame it is created in, the value jumps to
+/// its targets when created outside of a frame.
+pub struct ImplicitAnimation<T> {
+    controller: Option<Rc<AnimationController>>,
+    tween: Cell<Tween<T>>,
+    target: Cell<T>,
+    curve: RefCell<Rc<dyn Curve>>,
+}
+
+impl<T: Lerp + PartialEq> ImplicitAnimation<T> {
+    pub fn new(value: T) -> Self {
+        ImplicitAnimation {
+            controller: SchedulerBinding::current()
+                .map(|scheduler| Rc::new(AnimationController::new(&scheduler))),
+            tween: Cell::new(Tween::new(value, value)),
+            target: Cell::new(value),
+            curve: RefCell::new(Rc::new(Linear)),
+        }
+    }
+
+    /// Starts animating to `target` over `duration` along `curve` unless it is the target
+    /// already, returns the value of the frame.
+    pub fn animate_to(&self, target: T, duration: Duration, curve: Rc<dyn Curve>) -> T {
+        self.curve.replace(curve);
+        if target != self.target.get() {
+            let begin = self.value();
+            self.target.set(target);
+            match &self.controller {
+                Some(controller) if !duration.is_zero() => {
+                    self.tween.set(Tween::new(begin, target));
+                    controller.set_duration(Some(duration));
+                    controller.set_value(0.0);
+                    controller.forward();
+                }
+                _ => self.tween.set(Tween::new(target, target)),
+            }
+        }
+        self.value()
+    }
+
+    /// The value of the frame, along the curve from where the animation started.
+    pub fn value(&self) -> T {
+        let tween = self.tween.get();
+        match &self.controller {
+            Some(controller) => tween.evaluate_curved(controller, &**self.curve.borrow()),
+            None => tween.end,
+        }
+    }
+
+    pub fn is_animating(&self) -> bool {
+        self.controller
+            .as_ref()
+            .is_some_and(|controller| controller.is_animating())
+    }
+}
+
+impl<T> fmt::Debug for ImplicitAnimation<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ImplicitAnimation")
+            .field("controller", &self.controller)
+            .finish()
+    }
+}
+
+/// A [`ConstrainedBox`](crate::widgets::ConstrainedBox) animating to its new constraints
+/// over `duration` when they change.
+#[derive(Debug)]
+pub struct AnimatedConstrainedBox {
+    pub constraints: BoxConstraints,
+    pub duration: Duration,
+    pub curve: Rc<dyn Curve>,
+}
+
+impl AnimatedConstrainedBox {
+    pub fn new(constraints: BoxConstraints, duration: Duration) -> Self {
+        AnimatedConstrainedBox {
+            constraints,
+            duration,
+            curve: Rc::new(Linear),
+        }
+    }
+
+    pub fn with_curve(mut self, curve: impl Curve + 'static) -> Self {
+        self.curve = Rc::new(curve);
+        self
+    }
+}
+
+impl Widget for AnimatedConstrainedBox {
+    #[track_caller]
+    fn create(&self, context: BuildContext) -> Rc<RefCell<dyn RenderBox>> {
+        let animation = context.state(|| Rc::new(ImplicitAnimation::new(self.constraints)));
+        let constraints = animation.animate_to(self.constraints, self.duration, self.curve.clone());
+        context.memo(
+            |_| Rc::new(RefCell::new(RenderConstrainedBox::new(constraints))),
+            |n| n.borrow().additional_constraints == constraints,
+            |n| n.borrow_mut().set_additional_constraints(constraints),
+            |n| n.clone(),
+        )
+    }
+}
+
+/// An [`Opacity`](crate::widgets::Opacity) fading to its new opacity over `duration`
+/// when it changes.
+#[derive(Debug)]
+pub struct AnimatedOpacity {
+    pub child: Box<dyn Widget>,
+    pub opacity: f32,
+    pub duration: Duration,
+    pub curve: Rc<dyn Curve>,
+}
+
+impl AnimatedOpacity {
+    pub fn new<T>(opacity: f32, duration: Duration, child: T) -> Self
+    where
+        T: 'static + Widget,
+    {
+        AnimatedOpacity {
+            child: Box::new(child),
+            opacity,
+            duration,
+            curve: Rc::new(Linear),
+        }
+    }
+
+    pub fn with_curve(mut self, curve: impl Curve + 'static) -> Self {
+        self.curve = Rc::new(curve);
+        self
+    }
+}
+
+impl Widget for AnimatedOpacity {
+    #[track_caller]
+    fn create(&self, context: BuildContext) -> Rc<RefCell<dyn RenderBox>> {
+        let animation = context.state(|| Rc::new(ImplicitAnimation::new(self.opacity)));
+        let opacity = animation.animate_to(self.opacity, self.duration, self.curve.clone());
+        context.group(
+            |_| Rc::new(RefCell::new(RenderOpacity::new(opacity))),
+            |_| false,
+            |cx| self.child.create(cx),
+            |n, child| {
+                let mut n = n.borrow_mut();
+                n.set_child(child);
+                n.set_opacity(opacity);
+            },
+            |n| n.clone(),
+        )
+    }
+}
@@ -0,0 +1,67 @@
+use std::{
+    cell::RefCell,
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    rc::Rc,
+};
+
+use crate::{
+    rendering::RenderBox,
+    widgets::{BuildContext, Widget},
+};
+
+/// Identifies a widget among its siblings, so that its render objects and state follow it
+/// when the siblings change, and names it for finders in tests.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Key(String);
+
+impl Key {
+    pub fn new(key: impl Into<String>) -> Self {
+        Key(key.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The key of the slots composing the widget.
+    fn slot_key(&self) -> usize {
+        let mut hasher = DefaultHasher::new();
+        self.hash(&mut hasher);
+        hasher.finish() as usize
+    }
+}
+
+impl From<&str> for Key {
+    fn from(key: &str) -> Self {
+        Key::new(key)
+    }
+}
+
+/// Gives its child a key, the render object of the child records it.
+#[derive(Debug)]
+pub struct KeyedSubtree {
+    pub key: Key,
+    pub child: Box<dyn Widget>,
+}
+
+impl KeyedSubtree {
+    pub fn new<T>(key: impl Into<Key>, child: T) -> Self
+    where
+        T: 'static + Widget,
+    {
+        KeyedSubtree {
+            key: key.into(),
+            child: Box::new(child),
+        }
+    }
+}
+
+impl Widget for KeyedSubtree {
+    #[track_caller]
+    fn create(&self, context: BuildContext) -> Rc<RefCell<dyn RenderBox>> {
+        let child = context.tag(self.key.slot_key(), |cx| self.child.create(cx));
+        child.borrow().state().set_key(Some(self.key.clone()));
+        child
+    }
+}
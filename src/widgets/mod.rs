@@ -1,6 +1,9 @@
 mod widget;
 pub use widget::*;
 
+mod key;
+pub use key::*;
+
 mod view;
 pub use view::*;
 
@@ -9,3 +12,33 @@ pub use flex::*;
 
 mod constrained_box;
 pub use constrained_box::*;
+
+mod repaint_boundary;
+pub use repaint_boundary::*;
+
+mod opacity;
+pub use opacity::*;
+
+mod gesture_detector;
+pub use gesture_detector::*;
+
+mod rich_text;
+pub use rich_text::*;
+
+mod text;
+pub use text::*;
+
+mod text_field;
+pub use text_field::*;
+
+mod focus_scope;
+pub use focus_scope::*;
+
+mod shortcuts;
+pub use shortcuts::*;
+
+mod semantics;
+pub use semantics::*;
+
+mod implicit_animations;
+pub use implicit_animations::*;
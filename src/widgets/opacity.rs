@@ -0,0 +1,42 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    rendering::{RenderBox, RenderOpacity},
+    widgets::{BuildContext, Widget},
+};
+
+/// Paints its child with `opacity`, from 0 (transparent) to 1 (opaque).
+#[derive(Debug)]
+pub struct Opacity {
+    pub child: Box<dyn Widget>,
+    pub opacity: f32,
+}
+
+impl Opacity {
+    pub fn new<T>(opacity: f32, child: T) -> Self
+    where
+        T: 'static + Widget,
+    {
+        Opacity {
+            child: Box::new(child),
+            opacity,
+        }
+    }
+}
+
+impl Widget for Opacity {
+    #[track_caller]
+    fn create(&self, context: BuildContext) -> Rc<RefCell<dyn RenderBox>> {
+        context.group(
+            |_| Rc::new(RefCell::new(RenderOpacity::new(self.opacity))),
+            |_| false,
+            |cx| self.child.create(cx),
+            |n, child| {
+                let mut n = n.borrow_mut();
+                n.set_child(child);
+                n.set_opacity(self.opacity);
+            },
+            |n| n.clone(),
+        )
+    }
+}
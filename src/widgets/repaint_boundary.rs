@@ -0,0 +1,37 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    rendering::{RenderBox, RenderRepaintBoundary},
+    widgets::{BuildContext, Widget},
+};
+
+#[derive(Debug)]
+pub struct RepaintBoundary {
+    pub child: Box<dyn Widget>,
+}
+
+impl RepaintBoundary {
+    pub fn new<T>(child: T) -> Self
+    where
+        T: 'static + Widget,
+    {
+        RepaintBoundary {
+            child: Box::new(child),
+        }
+    }
+}
+
+impl Widget for RepaintBoundary {
+    #[track_caller]
+    fn create(&self, context: BuildContext) -> Rc<RefCell<dyn RenderBox>> {
+        context.group(
+            |_| Rc::new(RefCell::new(RenderRepaintBoundary::new())),
+            |_| false,
+            |cx| self.child.create(cx),
+            |n, child| {
+                n.borrow_mut().set_child(child);
+            },
+            |n| n.clone(),
+        )
+    }
+}
@@ -0,0 +1,106 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    painting::{TextSpan, TextStyle},
+    rendering::{RenderBox, RenderParagraph, TextAlign, TextDirection, TextOverflow},
+    widgets::{BuildContext, Widget},
+};
+
+/// A paragraph of spans in different styles.
+#[derive(Debug)]
+pub struct RichText {
+    pub text: TextSpan,
+    /// The style the spans inherit from.
+    pub style: TextStyle,
+    pub text_align: TextAlign,
+    pub text_direction: TextDirection,
+    pub soft_wrap: bool,
+    pub overflow: TextOverflow,
+    pub max_lines: Option<usize>,
+}
+
+impl RichText {
+    pub fn new(text: impl Into<TextSpan>) -> Self {
+        RichText {
+            text: text.into(),
+            style: Default::default(),
+            text_align: TextAlign::Start,
+            text_direction: TextDirection::LTR,
+            soft_wrap: true,
+            overflow: TextOverflow::Clip,
+            max_lines: None,
+        }
+    }
+
+    pub fn with_style(mut self, style: TextStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn with_text_align(mut self, text_align: TextAlign) -> Self {
+        self.text_align = text_align;
+        self
+    }
+
+    pub fn with_text_direction(mut self, text_direction: TextDirection) -> Self {
+        self.text_direction = text_direction;
+        self
+    }
+
+    pub fn with_soft_wrap(mut self, soft_wrap: bool) -> Self {
+        self.soft_wrap = soft_wrap;
+        self
+    }
+
+    pub fn with_overflow(mut self, overflow: TextOverflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    pub fn with_max_lines(mut self, max_lines: impl Into<Option<usize>>) -> Self {
+        self.max_lines = max_lines.into();
+        self
+    }
+
+    fn is_rendered_by(&self, paragraph: &RenderParagraph) -> bool {
+        let painter = &paragraph.painter;
+        painter.text == self.text
+            && painter.text.has_same_targets(&self.text)
+            && painter.style == self.style
+            && painter.text_align == self.text_align
+            && painter.text_direction == self.text_direction
+            && paragraph.soft_wrap == self.soft_wrap
+            && paragraph.overflow == self.overflow
+            && painter.max_lines == self.max_lines
+    }
+}
+
+impl Widget for RichText {
+    #[track_caller]
+    fn create(&self, context: BuildContext) -> Rc<RefCell<dyn RenderBox>> {
+        context.memo(
+            |_| {
+                let paragraph = RenderParagraph::new(self.text.clone())
+                    .with_style(self.style.clone())
+                    .with_text_align(self.text_align)
+                    .with_text_direction(self.text_direction)
+                    .with_soft_wrap(self.soft_wrap)
+                    .with_overflow(self.overflow)
+                    .with_max_lines(self.max_lines);
+                Rc::new(RefCell::new(paragraph))
+            },
+            |n| self.is_rendered_by(&n.borrow()),
+            |n| {
+                let mut n = n.borrow_mut();
+                n.set_text(self.text.clone());
+                n.set_style(self.style.clone());
+                n.set_text_align(self.text_align);
+                n.set_text_direction(self.text_direction);
+                n.set_soft_wrap(self.soft_wrap);
+                n.set_overflow(self.overflow);
+                n.set_max_lines(self.max_lines);
+            },
+            |n| n.clone(),
+        )
+    }
+}
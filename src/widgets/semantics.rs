@@ -0,0 +1,193 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    rendering::{RenderBox, RenderSemanticsAnnotations},
+    semantics::{SemanticsActionHandler, SemanticsProperties, SemanticsRole},
+    widgets::{BuildContext, Widget},
+};
+
+/// Tells assistive technologies what its child is, e.g. a button and its label.
+///
+/// Without `container` the annotations merge into the node of the nearest container
+/// above, together with those of the other descendants.
+#[derive(Debug)]
+pub struct Semantics {
+    pub child: Box<dyn Widget>,
+    pub properties: SemanticsProperties,
+    pub container: bool,
+    pub explicit_child_nodes: bool,
+}
+
+impl Semantics {
+    pub fn new<T>(child: T) -> Self
+    where
+        T: 'static + Widget,
+    {
+        Semantics {
+            child: Box::new(child),
+            properties: Default::default(),
+            container: false,
+            explicit_child_nodes: false,
+        }
+    }
+
+    pub fn with_role(mut self, role: SemanticsRole) -> Self {
+        self.properties.role = Some(role);
+        self
+    }
+
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.properties.label = Some(label.into());
+        self
+    }
+
+    pub fn with_value(mut self, value: impl Into<String>) -> Self {
+        self.properties.value = Some(value.into());
+        self
+    }
+
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.properties.enabled = Some(enabled);
+        self
+    }
+
+    pub fn with_checked(mut self, checked: bool) -> Self {
+        self.properties.checked = Some(checked);
+        self
+    }
+
+    pub fn with_on_tap(mut self, f: impl Fn() + 'static) -> Self {
+        self.properties.on_tap = Some(SemanticsActionHandler::new(move |_| f()));
+        self
+    }
+
+    pub fn with_on_long_press(mut self, f: impl Fn() + 'static) -> Self {
+        self.properties.on_long_press = Some(SemanticsActionHandler::new(move |_| f()));
+        self
+    }
+
+    pub fn with_on_increase(mut self, f: impl Fn() + 'static) -> Self {
+        self.properties.on_increase = Some(SemanticsActionHandler::new(move |_| f()));
+        self
+    }
+
+    pub fn with_on_decrease(mut self, f: impl Fn() + 'static) -> Self {
+        self.properties.on_decrease = Some(SemanticsActionHandler::new(move |_| f()));
+        self
+    }
+
+    /// Makes the annotations a node of their own.
+    pub fn with_container(mut self, container: bool) -> Self {
+        self.container = container;
+        self
+    }
+
+    /// Keeps the annotations of the descendants in nodes of their own, e.g. for the items
+    /// of a list.
+    pub fn with_explicit_child_nodes(mut self, explicit_child_nodes: bool) -> Self {
+        self.explicit_child_nodes = explicit_child_nodes;
+        self
+    }
+}
+
+impl Widget for Semantics {
+    #[track_caller]
+    fn create(&self, context: BuildContext) -> Rc<RefCell<dyn RenderBox>> {
+        context.group(
+            |_| {
+                let properties = self.properties.clone();
+                Rc::new(RefCell::new(RenderSemanticsAnnotations::new(properties)))
+            },
+            |_| false,
+            |cx| self.child.create(cx),
+            |n, child| {
+                let mut n = n.borrow_mut();
+                n.set_child(child);
+                n.set_properties(self.properties.clone());
+                n.set_container(self.container);
+                n.set_explicit_child_nodes(self.explicit_child_nodes);
+            },
+            |n| n.clone(),
+        )
+    }
+}
+
+/// Merges the semantics of all its descendants into a single node, e.g. a checkbox and
+/// the text labelling it.
+#[derive(Debug)]
+pub struct MergeSemantics {
+    pub child: Box<dyn Widget>,
+}
+
+impl MergeSemantics {
+    pub fn new<T>(child: T) -> Self
+    where
+        T: 'static + Widget,
+    {
+        MergeSemantics {
+            child: Box::new(child),
+        }
+    }
+}
+
+impl Widget for MergeSemantics {
+    #[track_caller]
+    fn create(&self, context: BuildContext) -> Rc<RefCell<dyn RenderBox>> {
+        context.group(
+            |_| {
+                let merge = RenderSemanticsAnnotations::new(Default::default())
+                    .with_merges_descendants(true);
+                Rc::new(RefCell::new(merge))
+            },
+            |_| false,
+            |cx| self.child.create(cx),
+            |n, child| n.borrow_mut().set_child(child),
+            |n| n.clone(),
+        )
+    }
+}
+
+/// Leaves its descendants out of the semantics tree, e.g. a decoration.
+#[derive(Debug)]
+pub struct ExcludeSemantics {
+    pub child: Box<dyn Widget>,
+    pub excluding: bool,
+}
+
+impl ExcludeSemantics {
+    pub fn new<T>(child: T) -> Self
+    where
+        T: 'static + Widget,
+    {
+        ExcludeSemantics {
+            child: Box::new(child),
+            excluding: true,
+        }
+    }
+
+    pub fn with_excluding(mut self, excluding: bool) -> Self {
+        self.excluding = excluding;
+        self
+    }
+}
+
+impl Widget for ExcludeSemantics {
+    #[track_caller]
+    fn create(&self, context: BuildContext) -> Rc<RefCell<dyn RenderBox>> {
+        context.group(
+            |_| {
+                let exclude = RenderSemanticsAnnotations::new(Default::default())
+                    .with_excludes_descendants(self.excluding);
+                Rc::new(RefCell::new(exclude))
+            },
+            |_| false,
+            |cx| self.child.create(cx),
+            |n, child| {
+                let mut n = n.borrow_mut();
+                n.set_child(child);
+                n.set_excludes_descendants(self.excluding);
+            },
+            |n| n.clone(),
+        )
+    }
+}
@@ -0,0 +1,95 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    rendering::{RenderBox, RenderFocus},
+    services::{ActionMap, FocusNode, ShortcutMap},
+    widgets::{BuildContext, Widget},
+};
+
+/// Triggers the intents of its shortcuts for the keys its focused descendants ignore.
+///
+/// The intents go to the nearest action bound to them from the focused node up, so a
+/// subtree can handle a shortcut declared above it.
+#[derive(Debug)]
+pub struct Shortcuts {
+    pub child: Box<dyn Widget>,
+    pub shortcuts: ShortcutMap,
+}
+
+impl Shortcuts {
+    pub fn new<T>(shortcuts: ShortcutMap, child: T) -> Self
+    where
+        T: 'static + Widget,
+    {
+        Shortcuts {
+            child: Box::new(child),
+            shortcuts,
+        }
+    }
+}
+
+impl Widget for Shortcuts {
+    #[track_caller]
+    fn create(&self, context: BuildContext) -> Rc<RefCell<dyn RenderBox>> {
+        context.group(
+            |_| {
+                Rc::new(RefCell::new(RenderFocus::new(unfocusable_node(
+                    "Shortcuts",
+                ))))
+            },
+            |_| false,
+            |cx| self.child.create(cx),
+            |n, child| {
+                let mut n = n.borrow_mut();
+                n.set_child(child);
+                n.focus_node.set_shortcuts(self.shortcuts.clone());
+            },
+            |n| n.clone(),
+        )
+    }
+}
+
+/// Binds actions to the intents its descendants ask for.
+#[derive(Debug)]
+pub struct Actions {
+    pub child: Box<dyn Widget>,
+    pub actions: ActionMap,
+}
+
+impl Actions {
+    pub fn new<T>(actions: ActionMap, child: T) -> Self
+    where
+        T: 'static + Widget,
+    {
+        Actions {
+            child: Box::new(child),
+            actions,
+        }
+    }
+}
+
+impl Widget for Actions {
+    #[track_caller]
+    fn create(&self, context: BuildContext) -> Rc<RefCell<dyn RenderBox>> {
+        context.group(
+            |_| Rc::new(RefCell::new(RenderFocus::new(unfocusable_node("Actions")))),
+            |_| false,
+            |cx| self.child.create(cx),
+            |n, child| {
+                let mut n = n.borrow_mut();
+                n.set_child(child);
+                n.focus_node.set_actions(self.actions.clone());
+            },
+            |n| n.clone(),
+        )
+    }
+}
+
+/// A node in the focus tree only to see the key events and intents of its descendants.
+fn unfocusable_node(debug_label: &str) -> Rc<FocusNode> {
+    let node = FocusNode::new()
+        .with_debug_label(debug_label)
+        .with_can_request_focus(false)
+        .with_skip_traversal(true);
+    Rc::new(node)
+}
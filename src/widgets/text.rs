@@ -0,0 +1,82 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    painting::{TextSpan, TextStyle},
+    rendering::{RenderBox, TextAlign, TextDirection, TextOverflow},
+    widgets::{BuildContext, RichText, Widget},
+};
+
+/// A run of text in a single style.
+#[derive(Debug)]
+pub struct Text {
+    pub data: String,
+    pub style: TextStyle,
+    pub text_align: TextAlign,
+    pub text_direction: TextDirection,
+    pub soft_wrap: bool,
+    pub overflow: TextOverflow,
+    pub max_lines: Option<usize>,
+}
+
+impl Text {
+    pub fn new(data: impl Into<String>) -> Self {
+        Text {
+            data: data.into(),
+            style: Default::default(),
+            text_align: TextAlign::Start,
+            text_direction: TextDirection::LTR,
+            soft_wrap: true,
+            overflow: TextOverflow::Clip,
+            max_lines: None,
+        }
+    }
+
+    pub fn with_style(mut self, style: TextStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn with_text_align(mut self, text_align: TextAlign) -> Self {
+        self.text_align = text_align;
+        self
+    }
+
+    pub fn with_text_direction(mut self, text_direction: TextDirection) -> Self {
+        self.text_direction = text_direction;
+        self
+    }
+
+    pub fn with_soft_wrap(mut self, soft_wrap: bool) -> Self {
+        self.soft_wrap = soft_wrap;
+        self
+    }
+
+    pub fn with_overflow(mut self, overflow: TextOverflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    pub fn with_max_lines(mut self, max_lines: impl Into<Option<usize>>) -> Self {
+        self.max_lines = max_lines.into();
+        self
+    }
+
+    fn to_rich_text(&self) -> RichText {
+        RichText {
+            text: TextSpan::new(self.data.clone()),
+            style: self.style.clone(),
+            text_align: self.text_align,
+            text_direction: self.text_direction,
+            soft_wrap: self.soft_wrap,
+            overflow: self.overflow,
+            max_lines: self.max_lines,
+        }
+    }
+}
+
+impl Widget for Text {
+    #[track_caller]
+    fn create(&self, context: BuildContext) -> Rc<RefCell<dyn RenderBox>> {
+        self.to_rich_text().create(context)
+    }
+}
@@ -0,0 +1,126 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    painting::TextStyle,
+    rendering::{RenderBox, RenderEditable, TextAlign},
+    services::{FocusNode, TextEditingController},
+    ui::Color,
+    widgets::{BuildContext, Widget},
+};
+
+/// A box of text edited with the keyboard once a pointer taps it.
+///
+/// The text lives in the controller, set it to change the text from the app.
+#[derive(Debug)]
+pub struct TextField {
+    pub controller: Rc<TextEditingController>,
+    pub focus_node: Option<Rc<FocusNode>>,
+    pub style: TextStyle,
+    pub text_align: TextAlign,
+    pub max_lines: Option<usize>,
+    pub max_length: Option<usize>,
+    pub cursor_color: Color,
+    pub selection_color: Color,
+}
+
+impl TextField {
+    pub fn new(controller: Rc<TextEditingController>) -> Self {
+        TextField {
+            controller,
+            focus_node: None,
+            style: Default::default(),
+            text_align: TextAlign::Start,
+            max_lines: Some(1),
+            max_length: None,
+            cursor_color: Color::BLACK,
+            selection_color: Color::from_argb(0x66, 0x33, 0x99, 0xff),
+        }
+    }
+
+    /// The node the field takes the focus with, e.g. to focus it from the app.
+    pub fn with_focus_node(mut self, focus_node: Rc<FocusNode>) -> Self {
+        self.focus_node = Some(focus_node);
+        self
+    }
+
+    pub fn with_style(mut self, style: TextStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn with_text_align(mut self, text_align: TextAlign) -> Self {
+        self.text_align = text_align;
+        self
+    }
+
+    /// Number of lines the field is high, it grows with the text when `None`.
+    pub fn with_max_lines(mut self, max_lines: impl Into<Option<usize>>) -> Self {
+        self.max_lines = max_lines.into();
+        self
+    }
+
+    pub fn with_max_length(mut self, max_length: impl Into<Option<usize>>) -> Self {
+        self.max_length = max_length.into();
+        self
+    }
+
+    pub fn with_cursor_color(mut self, cursor_color: impl Into<Color>) -> Self {
+        self.cursor_color = cursor_color.into();
+        self
+    }
+
+    pub fn with_selection_color(mut self, selection_color: impl Into<Color>) -> Self {
+        self.selection_color = selection_color.into();
+        self
+    }
+
+    fn is_rendered_by(&self, editable: &RenderEditable) -> bool {
+        Rc::ptr_eq(&editable.controller, &self.controller)
+            && self
+                .focus_node
+                .as_ref()
+                .map_or(true, |node| Rc::ptr_eq(node, &editable.focus_node))
+            && editable.painter.style == self.style
+            && editable.painter.text_align == self.text_align
+            && editable.max_lines == self.max_lines
+            && editable.max_length == self.max_length
+            && editable.cursor_color == self.cursor_color
+            && editable.selection_color == self.selection_color
+    }
+}
+
+impl Widget for TextField {
+    #[track_caller]
+    fn create(&self, context: BuildContext) -> Rc<RefCell<dyn RenderBox>> {
+        context.memo(
+            |_| {
+                let mut editable = RenderEditable::new(self.controller.clone())
+                    .with_style(self.style.clone())
+                    .with_text_align(self.text_align)
+                    .with_max_lines(self.max_lines)
+                    .with_max_length(self.max_length)
+                    .with_cursor_color(self.cursor_color)
+                    .with_selection_color(self.selection_color);
+                if let Some(focus_node) = &self.focus_node {
+                    editable = editable.with_focus_node(focus_node.clone());
+                }
+                Rc::new(RefCell::new(editable))
+            },
+            |n| self.is_rendered_by(&n.borrow()),
+            |n| {
+                let mut n = n.borrow_mut();
+                n.set_controller(self.controller.clone());
+                if let Some(focus_node) = &self.focus_node {
+                    n.set_focus_node(focus_node.clone());
+                }
+                n.set_style(self.style.clone());
+                n.set_text_align(self.text_align);
+                n.set_max_lines(self.max_lines);
+                n.set_max_length(self.max_length);
+                n.set_cursor_color(self.cursor_color);
+                n.set_selection_color(self.selection_color);
+            },
+            |n| n.clone(),
+        )
+    }
+}
@@ -28,7 +28,7 @@ impl Widget for View {
             |_| false,
             |cx| self.child.create(cx),
             |n, child| {
-                n.borrow_mut().child = Some(child);
+                n.borrow_mut().set_child(child);
             },
             |n| n.clone(),
         )
@@ -16,6 +16,17 @@ pub trait Widget: Debug {
     #[track_caller]
     fn create(&self, context: BuildContext) -> Rc<RefCell<dyn RenderBox>>;
 
+    /// A hash of the configuration of this widget and its descendants, for a parent to
+    /// skip recomposing them while it does not change. `None`, the default, for widgets
+    /// that cannot be compared, e.g. holding callbacks or keeping state, which are always
+    /// recomposed.
+    ///
+    /// Skipping is opt-in per widget: a widget containing one without a hash has none
+    /// either, it is recomposed and its comparable descendants skip themselves instead.
+    fn configuration_hash(&self) -> Option<u64> {
+        None
+    }
+
     fn into_flexible(self, flex: usize, fit: FlexFit) -> Flexible
     where
         Self: 'static + Sized,